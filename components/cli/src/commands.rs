@@ -1,11 +1,12 @@
 use crate::matches::{
-    files_or_urls, has_dump_layout_tree_subcommand, has_similarity_subcommand, scale_factor,
-    similarity_percent_only, window_height, window_width,
+    files_or_urls, has_dump_layout_tree_subcommand, has_lint_css_subcommand,
+    has_similarity_subcommand, scale_factor, similarity_percent_only, window_height, window_width,
 };
 use crate::{
-    dump_layout_tree_verbose, DumpLayoutVerbosity, DUMP_LAYOUT_CMD_NAME,
-    DUMP_LAYOUT_INPUT_ARG_NAME, RENDER_INPUT_ARG_NAME, SIMILARITY_CMD_NAME,
-    SIMILARITY_INPUT_ARG_NAME,
+    color_scheme, dump_layout_tree_verbose, lint_css_format, media_type, ColorScheme,
+    DumpLayoutVerbosity, LintCssFormat, MediaType, DUMP_LAYOUT_CMD_NAME,
+    DUMP_LAYOUT_INPUT_ARG_NAME, LINT_CSS_CMD_NAME, LINT_CSS_INPUT_ARG_NAME, RENDER_INPUT_ARG_NAME,
+    SIMILARITY_CMD_NAME, SIMILARITY_INPUT_ARG_NAME,
 };
 use clap::ArgMatches;
 
@@ -15,6 +16,8 @@ pub struct RenderCmd {
     pub window_width: Option<f32>,
     pub window_height: Option<f32>,
     pub scale_factor: Option<f32>,
+    pub media_type: MediaType,
+    pub color_scheme: ColorScheme,
 }
 
 #[derive(Clone, Debug)]
@@ -24,6 +27,8 @@ pub struct DumpLayoutCmd {
     pub window_height: f32,
     pub scale_factor: f32,
     pub verbosity: DumpLayoutVerbosity,
+    pub media_type: MediaType,
+    pub color_scheme: ColorScheme,
 }
 
 #[derive(Clone, Debug)]
@@ -33,12 +38,21 @@ pub struct SimilarityCmd {
     pub window_height: Option<f32>,
     pub scale_factor: Option<f32>,
     pub percent_only: bool,
+    pub media_type: MediaType,
+    pub color_scheme: ColorScheme,
+}
+
+#[derive(Clone, Debug)]
+pub struct LintCssCmd {
+    pub file_paths: Vec<String>,
+    pub format: LintCssFormat,
 }
 
 pub enum Command {
     Render(RenderCmd),
     DumpLayout(DumpLayoutCmd),
     Similarity(SimilarityCmd),
+    LintCss(LintCssCmd),
 }
 
 impl From<DumpLayoutCmd> for Command {
@@ -59,6 +73,12 @@ impl From<SimilarityCmd> for Command {
     }
 }
 
+impl From<LintCssCmd> for Command {
+    fn from(cmd: LintCssCmd) -> Self {
+        Command::LintCss(cmd)
+    }
+}
+
 pub fn get_command(global_matches: &ArgMatches) -> Command {
     if has_dump_layout_tree_subcommand(global_matches) {
         let matches = global_matches
@@ -72,6 +92,8 @@ pub fn get_command(global_matches: &ArgMatches) -> Command {
             window_height: window_height(matches).unwrap(),
             scale_factor: scale_factor(matches).unwrap(),
             verbosity: dump_layout_tree_verbose(matches).unwrap_or(DumpLayoutVerbosity::NonVerbose),
+            media_type: media_type(matches),
+            color_scheme: color_scheme(matches),
         }
         .into()
     } else if has_similarity_subcommand(global_matches) {
@@ -85,6 +107,19 @@ pub fn get_command(global_matches: &ArgMatches) -> Command {
             window_height: window_height(matches),
             scale_factor: scale_factor(matches),
             percent_only: similarity_percent_only(matches),
+            media_type: media_type(matches),
+            color_scheme: color_scheme(matches),
+        }
+        .into()
+    } else if has_lint_css_subcommand(global_matches) {
+        let matches = global_matches
+            .subcommand_matches(LINT_CSS_CMD_NAME)
+            .unwrap();
+        // unwrap safe here because this arg is marked as required for `lint-css`.
+        let file_paths = files_or_urls(matches, LINT_CSS_INPUT_ARG_NAME).unwrap();
+        LintCssCmd {
+            file_paths,
+            format: lint_css_format(matches),
         }
         .into()
     } else {
@@ -98,6 +133,8 @@ pub fn get_command(global_matches: &ArgMatches) -> Command {
             window_width: width,
             window_height: height,
             scale_factor,
+            media_type: media_type(global_matches),
+            color_scheme: color_scheme(global_matches),
         }
         .into()
     }
@@ -3,9 +3,11 @@ use clap::{App, Arg, ArgMatches, SubCommand};
 
 const DUMP_LAYOUT_CMD_NAME: &str = "dump-layout";
 const SIMILARITY_CMD_NAME: &str = "similarity";
+const LINT_CSS_CMD_NAME: &str = "lint-css";
 const RENDER_INPUT_ARG_NAME: &str = "FILES OR URLS";
 const DUMP_LAYOUT_INPUT_ARG_NAME: &str = "FILES";
 const SIMILARITY_INPUT_ARG_NAME: &str = "FILES";
+const LINT_CSS_INPUT_ARG_NAME: &str = "FILES";
 
 pub mod commands;
 pub mod matches;
@@ -41,6 +43,21 @@ pub fn setup_and_get_cli_args<'a>() -> ArgMatches<'a> {
         .takes_value(true)
         .validator(is_num_validator);
 
+    let media_type_arg = Arg::with_name("media-type")
+        .long("media-type")
+        .value_name("MEDIA TYPE")
+        .help("The media type to style documents for, which `@media` rules are evaluated against.")
+        .takes_value(true)
+        .possible_values(&["screen", "print"])
+        .default_value("screen");
+    let color_scheme_arg = Arg::with_name("color-scheme")
+        .long("color-scheme")
+        .value_name("COLOR SCHEME")
+        .help("The color scheme to tell documents the user prefers, e.g. for `(prefers-color-scheme: dark)` media queries.")
+        .takes_value(true)
+        .possible_values(&["light", "dark"])
+        .default_value("light");
+
     App::new("Kosmonaut")
         .version("0.1")
         .author("Tyler Wilcock (twilco)")
@@ -48,6 +65,8 @@ pub fn setup_and_get_cli_args<'a>() -> ArgMatches<'a> {
         .arg(width_arg.clone())
         .arg(height_arg.clone())
         .arg(scale_factor_arg.clone())
+        .arg(media_type_arg.clone())
+        .arg(color_scheme_arg.clone())
         .arg(
             Arg::with_name(RENDER_INPUT_ARG_NAME)
                 .help("File(s) or URL(s) for Kosmonaut to render.")
@@ -76,6 +95,8 @@ pub fn setup_and_get_cli_args<'a>() -> ArgMatches<'a> {
                 .arg(scale_factor_arg.clone().required(true))
                 .arg(width_arg.clone().required(true))
                 .arg(height_arg.clone().required(true))
+                .arg(media_type_arg.clone())
+                .arg(color_scheme_arg.clone())
         )
         .subcommand(
             SubCommand::with_name(SIMILARITY_CMD_NAME)
@@ -100,6 +121,35 @@ error.
                 .arg(scale_factor_arg)
                 .arg(width_arg)
                 .arg(height_arg)
+                .arg(media_type_arg)
+                .arg(color_scheme_arg)
+        )
+        .subcommand(
+            SubCommand::with_name(LINT_CSS_CMD_NAME)
+                .long_about("
+Reports the CSS that Kosmonaut ignores, such as unknown properties, invalid values, and unsupported \
+at-rules, exiting with an error if there is any.  CSS files are checked along with the stylesheets \
+they import.  HTML files have their embedded styles (and the stylesheets they import) and style \
+attributes checked.  Locations within embedded styles are relative to the combined contents of the \
+document's <style> elements.
+                ".trim())
+                .arg(
+                    Arg::with_name("format")
+                        .short("f")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .help("The format to print diagnostics in.")
+                        .takes_value(true)
+                        .possible_values(&["human", "json"])
+                        .default_value("human")
+                )
+                .arg(
+                    Arg::with_name(LINT_CSS_INPUT_ARG_NAME)
+                        .help("CSS and HTML files for Kosmonaut to check.")
+                        .index(1)
+                        .required(true)
+                        .min_values(1)
+                )
         )
         .get_matches()
 }
@@ -142,6 +192,54 @@ impl DumpLayoutVerbosity {
     }
 }
 
+/// The format `lint-css` prints diagnostics in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LintCssFormat {
+    /// One diagnostic per line, in the form `sheet:line:column: kind: css`.
+    Human,
+    /// A JSON array of diagnostic objects.
+    Json,
+}
+
+pub fn lint_css_format(lint_css_arg_matches: &ArgMatches) -> LintCssFormat {
+    match lint_css_arg_matches.value_of("format") {
+        Some("json") => LintCssFormat::Json,
+        _ => LintCssFormat::Human,
+    }
+}
+
+/// The media type documents are styled for.
+///
+/// https://drafts.csswg.org/mediaqueries-4/#media-types
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MediaType {
+    Screen,
+    Print,
+}
+
+pub fn media_type(arg_matches: &ArgMatches) -> MediaType {
+    match arg_matches.value_of("media-type") {
+        Some("print") => MediaType::Print,
+        _ => MediaType::Screen,
+    }
+}
+
+/// The color scheme documents are told the user prefers.
+///
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-color-scheme
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+pub fn color_scheme(arg_matches: &ArgMatches) -> ColorScheme {
+    match arg_matches.value_of("color-scheme") {
+        Some("dark") => ColorScheme::Dark,
+        _ => ColorScheme::Light,
+    }
+}
+
 pub fn dump_layout_tree_verbose(
     dump_layout_arg_matches: &ArgMatches,
 ) -> Option<DumpLayoutVerbosity> {
@@ -9,6 +9,10 @@ pub fn has_similarity_subcommand(arg_matches: &ArgMatches) -> bool {
     arg_matches.subcommand_matches("similarity").is_some()
 }
 
+pub fn has_lint_css_subcommand(arg_matches: &ArgMatches) -> bool {
+    arg_matches.subcommand_matches("lint-css").is_some()
+}
+
 pub fn files_or_urls(arg_matches: &ArgMatches, arg_name: &str) -> Option<Vec<String>> {
     arg_matches
         .values_of(arg_name)
@@ -1,10 +1,11 @@
 use crate::char::CharCommand;
 use cssparser::RGBA;
 use layout::behavior::BaseLayoutBoxBehavior;
+use layout::flow::block::BlockLevelBox;
 use layout::layout_box::LayoutBox;
 use primitives::rect::{PositionedRect, Rect};
 use primitives::sides::PhysicalSide;
-use style::values::computed::LineStyle;
+use style::values::computed::{Float, LineStyle, ZIndex};
 
 pub mod char;
 
@@ -15,7 +16,9 @@ pub fn build_display_list(layout_box: &LayoutBox, _scale_factor: f32) -> Display
     // display_list module shouldn't depend on anything OpenGL-specific.
     // TODO: Remove the three preceeding statements once text rendering is fixed.
     // let font_handle = FontHandle::new();
-    // let font = font_handle.get_font("Helvetica").unwrap();
+    // let font = font_handle
+    //     .get_font_for(&layout_box.computed_values().font_description())
+    //     .unwrap();
     // char_handle
     //     .prepare_char(
     //         &mut display_list,
@@ -58,8 +61,9 @@ pub enum DisplayCommand {
     ViewportBackground(RGBA),
 }
 
-/// Prepares a layout box for display in the correct order.  The order in which each part of a box
-/// is painted is defined here: https://www.w3.org/TR/CSS22/zindex.html
+/// Prepares the stacking context formed by `layout_box` for display in the correct order.  The
+/// order in which each part of a stacking context is painted is defined here:
+/// https://www.w3.org/TR/CSS22/zindex.html
 fn prepare_layout_box(display_list: &mut DisplayList, layout_box: &LayoutBox) {
     // Step 1 of painting order
     if layout_box.is_root() {
@@ -70,26 +74,167 @@ fn prepare_layout_box(display_list: &mut DisplayList, layout_box: &LayoutBox) {
         ));
         // TODO: Step 1.2, painting background images
     }
+    if let LayoutBox::BlockLevel(_) = layout_box {
+        prepare_block_listitem_block_equiv(display_list, layout_box);
+    }
 
-    match layout_box {
-        LayoutBox::BlockLevel(_) => prepare_block_listitem_block_equiv(display_list, layout_box),
-        LayoutBox::InlineLevel(_) => {
-            // TODO: Implement other steps of painting order, 3 -> 10
-            // println!("skipping render of non-block box")
+    let mut layers = StackingLayers::default();
+    if let Some(children) = layout_box.children() {
+        collect_stacking_layers(children, &mut layers, None);
+    }
+    // `sort_by_key` is stable, so stacking contexts with the same z-index stay in tree order.
+    layers.negative.sort_by_key(|(z_index, _)| *z_index);
+    layers.positive.sort_by_key(|(z_index, _)| *z_index);
+
+    // Step 3
+    for (_, stacking_context) in &layers.negative {
+        prepare_layout_box(display_list, stacking_context);
+    }
+    // Step 4
+    for layout_box in &layers.in_flow {
+        prepare_in_flow_box(display_list, layout_box);
+    }
+    // Step 5
+    for &index in &layers.floats {
+        prepare_pseudo_stacking_context(display_list, &layers.pseudo_stacking_contexts[index]);
+    }
+    // TODO: Implement other steps of painting order, 6 -> 7 (inline content)
+    // Step 8
+    for layer in &layers.positioned {
+        match *layer {
+            PositionedLayer::StackingContext(stacking_context) => {
+                prepare_layout_box(display_list, stacking_context)
+            }
+            PositionedLayer::PseudoStackingContext(index) => prepare_pseudo_stacking_context(
+                display_list,
+                &layers.pseudo_stacking_contexts[index],
+            ),
         }
     }
+    // Step 9
+    for (_, stacking_context) in &layers.positive {
+        prepare_layout_box(display_list, stacking_context);
+    }
+}
 
-    if let Some(children) = layout_box.children() {
-        for child in children {
-            prepare_layout_box(display_list, child);
+/// The descendants of a stacking context, split into the layers they are painted in.
+#[derive(Default)]
+struct StackingLayers<'a> {
+    /// Child stacking contexts with negative z-indices, along with their z-index.
+    negative: Vec<(i32, &'a LayoutBox)>,
+    /// Non-positioned, non-floated descendants, in tree order.
+    in_flow: Vec<&'a LayoutBox>,
+    /// Non-positioned floats, as indices into `pseudo_stacking_contexts`, in tree order.
+    floats: Vec<usize>,
+    /// Positioned descendants with a z-index of `auto` or zero, in tree order.
+    positioned: Vec<PositionedLayer<'a>>,
+    /// Child stacking contexts with positive z-indices, along with their z-index.
+    positive: Vec<(i32, &'a LayoutBox)>,
+    /// Boxes which are painted as if they formed a stacking context, except that their positioned
+    /// descendants belong to the parent stacking context.  Each holds the box followed by its
+    /// non-positioned descendants, in tree order.
+    pseudo_stacking_contexts: Vec<Vec<&'a LayoutBox>>,
+}
+
+enum PositionedLayer<'a> {
+    /// A box whose z-index is zero, which forms a stacking context.
+    StackingContext(&'a LayoutBox),
+    /// A box whose z-index is `auto`, as an index into `pseudo_stacking_contexts`.
+    PseudoStackingContext(usize),
+}
+
+/// Sorts `boxes` and their descendants into the `layers` of the stacking context they belong to.
+/// Non-positioned boxes are added to the pseudo-stacking context at the `pseudo_stacking_context`
+/// index of `layers.pseudo_stacking_contexts` if there is one.
+fn collect_stacking_layers<'a>(
+    boxes: &'a [LayoutBox],
+    layers: &mut StackingLayers<'a>,
+    pseudo_stacking_context: Option<usize>,
+) {
+    for layout_box in boxes {
+        // The pseudo-stacking context the non-positioned descendants of this box belong to.
+        let descendants_pseudo_stacking_context = if layout_box.position().is_positioned() {
+            let z_index = layout_box.computed_values().z_index;
+            match z_index {
+                ZIndex::Integer(z_index) if z_index < 0 => {
+                    layers.negative.push((z_index, layout_box));
+                    continue;
+                }
+                ZIndex::Integer(z_index) if z_index > 0 => {
+                    layers.positive.push((z_index, layout_box));
+                    continue;
+                }
+                ZIndex::Integer(_) => {
+                    layers
+                        .positioned
+                        .push(PositionedLayer::StackingContext(layout_box));
+                    continue;
+                }
+                ZIndex::Auto => {
+                    let index = push_pseudo_stacking_context(layers, layout_box);
+                    layers
+                        .positioned
+                        .push(PositionedLayer::PseudoStackingContext(index));
+                    Some(index)
+                }
+            }
+        } else {
+            match pseudo_stacking_context {
+                // Floats within a pseudo-stacking context are painted in tree order along with the
+                // rest of its boxes.
+                Some(index) => {
+                    layers.pseudo_stacking_contexts[index].push(layout_box);
+                    Some(index)
+                }
+                None if layout_box.float() != Float::None => {
+                    let index = push_pseudo_stacking_context(layers, layout_box);
+                    layers.floats.push(index);
+                    Some(index)
+                }
+                None => {
+                    layers.in_flow.push(layout_box);
+                    None
+                }
+            }
+        };
+        if let Some(children) = layout_box.children() {
+            collect_stacking_layers(children, layers, descendants_pseudo_stacking_context);
+        }
+    }
+}
+
+/// Adds a pseudo-stacking context formed by `layout_box` to `layers`, returning its index.  Its
+/// descendants should be collected afterwards.
+fn push_pseudo_stacking_context<'a>(
+    layers: &mut StackingLayers<'a>,
+    layout_box: &'a LayoutBox,
+) -> usize {
+    layers.pseudo_stacking_contexts.push(vec![layout_box]);
+    layers.pseudo_stacking_contexts.len() - 1
+}
+
+/// Prepares the boxes of a pseudo-stacking context for display.
+fn prepare_pseudo_stacking_context(display_list: &mut DisplayList, boxes: &[&LayoutBox]) {
+    for layout_box in boxes {
+        prepare_in_flow_box(display_list, layout_box);
+    }
+}
+
+/// Prepares the parts of a box painted outside of any stacking context it forms for display.
+fn prepare_in_flow_box(display_list: &mut DisplayList, layout_box: &LayoutBox) {
+    match layout_box {
+        LayoutBox::BlockLevel(_) => prepare_block_listitem_block_equiv(display_list, layout_box),
+        LayoutBox::InlineLevel(_) => {
+            // TODO: Paint inline-level boxes, step 7 of painting order
         }
     }
 }
 
-/// Preparation for step 2 from: https://www.w3.org/TR/CSS22/zindex.html
+/// Preparation for steps 2 and 4 from: https://www.w3.org/TR/CSS22/zindex.html
 fn prepare_block_listitem_block_equiv(display_list: &mut DisplayList, layout_box: &LayoutBox) {
     prepare_background(display_list, layout_box);
     prepare_borders(display_list, layout_box);
+    prepare_column_rules(display_list, layout_box);
     // TODO: Render text - https://learnopengl.com/In-Practice/Text-Rendering
 }
 
@@ -112,6 +257,28 @@ fn prepare_borders(display_list: &mut DisplayList, layout_box: &LayoutBox) {
     prepare_border(display_list, layout_box, PhysicalSide::Top);
 }
 
+/// Prepares the rules between the columns of `layout_box`, if it's a multi-column container, for
+/// display by converting them to display commands.
+// TODO: Rules are always painted solid, whatever their style.
+fn prepare_column_rules(display_list: &mut DisplayList, layout_box: &LayoutBox) {
+    let column_rules = match layout_box {
+        LayoutBox::BlockLevel(BlockLevelBox::BlockContainer(bc)) => bc.column_rules(),
+        _ => return,
+    };
+    let cvs = layout_box.computed_values();
+    let rule_style = cvs.column_rule_style;
+    let rule_color_rgba = cvs.column_rule_color.rgba;
+    if rule_style == LineStyle::None
+        || rule_style == LineStyle::Hidden
+        || rule_color_rgba == RGBA::transparent()
+    {
+        return;
+    }
+    for rule in column_rules {
+        display_list.push(DisplayCommand::RectSolidColor(rule_color_rgba, *rule));
+    }
+}
+
 /// Prepares the border `side` of `layout_box` for display by converting it to a display command.
 fn prepare_border(display_list: &mut DisplayList, layout_box: &LayoutBox, side: PhysicalSide) {
     let cvs = layout_box.computed_values();
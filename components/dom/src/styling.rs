@@ -1,16 +1,33 @@
 use crate::tree::{NodeData, NodeRef};
-use style::properties::ContextualPropertyDeclaration;
+use cssparser::{Parser, ParserInput};
+use kosmonaut_selectors::Specificity;
+use style::diagnostics::{DiagnosticReporter, DiagnosticSink, IgnoreDiagnostics};
+use style::media_queries::Device;
+use style::properties::{
+    parse_property_declaration_list, ContextualPropertyDeclaration, ContextualPropertyDeclarations,
+};
 use style::stylesheet::Stylesheet;
 use style::values::computed::{compute_values, ComputedValues};
+use style::viewport::ViewportSize;
 use style::{CascadeOrigin, CssOrigin, CssRule};
 
+/// Styles `dom` for the given `device`.
+///
+/// Any declarations applied to `dom` by a previous call are thrown away first, so this can be
+/// called again to restyle the document when the device changes (e.g. the viewport is resized),
+/// which may change the result of media queries.
 pub fn apply_styles(
     dom: NodeRef,
     embedded_styles: &[CssRule],
     ua_sheets: &[Stylesheet],
     user_sheets: &[Stylesheet],
     author_sheets: &[Stylesheet],
+    device: &Device,
 ) {
+    dom.inclusive_descendants().for_each(|node| {
+        *node.contextual_decls_mut() = ContextualPropertyDeclarations::new();
+    });
+
     // https://www.w3.org/TR/css-cascade-3/#value-stages
     // The final value of a CSS property for a given element or box is the result of a multi-step calculation:
 
@@ -20,6 +37,7 @@ pub fn apply_styles(
             &dom,
             stylesheet.rules(),
             stylesheet.css_origin(CascadeOrigin::UserAgent),
+            device,
         );
     });
 
@@ -28,6 +46,7 @@ pub fn apply_styles(
             &dom,
             stylesheet.rules(),
             stylesheet.css_origin(CascadeOrigin::User),
+            device,
         );
     });
 
@@ -36,27 +55,14 @@ pub fn apply_styles(
             &dom,
             stylesheet.rules(),
             stylesheet.css_origin(CascadeOrigin::Author),
+            device,
         );
     });
-    apply_css_rules_to_node(&dom, embedded_styles, CssOrigin::Embedded);
-    // collect all inline styles
-    dom.inclusive_descendants().for_each(|node| {
-        if let NodeData::Element(element_data) = node.data() {
-            match element_data.attributes.try_borrow() {
-                Ok(attrs) => {
-                    if let Some(style_str) = attrs.get("style") {
-                        // TODO: Parse inline style and apply it to node.  Make sure these styles have a greater specificity than anything else
-                        // cssparser::ParserInput::new(style_str)
-                        dbg!("found inline style but did not collect it: {:?}", style_str);
-                    }
-                }
-                Err(_e) => {
-                    dbg!("couldn't borrow node attributes");
-                }
-            }
-        }
-    });
-    cascade_and_compute(&dom);
+    apply_css_rules_to_node(&dom, embedded_styles, CssOrigin::Embedded, device);
+    // Styles are re-applied whenever the device changes, so diagnostics reported here would be
+    // repeated.
+    apply_inline_styles(&dom, &mut IgnoreDiagnostics);
+    cascade_and_compute(&dom, device.viewport);
 }
 
 /// Performs steps 2-4 of https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#value-stages.
@@ -66,7 +72,14 @@ pub fn apply_styles(
 /// 2) Cascading — https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#cascade
 /// 3) Defaulting to specified values — https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#specified-value
 /// 4) Resolving specified values to computed values — https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#computed
-pub fn cascade_and_compute(start_node: &NodeRef) {
+///
+/// Computed values can depend on the size of the `viewport` (e.g. viewport-percentage lengths),
+/// so this must be re-run whenever the viewport changes size.
+pub fn cascade_and_compute(start_node: &NodeRef, viewport: ViewportSize) {
+    // The computed font size of the root element, used to resolve `rem` lengths.  Nodes are
+    // visited in tree order, so this is known by the time any of the root's descendants are
+    // computed.
+    let mut root_font_size = None;
     start_node.inclusive_descendants().for_each(|node| {
         // Step 2
         node.contextual_decls_mut().cascade_sort();
@@ -78,11 +91,28 @@ pub fn cascade_and_compute(start_node: &NodeRef) {
             // TODO: This _could_ be an expensive clone when we actually support all CSS properties.
             p.computed_values().clone()
         });
-        *node.computed_values_mut() =
-            compute_values(&*node.contextual_decls(), &parent_computed_values);
+        let computed_values = compute_values(
+            &*node.contextual_decls(),
+            &parent_computed_values,
+            root_font_size,
+            viewport,
+        );
+        if root_font_size.is_none() && is_root_element(&node) {
+            root_font_size = Some(computed_values.font_size);
+        }
+        *node.computed_values_mut() = computed_values;
     });
 }
 
+/// Whether `node` is the root element of its document, e.g. `<html>`.
+fn is_root_element(node: &NodeRef) -> bool {
+    node.as_element().is_some()
+        && match node.parent() {
+            Some(parent) => parent.as_document().is_some(),
+            None => true,
+        }
+}
+
 pub fn extract_embedded_styles(node: NodeRef) -> String {
     node.inclusive_descendants()
         .filter(|child| {
@@ -108,9 +138,63 @@ pub fn extract_embedded_styles(node: NodeRef) -> String {
         })
 }
 
-pub fn apply_css_rules_to_node(node: &NodeRef, rules: &[CssRule], origin: CssOrigin) {
-    rules.iter().for_each(|rule| {
-        if let CssRule::Style(style_rule) = rule {
+/// Parses the `style` attribute of each element in the tree rooted at `start_node`, applying the
+/// resulting declarations to the element they were found on.  Declarations that are dropped are
+/// reported to `sink`.
+///
+/// https://www.w3.org/TR/css-style-attr/
+pub fn apply_inline_styles(start_node: &NodeRef, sink: &mut dyn DiagnosticSink) {
+    start_node.inclusive_descendants().for_each(|node| {
+        if let NodeData::Element(element_data) = node.data() {
+            let style_str = match element_data.attributes.try_borrow() {
+                Ok(attrs) => match attrs.get("style") {
+                    Some(style_str) => style_str.to_owned(),
+                    None => return,
+                },
+                // The attributes are only borrowed mutably while the tree is being built or
+                // scripted, neither of which happens during styling, so there's nothing to report
+                // and the element is skipped.
+                Err(_) => return,
+            };
+            let mut parser_input = ParserInput::new(&style_str);
+            let mut parser = Parser::new(&mut parser_input);
+            let sheet_name = format!("<{}> style attribute", element_data.name.local);
+            let block = parse_property_declaration_list(
+                &mut parser,
+                &mut DiagnosticReporter::new(&sheet_name, sink),
+            );
+            block
+                .declarations()
+                .iter()
+                .enumerate()
+                .for_each(|(index, decl)| {
+                    node.add_decl(ContextualPropertyDeclaration {
+                        inner_decl: decl.clone(),
+                        important: block
+                            .declarations_importance()
+                            .get(index)
+                            .expect("important bit not set for declaration"),
+                        origin: CssOrigin::Inline,
+                        source_location: None,
+                        // Style attributes have no selector, and thus no specificity.  Instead,
+                        // the cascade places them above all selector-based declarations.
+                        specificity: Specificity::new(0),
+                    });
+                });
+        }
+    });
+}
+
+/// Applies the declarations of each rule in `rules` to the nodes its selectors match.  The rules
+/// nested within `@media` rules are only applied if the rule's media queries match `device`.
+pub fn apply_css_rules_to_node(
+    node: &NodeRef,
+    rules: &[CssRule],
+    origin: CssOrigin,
+    device: &Device,
+) {
+    rules.iter().for_each(|rule| match rule {
+        CssRule::Style(style_rule) => {
             node.select(&style_rule.selectors)
                 .for_each(|matching_node| {
                     style_rule
@@ -133,5 +217,18 @@ pub fn apply_css_rules_to_node(node: &NodeRef, rules: &[CssRule], origin: CssOri
                         });
                 });
         }
+        CssRule::Media(media_rule) => {
+            if media_rule.media_queries.evaluate(device) {
+                apply_css_rules_to_node(node, &media_rule.rules, origin.clone(), device);
+            }
+        }
+        CssRule::Supports(supports_rule) => {
+            if supports_rule.enabled {
+                apply_css_rules_to_node(node, &supports_rule.rules, origin.clone(), device);
+            }
+        }
+        // Imports are resolved into the rules they import before styles are applied, so any left
+        // over here couldn't be loaded.
+        CssRule::Import(_) | CssRule::None => {}
     });
 }
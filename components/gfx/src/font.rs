@@ -1,14 +1,25 @@
 use accountable_refcell::{Ref, RefCell};
 use font_kit::error::{FontLoadingError, SelectionError};
+use font_kit::family_name::FamilyName;
 use font_kit::loaders::default::Font;
+use font_kit::properties::{Properties, Stretch, Style, Weight};
 use font_kit::source::SystemSource;
+use layout::text::{ApproximateTextMeasurer, FontMetrics, TextMeasurer};
+use primitives::units::{CSSFloat, CSSPixelLength};
 use std::collections::HashMap;
+use style::values::computed::font::{
+    FamilyName as CssFamilyName, FontDescription, FontFamily, FontStyle, GenericFontFamily,
+};
 
 /// Provides a handle for loading and caching fonts that abstracts over all different font loaders
 /// and sources.
 #[derive(Default)]
 pub struct FontHandle {
     cached_fonts: RefCell<HashMap<String, Font>>,
+    /// The PostScript names of the faces selected for previously seen font descriptions, or
+    /// `None` for descriptions no face could be loaded for, so that selection isn't retried for
+    /// every piece of text they're used by.
+    selected_faces: RefCell<HashMap<FaceQuery, Option<PostscriptName>>>,
 }
 
 pub type PostscriptName = String;
@@ -17,9 +28,45 @@ impl FontHandle {
     pub fn new() -> FontHandle {
         FontHandle {
             cached_fonts: RefCell::new(HashMap::new()),
+            selected_faces: RefCell::new(HashMap::new()),
         }
     }
 
+    /// Gets the installed face that best matches the given computed font description, e.g. as
+    /// returned by `ComputedValues::font_description`.  The description's size is ignored, since
+    /// faces are scalable.
+    pub fn get_font_for(&self, description: &FontDescription) -> Result<Ref<Font>, FontError> {
+        let query = FaceQuery::new(description);
+        let selected_face = self.selected_faces.borrow().get(&query).cloned();
+        let postscript_name = match selected_face {
+            Some(Some(postscript_name)) => postscript_name,
+            Some(None) => return Err(FontError::Selection(SelectionError::NotFound)),
+            None => {
+                let selected = self.select_face(&query);
+                let postscript_name = selected.as_ref().ok().cloned();
+                self.selected_faces
+                    .borrow_mut()
+                    .insert(query, postscript_name);
+                selected?
+            }
+        };
+        self.get_font(&postscript_name)
+    }
+
+    /// Selects and loads the installed face that best matches `query`, and returns its PostScript
+    /// name.
+    fn select_face(&self, query: &FaceQuery) -> Result<PostscriptName, FontError> {
+        let font = SystemSource::new()
+            .select_best_match(&family_names(&query.family), &query.properties())?
+            .load()?;
+        let postscript_name = font.postscript_name().unwrap_or_else(|| font.full_name());
+        self.cached_fonts
+            .borrow_mut()
+            .entry(postscript_name.clone())
+            .or_insert(font);
+        Ok(postscript_name)
+    }
+
     pub fn get_font(&self, postscript_name: &str) -> Result<Ref<Font>, FontError> {
         let key = postscript_name.to_owned();
         {
@@ -35,12 +82,121 @@ impl FontHandle {
     }
 }
 
+/// Measures text with the installed faces that best match each font description, falling back to
+/// approximate measurements for descriptions no face can be loaded for.
+impl TextMeasurer for FontHandle {
+    fn font_metrics(&self, description: &FontDescription) -> FontMetrics {
+        let font = match self.get_font_for(description) {
+            Ok(font) => font,
+            Err(_) => return ApproximateTextMeasurer.font_metrics(description),
+        };
+        let metrics = font.metrics();
+        let scale = font_units_to_px(description, metrics.units_per_em);
+        FontMetrics {
+            ascent: CSSPixelLength::new(metrics.ascent * scale),
+            // Font-kit gives the descent as a negative distance from the baseline.
+            descent: CSSPixelLength::new(-metrics.descent * scale),
+            line_gap: CSSPixelLength::new(metrics.line_gap * scale),
+        }
+    }
+
+    fn advance_width(&self, text: &str, description: &FontDescription) -> CSSPixelLength {
+        let font = match self.get_font_for(description) {
+            Ok(font) => font,
+            Err(_) => return ApproximateTextMeasurer.advance_width(text, description),
+        };
+        let scale = font_units_to_px(description, font.metrics().units_per_em);
+        let advance: CSSFloat = text
+            .chars()
+            // Characters the face has no glyph for are drawn with its missing glyph, glyph zero.
+            .map(|c| font.glyph_for_char(c).unwrap_or(0))
+            .map(|glyph_id| font.advance(glyph_id).map_or(0., |advance| advance.x()))
+            .sum();
+        CSSPixelLength::new(advance * scale)
+    }
+}
+
+/// The number of CSS pixels per font unit for a face with `units_per_em` font units per em, at
+/// the size of `description`.
+fn font_units_to_px(description: &FontDescription, units_per_em: u32) -> CSSFloat {
+    description.size.size.px() / units_per_em as CSSFloat
+}
+
 fn load_font(postscript_name: &str) -> Result<Font, FontError> {
     Ok(SystemSource::new()
         .select_by_postscript_name(postscript_name)?
         .load()?)
 }
 
+/// The parts of a `FontDescription` that face selection depends on, in a hashable form.
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct FaceQuery {
+    family: FontFamily,
+    style: FaceStyle,
+    /// The bits of the `f32` weight.
+    weight: u32,
+    /// The bits of the `f32` stretch.
+    stretch: u32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum FaceStyle {
+    Normal,
+    Italic,
+    Oblique,
+}
+
+impl FaceQuery {
+    fn new(description: &FontDescription) -> FaceQuery {
+        FaceQuery {
+            family: description.family.clone(),
+            // Font-kit can't select by oblique angle.
+            style: match description.style {
+                FontStyle::Normal => FaceStyle::Normal,
+                FontStyle::Italic => FaceStyle::Italic,
+                FontStyle::Oblique(_) => FaceStyle::Oblique,
+            },
+            weight: description.weight.0.to_bits(),
+            stretch: (description.stretch.0).0.to_bits(),
+        }
+    }
+
+    fn properties(&self) -> Properties {
+        Properties {
+            style: match self.style {
+                FaceStyle::Normal => Style::Normal,
+                FaceStyle::Italic => Style::Italic,
+                FaceStyle::Oblique => Style::Oblique,
+            },
+            weight: Weight(f32::from_bits(self.weight)),
+            stretch: Stretch(f32::from_bits(self.stretch)),
+        }
+    }
+}
+
+/// The font-kit family names to select a face from for `family`, followed by the initial generic
+/// family, `serif`, to fall back to if none of the author's families are installed.
+fn family_names(family: &FontFamily) -> Vec<FamilyName> {
+    let mut names: Vec<FamilyName> = family
+        .0
+        .iter()
+        .map(|name| match name {
+            CssFamilyName::Specific(name) => FamilyName::Title(name.clone()),
+            CssFamilyName::Generic(GenericFontFamily::Serif) => FamilyName::Serif,
+            // Font-kit has no `system-ui` family, so fall back to the closest generic family.
+            CssFamilyName::Generic(GenericFontFamily::SansSerif)
+            | CssFamilyName::Generic(GenericFontFamily::SystemUi) => FamilyName::SansSerif,
+            CssFamilyName::Generic(GenericFontFamily::Monospace) => FamilyName::Monospace,
+            CssFamilyName::Generic(GenericFontFamily::Cursive) => FamilyName::Cursive,
+            CssFamilyName::Generic(GenericFontFamily::Fantasy) => FamilyName::Fantasy,
+        })
+        .collect();
+    if !names.contains(&FamilyName::Serif) {
+        names.push(FamilyName::Serif);
+    }
+    names
+}
+
 // TODO: This error type seems a bit too general.  May want to refactor as this module evolves.
 #[derive(Debug)]
 pub enum FontError {
@@ -59,3 +215,78 @@ impl From<SelectionError> for FontError {
         FontError::Selection(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use style::values::computed::font::FontSize;
+    use style::values::computed::ComputedValues;
+
+    // These tests don't depend on which faces are installed, so hold whether text is measured with
+    // an installed face or approximated.
+
+    fn description(family: FontFamily, size_px: CSSFloat) -> FontDescription {
+        FontDescription {
+            family,
+            size: FontSize {
+                size: CSSPixelLength::new(size_px),
+                keyword_size: None,
+            },
+            ..ComputedValues::default().font_description()
+        }
+    }
+
+    fn serif() -> FontFamily {
+        FontFamily(vec![CssFamilyName::Generic(GenericFontFamily::Serif)])
+    }
+
+    #[test]
+    fn measures_text_in_proportion_to_font_size() {
+        let font_handle = FontHandle::new();
+        let small = description(serif(), 16.);
+        let large = description(serif(), 32.);
+
+        let metrics = font_handle.font_metrics(&small);
+        assert!(metrics.ascent.px() > 0.);
+        assert!(metrics.descent.px() >= 0.);
+        let large_metrics = font_handle.font_metrics(&large);
+        assert!((large_metrics.ascent.px() - 2. * metrics.ascent.px()).abs() < 0.01);
+
+        let width = font_handle.advance_width("Kosmonaut", &small).px();
+        assert!(width > 0.);
+        let large_width = font_handle.advance_width("Kosmonaut", &large).px();
+        assert!((large_width - 2. * width).abs() < 0.01);
+        // Text is measured glyph by glyph, so the width of a run is the sum of its parts.
+        let parts_width = font_handle.advance_width("Kosmo", &small).px()
+            + font_handle.advance_width("naut", &small).px();
+        assert!((width - parts_width).abs() < 0.01);
+        assert_eq!(font_handle.advance_width("", &small).px(), 0.);
+    }
+
+    #[test]
+    fn selects_a_face_once_per_query() {
+        let font_handle = FontHandle::new();
+        font_handle.advance_width("a", &description(serif(), 16.));
+        font_handle.font_metrics(&description(serif(), 24.));
+        // Faces are scalable, so the size of a description doesn't change the face selected.
+        assert_eq!(font_handle.selected_faces.borrow().len(), 1);
+    }
+
+    #[test]
+    fn falls_back_to_serif_for_uninstalled_families() {
+        let font_handle = FontHandle::new();
+        let uninstalled = FontFamily(vec![CssFamilyName::Specific(
+            "Kosmonaut Uninstalled Family".to_owned(),
+        )]);
+        let _ = font_handle.get_font_for(&description(uninstalled.clone(), 16.));
+        let _ = font_handle.get_font_for(&description(serif(), 16.));
+        let selected_faces = font_handle.selected_faces.borrow();
+        let selected_face = |family: FontFamily| {
+            selected_faces
+                .get(&FaceQuery::new(&description(family, 16.)))
+                .cloned()
+        };
+        assert!(selected_face(uninstalled.clone()).is_some());
+        assert_eq!(selected_face(uninstalled), selected_face(serif()));
+    }
+}
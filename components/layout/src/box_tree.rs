@@ -1,13 +1,17 @@
 use crate::behavior::BaseLayoutBoxBehavior;
 use crate::flow::block::{AnonymousBlockBox, BlockLevelBox};
-use crate::flow::inline::{InlineBox, TextRun};
+use crate::flow::inline::{
+    AtomicInlineBox, InlineBox, InlineLevelBox, InlineLevelContent, TextRun,
+};
+use crate::flow::white_space::{process_white_space, CollapsibleSpaceState};
+use crate::flow::BlockContainer;
 use crate::formatting_context::{
     FormattingContext, FormattingContextRef, QualifiedFormattingContext,
 };
 use crate::layout_box::LayoutBox;
 use dom::tree::{NodeData, NodeRef};
-use style::values::computed::display::{DisplayBox, InnerDisplay, OuterDisplay};
-use style::values::computed::Display;
+use style::values::computed::display::{DisplayBox, InnerDisplay, InternalDisplay, OuterDisplay};
+use style::values::computed::{ComputedValues, Display, Float};
 
 /// Takes a DOM node and builds the corresponding box tree of it and its children.  Returns
 /// `None` if `node` is a `Display::None`.
@@ -24,6 +28,26 @@ use style::values::computed::Display;
 pub fn build_box_tree(
     node: NodeRef,
     parent_context: Option<FormattingContextRef>,
+) -> Option<LayoutBox> {
+    build_box_subtree(
+        node,
+        parent_context.clone(),
+        parent_context,
+        &mut CollapsibleSpaceState::line_start(),
+    )
+}
+
+/// Builds the box tree of `node` and its children.  `space_state` tracks white space collapsing
+/// through the inline formatting context the boxes of `node` join.
+///
+/// `block_context` is the block formatting context of the nearest block container the boxes of
+/// `node` are inside, which block-level boxes inside inline boxes join once they're split out of
+/// them.
+fn build_box_subtree(
+    node: NodeRef,
+    parent_context: Option<FormattingContextRef>,
+    block_context: Option<FormattingContextRef>,
+    space_state: &mut CollapsibleSpaceState,
 ) -> Option<LayoutBox> {
     if let NodeData::Document(_) = node.data() {
         // We don't want to create boxes for the document node nor the doctype nodes, so skip past
@@ -42,17 +66,13 @@ pub fn build_box_tree(
                 NodeData::Element(data) => local_name!("html") == data.name.local,
                 _ => false,
             })
-            .map(|html_node| build_box_tree(html_node, None))
-            .flatten();
+            .and_then(|html_node| build_box_subtree(html_node, None, None, space_state));
     }
 
-    let mut layout_box = if let NodeData::Text(text) = node.data() {
+    let mut layout_box = if let NodeData::Text(_) = node.data() {
         // https://drafts.csswg.org/css-display-3/#flow-layout
         // > If the [text] sequence contains no text, however, it does not generate a text run.
-        let contents = text.clone().take().trim().to_owned();
-        if contents.is_empty() {
-            return None;
-        }
+        let contents = process_text_node(&node, space_state)?;
         let pfc = parent_context.unwrap();
         assert!(pfc.is_inline_formatting_context());
         TextRun::new(node.clone(), pfc, contents).into()
@@ -63,14 +83,27 @@ pub fn build_box_tree(
         }
     };
 
+    if let LayoutBox::BlockLevel(_) = layout_box {
+        // The content of a block container starts on a new line.
+        *space_state = CollapsibleSpaceState::line_start();
+    }
+    let contains_blocks = is_block_container(&layout_box);
+    let block_context = if contains_blocks {
+        Some(layout_box.formatting_context())
+    } else {
+        block_context
+    };
     for child in node.children() {
-        if let NodeData::Text(text) = child.data() {
+        if let NodeData::Text(_) = child.data() {
             // https://drafts.csswg.org/css-display-3/#flow-layout
             // > If the [text] sequence contains no text, however, it does not generate a text run.
-            let contents = text.clone().take().trim().to_owned();
-            if contents.is_empty() {
-                continue;
-            }
+            //
+            // This includes white space that is collapsed away, which CSS2§9.2.1.1 notes does
+            // not generate anonymous inline boxes.
+            let contents = match process_text_node(&child, space_state) {
+                Some(contents) => contents,
+                None => continue,
+            };
 
             // Get (or create, if necessary) an inline container for this new text-run.
             let inline_container = get_or_create_inline_container(&mut layout_box, child.clone());
@@ -78,57 +111,178 @@ pub fn build_box_tree(
                 TextRun::new(
                     child.clone(),
                     inline_container.formatting_context(),
-                    text.clone().take().trim().to_owned(),
+                    contents,
                 )
                 .into(),
             );
             continue;
         }
-        handle_child_node_by_display(&mut layout_box, child);
+        handle_child_node_by_display(&mut layout_box, child, block_context.clone(), space_state);
+    }
+    if contains_blocks {
+        if let Some(children) = layout_box.children_mut() {
+            split_inline_boxes_around_blocks(children);
+        }
+        fix_up_table_structure(&mut layout_box);
     }
     Some(layout_box)
 }
 
-fn handle_child_node_by_display(parent_box: &mut LayoutBox, child_node: NodeRef) {
+/// Whether `layout_box` is a block container, whose children are block-level boxes (or whose
+/// inline-level content is held by anonymous block boxes).
+fn is_block_container(layout_box: &LayoutBox) -> bool {
+    matches!(
+        layout_box,
+        LayoutBox::BlockLevel(BlockLevelBox::BlockContainer(_))
+            | LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AtomicInline(_)
+            ))
+    )
+}
+
+/// Processes the white space of the given text node according to its `white-space`, returning
+/// `None` if there is no text left afterwards.
+fn process_text_node(
+    text_node: &NodeRef,
+    space_state: &mut CollapsibleSpaceState,
+) -> Option<String> {
+    let text = text_node.as_text()?.borrow();
+    let white_space = text_node.computed_values().white_space;
+    let contents = process_white_space(&text, white_space, space_state);
+    if contents.is_empty() {
+        None
+    } else {
+        Some(contents)
+    }
+}
+
+fn handle_child_node_by_display(
+    parent_box: &mut LayoutBox,
+    child_node: NodeRef,
+    block_context: Option<FormattingContextRef>,
+    space_state: &mut CollapsibleSpaceState,
+) {
     let child_computed_values = &*child_node.computed_values();
     match child_computed_values.display {
+        Display::Full(_) | Display::Internal(_) if is_out_of_flow(child_computed_values) => {
+            // Absolutely positioned and floated boxes are taken out of flow, so neither join the
+            // lines of their inline siblings nor affect how white space around them collapses.
+            // Those found after inline-level content are kept with it, so the content around them
+            // stays in one inline formatting context, which places floats beside the line they're
+            // on and gives absolutely positioned boxes their static position on it.  Otherwise,
+            // adding them to the parent box places their static position where a block-level box
+            // in place of the element would go, which is a guess CSS 2.1 section 10.3.7 allows.
+            if let Some(child_box) = build_box_subtree(
+                child_node.clone(),
+                None,
+                None,
+                &mut CollapsibleSpaceState::line_start(),
+            ) {
+                match trailing_inline_container(parent_box) {
+                    Some(inline_container) => inline_container.add_child(child_box),
+                    None => parent_box.add_child(child_box),
+                }
+            }
+        }
         Display::Full(full_display) => {
             match (full_display.outer(), full_display.inner()) {
                 (OuterDisplay::Block, InnerDisplay::Flow)
-                | (OuterDisplay::Block, InnerDisplay::FlowRoot) => {
-                    if let Some(child_box) =
-                        build_box_tree(child_node.clone(), Some(parent_box.formatting_context()))
-                    {
-                        // TODO: We don't handle the case where a block-flow child box is added to an inline box.
-                        // This current behavior is wrong — we should be checking if `node` is an `Display::Inline` and
-                        // doing something different here.  To fix, see: https://www.w3.org/TR/CSS2/visuren.html#box-gen
-                        // Namely, the paragraph that begins with "When an inline box contains an in-flow block-level box"
-                        // This concept _might_ be called "fragmenting".
-                        parent_box.add_child(child_box)
-                    }
-                }
+                | (OuterDisplay::Block, InnerDisplay::FlowRoot)
+                | (OuterDisplay::Block, InnerDisplay::Flex)
+                | (OuterDisplay::Block, InnerDisplay::Grid)
+                | (OuterDisplay::Block, InnerDisplay::Table) => add_block_level_child(
+                    parent_box,
+                    child_node.clone(),
+                    block_context,
+                    space_state,
+                ),
                 (OuterDisplay::Inline, InnerDisplay::Flow) => {
                     let inline_container =
                         get_or_create_inline_container(parent_box, child_node.clone());
-                    if let Some(child_box) = build_box_tree(
+                    if let Some(child_box) = build_box_subtree(
                         child_node.clone(),
                         Some(inline_container.formatting_context()),
+                        block_context,
+                        space_state,
                     ) {
                         inline_container.add_child(child_box)
                     }
                 }
-                (OuterDisplay::Inline, InnerDisplay::FlowRoot) => unimplemented!(),
+                (OuterDisplay::Inline, InnerDisplay::FlowRoot)
+                | (OuterDisplay::Inline, InnerDisplay::Flex)
+                | (OuterDisplay::Inline, InnerDisplay::Grid)
+                | (OuterDisplay::Inline, InnerDisplay::Table) => {
+                    let inline_container =
+                        get_or_create_inline_container(parent_box, child_node.clone());
+                    // The contents of an atomic inline are laid out in a formatting context of
+                    // their own, so white space collapses in them separately from the line the
+                    // atomic inline is on.
+                    let child_box = build_box_subtree(
+                        child_node.clone(),
+                        Some(inline_container.formatting_context()),
+                        None,
+                        &mut CollapsibleSpaceState::line_start(),
+                    );
+                    *space_state = CollapsibleSpaceState::after_atomic_inline();
+                    if let Some(child_box) = child_box {
+                        inline_container.add_child(child_box)
+                    }
+                }
             }
         }
+        // The internal boxes of a table are block-level, and any that aren't inside a table of
+        // the right structure are wrapped in anonymous table boxes once their parent box has been
+        // built.
+        Display::Internal(_) => {
+            add_block_level_child(parent_box, child_node.clone(), block_context, space_state)
+        }
         Display::Box(DisplayBox::None) => {}
     }
 }
 
+/// Builds the block-level boxes of `child_node` and adds them to `parent_box`.
+fn add_block_level_child(
+    parent_box: &mut LayoutBox,
+    child_node: NodeRef,
+    block_context: Option<FormattingContextRef>,
+    space_state: &mut CollapsibleSpaceState,
+) {
+    // A block-level box inside an inline box is split out of it once its block container has been
+    // built, so joins the formatting context of that block container rather than that of the
+    // inline box.
+    let child_box = build_box_subtree(
+        child_node,
+        block_context.clone(),
+        block_context,
+        space_state,
+    );
+    // Content following a block-level box starts on a new line.
+    *space_state = CollapsibleSpaceState::line_start();
+    if let Some(child_box) = child_box {
+        parent_box.add_child(child_box)
+    }
+}
+
 fn build_box_from_display(
     node: NodeRef,
     parent_context: Option<FormattingContextRef>,
 ) -> Option<LayoutBox> {
     let computed_values = node.computed_values();
+    if is_out_of_flow(&computed_values) && computed_values.display != Display::Box(DisplayBox::None)
+    {
+        // Absolutely positioned and floated boxes are blockified, and establish a new block
+        // formatting context for their contents (unless they're flex or grid containers, or
+        // tables).
+        // https://drafts.csswg.org/css-display-3/#transformations
+        // https://www.w3.org/TR/CSS22/visuren.html#block-formatting
+        let formatting_context = match computed_values.display.inner() {
+            Some(InnerDisplay::Flex) => FormattingContextRef::new_independent_flex(),
+            Some(InnerDisplay::Grid) => FormattingContextRef::new_independent_grid(),
+            Some(InnerDisplay::Table) => FormattingContextRef::new_independent_table(),
+            _ => FormattingContextRef::new_independent_block(),
+        };
+        return Some(BlockLevelBox::new_block_container(node.clone(), formatting_context).into());
+    }
     // Per the "Generated box" column from the table in this section, decide what boxes to generate
     // from this DOM node.  https://drafts.csswg.org/css-display/#the-display-properties
     Some(match computed_values.display {
@@ -136,8 +290,13 @@ fn build_box_from_display(
             match (full_display.outer(), full_display.inner()) {
                 (OuterDisplay::Block, InnerDisplay::Flow) => {
                     // Per https://www.w3.org/TR/css-display-3/#block-container, join this new block
-                    // container with our parent formatting context if it is a BFC.
+                    // container with our parent formatting context if it is a BFC.  Multi-column
+                    // containers establish a new BFC instead.
+                    // https://drafts.csswg.org/css-multicol-1/#columns
                     let formatting_context = match parent_context.clone() {
+                        _ if computed_values.is_multicol_container() => {
+                            FormattingContextRef::new_independent_block()
+                        }
                         Some(rc_qfc) => {
                             match *rc_qfc {
                                 QualifiedFormattingContext::Independent(
@@ -162,6 +321,21 @@ fn build_box_from_display(
                     )
                     .into()
                 }
+                (OuterDisplay::Block, InnerDisplay::Flex) => BlockLevelBox::new_block_container(
+                    node.clone(),
+                    FormattingContextRef::new_independent_flex(),
+                )
+                .into(),
+                (OuterDisplay::Block, InnerDisplay::Grid) => BlockLevelBox::new_block_container(
+                    node.clone(),
+                    FormattingContextRef::new_independent_grid(),
+                )
+                .into(),
+                (OuterDisplay::Block, InnerDisplay::Table) => BlockLevelBox::new_block_container(
+                    node.clone(),
+                    FormattingContextRef::new_independent_table(),
+                )
+                .into(),
                 (OuterDisplay::Inline, InnerDisplay::Flow) => {
                     let formatting_context = match parent_context.clone() {
                         Some(rc_qfc) => {
@@ -179,13 +353,80 @@ fn build_box_from_display(
                     };
                     InlineBox::new(node.clone(), formatting_context).into()
                 }
-                (OuterDisplay::Inline, InnerDisplay::FlowRoot) => unimplemented!(),
+                (OuterDisplay::Inline, InnerDisplay::FlowRoot) => {
+                    assert!(
+                        matches!(parent_context, Some(pfc) if pfc.is_inline_formatting_context())
+                    );
+                    // Atomic inlines take part in their parent inline formatting context, but
+                    // establish a new block formatting context for their contents.
+                    // https://www.w3.org/TR/CSS22/visuren.html#block-formatting
+                    AtomicInlineBox::new(
+                        node.clone(),
+                        FormattingContextRef::new_independent_block(),
+                    )
+                    .into()
+                }
+                (OuterDisplay::Inline, InnerDisplay::Flex) => {
+                    assert!(
+                        matches!(parent_context, Some(pfc) if pfc.is_inline_formatting_context())
+                    );
+                    // Inline-level flex containers are atomic inlines too.
+                    AtomicInlineBox::new(node.clone(), FormattingContextRef::new_independent_flex())
+                        .into()
+                }
+                (OuterDisplay::Inline, InnerDisplay::Grid) => {
+                    assert!(
+                        matches!(parent_context, Some(pfc) if pfc.is_inline_formatting_context())
+                    );
+                    // Inline-level grid containers are atomic inlines too.
+                    AtomicInlineBox::new(node.clone(), FormattingContextRef::new_independent_grid())
+                        .into()
+                }
+                (OuterDisplay::Inline, InnerDisplay::Table) => {
+                    assert!(
+                        matches!(parent_context, Some(pfc) if pfc.is_inline_formatting_context())
+                    );
+                    // As are inline-level tables.
+                    AtomicInlineBox::new(
+                        node.clone(),
+                        FormattingContextRef::new_independent_table(),
+                    )
+                    .into()
+                }
             }
         }
+        Display::Internal(InternalDisplay::TableCell)
+        | Display::Internal(InternalDisplay::TableCaption) => {
+            // Cells and captions establish a new block formatting context for their contents.
+            // https://www.w3.org/TR/CSS22/visuren.html#block-formatting
+            BlockLevelBox::new_block_container(
+                node.clone(),
+                FormattingContextRef::new_independent_block(),
+            )
+            .into()
+        }
+        Display::Internal(_) => {
+            // Rows, columns, and the groups of them join the table formatting context of their
+            // table.  Those that aren't inside a table get a new one, which is established by the
+            // anonymous table generated around them.
+            let formatting_context = match parent_context {
+                Some(pfc) if pfc.is_table_formatting_context() => pfc,
+                _ => FormattingContextRef::new_independent_table(),
+            };
+            BlockLevelBox::new_block_container(node.clone(), formatting_context).into()
+        }
         Display::Box(DisplayBox::None) => return None,
     })
 }
 
+/// Whether the boxes generated by an element with the given computed values are out of flow, i.e.
+/// are absolutely positioned or floated.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#positioning-scheme
+fn is_out_of_flow(computed_values: &ComputedValues) -> bool {
+    computed_values.position.is_absolutely_positioned() || computed_values.float != Float::None
+}
+
 fn get_or_create_inline_container(
     layout_box: &mut LayoutBox,
     node_for_container: NodeRef,
@@ -199,6 +440,233 @@ fn get_or_create_inline_container(
     layout_box.get_mut_inline_container().unwrap()
 }
 
+/// The inline container holding the inline-level content that `parent_box` currently ends with, if
+/// any.  The children of flex, grid, and table containers are laid out by their own formatting
+/// context rather than in lines, so out-of-flow boxes don't join the inline content among them.
+fn trailing_inline_container(parent_box: &mut LayoutBox) -> Option<&mut LayoutBox> {
+    let formatting_context = parent_box.formatting_context();
+    if formatting_context.is_flex_formatting_context()
+        || formatting_context.is_grid_formatting_context()
+        || formatting_context.is_table_formatting_context()
+    {
+        return None;
+    }
+    parent_box.get_mut_inline_container()
+}
+
+/// Splits the inline boxes among the inline-level content in `children`, the children of a block
+/// container, around the in-flow block-level boxes they contain.  The block-level boxes become
+/// siblings of the anonymous block boxes holding the inline-level content before and after them.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#anonymous-block-level
+fn split_inline_boxes_around_blocks(children: &mut Vec<LayoutBox>) {
+    let contains_blocks_in_inlines = |child: &LayoutBox| match child {
+        LayoutBox::BlockLevel(BlockLevelBox::AnonymousBlock(abb)) => {
+            abb.children().iter().any(contains_in_flow_blocks)
+        }
+        _ => false,
+    };
+    if !children.iter().any(contains_blocks_in_inlines) {
+        return;
+    }
+    for mut child in std::mem::take(children) {
+        if !contains_blocks_in_inlines(&child) {
+            children.push(child);
+            continue;
+        }
+        // Take the content out of the root inline box of the anonymous block box.
+        let inline_content = child
+            .children_mut()
+            .into_iter()
+            .flatten()
+            .filter_map(LayoutBox::children_mut)
+            .flat_map(std::mem::take)
+            .collect();
+        for piece in split_around_blocks(inline_content) {
+            match piece {
+                SplitPiece::Inline(content) if content.is_empty() => {}
+                SplitPiece::Inline(content) => {
+                    let mut inline_container = create_inline_container(content[0].node());
+                    let root_inline_box = inline_container
+                        .get_mut_inline_container()
+                        .expect("inline container should have a root inline box");
+                    for inline_child in content {
+                        root_inline_box.add_child(inline_child);
+                    }
+                    children.push(inline_container);
+                }
+                SplitPiece::Block(block) => children.push(block),
+            }
+        }
+    }
+}
+
+/// A piece of inline-level content split around the in-flow block-level boxes it contains.
+enum SplitPiece {
+    Inline(Vec<LayoutBox>),
+    Block(LayoutBox),
+}
+
+/// Splits `content` around the in-flow block-level boxes it contains, splitting any inline boxes
+/// holding them into a part before and after each.  The pieces alternate between inline-level
+/// content and block-level boxes, starting and ending with inline-level content.
+fn split_around_blocks(content: Vec<LayoutBox>) -> Vec<SplitPiece> {
+    let mut pieces = vec![SplitPiece::Inline(Vec::new())];
+    let push_inline = |pieces: &mut Vec<SplitPiece>, inline: LayoutBox| match pieces.last_mut() {
+        Some(SplitPiece::Inline(content)) => content.push(inline),
+        _ => unreachable!("pieces should end with inline-level content"),
+    };
+    for child in content {
+        match child {
+            LayoutBox::BlockLevel(_) if is_in_flow(&child) => {
+                pieces.push(SplitPiece::Block(child));
+                pieces.push(SplitPiece::Inline(Vec::new()));
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::InlineBox(mut ib),
+            )) if ib.children().iter().any(contains_in_flow_blocks) => {
+                let inner_pieces = split_around_blocks(std::mem::take(ib.children_mut()));
+                let last_index = inner_pieces.len() - 1;
+                for (index, piece) in inner_pieces.into_iter().enumerate() {
+                    match piece {
+                        SplitPiece::Inline(content) => {
+                            let part = ib.split_part(content, index == 0, index == last_index);
+                            push_inline(&mut pieces, part.into());
+                        }
+                        SplitPiece::Block(block) => {
+                            pieces.push(SplitPiece::Block(block));
+                            pieces.push(SplitPiece::Inline(Vec::new()));
+                        }
+                    }
+                }
+            }
+            _ => push_inline(&mut pieces, child),
+        }
+    }
+    pieces
+}
+
+/// Whether `layout_box` is or contains (through inline boxes) an in-flow block-level box.
+fn contains_in_flow_blocks(layout_box: &LayoutBox) -> bool {
+    match layout_box {
+        LayoutBox::BlockLevel(_) => is_in_flow(layout_box),
+        LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+            InlineLevelBox::AnonymousInline(_),
+        ))
+        | LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(InlineLevelBox::InlineBox(
+            _,
+        ))) => layout_box
+            .children()
+            .into_iter()
+            .flatten()
+            .any(contains_in_flow_blocks),
+        _ => false,
+    }
+}
+
+fn is_in_flow(layout_box: &LayoutBox) -> bool {
+    !layout_box.position().is_absolutely_positioned() && layout_box.float() == Float::None
+}
+
+/// The layout-internal display type of `layout_box`, if it's an in-flow box with one.  Floated and
+/// absolutely positioned boxes are blockified, so have no part in the structure of a table.
+pub(crate) fn internal_display(layout_box: &LayoutBox) -> Option<InternalDisplay> {
+    if !is_in_flow(layout_box) {
+        return None;
+    }
+    match layout_box.computed_values().display {
+        Display::Internal(internal_display) => Some(internal_display),
+        Display::Full(_) | Display::Box(_) => None,
+    }
+}
+
+/// Completes the structure of any tables among the children of `layout_box`, a block container,
+/// by dropping the children columns can't have, and generating anonymous table boxes around
+/// children that are missing the parent a table needs them to have.  Rows, groups, and tables
+/// generated this way are fixed up in turn, so anonymous cells are generated inside anonymous rows
+/// where needed.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+fn fix_up_table_structure(layout_box: &mut LayoutBox) {
+    let display = layout_box.computed_values().display;
+    let formatting_context = layout_box.formatting_context();
+    let children = match layout_box.children_mut() {
+        Some(children) => children,
+        None => return,
+    };
+    let is_proper_table_child = |child: &LayoutBox| matches!(internal_display(child), Some(internal) if internal != InternalDisplay::TableCell);
+    match display {
+        Display::Internal(InternalDisplay::TableColumn) => children.clear(),
+        Display::Internal(InternalDisplay::TableColumnGroup) => {
+            children.retain(|child| internal_display(child) == Some(InternalDisplay::TableColumn))
+        }
+        Display::Full(full_display) if full_display.inner() == InnerDisplay::Table => {
+            wrap_in_anonymous_boxes(
+                children,
+                |child| !is_proper_table_child(child),
+                Display::Internal(InternalDisplay::TableRow),
+                || formatting_context.clone(),
+            )
+        }
+        Display::Internal(internal) if internal.is_row_group() => wrap_in_anonymous_boxes(
+            children,
+            |child| internal_display(child) != Some(InternalDisplay::TableRow),
+            Display::Internal(InternalDisplay::TableRow),
+            || formatting_context.clone(),
+        ),
+        Display::Internal(InternalDisplay::TableRow) => wrap_in_anonymous_boxes(
+            children,
+            |child| internal_display(child) != Some(InternalDisplay::TableCell),
+            Display::Internal(InternalDisplay::TableCell),
+            FormattingContextRef::new_independent_block,
+        ),
+        // Cells and the proper children of a table that aren't inside one are wrapped in an
+        // anonymous table, which wraps the cells in anonymous rows in turn.
+        _ => wrap_in_anonymous_boxes(
+            children,
+            |child| internal_display(child).is_some(),
+            Display::new_full_display(OuterDisplay::Block, InnerDisplay::Table),
+            FormattingContextRef::new_independent_table,
+        ),
+    }
+}
+
+/// Wraps each run of consecutive `children` that `needs_wrapping` in an anonymous block container
+/// with the given `display`, whose formatting context is given by `formatting_context`.
+fn wrap_in_anonymous_boxes(
+    children: &mut Vec<LayoutBox>,
+    needs_wrapping: impl Fn(&LayoutBox) -> bool,
+    display: Display,
+    formatting_context: impl Fn() -> FormattingContextRef,
+) {
+    if !children.iter().any(&needs_wrapping) {
+        return;
+    }
+    let finish = |wrapper: BlockContainer| {
+        let mut wrapper = LayoutBox::from(BlockLevelBox::BlockContainer(wrapper));
+        fix_up_table_structure(&mut wrapper);
+        wrapper
+    };
+    let mut wrapper: Option<BlockContainer> = None;
+    for child in std::mem::take(children) {
+        if needs_wrapping(&child) {
+            wrapper
+                .get_or_insert_with(|| {
+                    BlockContainer::new_anonymous(child.node(), formatting_context(), display)
+                })
+                .add_child(child);
+            continue;
+        }
+        if let Some(wrapper) = wrapper.take() {
+            children.push(finish(wrapper));
+        }
+        children.push(child);
+    }
+    if let Some(wrapper) = wrapper {
+        children.push(finish(wrapper));
+    }
+}
+
 fn create_inline_container(node: NodeRef) -> LayoutBox {
     // Create a new IFC for this inline content.
     let mut anonymous_block_box =
@@ -0,0 +1,1020 @@
+use crate::behavior::{ApplyBoxSizingProperties, BaseLayoutBoxBehavior};
+use crate::containing_block::ContainingBlock;
+use crate::dimensions::Dimensions;
+use crate::flow::block::BlockLevelBox;
+use crate::flow::line::max_content_inline_size;
+use crate::flow::OriginRelativeProgression;
+use crate::layout_box::{
+    content_box_size, content_intrinsic_width, max_content_contribution, IntrinsicSize, LayoutBox,
+};
+use crate::text::TextMeasurer;
+use crate::{BoxComponent, LayoutContext};
+use primitives::rect::PositionedRect;
+use primitives::sides::FlowSide;
+use primitives::units::{CSSFloat, CSSPixelLength};
+use std::ops::Range;
+use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use style::values::computed::{
+    ComputedValues, ContentAlignment, Direction, FlexBasis, FlexDirection, FlexWrap, ItemAlignment,
+    MaxSize, Order, WritingMode,
+};
+use style::values::used::ToPx;
+
+/// One of the two axes of a writing mode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum FlowAxis {
+    Inline,
+    Block,
+}
+
+/// The main and cross axes of a flex container, and the directions flex items are laid out along
+/// them.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#box-model
+#[derive(Clone, Copy, Debug)]
+struct FlexAxes {
+    main: FlowAxis,
+    /// Whether main-start is the end of the main axis in the container's writing mode, as it is
+    /// for `row-reverse` and `column-reverse`.
+    main_reversed: bool,
+    /// Whether cross-start is the end of the cross axis in the container's writing mode, as it is
+    /// for `wrap-reverse`.
+    cross_reversed: bool,
+    writing_mode: WritingMode,
+    direction: Direction,
+}
+
+impl FlexAxes {
+    fn new(
+        flex_direction: FlexDirection,
+        flex_wrap: FlexWrap,
+        writing_mode: WritingMode,
+        direction: Direction,
+    ) -> FlexAxes {
+        FlexAxes {
+            main: if flex_direction.is_column() {
+                FlowAxis::Block
+            } else {
+                FlowAxis::Inline
+            },
+            main_reversed: flex_direction.is_reverse(),
+            cross_reversed: flex_wrap == FlexWrap::WrapReverse,
+            writing_mode,
+            direction,
+        }
+    }
+
+    fn cross(&self) -> FlowAxis {
+        match self.main {
+            FlowAxis::Inline => FlowAxis::Block,
+            FlowAxis::Block => FlowAxis::Inline,
+        }
+    }
+
+    /// The sides of the flex container at the start and end of `axis`, in the order flex items
+    /// are laid out along it.
+    fn sides(&self, axis: FlowAxis) -> (FlowSide, FlowSide) {
+        let (start, end) = match axis {
+            FlowAxis::Inline => (FlowSide::InlineStart, FlowSide::InlineEnd),
+            FlowAxis::Block => (FlowSide::BlockStart, FlowSide::BlockEnd),
+        };
+        if self.is_reversed(axis) {
+            (end, start)
+        } else {
+            (start, end)
+        }
+    }
+
+    fn is_reversed(&self, axis: FlowAxis) -> bool {
+        if axis == self.main {
+            self.main_reversed
+        } else {
+            self.cross_reversed
+        }
+    }
+
+    fn is_horizontal(&self, axis: FlowAxis) -> bool {
+        (axis == FlowAxis::Inline) == self.writing_mode.is_horizontal()
+    }
+
+    /// The size of the content box of `dimensions` along `axis`, expanded by `expanded_by`.
+    fn size(
+        &self,
+        dimensions: &Dimensions,
+        axis: FlowAxis,
+        expanded_by: Option<BoxComponent>,
+    ) -> CSSPixelLength {
+        let rect = match expanded_by {
+            None => dimensions.content,
+            Some(BoxComponent::Padding) => dimensions.padding_box(),
+            Some(BoxComponent::Border) => dimensions.border_box(),
+            Some(BoxComponent::Margin) => dimensions.margin_box(),
+        };
+        if self.is_horizontal(axis) {
+            rect.width()
+        } else {
+            rect.height()
+        }
+    }
+
+    fn set_size(&self, dimensions: &mut Dimensions, axis: FlowAxis, size: CSSPixelLength) {
+        match axis {
+            FlowAxis::Inline => dimensions.set_inline_size(size, self.writing_mode),
+            FlowAxis::Block => dimensions.set_block_size(size, self.writing_mode),
+        }
+    }
+
+    /// The sum of the padding and borders of `dimensions` on both sides of `axis`.
+    fn padding_border(&self, dimensions: &Dimensions, axis: FlowAxis) -> CSSPixelLength {
+        self.size(dimensions, axis, Some(BoxComponent::Border)) - self.size(dimensions, axis, None)
+    }
+
+    /// The `width` or `height` property of `cvs`, whichever applies along `axis`.
+    fn size_property(&self, cvs: &ComputedValues, axis: FlowAxis) -> LengthPercentageOrAuto {
+        match axis {
+            FlowAxis::Inline => cvs.inline_size(self.writing_mode),
+            FlowAxis::Block => cvs.block_size(self.writing_mode),
+        }
+    }
+
+    fn size_limits(
+        &self,
+        cvs: &ComputedValues,
+        axis: FlowAxis,
+        containing_size: Option<CSSPixelLength>,
+        padding_border: CSSPixelLength,
+    ) -> SizeLimits {
+        size_limits(
+            cvs,
+            axis,
+            self.writing_mode,
+            containing_size,
+            padding_border,
+        )
+    }
+
+    /// Moves `item`, whose margin box is at the origin, so that its margin box starts `offset`
+    /// from the start of `content` along `axis`, in the direction flex items are laid out in.
+    fn place(
+        &self,
+        item: &mut BlockLevelBox,
+        axis: FlowAxis,
+        content: PositionedRect,
+        offset: CSSPixelLength,
+    ) {
+        let reversed = self.is_reversed(axis);
+        place(
+            item,
+            axis,
+            reversed,
+            content,
+            offset,
+            self.writing_mode,
+            self.direction,
+        );
+    }
+}
+
+/// The limits the `min-` and `max-` sizing properties put on the content box of a box along an
+/// axis.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SizeLimits {
+    pub(crate) min: CSSPixelLength,
+    pub(crate) max: Option<CSSPixelLength>,
+}
+
+impl SizeLimits {
+    pub(crate) fn clamp(self, size: CSSPixelLength) -> CSSPixelLength {
+        self.max.map_or(size, |max| size.min(max)).max(self.min)
+    }
+}
+
+/// The content box size limits given by the `min-` and `max-` sizing properties of `cvs` along
+/// `axis` of `writing_mode`, where `containing_size` is what percentages refer to, if it's
+/// definite.
+pub(crate) fn size_limits(
+    cvs: &ComputedValues,
+    axis: FlowAxis,
+    writing_mode: WritingMode,
+    containing_size: Option<CSSPixelLength>,
+    padding_border: CSSPixelLength,
+) -> SizeLimits {
+    let (min, max) = match axis {
+        FlowAxis::Inline => (
+            cvs.min_inline_size(writing_mode),
+            cvs.max_inline_size(writing_mode),
+        ),
+        FlowAxis::Block => (
+            cvs.min_block_size(writing_mode),
+            cvs.max_block_size(writing_mode),
+        ),
+    };
+    let to_content_box = |size| content_box_size(size, cvs.box_sizing, padding_border);
+    let max = match max {
+        MaxSize::None => None,
+        MaxSize::LengthPercentage(lp) => definite_size(
+            &LengthPercentageOrAuto::LengthPercentage(lp),
+            containing_size,
+        ),
+    };
+    SizeLimits {
+        // TODO: The automatic minimum size of flex and grid items, which keeps them from
+        // shrinking below their min-content size, isn't implemented, so `auto` is zero.
+        min: definite_size(&min, containing_size).map_or(CSSPixelLength::new(0.), to_content_box),
+        max: max.map(to_content_box),
+    }
+}
+
+/// Moves `item`, whose margin box is at the origin, so that its margin box starts `offset` from
+/// the start of `content` along `axis` of `writing_mode`, or from its end if `reversed`.
+pub(crate) fn place(
+    item: &mut BlockLevelBox,
+    axis: FlowAxis,
+    reversed: bool,
+    content: PositionedRect,
+    offset: CSSPixelLength,
+    writing_mode: WritingMode,
+    direction: Direction,
+) {
+    let progression = match axis {
+        FlowAxis::Inline => OriginRelativeProgression::inline_start_origin_relative_direction(
+            writing_mode,
+            direction,
+        ),
+        FlowAxis::Block => {
+            OriginRelativeProgression::block_start_origin_relative_direction(writing_mode)
+        }
+    };
+    let towards_origin = (progression == OriginRelativeProgression::TowardsOrigin) != reversed;
+    let margin_box = item.dimensions().margin_box();
+    let horizontal = (axis == FlowAxis::Inline) == writing_mode.is_horizontal();
+    let (content_start, content_size, item_start, item_size) = if horizontal {
+        (
+            content.start_x,
+            content.width(),
+            margin_box.start_x,
+            margin_box.width(),
+        )
+    } else {
+        (
+            content.start_y,
+            content.height(),
+            margin_box.start_y,
+            margin_box.height(),
+        )
+    };
+    let target = if towards_origin {
+        content_start + (content_size - offset - item_size).px()
+    } else {
+        content_start + offset.px()
+    };
+    if horizontal {
+        item.translate(target - item_start, 0.);
+    } else {
+        item.translate(0., target - item_start);
+    }
+}
+
+/// A flex item, and the sizes the flex layout algorithm resolves for it.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-items
+#[derive(Clone, Debug)]
+struct FlexItem {
+    /// The index of the item among the children of the flex container.
+    index: usize,
+    order: Order,
+    /// The sum of the margins, borders, and padding of the item along the main axis, where `auto`
+    /// margins are zero.
+    main_edges: CSSPixelLength,
+    main_limits: SizeLimits,
+    /// The flex base size and hypothetical main size of the item's content box.
+    ///
+    /// https://drafts.csswg.org/css-flexbox-1/#algo-main-item
+    base_size: CSSPixelLength,
+    hypothetical_size: CSSPixelLength,
+    /// The main size of the item's content box, once flexible lengths are resolved.
+    main_size: CSSPixelLength,
+    frozen: bool,
+    grow: CSSFloat,
+    shrink: CSSFloat,
+    alignment: ItemAlignment,
+    /// Whether the main-start and main-end margins of the item are `auto`.
+    auto_main_margins: (bool, bool),
+    /// Whether the cross-start and cross-end margins of the item are `auto`.
+    auto_cross_margins: (bool, bool),
+    /// Whether the item is stretched to the cross size of its line.
+    stretched: bool,
+    cross_limits: SizeLimits,
+    /// The distance from the cross-start edge of the item's margin box to its baseline, if it
+    /// participates in baseline alignment.
+    baseline: Option<CSSPixelLength>,
+}
+
+impl FlexItem {
+    fn outer_hypothetical_size(&self) -> CSSPixelLength {
+        self.hypothetical_size + self.main_edges
+    }
+
+    fn outer_main_size(&self) -> CSSPixelLength {
+        self.main_size + self.main_edges
+    }
+}
+
+/// A flex line, and where it's placed along the cross axis of the flex container.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-lines
+#[derive(Clone, Debug)]
+struct FlexLine {
+    /// The range of the line's items in the list of flex items in `order`.
+    items: Range<usize>,
+    cross_size: CSSPixelLength,
+    /// The largest distance from the cross-start edge of the margin box of a baseline-aligned
+    /// item on the line to its baseline.
+    max_baseline: CSSPixelLength,
+    /// The distance from the cross-start edge of the container's content box to the line.
+    offset: CSSPixelLength,
+}
+
+/// Lays out the children of the flex container `container` as flex items.  The content box inline
+/// size of the container must already be known, and its block size is set to the size of its
+/// lines if it's `auto`.  Absolutely positioned children aren't flex items, and are placed at the
+/// start of the container's content box.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#layout-algorithm
+pub(crate) fn layout_flex_container(container: &mut BlockLevelBox, context: &LayoutContext) {
+    let zero = CSSPixelLength::new(0.);
+    let cvs = container.computed_values();
+    let writing_mode = cvs.writing_mode;
+    let direction = cvs.direction;
+    let axes = FlexAxes::new(cvs.flex_direction, cvs.flex_wrap, writing_mode, direction);
+    let multi_line = cvs.flex_wrap != FlexWrap::Nowrap;
+    let justify_content = cvs.justify_content;
+    let align_content = cvs.align_content;
+    let align_items = cvs.align_items;
+
+    let mut dimensions = container.dimensions();
+    let inline_size = dimensions.get_inline_size(None, writing_mode);
+    let block_padding_border = axes.padding_border(&dimensions, FlowAxis::Block);
+    let cb_block_size = context.containing_block.block_size(writing_mode);
+    let block_limits = axes.size_limits(
+        &cvs,
+        FlowAxis::Block,
+        Some(cb_block_size),
+        block_padding_border,
+    );
+    let block_size = match cvs.block_size(writing_mode) {
+        LengthPercentageOrAuto::LengthPercentage(lp) => Some(block_limits.clamp(content_box_size(
+            lp.to_px(cb_block_size),
+            cvs.box_sizing,
+            block_padding_border,
+        ))),
+        LengthPercentageOrAuto::Auto => None,
+    };
+    let inline_gap = cvs.column_gap.to_px(inline_size);
+    let block_gap = cvs.row_gap.to_px(block_size.unwrap_or(zero));
+    drop(cvs);
+    let (inner_main, inner_cross, main_gap, cross_gap, main_limits) = match axes.main {
+        FlowAxis::Inline => (
+            Some(inline_size),
+            block_size,
+            inline_gap,
+            block_gap,
+            SizeLimits {
+                min: inline_size,
+                max: Some(inline_size),
+            },
+        ),
+        FlowAxis::Block => (
+            block_size,
+            Some(inline_size),
+            block_gap,
+            inline_gap,
+            block_limits,
+        ),
+    };
+
+    // Flex items are laid out against the content box of the container, with any definite block
+    // size it has.  Absolutely positioned children get their static position from a containing
+    // block with no block size, so they're placed at its start.
+    dimensions.set_block_size(block_size.unwrap_or(zero), writing_mode);
+    let item_context = LayoutContext::new(
+        ContainingBlock::new(dimensions.content, direction, writing_mode),
+        context.layout_viewport,
+        context.text_measurer,
+    );
+    dimensions.set_block_size(zero, writing_mode);
+    let static_position_context = LayoutContext::new(
+        ContainingBlock::new(dimensions.content, direction, writing_mode),
+        context.layout_viewport,
+        context.text_measurer,
+    );
+
+    let children = container.children_mut();
+    let mut items = Vec::new();
+    for (index, child) in children.iter_mut().enumerate() {
+        let is_absolutely_positioned = child.position().is_absolutely_positioned();
+        // The box tree only gives flex containers block-level children.
+        let item = match child {
+            LayoutBox::BlockLevel(blb) => blb,
+            LayoutBox::InlineLevel(_) => continue,
+        };
+        if is_absolutely_positioned {
+            item.solve_and_set_inline_level_properties(&static_position_context);
+            item.solve_and_set_block_level_properties(&static_position_context);
+            continue;
+        }
+        items.push(new_flex_item(
+            item,
+            index,
+            &axes,
+            &item_context,
+            inner_main,
+            inner_cross,
+            align_items,
+            multi_line,
+        ));
+    }
+    items.sort_by_key(|item| item.order);
+
+    // Collect the items into flex lines.
+    let available_main = inner_main.map_or(CSSFloat::INFINITY, |size| size.px());
+    let mut line_ranges = Vec::new();
+    let mut line_start = 0;
+    let mut line_main_size = zero;
+    for (i, item) in items.iter().enumerate() {
+        let outer_size = item.outer_hypothetical_size();
+        if i == line_start {
+            line_main_size = outer_size;
+        } else if multi_line && line_main_size + main_gap + outer_size > available_main {
+            line_ranges.push(line_start..i);
+            line_start = i;
+            line_main_size = outer_size;
+        } else {
+            line_main_size += main_gap + outer_size;
+        }
+    }
+    if !items.is_empty() {
+        line_ranges.push(line_start..items.len());
+    }
+
+    let gaps = |count: usize, gap: CSSPixelLength| gap * count.saturating_sub(1) as CSSFloat;
+    let container_main = inner_main.unwrap_or_else(|| {
+        main_limits.clamp(line_ranges.iter().fold(zero, |largest, range| {
+            let line_size = items[range.clone()]
+                .iter()
+                .fold(zero, |sum, item| sum + item.outer_hypothetical_size());
+            largest.max(line_size + gaps(range.len(), main_gap))
+        }))
+    });
+    for range in &line_ranges {
+        let available = container_main - gaps(range.len(), main_gap);
+        resolve_flexible_lengths(&mut items[range.clone()], available);
+    }
+
+    // Lay out the items at their main sizes to find their hypothetical cross sizes.
+    for item in &items {
+        let item_box = item_box(children, item.index);
+        match axes.main {
+            FlowAxis::Inline => {
+                layout_item_contents(item_box, &item_context, item.main_size, writing_mode);
+                item_box.apply_box_sizing_properties(item_context.containing_block);
+                axes.set_size(item_box.dimensions_mut(), FlowAxis::Inline, item.main_size);
+            }
+            FlowAxis::Block => {
+                axes.set_size(item_box.dimensions_mut(), FlowAxis::Block, item.main_size);
+            }
+        }
+    }
+    for item in &mut items {
+        let item_box = item_box(children, item.index);
+        let participates_in_baseline = item.alignment == ItemAlignment::Baseline
+            && item.auto_cross_margins == (false, false)
+            && axes.main == FlowAxis::Inline
+            && writing_mode.is_horizontal()
+            && !axes.cross_reversed;
+        if participates_in_baseline {
+            let margin_box = item_box.dimensions().margin_box();
+            let border_box = item_box.dimensions().border_box();
+            let baseline = item_box
+                .first_line_baseline()
+                .unwrap_or(border_box.start_y + border_box.height().px());
+            item.baseline = Some(CSSPixelLength::new(baseline - margin_box.start_y));
+        }
+    }
+
+    // Determine the cross size of each line.
+    let single_line_cross = if multi_line { None } else { inner_cross };
+    let mut lines = line_ranges
+        .into_iter()
+        .map(|range| {
+            let mut max_baseline = zero;
+            let mut max_descent = zero;
+            let mut max_outer_cross = zero;
+            for item in &items[range.clone()] {
+                let dimensions = item_box(children, item.index).dimensions();
+                let outer_cross = axes.size(&dimensions, axes.cross(), Some(BoxComponent::Margin));
+                match item.baseline {
+                    Some(baseline) => {
+                        max_baseline.max_assign(baseline);
+                        max_descent.max_assign(outer_cross - baseline);
+                    }
+                    None => max_outer_cross.max_assign(outer_cross),
+                }
+            }
+            FlexLine {
+                items: range,
+                cross_size: single_line_cross
+                    .unwrap_or_else(|| max_outer_cross.max(max_baseline + max_descent)),
+                max_baseline,
+                offset: zero,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let lines_cross_size =
+        lines.iter().fold(zero, |sum, line| sum + line.cross_size) + gaps(lines.len(), cross_gap);
+    let container_cross = inner_cross.unwrap_or_else(|| block_limits.clamp(lines_cross_size));
+
+    // Align the lines within the container with `align-content`.
+    let free_space = container_cross - lines_cross_size;
+    let (leading_space, between_space) = match align_content {
+        ContentAlignment::Normal | ContentAlignment::Stretch if multi_line => {
+            if free_space > 0. {
+                let extra = free_space / lines.len() as CSSFloat;
+                for line in &mut lines {
+                    line.cross_size += extra;
+                }
+            }
+            (zero, zero)
+        }
+        _ if multi_line => distribute(align_content, free_space, lines.len(), axes.cross_reversed),
+        _ => (zero, zero),
+    };
+    let mut cursor = leading_space;
+    for line in &mut lines {
+        line.offset = cursor;
+        cursor += line.cross_size + cross_gap + between_space;
+    }
+
+    // Stretch the items with an `auto` cross size to the cross size of their lines.
+    for line in &lines {
+        for item in &items[line.items.clone()] {
+            if !item.stretched {
+                continue;
+            }
+            let item_box = item_box(children, item.index);
+            let dimensions = item_box.dimensions();
+            let cross_edges = axes.size(&dimensions, axes.cross(), Some(BoxComponent::Margin))
+                - axes.size(&dimensions, axes.cross(), None);
+            let cross_size = item
+                .cross_limits
+                .clamp((line.cross_size - cross_edges).clamp_to_non_negative());
+            match axes.main {
+                FlowAxis::Inline => {
+                    axes.set_size(item_box.dimensions_mut(), FlowAxis::Block, cross_size);
+                }
+                FlowAxis::Block => {
+                    if cross_size != axes.size(&dimensions, FlowAxis::Inline, None) {
+                        layout_item_contents(item_box, &item_context, cross_size, writing_mode);
+                        axes.set_size(item_box.dimensions_mut(), FlowAxis::Block, item.main_size);
+                    }
+                }
+            }
+        }
+    }
+
+    // Size the container, so items can be placed from the end of its content box.
+    let container_block_size = match axes.main {
+        FlowAxis::Inline => container_cross,
+        FlowAxis::Block => container_main,
+    };
+    container
+        .dimensions_mut()
+        .set_block_size(container_block_size, writing_mode);
+    let content = container.dimensions().content;
+    let children = container.children_mut();
+
+    for line in &lines {
+        let line_items = &items[line.items.clone()];
+
+        // Distribute free space along the main axis to `auto` margins, or else with
+        // `justify-content`.
+        let used_main = line_items
+            .iter()
+            .fold(zero, |sum, item| sum + item.outer_main_size())
+            + gaps(line_items.len(), main_gap);
+        let free_space = container_main - used_main;
+        let auto_margin_count = line_items.iter().fold(0, |count, item| {
+            count + item.auto_main_margins.0 as usize + item.auto_main_margins.1 as usize
+        });
+        let auto_margin = if auto_margin_count > 0 && free_space > 0. {
+            free_space / auto_margin_count as CSSFloat
+        } else {
+            zero
+        };
+        let (leading_space, between_space) = if auto_margin_count > 0 {
+            (zero, zero)
+        } else {
+            distribute(
+                justify_content,
+                free_space,
+                line_items.len(),
+                axes.main_reversed,
+            )
+        };
+
+        let mut cursor = leading_space;
+        for item in line_items {
+            let item_box = item_box(children, item.index);
+            set_auto_margins(
+                item_box,
+                &axes,
+                item.auto_main_margins,
+                axes.sides(axes.main),
+                (auto_margin, auto_margin),
+            );
+            let dimensions = item_box.dimensions();
+            let outer_main = axes.size(&dimensions, axes.main, Some(BoxComponent::Margin));
+            axes.place(item_box, axes.main, content, cursor);
+            cursor += outer_main + main_gap + between_space;
+
+            // Align the item within its line along the cross axis with `auto` margins, or else
+            // with `align-self`.
+            let outer_cross = axes.size(&dimensions, axes.cross(), Some(BoxComponent::Margin));
+            let free_space = line.cross_size - outer_cross;
+            let offset = if item.auto_cross_margins != (false, false) {
+                if free_space > 0. {
+                    let margins = match item.auto_cross_margins {
+                        (true, true) => (free_space / 2., free_space / 2.),
+                        _ => (free_space, free_space),
+                    };
+                    set_auto_margins(
+                        item_box,
+                        &axes,
+                        item.auto_cross_margins,
+                        axes.sides(axes.cross()),
+                        margins,
+                    );
+                }
+                zero
+            } else {
+                match item.alignment {
+                    ItemAlignment::Normal | ItemAlignment::Stretch | ItemAlignment::FlexStart => {
+                        zero
+                    }
+                    ItemAlignment::Start | ItemAlignment::SelfStart if axes.cross_reversed => {
+                        free_space
+                    }
+                    ItemAlignment::Start | ItemAlignment::SelfStart => zero,
+                    ItemAlignment::End | ItemAlignment::SelfEnd if axes.cross_reversed => zero,
+                    ItemAlignment::End | ItemAlignment::SelfEnd | ItemAlignment::FlexEnd => {
+                        free_space
+                    }
+                    ItemAlignment::Center => free_space / 2.,
+                    ItemAlignment::Baseline => item
+                        .baseline
+                        .map_or(zero, |baseline| line.max_baseline - baseline),
+                }
+            };
+            axes.place(item_box, axes.cross(), content, line.offset + offset);
+        }
+    }
+}
+
+/// Creates the flex item for `item`, the child of a flex container at `index`, finding its flex
+/// base size and hypothetical main size.  `inner_main` and `inner_cross` are the sizes of the
+/// container's content box, if they're definite.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#algo-main-item
+#[allow(clippy::too_many_arguments)]
+fn new_flex_item(
+    item: &mut BlockLevelBox,
+    index: usize,
+    axes: &FlexAxes,
+    context: &LayoutContext,
+    inner_main: Option<CSSPixelLength>,
+    inner_cross: Option<CSSPixelLength>,
+    align_items: ItemAlignment,
+    multi_line: bool,
+) -> FlexItem {
+    let writing_mode = axes.writing_mode;
+    item.place_edges_at_origin(context.containing_block);
+    let dimensions = item.dimensions();
+    let main_padding_border = axes.padding_border(&dimensions, axes.main);
+    let cross_padding_border = axes.padding_border(&dimensions, axes.cross());
+    let main_edges = axes.size(&dimensions, axes.main, Some(BoxComponent::Margin))
+        - axes.size(&dimensions, axes.main, None);
+    let cross_edges = axes.size(&dimensions, axes.cross(), Some(BoxComponent::Margin))
+        - axes.size(&dimensions, axes.cross(), None);
+
+    let cvs = item.computed_values();
+    let is_auto =
+        |side| cvs.margin_flow_relative(side, writing_mode) == LengthPercentageOrAuto::Auto;
+    let (main_start, main_end) = axes.sides(axes.main);
+    let (cross_start, cross_end) = axes.sides(axes.cross());
+    let auto_main_margins = (is_auto(main_start), is_auto(main_end));
+    let auto_cross_margins = (is_auto(cross_start), is_auto(cross_end));
+    let alignment = cvs.align_self.resolve(align_items);
+    let cross_size_property = axes.size_property(&cvs, axes.cross());
+    let stretched = matches!(alignment, ItemAlignment::Normal | ItemAlignment::Stretch)
+        && cross_size_property == LengthPercentageOrAuto::Auto
+        && auto_cross_margins == (false, false);
+    let main_limits = axes.size_limits(&cvs, axes.main, inner_main, main_padding_border);
+    let cross_limits = axes.size_limits(&cvs, axes.cross(), inner_cross, cross_padding_border);
+    let box_sizing = cvs.box_sizing;
+    let basis = match &cvs.flex_basis {
+        FlexBasis::Width(LengthPercentageOrAuto::Auto) => axes.size_property(&cvs, axes.main),
+        FlexBasis::Width(lp_or_auto) => lp_or_auto.clone(),
+        FlexBasis::Content => LengthPercentageOrAuto::Auto,
+    };
+    let definite_basis = definite_size(&basis, inner_main)
+        .map(|size| content_box_size(size, box_sizing, main_padding_border));
+    let definite_cross_size = definite_size(&cross_size_property, inner_cross)
+        .map(|size| cross_limits.clamp(content_box_size(size, box_sizing, cross_padding_border)));
+    let order = cvs.order;
+    let grow = cvs.flex_grow.get();
+    let shrink = cvs.flex_shrink.get();
+    drop(cvs);
+
+    let base_size = match axes.main {
+        FlowAxis::Inline => {
+            definite_basis.unwrap_or_else(|| max_content_size(item, context.text_measurer))
+        }
+        FlowAxis::Block => {
+            // The inline size of an item in a column is known before its main size, so it's laid
+            // out here to measure its content.  A single-line container stretches its items to
+            // its own cross size, and others size them to fit their content until their lines are
+            // known.
+            let available = inner_cross.map_or(CSSPixelLength::new(0.), |size| size - cross_edges);
+            let inline_size = definite_cross_size.unwrap_or_else(|| {
+                if stretched && !multi_line {
+                    cross_limits.clamp(available.clamp_to_non_negative())
+                } else {
+                    let max_content = max_content_size(item, context.text_measurer);
+                    cross_limits.clamp(max_content.min(available).clamp_to_non_negative())
+                }
+            });
+            let content_size = layout_item_contents(item, context, inline_size, writing_mode);
+            definite_basis.unwrap_or(content_size)
+        }
+    };
+
+    FlexItem {
+        index,
+        order,
+        main_edges,
+        main_limits,
+        base_size,
+        hypothetical_size: main_limits.clamp(base_size),
+        main_size: base_size,
+        frozen: false,
+        grow,
+        shrink,
+        alignment,
+        auto_main_margins,
+        auto_cross_margins,
+        stretched,
+        cross_limits,
+        baseline: None,
+    }
+}
+
+/// Resolves the main sizes of the flex items on a line, growing or shrinking them by their flex
+/// factors to fill `available`, the main size of the container without the gaps between them.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#resolve-flexible-lengths
+fn resolve_flexible_lengths(items: &mut [FlexItem], available: CSSPixelLength) {
+    let zero = CSSPixelLength::new(0.);
+    let hypothetical_sum = items
+        .iter()
+        .fold(zero, |sum, item| sum + item.outer_hypothetical_size());
+    let growing = hypothetical_sum < available;
+    for item in items.iter_mut() {
+        let factor = if growing { item.grow } else { item.shrink };
+        item.frozen = factor == 0.
+            || (growing && item.base_size > item.hypothetical_size)
+            || (!growing && item.base_size < item.hypothetical_size);
+        item.main_size = if item.frozen {
+            item.hypothetical_size
+        } else {
+            item.base_size
+        };
+    }
+    let free_space = |items: &[FlexItem]| {
+        available
+            - items.iter().fold(zero, |sum, item| {
+                let size = if item.frozen {
+                    item.main_size
+                } else {
+                    item.base_size
+                };
+                sum + size + item.main_edges
+            })
+    };
+    let initial_free_space = free_space(items);
+
+    let mut violations = vec![zero; items.len()];
+    while items.iter().any(|item| !item.frozen) {
+        let mut remaining_free_space = free_space(items);
+        let factor_sum = items
+            .iter()
+            .filter(|item| !item.frozen)
+            .map(|item| if growing { item.grow } else { item.shrink })
+            .sum::<CSSFloat>();
+        if factor_sum < 1. {
+            let scaled = initial_free_space * factor_sum;
+            if scaled.abs() < remaining_free_space.abs() {
+                remaining_free_space = scaled;
+            }
+        }
+
+        let scaled_shrink_sum = items
+            .iter()
+            .filter(|item| !item.frozen)
+            .map(|item| item.shrink * item.base_size.px())
+            .sum::<CSSFloat>();
+        for item in items.iter_mut().filter(|item| !item.frozen) {
+            item.main_size = if remaining_free_space == zero {
+                item.base_size
+            } else if growing {
+                item.base_size + remaining_free_space * (item.grow / factor_sum)
+            } else if scaled_shrink_sum > 0. {
+                let ratio = item.shrink * item.base_size.px() / scaled_shrink_sum;
+                item.base_size - remaining_free_space.abs() * ratio
+            } else {
+                item.base_size
+            };
+        }
+
+        // Clamp the items by their min and max sizes, and freeze those that violated them in
+        // the direction of the total violation.
+        let mut total_violation = zero;
+        for (item, violation) in items.iter_mut().zip(violations.iter_mut()) {
+            if item.frozen {
+                continue;
+            }
+            let clamped = item
+                .main_limits
+                .clamp(item.main_size)
+                .clamp_to_non_negative();
+            *violation = clamped - item.main_size;
+            total_violation += *violation;
+            item.main_size = clamped;
+        }
+        for (item, violation) in items.iter_mut().zip(violations.iter()) {
+            item.frozen = item.frozen
+                || total_violation == zero
+                || (total_violation > 0. && *violation > 0.)
+                || (total_violation < 0. && *violation < 0.);
+        }
+    }
+}
+
+/// The space before the first of `count` items or lines and between each of them when `free_space`
+/// is distributed along an axis with `alignment`.  `reversed` is whether the start of the
+/// container's axis is at the end of its writing mode.
+///
+/// https://drafts.csswg.org/css-align-3/#distribution-values
+pub(crate) fn distribute(
+    alignment: ContentAlignment,
+    free_space: CSSPixelLength,
+    count: usize,
+    reversed: bool,
+) -> (CSSPixelLength, CSSPixelLength) {
+    let zero = CSSPixelLength::new(0.);
+    let count = count as CSSFloat;
+    match alignment {
+        ContentAlignment::Normal | ContentAlignment::Stretch | ContentAlignment::FlexStart => {
+            (zero, zero)
+        }
+        ContentAlignment::Start if reversed => (free_space, zero),
+        ContentAlignment::Start => (zero, zero),
+        ContentAlignment::End if reversed => (zero, zero),
+        ContentAlignment::End | ContentAlignment::FlexEnd => (free_space, zero),
+        ContentAlignment::Center => (free_space / 2., zero),
+        ContentAlignment::SpaceBetween if free_space > 0. && count > 1. => {
+            (zero, free_space / (count - 1.))
+        }
+        ContentAlignment::SpaceBetween => (zero, zero),
+        ContentAlignment::SpaceAround if free_space > 0. && count > 1. => {
+            (free_space / count / 2., free_space / count)
+        }
+        ContentAlignment::SpaceEvenly if free_space > 0. && count > 1. => {
+            (free_space / (count + 1.), free_space / (count + 1.))
+        }
+        ContentAlignment::SpaceAround | ContentAlignment::SpaceEvenly => (free_space / 2., zero),
+    }
+}
+
+/// Sets the margins of `item` on `sides` that are `auto`, as given by `auto_margins`, to
+/// `margins`.
+fn set_auto_margins(
+    item: &mut BlockLevelBox,
+    axes: &FlexAxes,
+    auto_margins: (bool, bool),
+    sides: (FlowSide, FlowSide),
+    margins: (CSSPixelLength, CSSPixelLength),
+) {
+    let dimensions = item.dimensions_mut();
+    if auto_margins.0 {
+        dimensions.set(
+            sides.0,
+            BoxComponent::Margin,
+            margins.0,
+            axes.writing_mode,
+            axes.direction,
+        );
+    }
+    if auto_margins.1 {
+        dimensions.set(
+            sides.1,
+            BoxComponent::Margin,
+            margins.1,
+            axes.writing_mode,
+            axes.direction,
+        );
+    }
+}
+
+/// Lays out the contents of the flex or grid item `item` at a content box inline size of
+/// `inline_size`, returning the block size of its contents.
+pub(crate) fn layout_item_contents(
+    item: &mut BlockLevelBox,
+    context: &LayoutContext,
+    inline_size: CSSPixelLength,
+    writing_mode: WritingMode,
+) -> CSSPixelLength {
+    let dimensions = item.dimensions_mut();
+    dimensions.set_inline_size(inline_size, writing_mode);
+    dimensions.set_block_size(CSSPixelLength::new(0.), writing_mode);
+    item.layout_children_in_new_formatting_context(context);
+    item.dimensions().content_box_block_size(writing_mode)
+}
+
+/// The max-content width of the content box of the flex item `item`.
+fn max_content_size(item: &BlockLevelBox, text_measurer: &dyn TextMeasurer) -> CSSPixelLength {
+    match item {
+        BlockLevelBox::AnonymousBlock(abb) => {
+            max_content_inline_size(abb.children(), text_measurer)
+        }
+        BlockLevelBox::BlockContainer(_) => content_intrinsic_width(
+            &item.computed_values(),
+            &item.formatting_context(),
+            item.children(),
+            IntrinsicSize::MaxContent,
+            text_measurer,
+        ),
+    }
+}
+
+/// The flex or grid item at `index` among the children of its container.
+pub(crate) fn item_box(children: &mut [LayoutBox], index: usize) -> &mut BlockLevelBox {
+    match &mut children[index] {
+        LayoutBox::BlockLevel(blb) => blb,
+        LayoutBox::InlineLevel(_) => panic!("flex and grid items should be block-level"),
+    }
+}
+
+/// The content box size given by `lp_or_auto` if it's definite: not `auto`, nor a percentage of a
+/// `containing_size` that isn't definite.
+pub(crate) fn definite_size(
+    lp_or_auto: &LengthPercentageOrAuto,
+    containing_size: Option<CSSPixelLength>,
+) -> Option<CSSPixelLength> {
+    match lp_or_auto {
+        LengthPercentageOrAuto::Auto => None,
+        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(length)) => Some(*length),
+        LengthPercentageOrAuto::LengthPercentage(lp) => containing_size.map(|size| lp.to_px(size)),
+    }
+}
+
+/// An approximation of the max-content width of a flex container with the computed values
+/// `container_values` and the given `children`: the sum of the max-content contributions of its
+/// items and the fixed gaps between them if they're laid out in a row, or the largest of those
+/// contributions in a column.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#intrinsic-main-sizes
+pub(crate) fn max_content_width(
+    container_values: &ComputedValues,
+    children: &[LayoutBox],
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    let zero = CSSPixelLength::new(0.);
+    let contributions = children
+        .iter()
+        .filter(|child| !child.position().is_absolutely_positioned())
+        .map(|child| match child {
+            LayoutBox::BlockLevel(BlockLevelBox::AnonymousBlock(abb)) => {
+                max_content_inline_size(abb.children(), text_measurer)
+            }
+            _ => max_content_contribution(child, text_measurer),
+        });
+    if container_values.flex_direction.is_column() {
+        return contributions.fold(zero, CSSPixelLength::max);
+    }
+    let gap = container_values.column_gap.to_px(zero);
+    let (count, sum) = contributions.fold((0, zero), |(count, sum), contribution| {
+        (count + 1, sum + contribution)
+    });
+    sum + gap * (count as CSSFloat - 1.).max(0.)
+}
@@ -2,18 +2,29 @@ use crate::apply_box_sizing_properties_base_box_passthrough_impls;
 use crate::behavior::{ApplyBoxSizingProperties, BaseLayoutBoxBehavior};
 use crate::containing_block::ContainingBlock;
 use crate::dimensions::Dimensions;
+use crate::flex;
+use crate::flow::float::{layout_float, FloatContext};
+use crate::flow::line::{layout_inline_formatting_context, LineBox};
+use crate::flow::margin_collapse::{CollapsedBlockMargins, CollapsedMargin};
 use crate::flow::{BlockContainer, OriginRelativeProgression};
 use crate::formatting_context::FormattingContextRef;
-use crate::layout_box::{get_anonymous_inline_layout_box, BaseBox, LayoutBox};
+use crate::grid;
+use crate::layout_box::{
+    content_box_size, content_intrinsic_width, get_anonymous_inline_layout_box, BaseBox,
+    IntrinsicSize, LayoutBox,
+};
 use crate::layout_box_behavior_base_box_passthrough_impls;
+use crate::multicol;
+use crate::table;
 use crate::{BoxComponent, DumpLayoutFormat, Layout, LayoutContext};
 use accountable_refcell::Ref;
 use dom::tree::NodeRef;
 use enum_dispatch::enum_dispatch;
+use primitives::rect::{PositionedRect, Rect};
 use primitives::sides::{FlowSide, PhysicalSide};
 use primitives::units::{CSSFloat, CSSPixelLength};
 use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
-use style::values::computed::ComputedValues;
+use style::values::computed::{BoxSizing, Clear, ComputedValues, Float, MaxSize};
 use style::values::used::ToPx;
 
 #[enum_dispatch]
@@ -47,6 +58,72 @@ impl BlockLevelBox {
         }
     }
 
+    pub fn children_mut(&mut self) -> &mut Vec<LayoutBox> {
+        match self {
+            BlockLevelBox::AnonymousBlock(ab) => ab.children_mut(),
+            BlockLevelBox::BlockContainer(bc) => bc.children_mut(),
+        }
+    }
+
+    /// Moves this box and all of its descendants by `dx` horizontally and `dy` vertically.
+    pub fn translate(&mut self, dx: CSSFloat, dy: CSSFloat) {
+        let content = &mut self.dimensions_mut().content;
+        content.start_x += dx;
+        content.start_y += dy;
+        if let BlockLevelBox::AnonymousBlock(abb) = self {
+            for line_box in &mut abb.line_boxes {
+                line_box.rect.start_x += dx;
+                line_box.rect.start_y += dy;
+            }
+        }
+        for child in self.children_mut() {
+            child.translate(dx, dy);
+        }
+    }
+
+    /// The y-coordinate of the baseline of the last line box in normal flow inside this box, if
+    /// there is one.
+    pub(crate) fn last_line_baseline(&self) -> Option<CSSFloat> {
+        match self {
+            BlockLevelBox::AnonymousBlock(abb) => abb
+                .line_boxes()
+                .last()
+                .map(|line_box| line_box.rect.start_y + line_box.baseline.px()),
+            BlockLevelBox::BlockContainer(bc) => bc
+                .children()
+                .iter()
+                .rev()
+                .filter(|child| {
+                    !child.position().is_absolutely_positioned() && child.float() == Float::None
+                })
+                .find_map(|child| match child {
+                    LayoutBox::BlockLevel(blb) => blb.last_line_baseline(),
+                    LayoutBox::InlineLevel(_) => None,
+                }),
+        }
+    }
+
+    /// The y-coordinate of the baseline of the first line box in normal flow inside this box, if
+    /// there is one.
+    pub(crate) fn first_line_baseline(&self) -> Option<CSSFloat> {
+        match self {
+            BlockLevelBox::AnonymousBlock(abb) => abb
+                .line_boxes()
+                .first()
+                .map(|line_box| line_box.rect.start_y + line_box.baseline.px()),
+            BlockLevelBox::BlockContainer(bc) => bc
+                .children()
+                .iter()
+                .filter(|child| {
+                    !child.position().is_absolutely_positioned() && child.float() == Float::None
+                })
+                .find_map(|child| match child {
+                    LayoutBox::BlockLevel(blb) => blb.first_line_baseline(),
+                    LayoutBox::InlineLevel(_) => None,
+                }),
+        }
+    }
+
     pub fn get_mut_inline_container(&mut self) -> Option<&mut LayoutBox> {
         match self {
             BlockLevelBox::AnonymousBlock(abb) => {
@@ -84,11 +161,9 @@ impl BlockLevelBox {
         let flipped_block_start_coord =
             compute_flipped_block_start_coord(ComputeFlippedBlockStartCoordInput {
                 layout_viewport_block_size,
-                containing_block_block_size: containing_block.self_relative_block_size(),
                 content_box_block_size: self
                     .dimensions()
                     .content_box_block_size(containing_writing_mode),
-                preferred_block_size: self.computed_values().block_size(containing_writing_mode),
                 current_block_start_coord: self
                     .dimensions()
                     .get_block_start_coord(containing_writing_mode),
@@ -97,15 +172,197 @@ impl BlockLevelBox {
             .set_block_start_coord(flipped_block_start_coord, containing_writing_mode);
     }
 
-    fn layout_children(&mut self, context: &LayoutContext) {
+    /// Lays out the children of this box in a new block formatting context, which this box grows
+    /// to contain the floats of when its height is `auto`.  The margins of the children don't
+    /// collapse with those of this box.  The children of flex and grid containers are laid out as
+    /// flex or grid items instead, those of tables as the parts of a table, and those of
+    /// multi-column containers in columns.
+    ///
+    /// https://www.w3.org/TR/CSS22/visudet.html#root-height
+    pub(crate) fn layout_children_in_new_formatting_context(&mut self, context: &LayoutContext) {
+        if self.formatting_context().is_flex_formatting_context() {
+            flex::layout_flex_container(self, context);
+            return;
+        }
+        if self.formatting_context().is_grid_formatting_context() {
+            grid::layout_grid_container(self, context);
+            return;
+        }
+        if self.formatting_context().is_table_formatting_context() {
+            table::layout_table(self, context);
+            return;
+        }
+        if matches!(self, BlockLevelBox::BlockContainer(_))
+            && self.computed_values().is_multicol_container()
+        {
+            multicol::layout_multicol_container(self, context);
+            return;
+        }
+        self.layout_children_in_new_block_formatting_context(context);
+    }
+
+    /// Lays out the children of this box in a new block formatting context, growing this box to
+    /// contain their floats, like `layout_children_in_new_formatting_context` does for boxes that
+    /// aren't flex or grid containers, tables, or multi-column containers.
+    pub(crate) fn layout_children_in_new_block_formatting_context(
+        &mut self,
+        context: &LayoutContext,
+    ) {
+        let mut floats = FloatContext::new(self.formatting_context());
+        self.layout_children(context, &mut floats, None, false);
+        if let Some(lowest_float_bottom) = floats.lowest_float_bottom() {
+            let content = self.dimensions().content;
+            let float_containing_height =
+                CSSPixelLength::new(lowest_float_bottom - content.start_y);
+            if float_containing_height > content.height() {
+                self.dimensions_mut().set_height(float_containing_height);
+            }
+        }
+    }
+
+    /// Lays out this box with its margin box at the origin, establishing a new block formatting
+    /// context for its children.  An `auto` width is the shrink-to-fit width, and `auto` margins
+    /// are zero.  Floats and inline-blocks are sized this way, and then moved to wherever they end
+    /// up being placed.
+    ///
+    /// https://www.w3.org/TR/CSS22/visudet.html#float-width
+    /// https://www.w3.org/TR/CSS22/visudet.html#inlineblock-width
+    /// https://www.w3.org/TR/CSS22/visudet.html#block-root-margin
+    pub(crate) fn layout_shrink_to_fit(&mut self, context: &LayoutContext) {
+        let containing_block = context.containing_block;
+        let cb_width = containing_block.width();
+        self.place_edges_at_origin(containing_block);
+
+        let dimensions = self.dimensions();
+        let padding_border_width = dimensions.get_padding_physical(PhysicalSide::Left)
+            + dimensions.get_padding_physical(PhysicalSide::Right)
+            + dimensions.get_border_physical(PhysicalSide::Left)
+            + dimensions.get_border_physical(PhysicalSide::Right);
+        let margin_width = dimensions.get_margin_physical(PhysicalSide::Left)
+            + dimensions.get_margin_physical(PhysicalSide::Right);
+        let cvs = self.computed_values();
+        let to_content_box = |size| content_box_size(size, cvs.box_sizing, padding_border_width);
+        let mut width = match &cvs.width.size {
+            LengthPercentageOrAuto::LengthPercentage(lp) => to_content_box(lp.to_px(cb_width)),
+            LengthPercentageOrAuto::Auto => {
+                // The shrink-to-fit width: the available width, but no narrower than the
+                // preferred minimum (min-content) width and no wider than the preferred
+                // (max-content) width.
+                let available_width = cb_width - margin_width - padding_border_width;
+                let intrinsic_width = |size| {
+                    content_intrinsic_width(
+                        &cvs,
+                        &self.formatting_context(),
+                        self.children(),
+                        size,
+                        context.text_measurer,
+                    )
+                };
+                let min_content_width = intrinsic_width(IntrinsicSize::MinContent);
+                let max_content_width = intrinsic_width(IntrinsicSize::MaxContent);
+                available_width
+                    .max(min_content_width)
+                    .min(max_content_width)
+            }
+        };
+        if let Some(max_width) = cvs.max_width.to_px(cb_width) {
+            width = width.min(to_content_box(max_width));
+        }
+        width = width.max(to_content_box(cvs.min_width.size.to_px(cb_width)));
+        drop(cvs);
+
+        let dimensions = self.dimensions_mut();
+        dimensions.set_width(width);
+        dimensions.set_height(CSSPixelLength::new(0.));
+        self.layout_children_in_new_formatting_context(context);
+        self.apply_box_sizing_properties(containing_block);
+    }
+
+    /// Sets the margins, borders, and padding of this box, and places its content box so that its
+    /// margin box is at the origin.  `auto` margins are zero, and percentages refer to the width
+    /// of `containing_block` on every side.
+    pub(crate) fn place_edges_at_origin(&mut self, containing_block: ContainingBlock) {
+        let cb_width = containing_block.width();
+        let cvs = self.computed_values();
+        let margin = |lp_or_auto: &LengthPercentageOrAuto| lp_or_auto.to_px(cb_width);
+        let padding = |lp: &LengthPercentage| lp.to_px(cb_width);
+        let margins = [
+            (PhysicalSide::Left, margin(&cvs.margin_left.size)),
+            (PhysicalSide::Right, margin(&cvs.margin_right.size)),
+            (PhysicalSide::Top, margin(&cvs.margin_top.size)),
+            (PhysicalSide::Bottom, margin(&cvs.margin_bottom.size)),
+        ];
+        let paddings = [
+            (PhysicalSide::Left, padding(&cvs.padding_left.size)),
+            (PhysicalSide::Right, padding(&cvs.padding_right.size)),
+            (PhysicalSide::Top, padding(&cvs.padding_top.size)),
+            (PhysicalSide::Bottom, padding(&cvs.padding_bottom.size)),
+        ];
+        let borders = [
+            (PhysicalSide::Left, cvs.border_left_width.size),
+            (PhysicalSide::Right, cvs.border_right_width.size),
+            (PhysicalSide::Top, cvs.border_top_width.size),
+            (PhysicalSide::Bottom, cvs.border_bottom_width.size),
+        ];
+        drop(cvs);
+
+        let dimensions = self.dimensions_mut();
+        for &(side, size) in &margins {
+            dimensions.set_margin_physical(side, size);
+        }
+        for &(side, size) in &paddings {
+            dimensions.set_padding_physical(side, size);
+        }
+        for &(side, size) in &borders {
+            dimensions.set_border_physical(side, size);
+        }
+        dimensions.set_start_x((margins[0].1 + borders[0].1 + paddings[0].1).px());
+        dimensions.set_start_y((margins[2].1 + borders[2].1 + paddings[2].1).px());
+    }
+
+    /// Lays out the children of this box, which belong to the block formatting context whose
+    /// floats are `floats`, collapsing the adjoining margins of the in-flow children.
+    ///
+    /// `start_margin` is `Some` if this box's block-start margin adjoins that of its first in-flow
+    /// child, holding the collapsed margin this box is currently placed after.  This box is then
+    /// moved down by however much its children's margins grow that collapsed margin.
+    /// `collapse_end` is whether this box's block-end margin adjoins that of its last in-flow
+    /// child.  Returns the margins of the children that collapse with this box's own margins.
+    ///
+    /// https://www.w3.org/TR/CSS22/box.html#collapsing-margins
+    pub(crate) fn layout_children(
+        &mut self,
+        context: &LayoutContext,
+        floats: &mut FloatContext,
+        mut start_margin: Option<CollapsedMargin>,
+        collapse_end: bool,
+    ) -> CollapsedBlockMargins {
+        if let BlockLevelBox::AnonymousBlock(abb) = self {
+            // Anonymous block boxes hold the inline-level content of their block container, which
+            // is laid out into line boxes.  Line boxes separate the margins around them.
+            let has_line_boxes = layout_inline_formatting_context(abb, context, floats);
+            return CollapsedBlockMargins {
+                collapsed_through: !has_line_boxes,
+                ..CollapsedBlockMargins::default()
+            };
+        }
         let direction = self.computed_values().direction;
         let writing_mode = self.computed_values().writing_mode;
+        let containing_writing_mode = context.containing_block.writing_mode();
 
         let (children, self_dimensions) = match self {
             BlockLevelBox::AnonymousBlock(abb) => (&mut abb.children, abb.base.dimensions_mut()),
             BlockLevelBox::BlockContainer(bc) => (&mut bc.children, bc.base.dimensions_mut()),
         };
-        for child in children {
+        // The margins of the children that collapse with this box's block-start margin.
+        let mut children_start_margin = CollapsedMargin::zero();
+        // The margins after the last in-flow child that haven't been placed yet, since they may
+        // still collapse with the margins of the children after it.
+        let mut pending_margin = CollapsedMargin::zero();
+        let first_float = floats.float_count();
+        for index in 0..children.len() {
+            let (preceding_children, children) = children.split_at_mut(index);
+            let child = &mut children[0];
             // The rectangle selected as the containing block will need to change when we support other
             // `position` property types (e.g. some may want the content-box, others the margin-box, etc).
             // For now, the behavior of the default `position` value, "static" is hardcoded here.
@@ -113,18 +370,302 @@ impl BlockLevelBox {
             // 10.1.2: For other [not-root] elements, if the element's position is 'relative' or
             // 'static', the containing block is formed by the content edge of the nearest block
             // container ancestor box.
-            child.layout(LayoutContext::new(
+            let child_context = LayoutContext::new(
                 ContainingBlock::new(self_dimensions.content, direction, writing_mode),
                 context.layout_viewport,
-            ));
+                context.text_measurer,
+            );
+            if child.position().is_absolutely_positioned() {
+                // Absolutely positioned boxes are laid out against their own containing block once
+                // its size is known, and take up no space in flow.  For now, solve where the box
+                // would be were it `position: static`, which its `auto` insets are relative to.
+                // https://www.w3.org/TR/CSS22/visudet.html#abs-non-replaced-width
+                if let LayoutBox::BlockLevel(blb) = child {
+                    blb.solve_and_set_inline_level_properties(&child_context);
+                    blb.solve_and_set_block_level_properties(&child_context);
+                }
+                continue;
+            }
+            if let Some(side) = child.float().physical_side(direction) {
+                // Floats are taken out of flow, and so take up no space in it.  They're placed no
+                // higher than where the next in-flow box would go.
+                if let LayoutBox::BlockLevel(blb) = child {
+                    let float_min_y = self_dimensions.content.start_y
+                        + self_dimensions.content.height().px()
+                        + pending_margin.resolve().px();
+                    layout_float(blb, side, float_min_y, &child_context, floats);
+                }
+                continue;
+            }
+
+            let floats_before_child = floats.float_count();
+            let (adjoining_margin, placed_margin) = match start_margin {
+                Some(margin) => (margin, margin.resolve()),
+                None => (pending_margin, CSSPixelLength::new(0.)),
+            };
+            let (margins, border_box_block_size) = match child {
+                LayoutBox::BlockLevel(blb) => (
+                    blb.layout_in_flow(child_context, floats, adjoining_margin, placed_margin),
+                    blb.dimensions().border_box_block_size(writing_mode),
+                ),
+                LayoutBox::InlineLevel(_) => {
+                    child.layout(child_context);
+                    (
+                        CollapsedBlockMargins::default(),
+                        child.dimensions().margin_box_block_size(writing_mode),
+                    )
+                }
+            };
+            // The distance from where the previous in-flow child's border box ended (or from the
+            // start of our content box) to where this child's border box starts.
+            let offset = adjoining_margin.adjoin(margins.start).resolve() - placed_margin
+                + margins.clearance;
 
-            // Add this child's margin-box to our content box so the next child is laid out after
-            // this one.
-            self_dimensions.add_to_block_size(
-                child.dimensions().margin_box_block_size(writing_mode),
-                context.containing_block.writing_mode(),
+            match start_margin {
+                Some(margin) if margins.clearance == CSSPixelLength::new(0.) => {
+                    // With no border or padding in between, our content box starts where this
+                    // child's border box does, so move down to meet it, along with everything
+                    // laid out before it.
+                    self_dimensions.add_to_block_start_coord(offset.px(), containing_writing_mode);
+                    let (dx, dy) = if writing_mode.is_horizontal() {
+                        (0., offset.px())
+                    } else {
+                        (offset.px(), 0.)
+                    };
+                    for preceding_child in preceding_children {
+                        preceding_child.translate(dx, dy);
+                    }
+                    floats.translate_floats(first_float..floats_before_child, dx, dy);
+
+                    children_start_margin = children_start_margin.adjoin(margins.start);
+                    if margins.collapsed_through {
+                        start_margin = Some(margin.adjoin(margins.start));
+                    } else {
+                        start_margin = None;
+                        self_dimensions
+                            .set_block_size(border_box_block_size, containing_writing_mode);
+                        pending_margin = margins.end;
+                    }
+                }
+                _ => {
+                    start_margin = None;
+                    if margins.collapsed_through {
+                        pending_margin = pending_margin.adjoin(margins.start);
+                    } else {
+                        // Add the space up to and including this child's border box to our content
+                        // box so the next child is laid out after this one.
+                        self_dimensions.add_to_block_size(
+                            offset + border_box_block_size,
+                            containing_writing_mode,
+                        );
+                        pending_margin = margins.end;
+                    }
+                }
+            }
+        }
+
+        if start_margin.is_some() {
+            // Every in-flow child collapsed through (if there were any), so all of their margins
+            // collapsed with our block-start margin.
+            return CollapsedBlockMargins {
+                start: children_start_margin,
+                collapsed_through: true,
+                ..CollapsedBlockMargins::default()
+            };
+        }
+        let end_margin = if collapse_end {
+            pending_margin
+        } else {
+            self_dimensions.add_to_block_size(pending_margin.resolve(), containing_writing_mode);
+            CollapsedMargin::zero()
+        };
+        CollapsedBlockMargins {
+            start: children_start_margin,
+            end: end_margin,
+            ..CollapsedBlockMargins::default()
+        }
+    }
+
+    /// Lays out this in-flow box in the block formatting context whose floats are `floats`, and
+    /// returns its margins collapsed with those of its children.
+    ///
+    /// `adjoining_margin` is the collapsed margin of the margins before this box that adjoin its
+    /// block-start margin, of which `placed_margin` has already been placed before the start of
+    /// its containing block's remaining space.
+    ///
+    /// https://www.w3.org/TR/CSS22/box.html#collapsing-margins
+    /// https://www.w3.org/TR/CSS22/visuren.html#flow-control
+    // TODO: Like floats, clearance is only correct in horizontal writing modes.
+    pub(crate) fn layout_in_flow(
+        &mut self,
+        context: LayoutContext,
+        floats: &mut FloatContext,
+        adjoining_margin: CollapsedMargin,
+        placed_margin: CSSPixelLength,
+    ) -> CollapsedBlockMargins {
+        // Anonymous block boxes contain the inline formatting context of their parent's inline
+        // content, so they always belong to their parent's block formatting context.
+        let (establishes_formatting_context, clear) = match self {
+            BlockLevelBox::AnonymousBlock(_) => (false, Clear::None),
+            BlockLevelBox::BlockContainer(bc) => (
+                bc.formatting_context() != *floats.formatting_context(),
+                bc.computed_values().clear,
+            ),
+        };
+        let containing_block = context.containing_block;
+        let clearance_position = floats.clearance_position(clear, containing_block.direction());
+
+        let context = if establishes_formatting_context {
+            // The border box of a box establishing a new block formatting context can't overlap
+            // the floats of the one it's in.  CSS 2.1 allows making the box narrower so it fits
+            // beside them, which is done by narrowing its containing block to the space they
+            // leave at the top of the box.
+            // https://www.w3.org/TR/CSS22/visuren.html#bfc-next-to-float
+            let cb_rect = *containing_block.positioned_rect();
+            let start_y = cb_rect.start_y + cb_rect.height().px();
+            let (start_x, end_x) = floats.available_space(
+                clearance_position.map_or(start_y, |position| position.max(start_y)),
+                CSSPixelLength::new(0.),
+                cb_rect.start_x,
+                cb_rect.start_x + cb_rect.width().px(),
+            );
+            LayoutContext::new(
+                ContainingBlock::new(
+                    PositionedRect {
+                        start_x,
+                        start_y: cb_rect.start_y,
+                        rect: Rect {
+                            width: CSSPixelLength::new(end_x - start_x),
+                            height: cb_rect.height(),
+                        },
+                    },
+                    containing_block.direction(),
+                    containing_block.writing_mode(),
+                ),
+                context.layout_viewport,
+                context.text_measurer,
+            )
+        } else {
+            context
+        };
+
+        self.solve_and_set_inline_level_properties(&context);
+        self.solve_and_set_block_level_properties(&context);
+        let containing_block = context.containing_block;
+        let writing_mode = containing_block.writing_mode();
+        let direction = containing_block.direction();
+        let dimensions = self.dimensions();
+        let margin_block_start = dimensions.get(
+            FlowSide::BlockStart,
+            BoxComponent::Margin,
+            writing_mode,
+            direction,
+        );
+        let margin_block_end = dimensions.get(
+            FlowSide::BlockEnd,
+            BoxComponent::Margin,
+            writing_mode,
+            direction,
+        );
+        let has_border_or_padding = |side| {
+            dimensions.get(side, BoxComponent::Border, writing_mode, direction)
+                + dimensions.get(side, BoxComponent::Padding, writing_mode, direction)
+                != CSSPixelLength::new(0.)
+        };
+        let block_start_separated = has_border_or_padding(FlowSide::BlockStart);
+        let block_end_separated = has_border_or_padding(FlowSide::BlockEnd);
+
+        // Our border box goes after the margins before us collapsed with our block-start margin,
+        // rather than after our block-start margin alone.
+        let collapsed_start_margin =
+            adjoining_margin.adjoin(CollapsedMargin::new(margin_block_start));
+        self.dimensions_mut().add_to_block_start_coord(
+            (collapsed_start_margin.resolve() - placed_margin - margin_block_start).px(),
+            writing_mode,
+        );
+        let mut clearance = CSSPixelLength::new(0.);
+        if let Some(clearance_position) = clearance_position {
+            let border_box_start_y = self.dimensions().border_box().start_y;
+            if border_box_start_y < clearance_position {
+                clearance = CSSPixelLength::new(clearance_position - border_box_start_y);
+                self.dimensions_mut().content.start_y += clearance.px();
+            }
+        }
+
+        // Our margins collapse with those of our children when they're in the same block
+        // formatting context and writing mode, and there's no border, padding, or clearance
+        // between them.  Our block-end margin only collapses with the children's if our block
+        // size is determined by them.
+        let cvs = self.computed_values();
+        let collapses_with_children =
+            !establishes_formatting_context && cvs.writing_mode == writing_mode;
+        let block_size_from_children = cvs.block_size(writing_mode) == LengthPercentageOrAuto::Auto
+            && cvs
+                .min_block_size(writing_mode)
+                .to_px(containing_block.self_relative_block_size())
+                == CSSPixelLength::new(0.);
+        drop(cvs);
+        let collapse_start = collapses_with_children
+            && !block_start_separated
+            && clearance == CSSPixelLength::new(0.);
+        let collapse_end =
+            collapses_with_children && !block_end_separated && block_size_from_children;
+
+        let children_margins = if establishes_formatting_context {
+            self.layout_children_in_new_formatting_context(&context);
+            CollapsedBlockMargins::default()
+        } else {
+            self.layout_children(
+                &context,
+                floats,
+                Some(collapsed_start_margin).filter(|_| collapse_start),
+                collapse_end,
+            )
+        };
+        // After computing and applying values normally through layout, override these values with
+        // the author's preferred box sizes (if present), constrained by their minimums and
+        // maximums.
+        self.apply_box_sizing_properties(containing_block);
+
+        let start_margin = match collapse_start {
+            true => CollapsedMargin::new(margin_block_start).adjoin(children_margins.start),
+            false => CollapsedMargin::new(margin_block_start),
+        };
+        let end_margin = CollapsedMargin::new(margin_block_end).adjoin(children_margins.end);
+        // A box with no content, border, or padding in the block axis has its block-start and
+        // block-end margins adjoin, and they collapse through it.
+        let margins = if children_margins.collapsed_through
+            && !block_end_separated
+            && self.dimensions().content_box_block_size(writing_mode) == CSSPixelLength::new(0.)
+        {
+            let collapsed_through_margin = start_margin.adjoin(end_margin);
+            CollapsedBlockMargins {
+                start: collapsed_through_margin,
+                end: collapsed_through_margin,
+                collapsed_through: true,
+                clearance,
+            }
+        } else {
+            CollapsedBlockMargins {
+                start: start_margin,
+                end: end_margin,
+                collapsed_through: false,
+                clearance,
+            }
+        };
+
+        // After we've laid out our children and applied the sizing properties, we have enough
+        // information (our used size) to flip our block-start coordinate, if it's necessary.
+        // https://www.w3.org/TR/css-sizing-3/#intrinsic
+        if context.block_start_origin_relative_progression()
+            == OriginRelativeProgression::TowardsOrigin
+        {
+            self.flip_block_start_coord(
+                context.layout_viewport_block_size(containing_block.writing_mode()),
+                containing_block,
             );
         }
+        margins
     }
 
     pub fn solve_and_set_inline_level_properties(&mut self, context: &LayoutContext) {
@@ -143,6 +684,16 @@ impl BlockLevelBox {
         let padding_inline_end =
             computed_values.padding_flow_relative(FlowSide::InlineEnd, writing_mode);
 
+        // Tables with an `auto` width are sized by their columns rather than stretched to fill
+        // their containing block.
+        let inline_size = match computed_values.inline_size(writing_mode) {
+            LengthPercentageOrAuto::Auto
+                if self.formatting_context().is_table_formatting_context() =>
+            {
+                table::auto_inline_size(self, containing_block, context.text_measurer)
+            }
+            inline_size => inline_size,
+        };
         let solved_inline_sizes = solve_block_level_inline_size(SolveInlineSizeInput {
             containing_block,
             margin_inline_start: computed_values
@@ -151,9 +702,12 @@ impl BlockLevelBox {
                 .margin_flow_relative(FlowSide::InlineEnd, writing_mode),
             border_inline_start,
             border_inline_end,
-            padding_inline_start,
-            padding_inline_end,
-            inline_size: computed_values.inline_size(writing_mode),
+            padding_inline_start: padding_inline_start.clone(),
+            padding_inline_end: padding_inline_end.clone(),
+            inline_size,
+            min_inline_size: computed_values.min_inline_size(writing_mode),
+            max_inline_size: computed_values.max_inline_size(writing_mode),
+            box_sizing: computed_values.box_sizing,
         });
         // Release this &self borrow so we can mutably borrow below.
         drop(computed_values);
@@ -200,10 +754,10 @@ impl BlockLevelBox {
 
         let cvs = self.computed_values();
         let (ml, mr, mb, mt) = (
-            cvs.margin_left,
-            cvs.margin_right,
-            cvs.margin_bottom,
-            cvs.margin_top,
+            cvs.margin_left.clone(),
+            cvs.margin_right.clone(),
+            cvs.margin_bottom.clone(),
+            cvs.margin_top.clone(),
         );
         drop(cvs);
         if let LengthPercentageOrAuto::LengthPercentage(lp) = ml.size {
@@ -256,7 +810,7 @@ impl BlockLevelBox {
         let auto = LengthPercentageOrAuto::Auto;
         // If the block-start or blond-end margins are auto, their used value is 0.
         if margin_block_start == auto {
-            margin_block_start = zero
+            margin_block_start = zero.clone()
         }
         if margin_block_end == auto {
             margin_block_end = zero
@@ -327,25 +881,16 @@ impl Layout for BlockLevelBox {
     // [1] "self-relative" means the containing block evaluates abstract flow directions against its
     // own writing-mode, rather than that of it's own containing block.
     fn layout(&mut self, context: LayoutContext) {
-        self.solve_and_set_inline_level_properties(&context);
-        self.solve_and_set_block_level_properties(&context);
-        self.layout_children(&context);
-        let containing_block = context.containing_block;
-        // After we've laid out our children, we have enough information (our intrinsic,
-        // content-based size) to flip our block-start coordinate, if it's necessary.
-        // https://www.w3.org/TR/css-sizing-3/#intrinsic
-        if context.block_start_origin_relative_progression()
-            == OriginRelativeProgression::TowardsOrigin
-        {
-            self.flip_block_start_coord(
-                context.layout_viewport_block_size(containing_block.writing_mode()),
-                containing_block,
-            );
-        }
-
-        // After computing and applying values normally through layout, override these values with
-        // the author's preferred box sizes (if present).
-        self.apply_box_sizing_properties(containing_block);
+        // A box laid out on its own (like the root box) has no surrounding block formatting
+        // context with floats to flow around, so lay it out in an empty one.  It establishes a
+        // formatting context of its own for its children.
+        let mut floats = FloatContext::new(FormattingContextRef::new_independent_block());
+        self.layout_in_flow(
+            context,
+            &mut floats,
+            CollapsedMargin::zero(),
+            CSSPixelLength::new(0.),
+        );
     }
 }
 
@@ -354,16 +899,27 @@ impl Layout for BlockLevelBox {
 pub struct AnonymousBlockBox {
     base: BaseBox,
     children: Vec<LayoutBox>,
+    /// The line boxes the inline formatting context of this box was laid out into.
+    line_boxes: Vec<LineBox>,
 }
 
 impl AnonymousBlockBox {
     pub fn new(node: NodeRef, formatting_context: FormattingContextRef) -> Self {
         Self {
-            base: BaseBox::new(node, formatting_context),
+            base: BaseBox::new_anonymous(node, formatting_context),
             children: Vec::new(),
+            line_boxes: Vec::new(),
         }
     }
 
+    pub fn line_boxes(&self) -> &[LineBox] {
+        &self.line_boxes
+    }
+
+    pub(crate) fn set_line_boxes(&mut self, line_boxes: Vec<LineBox>) {
+        self.line_boxes = line_boxes;
+    }
+
     pub fn add_child(&mut self, child: LayoutBox) {
         self.children.push(child)
     }
@@ -393,7 +949,7 @@ impl DumpLayoutFormat for AnonymousBlockBox {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct SolveInlineSizeInput {
     pub containing_block: ContainingBlock,
     pub margin_inline_start: LengthPercentageOrAuto,
@@ -403,6 +959,9 @@ pub struct SolveInlineSizeInput {
     pub padding_inline_start: LengthPercentage,
     pub padding_inline_end: LengthPercentage,
     pub inline_size: LengthPercentageOrAuto,
+    pub min_inline_size: LengthPercentageOrAuto,
+    pub max_inline_size: MaxSize,
+    pub box_sizing: BoxSizing,
 }
 
 pub struct SolveInlineSizeOutput {
@@ -415,12 +974,46 @@ pub struct SolveInlineSizeOutput {
 /// reality.  If there are specified values for inline margin properties, they always override the
 /// values calculated by the spec formula.
 pub fn solve_block_level_inline_size(input: SolveInlineSizeInput) -> SolveInlineSizeOutput {
-    let mut spec_inline_sizes = solve_block_level_inline_size_to_spec(input);
-    if let LengthPercentageOrAuto::LengthPercentage(lp) = input.margin_inline_start {
+    let containing_inline_size = input.containing_block.self_relative_inline_size();
+    let padding_border_inline_size = input.border_inline_start
+        + input.border_inline_end
+        + input.padding_inline_start.to_px(containing_inline_size)
+        + input.padding_inline_end.to_px(containing_inline_size);
+    let to_content_box =
+        |size| content_box_size(size, input.box_sizing, padding_border_inline_size);
+    let with_inline_size = |inline_size| SolveInlineSizeInput {
+        inline_size: LengthPercentageOrAuto::new_len_px(inline_size),
+        ..input.clone()
+    };
+
+    let mut spec_inline_sizes = match &input.inline_size {
+        LengthPercentageOrAuto::LengthPercentage(lp) => solve_block_level_inline_size_to_spec(
+            with_inline_size(to_content_box(lp.to_px(containing_inline_size))),
+        ),
+        LengthPercentageOrAuto::Auto => solve_block_level_inline_size_to_spec(input.clone()),
+    };
+    // CSS 2.1 section 10.4: if the tentative used inline size is greater than the maximum, the
+    // rules above are applied again using the maximum as the computed inline size.  Then, if the
+    // resulting inline size is smaller than the minimum, they're applied again using the minimum.
+    // https://www.w3.org/TR/CSS22/visudet.html#min-max-widths
+    if let Some(max_inline_size) = input.max_inline_size.to_px(containing_inline_size) {
+        let max_inline_size = to_content_box(max_inline_size);
+        if spec_inline_sizes.inline_size > max_inline_size {
+            spec_inline_sizes =
+                solve_block_level_inline_size_to_spec(with_inline_size(max_inline_size));
+        }
+    }
+    let min_inline_size = to_content_box(input.min_inline_size.to_px(containing_inline_size));
+    if spec_inline_sizes.inline_size < min_inline_size {
+        spec_inline_sizes =
+            solve_block_level_inline_size_to_spec(with_inline_size(min_inline_size));
+    }
+
+    if let LengthPercentageOrAuto::LengthPercentage(lp) = &input.margin_inline_start {
         spec_inline_sizes.margin_inline_start =
             lp.to_px(input.containing_block.self_relative_inline_size())
     }
-    if let LengthPercentageOrAuto::LengthPercentage(lp) = input.margin_inline_end {
+    if let LengthPercentageOrAuto::LengthPercentage(lp) = &input.margin_inline_end {
         spec_inline_sizes.margin_inline_end =
             lp.to_px(input.containing_block.self_relative_inline_size())
     }
@@ -441,6 +1034,7 @@ fn solve_block_level_inline_size_to_spec(input: SolveInlineSizeInput) -> SolveIn
         padding_inline_start,
         padding_inline_end,
         mut inline_size,
+        ..
     } = input;
 
     let margin_box_inline_size = margin_inline_start
@@ -460,8 +1054,8 @@ fn solve_block_level_inline_size_to_spec(input: SolveInlineSizeInput) -> SolveIn
     // or 'margin-right' are, for the following rules, treated as zero.
     if inline_size != auto && margin_box_inline_size > containing_block.self_relative_inline_size()
     {
-        margin_inline_start = zero;
-        margin_inline_end = zero;
+        margin_inline_start = zero.clone();
+        margin_inline_end = zero.clone();
     }
 
     // This can be be negative, indicating an overflow (this box has a larger inline-size than
@@ -491,7 +1085,7 @@ fn solve_block_level_inline_size_to_spec(input: SolveInlineSizeInput) -> SolveIn
             // follows from the resulting equality.
             inline_size = available_inline_space;
             if margin_inline_start == auto {
-                margin_inline_start = zero;
+                margin_inline_start = zero.clone();
             }
             if margin_inline_end == auto {
                 margin_inline_end = zero;
@@ -502,7 +1096,7 @@ fn solve_block_level_inline_size_to_spec(input: SolveInlineSizeInput) -> SolveIn
             // This centers the element in the inline-direction.
             let half_remaining_inline_size =
                 LengthPercentageOrAuto::new_len_px(available_inline_space_px / 2.0);
-            margin_inline_start = half_remaining_inline_size;
+            margin_inline_start = half_remaining_inline_size.clone();
             margin_inline_end = half_remaining_inline_size;
         }
         // If there is exactly one value specified as 'auto', its used value follows from the
@@ -536,11 +1130,9 @@ fn compute_block_start_coord(
 
 struct ComputeFlippedBlockStartCoordInput {
     pub layout_viewport_block_size: CSSPixelLength,
-    pub containing_block_block_size: CSSPixelLength,
-    /// The content size of the given box to flip.  The given box must have laid out its children
-    /// in order to get this value.
+    /// The used content size of the given box to flip.  The given box must have laid out its
+    /// children and applied its sizing properties in order to get this value.
     pub content_box_block_size: CSSPixelLength,
-    pub preferred_block_size: LengthPercentageOrAuto,
     /// The block start coordinate that was computed for "normal" block progression
     /// (`OriginRelativeProgression::AwayFromOrigin`).
     pub current_block_start_coord: CSSFloat,
@@ -549,21 +1141,11 @@ struct ComputeFlippedBlockStartCoordInput {
 fn compute_flipped_block_start_coord(input: ComputeFlippedBlockStartCoordInput) -> CSSFloat {
     let ComputeFlippedBlockStartCoordInput {
         layout_viewport_block_size,
-        containing_block_block_size,
         content_box_block_size,
-        preferred_block_size,
         current_block_start_coord,
     } = input;
 
-    // If the given box has a preferred (extrinsic) block size, we must use that to position it.
-    // Otherwise, use the content (intrinsic) block size calculated during layout.
-    let block_size_for_positioning = if preferred_block_size != LengthPercentageOrAuto::Auto {
-        preferred_block_size.to_px(containing_block_block_size)
-    } else {
-        content_box_block_size
-    };
-
-    (layout_viewport_block_size - current_block_start_coord - block_size_for_positioning).px()
+    (layout_viewport_block_size - current_block_start_coord - content_box_block_size).px()
 }
 
 fn compute_inline_start_coord(
@@ -0,0 +1,244 @@
+use crate::behavior::BaseLayoutBoxBehavior;
+use crate::flow::block::BlockLevelBox;
+use crate::formatting_context::FormattingContextRef;
+use crate::LayoutContext;
+use primitives::rect::{PositionedRect, Rect};
+use primitives::sides::PhysicalSide;
+use primitives::units::{CSSFloat, CSSPixelLength};
+use std::ops::Range;
+use style::values::computed::{Clear, Direction};
+
+/// The floats placed so far in a block formatting context, which the content laid out after them
+/// in the same formatting context flows around.
+///
+/// Floats are tracked by the physical position of their margin boxes, so like the rest of float
+/// layout, this assumes the block formatting context has a horizontal writing mode.
+// TODO: Track floats in flow-relative terms to support vertical writing modes.
+#[derive(Debug)]
+pub struct FloatContext {
+    /// The block formatting context these floats were placed in.
+    formatting_context: FormattingContextRef,
+    floats: Vec<PlacedFloat>,
+    /// The top of the margin box of the most recently placed float.  Later floats can't be placed
+    /// any higher.
+    ceiling: CSSFloat,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PlacedFloat {
+    /// The side of its containing block the float was shifted to, either `Left` or `Right`.
+    side: PhysicalSide,
+    margin_box: PositionedRect,
+}
+
+impl PlacedFloat {
+    fn top(&self) -> CSSFloat {
+        self.margin_box.start_y
+    }
+
+    fn bottom(&self) -> CSSFloat {
+        self.margin_box.start_y + self.margin_box.height().px()
+    }
+
+    fn left(&self) -> CSSFloat {
+        self.margin_box.start_x
+    }
+
+    fn right(&self) -> CSSFloat {
+        self.margin_box.start_x + self.margin_box.width().px()
+    }
+}
+
+impl FloatContext {
+    pub fn new(formatting_context: FormattingContextRef) -> Self {
+        FloatContext {
+            formatting_context,
+            floats: Vec::new(),
+            ceiling: CSSFloat::MIN,
+        }
+    }
+
+    pub fn formatting_context(&self) -> &FormattingContextRef {
+        &self.formatting_context
+    }
+
+    /// The number of floats placed in this context so far.
+    pub fn float_count(&self) -> usize {
+        self.floats.len()
+    }
+
+    /// Moves the floats in the given range of placement order by `dx` horizontally and `dy`
+    /// vertically, for when the boxes they were placed in are moved.
+    pub fn translate_floats(&mut self, floats: Range<usize>, dx: CSSFloat, dy: CSSFloat) {
+        if floats.end == self.floats.len() && !floats.is_empty() {
+            self.ceiling += dy;
+        }
+        for float in &mut self.floats[floats] {
+            float.margin_box.start_x += dx;
+            float.margin_box.start_y += dy;
+        }
+    }
+
+    /// The bottom of the lowest margin box of any float in this context, or `None` if there are no
+    /// floats.
+    pub fn lowest_float_bottom(&self) -> Option<CSSFloat> {
+        self.floats
+            .iter()
+            .map(PlacedFloat::bottom)
+            .reduce(CSSFloat::max)
+    }
+
+    /// The y-coordinate that a box with the given `clear` must have its top border edge at or below
+    /// to be below every float it clears, or `None` if it clears no floats.  `direction` is that
+    /// of the box's containing block, which logical values of `clear` are relative to.
+    ///
+    /// https://www.w3.org/TR/CSS22/visuren.html#flow-control
+    pub fn clearance_position(&self, clear: Clear, direction: Direction) -> Option<CSSFloat> {
+        self.floats
+            .iter()
+            .filter(|float| clear.clears(float.side, direction))
+            .map(PlacedFloat::bottom)
+            .reduce(CSSFloat::max)
+    }
+
+    /// The horizontal span of the space between `containing_start_x` and `containing_end_x` that
+    /// isn't taken up by floats from `start_y` down to `start_y + height`, as the start and end
+    /// x-coordinates of the space.  A `height` of zero looks for the space along the line at
+    /// `start_y`.
+    ///
+    /// This is the space that line boxes, and boxes that establish a new block formatting context,
+    /// are shortened to fit within.
+    pub fn available_space(
+        &self,
+        start_y: CSSFloat,
+        height: CSSPixelLength,
+        containing_start_x: CSSFloat,
+        containing_end_x: CSSFloat,
+    ) -> (CSSFloat, CSSFloat) {
+        self.floats_in_band(start_y, height).fold(
+            (containing_start_x, containing_end_x),
+            |(start, end), float| match float.side {
+                PhysicalSide::Left => (start.max(float.right()), end),
+                _ => (start, end.min(float.left())),
+            },
+        )
+    }
+
+    /// The bottom of the first float to end among those that vertically overlap the band from
+    /// `start_y` down to `start_y + height`, which is the next place the space available beside
+    /// floats can widen, or `None` if no floats overlap the band.
+    pub fn next_float_bottom(&self, start_y: CSSFloat, height: CSSPixelLength) -> Option<CSSFloat> {
+        self.floats_in_band(start_y, height)
+            .map(PlacedFloat::bottom)
+            .reduce(CSSFloat::min)
+    }
+
+    /// The floats whose margin boxes vertically overlap the band from `start_y` down to
+    /// `start_y + height`.
+    fn floats_in_band(
+        &self,
+        start_y: CSSFloat,
+        height: CSSPixelLength,
+    ) -> impl Iterator<Item = &PlacedFloat> {
+        let end_y = start_y + height.px();
+        self.floats.iter().filter(move |float| {
+            float.bottom() > start_y && (float.top() <= start_y || float.top() < end_y)
+        })
+    }
+
+    /// Places a float whose margin box is `margin_box_size` on the `side` (`Left` or `Right`) of
+    /// its containing block, `containing_block`, as high as it can go without being placed higher
+    /// than `min_y` or overlapping any other float, and returns the position of its margin box.
+    ///
+    /// Corresponds to the rules of CSS 2.1 section 9.5.1.
+    /// https://www.w3.org/TR/CSS22/visuren.html#float-rules
+    pub fn place_float(
+        &mut self,
+        side: PhysicalSide,
+        margin_box_size: Rect,
+        min_y: CSSFloat,
+        containing_block: &PositionedRect,
+    ) -> (CSSFloat, CSSFloat) {
+        let containing_start_x = containing_block.start_x;
+        let containing_end_x = containing_block.start_x + containing_block.width().px();
+        let mut y = min_y.max(self.ceiling);
+        let (start_x, end_x) = loop {
+            let (start_x, end_x) = self.available_space(
+                y,
+                margin_box_size.height,
+                containing_start_x,
+                containing_end_x,
+            );
+            // Move below the float that ends first until the float fits beside the floats it
+            // overlaps, or overlaps none at all (in which case it's wider than its containing
+            // block, and overflows it).
+            match self.next_float_bottom(y, margin_box_size.height) {
+                Some(next_y) if end_x - start_x < margin_box_size.width.px() => y = next_y,
+                _ => break (start_x, end_x),
+            }
+        };
+        let x = match side {
+            PhysicalSide::Left => start_x,
+            _ => end_x - margin_box_size.width.px(),
+        };
+
+        self.floats.push(PlacedFloat {
+            side,
+            margin_box: PositionedRect {
+                start_x: x,
+                start_y: y,
+                rect: margin_box_size,
+            },
+        });
+        self.ceiling = y;
+        (x, y)
+    }
+}
+
+/// Lays out a floated box, shifted to the `side` (`Left` or `Right`) of its containing block, and
+/// places it in `floats`.  `min_y` is where the box would have been placed were it in flow, which
+/// it can't be placed any higher than.
+///
+/// Floats establish a new block formatting context for their contents, and if their width is
+/// `auto`, are sized to fit their content.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#float-width
+pub(crate) fn layout_float(
+    blb: &mut BlockLevelBox,
+    side: PhysicalSide,
+    min_y: CSSFloat,
+    context: &LayoutContext,
+    floats: &mut FloatContext,
+) {
+    // The float is laid out with its margin box at the origin, then moved to wherever it ends up
+    // being placed, which depends on its height.
+    blb.layout_shrink_to_fit(context);
+    place_laid_out_float(blb, side, min_y, context, floats);
+}
+
+/// Places a floated box that's already been laid out by `layout_shrink_to_fit` in `floats`, no
+/// higher than `min_y`, and moves it to where it was placed.  Floats among inline content are
+/// measured before they're placed, to find whether they fit beside the line they're on.
+pub(crate) fn place_laid_out_float(
+    blb: &mut BlockLevelBox,
+    side: PhysicalSide,
+    min_y: CSSFloat,
+    context: &LayoutContext,
+    floats: &mut FloatContext,
+) {
+    let containing_block = context.containing_block;
+    // The `clear` of a float moves the top of its margin box below the floats it clears.
+    let clear = blb.computed_values().clear;
+    let min_y = floats
+        .clearance_position(clear, containing_block.direction())
+        .map_or(min_y, |clearance_position| clearance_position.max(min_y));
+
+    let margin_box = blb.dimensions().margin_box();
+    let (x, y) = floats.place_float(
+        side,
+        margin_box.rect,
+        min_y,
+        containing_block.positioned_rect(),
+    );
+    blb.translate(x - margin_box.start_x, y - margin_box.start_y);
+}
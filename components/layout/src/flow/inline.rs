@@ -2,6 +2,9 @@ use crate::apply_box_sizing_properties_base_box_passthrough_impls;
 use crate::behavior::{ApplyBoxSizingProperties, BaseLayoutBoxBehavior};
 use crate::containing_block::ContainingBlock;
 use crate::dimensions::Dimensions;
+use crate::flow::block::BlockLevelBox;
+use crate::flow::white_space::FORCED_LINE_BREAK;
+use crate::flow::OriginRelativeProgression;
 use crate::formatting_context::FormattingContextRef;
 use crate::layout_box::{BaseBox, LayoutBox};
 use crate::layout_box_behavior_base_box_passthrough_impls;
@@ -9,7 +12,10 @@ use crate::{DumpLayoutFormat, Layout, LayoutContext};
 use accountable_refcell::Ref;
 use dom::tree::NodeRef;
 use enum_dispatch::enum_dispatch;
-use style::values::computed::ComputedValues;
+use primitives::rect::PositionedRect;
+use primitives::units::{CSSFloat, CSSPixelLength};
+use style::values::computed::{ComputedValues, TextAlign, TextIndent};
+use style::values::used::ToPx;
 
 /// Content that participates in inline layout. Specifically, inline-level boxes and text runs.
 ///
@@ -41,6 +47,35 @@ impl ApplyBoxSizingProperties for InlineLevelContent {
     fn apply_box_sizing_properties(&mut self, _containing_block: ContainingBlock) {}
 }
 
+impl InlineLevelContent {
+    /// Moves this content, its fragments, and all of its descendants by `dx` horizontally and `dy`
+    /// vertically.
+    pub fn translate(&mut self, dx: CSSFloat, dy: CSSFloat) {
+        let content = &mut self.dimensions_mut().content;
+        content.start_x += dx;
+        content.start_y += dy;
+        match self {
+            InlineLevelContent::InlineLevelBox(ilb) => {
+                if let InlineLevelBox::InlineBox(ib) = ilb {
+                    for fragment in &mut ib.fragments {
+                        fragment.start_x += dx;
+                        fragment.start_y += dy;
+                    }
+                }
+                for child in ilb.children_mut() {
+                    child.translate(dx, dy);
+                }
+            }
+            InlineLevelContent::TextRun(tr) => {
+                for fragment in &mut tr.fragments {
+                    fragment.rect.start_x += dx;
+                    fragment.rect.start_y += dy;
+                }
+            }
+        }
+    }
+}
+
 impl Layout for InlineLevelContent {
     fn layout(&mut self, context: LayoutContext) {
         match self {
@@ -70,10 +105,13 @@ pub enum InlineLevelBox {
     /// A non-replaced inline-level box whose inner display type is flow. The contents of an inline
     /// box participate in the same inline formatting context as the inline box itself.
     ///
-    /// This is also known as an "inline-block".
-    ///
     /// https://drafts.csswg.org/css-display/#inline-box
     InlineBox(InlineBox),
+    /// An inline-level box that takes part in its inline formatting context as a single opaque
+    /// box, like an inline-block.
+    ///
+    /// https://drafts.csswg.org/css-display/#atomic-inline
+    AtomicInline(AtomicInlineBox),
 }
 
 impl InlineLevelBox {
@@ -81,6 +119,7 @@ impl InlineLevelBox {
         match self {
             InlineLevelBox::AnonymousInline(aib) => aib.children.push(new_child),
             InlineLevelBox::InlineBox(ib) => ib.children.push(new_child),
+            InlineLevelBox::AtomicInline(aib) => aib.add_child(new_child),
         }
     }
 
@@ -88,20 +127,33 @@ impl InlineLevelBox {
         match self {
             InlineLevelBox::AnonymousInline(aib) => aib.children(),
             InlineLevelBox::InlineBox(ib) => ib.children(),
+            InlineLevelBox::AtomicInline(aib) => aib.children(),
+        }
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<LayoutBox> {
+        match self {
+            InlineLevelBox::AnonymousInline(aib) => aib.children_mut(),
+            InlineLevelBox::InlineBox(ib) => ib.children_mut(),
+            InlineLevelBox::AtomicInline(aib) => aib.children_mut(),
         }
     }
 
     pub fn is_anonymous_inline(&self) -> bool {
         match self {
             InlineLevelBox::AnonymousInline(_) => true,
-            InlineLevelBox::InlineBox(_) => false,
+            InlineLevelBox::InlineBox(_) | InlineLevelBox::AtomicInline(_) => false,
         }
     }
 }
 
 impl Layout for InlineLevelBox {
-    fn layout(&mut self, _context: LayoutContext) {
-        unimplemented!()
+    fn layout(&mut self, context: LayoutContext) {
+        match self {
+            InlineLevelBox::AtomicInline(aib) => aib.layout(context),
+            // The rest are laid out as part of the lines of their inline formatting context.
+            InlineLevelBox::AnonymousInline(_) | InlineLevelBox::InlineBox(_) => unimplemented!(),
+        }
     }
 }
 
@@ -114,7 +166,7 @@ pub struct AnonymousInlineBox {
 impl AnonymousInlineBox {
     pub fn new(node: NodeRef, formatting_context: FormattingContextRef) -> Self {
         Self {
-            base: BaseBox::new(node, formatting_context),
+            base: BaseBox::new_anonymous(node, formatting_context),
             children: Vec::new(),
         }
     }
@@ -122,6 +174,10 @@ impl AnonymousInlineBox {
     pub fn children(&self) -> &Vec<LayoutBox> {
         &self.children
     }
+
+    pub fn children_mut(&mut self) -> &mut Vec<LayoutBox> {
+        &mut self.children
+    }
 }
 
 impl BaseLayoutBoxBehavior for AnonymousInlineBox {
@@ -144,6 +200,16 @@ impl DumpLayoutFormat for AnonymousInlineBox {
 pub struct InlineBox {
     base: BaseBox,
     children: Vec<LayoutBox>,
+    /// The content areas of the pieces of this box on each line it spans, set by line layout.
+    fragments: Vec<PositionedRect>,
+    /// Whether this box continues an inline box generated by the same element that was split
+    /// around a block-level box, in which case its inline-start margin, border, and padding
+    /// belong to that earlier box instead.
+    split_before: bool,
+    /// Whether this box is continued by an inline box generated by the same element after a
+    /// block-level box it was split around, in which case its inline-end margin, border, and
+    /// padding belong to that later box instead.
+    split_after: bool,
 }
 
 impl InlineBox {
@@ -151,12 +217,50 @@ impl InlineBox {
         Self {
             base: BaseBox::new(node, formatting_context),
             children: Vec::new(),
+            fragments: Vec::new(),
+            split_before: false,
+            split_after: false,
+        }
+    }
+
+    /// Creates one of the parts this box is split into around the block-level boxes it contains,
+    /// holding `children`.  Only the first part (`is_first`) has this box's inline-start margin,
+    /// border, and padding, and only the last part (`is_last`) has its inline-end ones.
+    ///
+    /// https://www.w3.org/TR/CSS22/visuren.html#anonymous-block-level
+    pub fn split_part(&self, children: Vec<LayoutBox>, is_first: bool, is_last: bool) -> Self {
+        Self {
+            base: BaseBox::new(self.node(), self.formatting_context()),
+            children,
+            fragments: Vec::new(),
+            split_before: self.split_before || !is_first,
+            split_after: self.split_after || !is_last,
         }
     }
 
-    fn children(&self) -> &Vec<LayoutBox> {
+    pub fn split_before(&self) -> bool {
+        self.split_before
+    }
+
+    pub fn split_after(&self) -> bool {
+        self.split_after
+    }
+
+    pub fn children(&self) -> &Vec<LayoutBox> {
         &self.children
     }
+
+    pub fn children_mut(&mut self) -> &mut Vec<LayoutBox> {
+        &mut self.children
+    }
+
+    pub fn fragments(&self) -> &[PositionedRect] {
+        &self.fragments
+    }
+
+    pub(crate) fn set_fragments(&mut self, fragments: Vec<PositionedRect>) {
+        self.fragments = fragments;
+    }
 }
 
 impl BaseLayoutBoxBehavior for InlineBox {
@@ -178,30 +282,216 @@ impl DumpLayoutFormat for InlineBox {
     }
 }
 
+/// An atomic inline-level box, which is laid out as a block container establishing a new block
+/// formatting context, and then placed on a line as a single unbreakable box.
+///
+/// https://drafts.csswg.org/css-display/#atomic-inline
+/// https://www.w3.org/TR/CSS22/visuren.html#inline-boxes
+#[derive(Clone, Debug)]
+pub struct AtomicInlineBox {
+    /// The block container this box's contents are laid out in.  It's generated by the same
+    /// element as this box, and is always a `BlockLevelBox::BlockContainer`.
+    block_container: BlockLevelBox,
+}
+
+impl AtomicInlineBox {
+    pub fn new(node: NodeRef, formatting_context: FormattingContextRef) -> Self {
+        Self {
+            block_container: BlockLevelBox::new_block_container(node, formatting_context),
+        }
+    }
+
+    pub fn add_child(&mut self, new_child: LayoutBox) {
+        self.block_container.add_child(new_child)
+    }
+
+    pub fn children(&self) -> &Vec<LayoutBox> {
+        self.block_container.children()
+    }
+
+    pub fn children_mut(&mut self) -> &mut Vec<LayoutBox> {
+        self.block_container.children_mut()
+    }
+
+    pub fn get_mut_inline_container(&mut self) -> Option<&mut LayoutBox> {
+        self.block_container.get_mut_inline_container()
+    }
+
+    /// Moves this box and all of its descendants by `dx` horizontally and `dy` vertically.
+    pub fn translate(&mut self, dx: CSSFloat, dy: CSSFloat) {
+        self.block_container.translate(dx, dy)
+    }
+
+    /// The y-coordinate of this box's baseline, which is that of its last line box in normal flow,
+    /// or the bottom edge of its margin box if it has no such line box.
+    ///
+    /// https://www.w3.org/TR/CSS22/visudet.html#propdef-vertical-align
+    pub fn baseline(&self) -> CSSFloat {
+        self.block_container
+            .last_line_baseline()
+            .unwrap_or_else(|| {
+                let margin_box = self.dimensions().margin_box();
+                margin_box.start_y + margin_box.height().px()
+            })
+    }
+}
+
+impl BaseLayoutBoxBehavior for AtomicInlineBox {
+    fn computed_values(&self) -> Ref<ComputedValues> {
+        self.block_container.computed_values()
+    }
+
+    fn dimensions(&self) -> Dimensions {
+        self.block_container.dimensions()
+    }
+
+    fn dimensions_mut(&mut self) -> &mut Dimensions {
+        self.block_container.dimensions_mut()
+    }
+
+    fn formatting_context(&self) -> FormattingContextRef {
+        self.block_container.formatting_context()
+    }
+
+    fn is_root(&self) -> bool {
+        self.block_container.is_root()
+    }
+
+    fn node(&self) -> NodeRef {
+        self.block_container.node()
+    }
+}
+
+impl ApplyBoxSizingProperties for AtomicInlineBox {
+    fn apply_box_sizing_properties(&mut self, containing_block: ContainingBlock) {
+        self.block_container
+            .apply_box_sizing_properties(containing_block)
+    }
+}
+
+impl Layout for AtomicInlineBox {
+    /// Lays out this box with its margin box at the origin.  Line layout moves it into place once
+    /// the line it's on is known.
+    fn layout(&mut self, context: LayoutContext) {
+        self.block_container.layout_shrink_to_fit(&context);
+    }
+}
+
+impl DumpLayoutFormat for AtomicInlineBox {
+    fn dump_layout_format(&self) -> String {
+        let node_data = self.node().data().dump_layout_format();
+        if node_data.is_empty() {
+            "AtomicInlineBox".to_string()
+        } else {
+            format!("{} {}", node_data, "AtomicInlineBox")
+        }
+    }
+}
+
 /// A representation of the contents of a text DOM node.
 ///
 /// https://drafts.csswg.org/css-display-3/#text-run
 #[derive(Clone, Debug)]
 pub struct TextRun {
     base: BaseBox,
-    /// The text contents of the node.
+    /// The text contents of the node, after white space processing.
     ///
     /// TODO: This can be an owned String for now for simplicity's sake, but it would be probably
     /// be more efficient if this were a `&'DOM_LIFETIME str`.
     contents: String,
+    /// The pieces of this run placed on each line it spans, set by line layout.
+    fragments: Vec<TextFragment>,
+}
+
+/// The part of a text run placed on a single line.
+#[derive(Clone, Debug)]
+pub struct TextFragment {
+    /// The text of the fragment, less any collapsible spaces removed from the end of its line.
+    pub text: String,
+    /// The content area of the fragment, which is as tall as the font of its text run.
+    pub rect: PositionedRect,
 }
 
 impl TextRun {
     pub fn new(node: NodeRef, formatting_context: FormattingContextRef, contents: String) -> Self {
+        // Case mapping can change the number of characters in the text, so it must be applied
+        // before the run is measured.
+        // https://drafts.csswg.org/css-text-3/#text-transform-order
+        let text_transform = node.computed_values().text_transform;
+        let contents = if text_transform.is_none() {
+            contents
+        } else {
+            text_transform.transform(&contents)
+        };
         Self {
             base: BaseBox::new(node, formatting_context),
             contents,
+            fragments: Vec::new(),
         }
     }
 
+    /// The contents of this run, after white space processing and `text-transform` have been
+    /// applied.
     pub fn contents(&self) -> String {
         self.contents.clone()
     }
+
+    pub fn fragments(&self) -> &[TextFragment] {
+        &self.fragments
+    }
+
+    pub(crate) fn set_fragments(&mut self, fragments: Vec<TextFragment>) {
+        self.fragments = fragments;
+    }
+
+    /// The pieces of this run's contents between its forced line breaks, which come from
+    /// preserved segment breaks.  Each piece but the last ends in a forced line break.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.contents.split(FORCED_LINE_BREAK)
+    }
+
+    /// The number of justification opportunities in this run, which is where `text-align:
+    /// justify` distributes the free space of a line.  For now, these are only word separators.
+    ///
+    /// https://drafts.csswg.org/css-text-3/#justification-opportunity
+    pub fn justification_opportunities(&self) -> usize {
+        self.contents
+            .chars()
+            .filter(|&c| is_word_separator(c))
+            .count()
+    }
+
+    /// The space `letter-spacing` and `word-spacing` add to the measured inline size of this run.
+    /// Letter spacing is added after every character, and word spacing after every word
+    /// separator.
+    ///
+    /// https://drafts.csswg.org/css-text-3/#spacing
+    pub fn added_spacing(&self) -> CSSPixelLength {
+        let computed_values = self.computed_values();
+        let characters = self
+            .contents
+            .chars()
+            .filter(|&c| c != FORCED_LINE_BREAK)
+            .count();
+        computed_values.letter_spacing.0 * characters as CSSFloat
+            + computed_values.word_spacing.0 * self.justification_opportunities() as CSSFloat
+    }
+}
+
+/// Whether `c` is a word-separator character, which `word-spacing` applies to.
+///
+/// https://drafts.csswg.org/css-text-3/#word-separator
+pub(crate) fn is_word_separator(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0020}'
+            | '\u{00A0}'
+            | '\u{1361}'
+            | '\u{10100}'
+            | '\u{10101}'
+            | '\u{1039F}'
+            | '\u{1091F}'
+    )
 }
 
 impl BaseLayoutBoxBehavior for TextRun {
@@ -218,3 +508,99 @@ impl DumpLayoutFormat for TextRun {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct AlignLineContentInput {
+    /// The containing block of the line box, formed by its block container.
+    pub containing_block: ContainingBlock,
+    pub text_align: TextAlign,
+    pub text_indent: TextIndent,
+    /// Whether this is the first line box of the block container, which `text-indent` applies to.
+    pub is_first_line: bool,
+    /// Whether this is the last line box of the block container (or ends in a forced break), which
+    /// is not justified.
+    pub is_last_line: bool,
+    /// The inline size of the content of the line, including any `letter-spacing` and
+    /// `word-spacing`.
+    pub content_inline_size: CSSPixelLength,
+    pub justification_opportunities: usize,
+}
+
+pub struct AlignLineContentOutput {
+    /// The physical coordinate (x in horizontal writing modes, y otherwise) of the origin-side
+    /// edge of the line's content.
+    pub content_start_coord: CSSFloat,
+    /// The space to add at each justification opportunity of the line.
+    pub justification_spacing: CSSPixelLength,
+}
+
+/// Positions the content of a line box along the inline axis according to `text-align` and
+/// `text-indent`.  `start` and `end` (and `left` and `right`, by way of the direction) are resolved
+/// against the direction and writing mode of the containing block to find which physical side of
+/// the line the content is placed against.
+///
+/// https://drafts.csswg.org/css-text-3/#text-align-property
+/// https://drafts.csswg.org/css-text-3/#text-indent-property
+pub fn align_line_content(input: AlignLineContentInput) -> AlignLineContentOutput {
+    let AlignLineContentInput {
+        containing_block,
+        text_align,
+        text_indent,
+        is_first_line,
+        is_last_line,
+        content_inline_size,
+        justification_opportunities,
+    } = input;
+
+    let zero = CSSPixelLength::new(0.);
+    let line_inline_size = containing_block.self_relative_inline_size();
+    let indent = if is_first_line {
+        text_indent.0.to_px(line_inline_size)
+    } else {
+        zero
+    };
+    let free_space = line_inline_size - indent - content_inline_size;
+    // Content that is too long to fit its line is start-aligned, whatever the alignment.
+    let (align_offset, justification_spacing) = if free_space <= zero {
+        (zero, zero)
+    } else {
+        match text_align.to_flow_relative(containing_block.direction()) {
+            TextAlign::Start => (zero, zero),
+            TextAlign::End => (free_space, zero),
+            TextAlign::Center => (free_space / 2., zero),
+            TextAlign::Justify => {
+                if is_last_line || justification_opportunities == 0 {
+                    (zero, zero)
+                } else {
+                    (zero, free_space / justification_opportunities as CSSFloat)
+                }
+            }
+            TextAlign::Left | TextAlign::Right => {
+                unreachable!("line-relative alignments should have been made flow-relative")
+            }
+        }
+    };
+    let aligned_content_inline_size =
+        content_inline_size + justification_spacing * justification_opportunities as CSSFloat;
+    let inline_start_offset = indent + align_offset;
+    let content_start_coord =
+        match OriginRelativeProgression::inline_start_origin_relative_direction(
+            containing_block.writing_mode(),
+            containing_block.direction(),
+        ) {
+            OriginRelativeProgression::AwayFromOrigin => {
+                containing_block.self_relative_inline_start_coord() + inline_start_offset
+            }
+            // The inline-start edge of the line is the side furthest from the origin, so the content
+            // starts that far back from it.
+            OriginRelativeProgression::TowardsOrigin => {
+                containing_block.self_relative_inline_start_coord() + line_inline_size
+                    - inline_start_offset
+                    - aligned_content_inline_size
+            }
+        };
+    AlignLineContentOutput {
+        content_start_coord: content_start_coord.px(),
+        justification_spacing,
+    }
+}
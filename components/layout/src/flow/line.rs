@@ -0,0 +1,1290 @@
+use crate::behavior::BaseLayoutBoxBehavior;
+use crate::containing_block::ContainingBlock;
+use crate::flow::block::{AnonymousBlockBox, BlockLevelBox};
+use crate::flow::float::{layout_float, place_laid_out_float, FloatContext};
+use crate::flow::inline::{
+    align_line_content, is_word_separator, AlignLineContentInput, AlignLineContentOutput,
+    InlineLevelBox, InlineLevelContent, TextFragment,
+};
+use crate::flow::white_space::FORCED_LINE_BREAK;
+use crate::layout_box::{max_content_contribution, IntrinsicSize, LayoutBox};
+use crate::text::TextMeasurer;
+use crate::{Layout, LayoutContext};
+use dom::tree::NodeData;
+use primitives::rect::{PositionedRect, Rect};
+use primitives::sides::PhysicalSide;
+use primitives::units::{CSSFloat, CSSPixelLength};
+use std::ops::Range;
+use std::vec::IntoIter;
+use style::values::computed::font::FontDescription;
+use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use style::values::computed::{ComputedValues, Float, LineHeight, WhiteSpace};
+use style::values::used::ToPx;
+use xi_unicode::LineBreakIterator;
+
+/// The character atomic inlines stand in for in the text of an inline formatting context when
+/// finding its line break opportunities.
+const OBJECT_REPLACEMENT_CHARACTER: char = '\u{FFFC}';
+
+/// A box holding one line of the content of an inline formatting context.  Line boxes are stacked
+/// one after another in the block container holding the inline formatting context.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#inline-formatting
+#[derive(Clone, Copy, Debug)]
+pub struct LineBox {
+    pub rect: PositionedRect,
+    /// The distance from the top of the line box to its baseline.
+    pub baseline: CSSPixelLength,
+}
+
+/// Lays out the inline formatting context of `abb` into line boxes, stacked from the top of its
+/// content box (whose width must already be solved) down.  The height of the content box is set
+/// to the total height of the line boxes, which are shortened to fit beside `floats`.  Floats
+/// among the content are placed in `floats` too, beside the line they're found on if they fit
+/// there, or below it if not.
+///
+/// Content is broken into lines at the line break opportunities of UAX #14, with inline-level
+/// boxes and text aligned on their baselines, and each line box made just tall enough to contain
+/// the layout bounds of everything on it.  Atomic inlines are laid out first, and placed on lines
+/// as single unbreakable boxes.  Returns whether any line boxes were generated.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#inline-formatting
+/// https://www.w3.org/TR/CSS22/visudet.html#line-height
+/// https://www.unicode.org/reports/tr14/
+// TODO: Lines are only laid out in horizontal writing modes, and always left to right, since
+// there is no bidi reordering yet.  `vertical-align` isn't supported, so everything is aligned on
+// the baseline.
+pub(crate) fn layout_inline_formatting_context(
+    abb: &mut AnonymousBlockBox,
+    context: &LayoutContext,
+    floats: &mut FloatContext,
+) -> bool {
+    let text_measurer = context.text_measurer;
+    // Anonymous block boxes are generated for a child node of their block container, whose
+    // inherited properties (like the font of the root inline box's strut) are those of the block
+    // container itself.
+    let container_node = abb
+        .node()
+        .parent()
+        .filter(|parent| matches!(parent.data(), NodeData::Element(_)))
+        .unwrap_or_else(|| abb.node());
+    let container_values = container_node.computed_values();
+    let strut = InlineMetrics::new(&container_values, text_measurer);
+    let text_align = container_values.text_align;
+    let text_indent = container_values.text_indent.clone();
+    let direction = container_values.direction;
+    let writing_mode = container_values.writing_mode;
+    drop(container_values);
+    if !writing_mode.is_horizontal() {
+        return false;
+    }
+
+    let content_box = abb.dimensions().content;
+    let containing_start_x = content_box.start_x;
+    let containing_end_x = content_box.start_x + content_box.width().px();
+    // The block container is the containing block of the atomic inlines in its lines, and of the
+    // floats among them.
+    let child_context = LayoutContext::new(
+        ContainingBlock::new(content_box, direction, writing_mode),
+        context.layout_viewport,
+        text_measurer,
+    );
+    layout_atomic_inlines(abb.children_mut(), &child_context);
+    let content = InlineContent::collect(
+        abb.children(),
+        content_box.width(),
+        AtomicInlineSizing::LaidOut,
+        text_measurer,
+    );
+    let mut out_of_flow_boxes = Vec::new();
+    collect_out_of_flow_boxes(abb.children_mut(), &mut out_of_flow_boxes);
+    let mut is_placed = vec![false; out_of_flow_boxes.len()];
+    let mut fragments = InlineFragments {
+        inline_boxes: vec![Vec::new(); content.inline_boxes.len()],
+        text_runs: vec![Vec::new(); content.text_runs.len()],
+        atomic_inlines: vec![(0., 0.); content.atomic_inlines.len()],
+    };
+    let mut line_boxes = Vec::new();
+    let mut line_top = content_box.start_y;
+    // The inline boxes continued from the end of the previous line.
+    let mut open_boxes = Vec::new();
+    let mut line_start = 0;
+    // An inline formatting context with no text and no inline margins, borders, or padding holds
+    // nothing to see, so generates no line boxes.
+    let has_content = content.items.iter().any(|item| match item {
+        InlineItem::StartInlineBox { spacing, .. } | InlineItem::EndInlineBox { spacing, .. } => {
+            *spacing != 0.
+        }
+        InlineItem::Text(_) | InlineItem::AtomicInline(_) => true,
+        InlineItem::OutOfFlow(_) => false,
+    });
+    while has_content && line_start < content.items.len() {
+        let is_first_line = line_boxes.is_empty();
+        let indent = if is_first_line {
+            text_indent.0.to_px(content_box.width())
+        } else {
+            CSSPixelLength::new(0.)
+        };
+        // The height of the line isn't known until its content is, so the line is fit beside the
+        // floats next to a line as tall as the strut.  If floats leave too little room for even
+        // the first unbreakable segment of its content, the line is moved down past them until it
+        // fits or no floats are left beside it.
+        // https://www.w3.org/TR/CSS22/visuren.html#floats
+        let strut_height = CSSPixelLength::new(strut.line_height());
+        let (start_x, end_x) = loop {
+            let (start_x, end_x) = floats.available_space(
+                line_top,
+                strut_height,
+                containing_start_x,
+                containing_end_x,
+            );
+            let is_shortened = end_x - start_x < containing_end_x - containing_start_x;
+            let fits = content.segment_fits(line_start, end_x - start_x - indent.px());
+            match floats.next_float_bottom(line_top, strut_height) {
+                Some(next_y) if is_shortened && !fits => line_top = next_y,
+                _ => break (start_x, end_x),
+            }
+        };
+        let line_width = CSSPixelLength::new(end_x - start_x);
+        let line_end = content.fit_line(line_start, (line_width - indent).px());
+
+        // A float found on the line is placed beside it if it fits next to the content before it,
+        // which shortens the line, so the line is fit again.  Otherwise, it's placed below the
+        // line, along with the floats after it, which can't be placed any higher.
+        // https://www.w3.org/TR/CSS22/visuren.html#float-rules
+        let mut deferred_floats = Vec::new();
+        let mut placed_float = false;
+        for item_index in line_start..line_end {
+            let index = match &content.items[item_index] {
+                InlineItem::OutOfFlow(item) if !is_placed[item.index] => item.index,
+                _ => continue,
+            };
+            let blb = &mut *out_of_flow_boxes[index];
+            let side = match blb.computed_values().float.physical_side(direction) {
+                Some(side) => side,
+                None => continue,
+            };
+            is_placed[index] = true;
+            blb.layout_shrink_to_fit(&child_context);
+            let float_width = blb.dimensions().margin_box().width().px();
+            let preceding = line_start..item_index;
+            let fits = content.last_text_item(preceding.clone()).is_none()
+                || content.line_content_width(preceding) + float_width
+                    <= (line_width - indent).px();
+            if fits && deferred_floats.is_empty() {
+                place_laid_out_float(blb, side, line_top, &child_context, floats);
+                placed_float = true;
+                break;
+            }
+            deferred_floats.push((index, side));
+        }
+        if placed_float {
+            continue;
+        }
+
+        let line = line_start..line_end;
+        let is_last_line =
+            line_end == content.items.len() || content.ends_in_forced_break(line.clone());
+        let AlignLineContentOutput {
+            content_start_coord,
+            justification_spacing,
+        } = align_line_content(AlignLineContentInput {
+            containing_block: ContainingBlock::new(
+                PositionedRect {
+                    start_x,
+                    start_y: line_top,
+                    rect: Rect {
+                        width: line_width,
+                        height: CSSPixelLength::new(0.),
+                    },
+                },
+                direction,
+                writing_mode,
+            ),
+            text_align,
+            text_indent: text_indent.clone(),
+            is_first_line,
+            is_last_line,
+            content_inline_size: CSSPixelLength::new(content.line_content_width(line.clone())),
+            justification_opportunities: content.line_justification_opportunities(line.clone()),
+        });
+
+        let mut placed = content.place_line(
+            line,
+            &mut open_boxes,
+            content_start_coord,
+            justification_spacing.px(),
+        );
+        // Absolutely positioned boxes have their static position where they're found on the line.
+        for (index, x) in std::mem::take(&mut placed.out_of_flow) {
+            if !is_placed[index] {
+                set_static_position(&mut *out_of_flow_boxes[index], x, line_top, &child_context);
+                is_placed[index] = true;
+            }
+        }
+        let (baseline, line_height) =
+            placed.assign_fragments(&content, strut, line_top, &mut fragments);
+        line_boxes.push(LineBox {
+            rect: PositionedRect {
+                start_x,
+                start_y: line_top,
+                rect: Rect {
+                    width: line_width,
+                    height: CSSPixelLength::new(line_height),
+                },
+            },
+            baseline: CSSPixelLength::new(baseline),
+        });
+        line_top += line_height;
+        line_start = line_end;
+        for (index, side) in deferred_floats {
+            place_laid_out_float(
+                &mut *out_of_flow_boxes[index],
+                side,
+                line_top,
+                &child_context,
+                floats,
+            );
+        }
+    }
+    // Out-of-flow boxes in an inline formatting context that generates no line boxes are placed
+    // where its first line would have been.
+    for (index, blb) in out_of_flow_boxes.into_iter().enumerate() {
+        if is_placed[index] {
+            continue;
+        }
+        let side = blb.computed_values().float.physical_side(direction);
+        match side {
+            Some(side) => layout_float(blb, side, line_top, &child_context, floats),
+            None => set_static_position(blb, containing_start_x, line_top, &child_context),
+        }
+    }
+
+    let lines_rect = PositionedRect {
+        rect: Rect {
+            width: content_box.width(),
+            height: CSSPixelLength::new(line_top - content_box.start_y),
+        },
+        ..content_box
+    };
+    let has_line_boxes = !line_boxes.is_empty();
+    abb.dimensions_mut().set_height(lines_rect.height());
+    abb.set_line_boxes(line_boxes);
+    let mut inline_box_fragments = fragments.inline_boxes.into_iter();
+    let mut text_run_fragments = fragments.text_runs.into_iter();
+    let mut atomic_inline_positions = fragments.atomic_inlines.into_iter();
+    assign_fragments(
+        abb.children_mut(),
+        lines_rect,
+        content_box.width(),
+        &mut InlineFragmentIters {
+            inline_boxes: &mut inline_box_fragments,
+            text_runs: &mut text_run_fragments,
+            atomic_inlines: &mut atomic_inline_positions,
+        },
+    );
+    has_line_boxes
+}
+
+/// The max-content inline size of the inline formatting context whose root inline box is in
+/// `children`: the width of its widest line when it's only broken at forced line breaks.
+/// Percentage margins and padding of inline boxes are treated as zero, and atomic inlines are as
+/// wide as their max-content contributions.
+///
+/// https://drafts.csswg.org/css-sizing-3/#max-content
+pub(crate) fn max_content_inline_size(
+    children: &[LayoutBox],
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    intrinsic_inline_size(children, IntrinsicSize::MaxContent, text_measurer)
+}
+
+/// The intrinsic inline size `size` of the inline formatting context whose root inline box is in
+/// `children`, measured like `max_content_inline_size`.  Its min-content inline size is the width
+/// of its widest line when it's broken at every soft wrap opportunity.
+///
+/// https://drafts.csswg.org/css-sizing-3/#min-content
+pub(crate) fn intrinsic_inline_size(
+    children: &[LayoutBox],
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    let content = InlineContent::collect(
+        children,
+        CSSPixelLength::new(0.),
+        AtomicInlineSizing::MaxContent,
+        text_measurer,
+    );
+    let available_width = match size {
+        IntrinsicSize::MinContent => 0.,
+        IntrinsicSize::MaxContent => CSSFloat::INFINITY,
+    };
+    let mut widest = 0.;
+    let mut line_start = 0;
+    while line_start < content.items.len() {
+        let line_end = content.fit_line(line_start, available_width);
+        let line = line_start..line_end;
+        let line_width = content.line_content_width(line.clone());
+        // Floats are placed beside the line they're found on, so they add to its width when it's
+        // as long as it can be, but only need to fit on their own when it's as short as it can be.
+        let float_widths = content.line_float_widths(line);
+        let width = match size {
+            IntrinsicSize::MinContent => float_widths.fold(line_width, CSSFloat::max),
+            IntrinsicSize::MaxContent => line_width + float_widths.sum::<CSSFloat>(),
+        };
+        widest = width.max(widest);
+        line_start = line_end;
+    }
+    CSSPixelLength::new(widest)
+}
+
+/// Lays out the atomic inlines in `children` and its inline boxes, with their margin boxes at the
+/// origin, in the containing block of `context`.
+fn layout_atomic_inlines(children: &mut [LayoutBox], context: &LayoutContext) {
+    for child in children {
+        match child {
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AtomicInline(aib),
+            )) => aib.layout(*context),
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(ilb)) => {
+                layout_atomic_inlines(ilb.children_mut(), context)
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::TextRun(_)) | LayoutBox::BlockLevel(_) => {}
+        }
+    }
+}
+
+/// Collects the floated and absolutely positioned boxes among the inline-level content in
+/// `children` (but not inside its atomic inlines), in tree order.
+fn collect_out_of_flow_boxes<'a>(
+    children: &'a mut [LayoutBox],
+    boxes: &mut Vec<&'a mut BlockLevelBox>,
+) {
+    for child in children {
+        match child {
+            LayoutBox::BlockLevel(blb) => boxes.push(blb),
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::InlineBox(ib),
+            )) => collect_out_of_flow_boxes(ib.children_mut(), boxes),
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AnonymousInline(aib),
+            )) => collect_out_of_flow_boxes(aib.children_mut(), boxes),
+            LayoutBox::InlineLevel(_) => {}
+        }
+    }
+}
+
+/// Gives an absolutely positioned box found among inline content the static position that
+/// `layout_positioned_boxes` lays it out from, with its margin box at `x` along the line whose top
+/// is at `y`.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#abs-non-replaced-width
+fn set_static_position(blb: &mut BlockLevelBox, x: CSSFloat, y: CSSFloat, context: &LayoutContext) {
+    blb.solve_and_set_inline_level_properties(context);
+    blb.solve_and_set_block_level_properties(context);
+    let margin_box = blb.dimensions().margin_box();
+    blb.translate(x - margin_box.start_x, y - margin_box.start_y);
+}
+
+/// How the atomic inlines of an inline formatting context are measured when its content is
+/// collected.
+#[derive(Clone, Copy, Debug)]
+enum AtomicInlineSizing {
+    /// By the margin boxes and baselines they were laid out with.
+    LaidOut,
+    /// By their max-content contributions, before they've been laid out.
+    MaxContent,
+}
+
+/// The extents of an inline box, or of the text of a text run, on either side of the baseline.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#inline-box-height
+#[derive(Clone, Copy, Debug)]
+struct InlineMetrics {
+    /// The distance from the top of the content area to the baseline.
+    ascent: CSSFloat,
+    /// The distance from the baseline to the bottom of the content area.
+    descent: CSSFloat,
+    /// Half the difference between the `line-height` and the height of the content area, which is
+    /// added above and below the content area to give the box's layout bounds.
+    half_leading: CSSFloat,
+}
+
+impl InlineMetrics {
+    /// https://www.w3.org/TR/CSS22/visudet.html#leading
+    fn new(computed_values: &ComputedValues, text_measurer: &dyn TextMeasurer) -> Self {
+        let font = computed_values.font_description();
+        let metrics = text_measurer.font_metrics(&font);
+        let content_height = metrics.content_height();
+        let line_height = match computed_values.line_height {
+            LineHeight::Normal => content_height + metrics.line_gap,
+            LineHeight::Number(number) => font.size.size * number,
+            LineHeight::Length(length) => length,
+        };
+        InlineMetrics {
+            ascent: metrics.ascent.px(),
+            descent: metrics.descent.px(),
+            half_leading: (line_height - content_height).px() / 2.,
+        }
+    }
+
+    fn layout_ascent(&self) -> CSSFloat {
+        self.ascent + self.half_leading
+    }
+
+    fn layout_descent(&self) -> CSSFloat {
+        self.descent + self.half_leading
+    }
+
+    /// The metrics of an atomic inline whose margin box is `height` tall, with its baseline
+    /// `ascent` below the top of its margin box.  The margin box of an atomic inline is its layout
+    /// bounds, so it has no leading.
+    fn atomic(ascent: CSSFloat, height: CSSFloat) -> Self {
+        InlineMetrics {
+            ascent,
+            descent: height - ascent,
+            half_leading: 0.,
+        }
+    }
+
+    fn line_height(&self) -> CSSFloat {
+        self.layout_ascent() + self.layout_descent()
+    }
+
+    /// The content area of a fragment from `start_x` to `end_x` on the line whose baseline is at
+    /// `baseline_y`.
+    fn content_area(
+        &self,
+        start_x: CSSFloat,
+        end_x: CSSFloat,
+        baseline_y: CSSFloat,
+    ) -> PositionedRect {
+        PositionedRect {
+            start_x,
+            start_y: baseline_y - self.ascent,
+            rect: Rect {
+                width: CSSPixelLength::new(end_x - start_x),
+                height: CSSPixelLength::new(self.ascent + self.descent),
+            },
+        }
+    }
+}
+
+/// A line break opportunity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LineBreak {
+    /// A place the line may be broken if the content after it doesn't fit.
+    Soft,
+    /// A place the line must be broken, e.g. at a preserved segment break.
+    Forced,
+}
+
+/// The inline-level content of an inline formatting context, flattened into the sequence of
+/// items that are packed into line boxes.
+struct InlineContent {
+    items: Vec<InlineItem>,
+    /// The metrics of each inline box, in tree order.
+    inline_boxes: Vec<InlineMetrics>,
+    /// The metrics of the text of each text run, in tree order.
+    text_runs: Vec<InlineMetrics>,
+    /// The metrics of the margin box of each atomic inline, in tree order.
+    atomic_inlines: Vec<InlineMetrics>,
+    /// The number of floated and absolutely positioned boxes.
+    out_of_flow_boxes: usize,
+}
+
+enum InlineItem {
+    /// The start of the inline box with the given index, whose inline-start margin, border, and
+    /// padding add up to `spacing`.
+    StartInlineBox {
+        index: usize,
+        spacing: CSSFloat,
+    },
+    /// The end of the inline box with the given index, whose inline-end margin, border, and
+    /// padding add up to `spacing`.
+    EndInlineBox {
+        index: usize,
+        spacing: CSSFloat,
+    },
+    Text(TextItem),
+    AtomicInline(AtomicInlineItem),
+    OutOfFlow(OutOfFlowItem),
+}
+
+/// An atomic inline, which is kept whole on one line.
+struct AtomicInlineItem {
+    /// The index of the atomic inline.
+    index: usize,
+    /// The inline size of the atomic inline's margin box.
+    width: CSSFloat,
+    /// The line break opportunity after the atomic inline, if any.
+    break_after: Option<LineBreak>,
+}
+
+/// A floated or absolutely positioned box, which takes up no space on the line it's found on.
+struct OutOfFlowItem {
+    /// The index of the box among the floated and absolutely positioned boxes, in tree order.
+    index: usize,
+    /// The max-content contribution of a float measured by `AtomicInlineSizing::MaxContent`, which
+    /// counts toward the intrinsic inline size of the line it's on, and is otherwise zero.
+    float_width: CSSFloat,
+}
+
+/// A piece of the text of a text run between two line break opportunities, which is kept
+/// together on one line.
+struct TextItem {
+    /// The index of the text run this text is from.
+    run: usize,
+    text: String,
+    /// The inline size of the text, including its `letter-spacing` and `word-spacing`.
+    width: CSSFloat,
+    justification_opportunities: usize,
+    trailing_space: TrailingSpace,
+    /// The line break opportunity after this text, if any.
+    break_after: Option<LineBreak>,
+}
+
+/// The spaces at the end of a piece of text that don't take up space when they end a line.
+/// Collapsible spaces are removed from the end of a line, while spaces preserved by `white-space:
+/// pre-wrap` hang past its end.
+///
+/// https://drafts.csswg.org/css-text-3/#white-space-phase-2
+#[derive(Default)]
+struct TrailingSpace {
+    /// The length of the spaces in bytes.
+    len: usize,
+    /// The inline size of the spaces, including their `letter-spacing` and `word-spacing`.
+    width: CSSFloat,
+    collapsible: bool,
+}
+
+/// The text of a text run, before it's broken up into `TextItem`s.
+struct TextRunText {
+    run: usize,
+    /// The range of the run's text within the text of the whole inline formatting context.
+    range: Range<usize>,
+    font: FontDescription,
+    letter_spacing: CSSFloat,
+    word_spacing: CSSFloat,
+    white_space: WhiteSpace,
+}
+
+/// An `InlineItem` whose text hasn't yet been broken up at line break opportunities.
+enum UnbrokenItem {
+    Item(InlineItem),
+    Text(TextRunText),
+    /// An atomic inline, which takes up the given range of the text of the inline formatting
+    /// context, and can be broken after when `wraps`.
+    AtomicInline {
+        item: AtomicInlineItem,
+        range: Range<usize>,
+        wraps: bool,
+    },
+}
+
+impl InlineContent {
+    /// Flattens the content of the inline formatting context whose root inline box is in
+    /// `children`.  The percentage margins and padding of inline boxes refer to
+    /// `percentage_basis`, the inline size of their containing block.
+    fn collect(
+        children: &[LayoutBox],
+        percentage_basis: CSSPixelLength,
+        atomic_inline_sizing: AtomicInlineSizing,
+        text_measurer: &dyn TextMeasurer,
+    ) -> Self {
+        let mut content = InlineContent {
+            items: Vec::new(),
+            inline_boxes: Vec::new(),
+            text_runs: Vec::new(),
+            atomic_inlines: Vec::new(),
+            out_of_flow_boxes: 0,
+        };
+        // Break opportunities can depend on the text on both sides of an inline box boundary, so
+        // they're found in the text of the whole inline formatting context at once.
+        let mut text = String::new();
+        let mut unbroken_items = Vec::new();
+        content.collect_children(
+            children,
+            percentage_basis,
+            atomic_inline_sizing,
+            text_measurer,
+            &mut text,
+            &mut unbroken_items,
+        );
+
+        let break_opportunities = LineBreakIterator::new(&text).collect::<Vec<_>>();
+        for item in unbroken_items {
+            let run_text = match item {
+                UnbrokenItem::Item(item) => {
+                    content.items.push(item);
+                    continue;
+                }
+                UnbrokenItem::AtomicInline {
+                    mut item,
+                    range,
+                    wraps,
+                } => {
+                    let can_break_after = break_opportunities
+                        .iter()
+                        .any(|&(position, _)| position == range.end);
+                    if can_break_after && range.end < text.len() && wraps {
+                        item.break_after = Some(LineBreak::Soft);
+                    }
+                    content.items.push(InlineItem::AtomicInline(item));
+                    continue;
+                }
+                UnbrokenItem::Text(run_text) => run_text,
+            };
+            let range = run_text.range.clone();
+            let mut piece_start = range.start;
+            let run_breaks = break_opportunities
+                .iter()
+                .map(|&(position, _)| position)
+                .filter(|&position| position > range.start && position <= range.end);
+            for position in run_breaks {
+                let break_after = if text[..position].ends_with(FORCED_LINE_BREAK) {
+                    Some(LineBreak::Forced)
+                } else if position < text.len() && run_text.white_space.wraps() {
+                    Some(LineBreak::Soft)
+                } else {
+                    None
+                };
+                let piece = &text[piece_start..position];
+                content.items.push(InlineItem::Text(run_text.text_item(
+                    piece,
+                    break_after,
+                    text_measurer,
+                )));
+                piece_start = position;
+            }
+            if piece_start < range.end {
+                let piece = &text[piece_start..range.end];
+                content.items.push(InlineItem::Text(run_text.text_item(
+                    piece,
+                    None,
+                    text_measurer,
+                )));
+            }
+        }
+        content
+    }
+
+    fn collect_children(
+        &mut self,
+        children: &[LayoutBox],
+        percentage_basis: CSSPixelLength,
+        atomic_inline_sizing: AtomicInlineSizing,
+        text_measurer: &dyn TextMeasurer,
+        text: &mut String,
+        unbroken_items: &mut Vec<UnbrokenItem>,
+    ) {
+        for child in children {
+            match child {
+                LayoutBox::InlineLevel(InlineLevelContent::TextRun(tr)) => {
+                    let cvs = tr.computed_values();
+                    let run = self.text_runs.len();
+                    self.text_runs.push(InlineMetrics::new(&cvs, text_measurer));
+                    let start = text.len();
+                    text.push_str(&tr.contents());
+                    unbroken_items.push(UnbrokenItem::Text(TextRunText {
+                        run,
+                        range: start..text.len(),
+                        font: cvs.font_description(),
+                        letter_spacing: cvs.letter_spacing.0.px(),
+                        word_spacing: cvs.word_spacing.0.px(),
+                        white_space: cvs.white_space,
+                    }));
+                }
+                LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                    InlineLevelBox::InlineBox(ib),
+                )) => {
+                    let cvs = ib.computed_values();
+                    let index = self.inline_boxes.len();
+                    self.inline_boxes
+                        .push(InlineMetrics::new(&cvs, text_measurer));
+                    // An inline box split around a block-level box only has its inline-start edges
+                    // on its first part, and its inline-end edges on its last.
+                    let zero = CSSPixelLength::new(0.);
+                    let start_spacing = if ib.split_before() {
+                        zero
+                    } else {
+                        cvs.margin_left.size.to_px(percentage_basis)
+                            + cvs.border_left_width.size
+                            + cvs.padding_left.size.to_px(percentage_basis)
+                    };
+                    let end_spacing = if ib.split_after() {
+                        zero
+                    } else {
+                        cvs.margin_right.size.to_px(percentage_basis)
+                            + cvs.border_right_width.size
+                            + cvs.padding_right.size.to_px(percentage_basis)
+                    };
+                    drop(cvs);
+                    unbroken_items.push(UnbrokenItem::Item(InlineItem::StartInlineBox {
+                        index,
+                        spacing: start_spacing.px(),
+                    }));
+                    self.collect_children(
+                        ib.children(),
+                        percentage_basis,
+                        atomic_inline_sizing,
+                        text_measurer,
+                        text,
+                        unbroken_items,
+                    );
+                    unbroken_items.push(UnbrokenItem::Item(InlineItem::EndInlineBox {
+                        index,
+                        spacing: end_spacing.px(),
+                    }));
+                }
+                LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                    InlineLevelBox::AnonymousInline(aib),
+                )) => self.collect_children(
+                    aib.children(),
+                    percentage_basis,
+                    atomic_inline_sizing,
+                    text_measurer,
+                    text,
+                    unbroken_items,
+                ),
+                LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                    InlineLevelBox::AtomicInline(aib),
+                )) => {
+                    let index = self.atomic_inlines.len();
+                    let (width, metrics) = match atomic_inline_sizing {
+                        AtomicInlineSizing::LaidOut => {
+                            let margin_box = aib.dimensions().margin_box();
+                            let height = margin_box.height().px();
+                            let ascent = aib.baseline() - margin_box.start_y;
+                            (
+                                margin_box.width().px(),
+                                InlineMetrics::atomic(ascent, height),
+                            )
+                        }
+                        AtomicInlineSizing::MaxContent => (
+                            max_content_contribution(child, text_measurer).px(),
+                            InlineMetrics::atomic(0., 0.),
+                        ),
+                    };
+                    self.atomic_inlines.push(metrics);
+                    // Atomic inlines are line broken around as if they were an object replacement
+                    // character.
+                    // https://drafts.csswg.org/css-text-3/#line-breaking
+                    let start = text.len();
+                    text.push(OBJECT_REPLACEMENT_CHARACTER);
+                    unbroken_items.push(UnbrokenItem::AtomicInline {
+                        item: AtomicInlineItem {
+                            index,
+                            width,
+                            break_after: None,
+                        },
+                        range: start..text.len(),
+                        wraps: aib.computed_values().white_space.wraps(),
+                    });
+                }
+                // In-flow block-level boxes are split out of inline boxes when the box tree is
+                // built, so those left are floated or absolutely positioned, and are placed beside
+                // the line they're found on rather than on it.
+                LayoutBox::BlockLevel(_) => {
+                    let index = self.out_of_flow_boxes;
+                    self.out_of_flow_boxes += 1;
+                    let float_width = match atomic_inline_sizing {
+                        AtomicInlineSizing::MaxContent if child.float() != Float::None => {
+                            max_content_contribution(child, text_measurer).px()
+                        }
+                        _ => 0.,
+                    };
+                    unbroken_items.push(UnbrokenItem::Item(InlineItem::OutOfFlow(OutOfFlowItem {
+                        index,
+                        float_width,
+                    })));
+                }
+            }
+        }
+    }
+
+    /// Whether the unbreakable segment of items starting with the item at `line_start` fits on a
+    /// line of `available_width`.
+    fn segment_fits(&self, line_start: usize, available_width: CSSFloat) -> bool {
+        let segment = self.segment(line_start);
+        segment.width - segment.trailing_space_width <= available_width
+    }
+
+    /// The index of the item after the last item that fits on a line of `available_width`
+    /// starting with the item at `line_start`.  At least one unbreakable segment of items is
+    /// always put on the line, even if it doesn't fit.
+    fn fit_line(&self, line_start: usize, available_width: CSSFloat) -> usize {
+        let mut line_end = line_start;
+        let mut width = 0.;
+        while line_end < self.items.len() {
+            let segment = self.segment(line_end);
+            let fits = width + segment.width - segment.trailing_space_width <= available_width;
+            if line_end > line_start && !fits {
+                break;
+            }
+            width += segment.width;
+            line_end = segment.end;
+            if segment.break_after == Some(LineBreak::Forced) {
+                break;
+            }
+        }
+        line_end
+    }
+
+    /// The items from `start` up to and including the next line break opportunity, which must be
+    /// placed on the same line.  Inline boxes ending right at the break are kept with the text
+    /// before it.
+    fn segment(&self, start: usize) -> Segment {
+        let mut segment = Segment {
+            end: start,
+            width: 0.,
+            trailing_space_width: 0.,
+            break_after: None,
+        };
+        for item in &self.items[start..] {
+            match item {
+                InlineItem::StartInlineBox { spacing, .. } => {
+                    if segment.break_after.is_some() {
+                        break;
+                    }
+                    segment.width += spacing;
+                }
+                InlineItem::EndInlineBox { spacing, .. } => segment.width += spacing,
+                InlineItem::Text(text) => {
+                    if segment.break_after.is_some() {
+                        break;
+                    }
+                    segment.width += text.width;
+                    segment.trailing_space_width = text.trailing_space.width;
+                    segment.break_after = text.break_after;
+                }
+                InlineItem::AtomicInline(atomic) => {
+                    if segment.break_after.is_some() {
+                        break;
+                    }
+                    segment.width += atomic.width;
+                    segment.trailing_space_width = 0.;
+                    segment.break_after = atomic.break_after;
+                }
+                InlineItem::OutOfFlow(_) => {}
+            }
+            segment.end += 1;
+        }
+        segment
+    }
+
+    /// The index of the last text or atomic inline item of the given `line`.  If it's text, its
+    /// trailing spaces don't take up space.
+    fn last_text_item(&self, line: Range<usize>) -> Option<usize> {
+        let start = line.start;
+        self.items[line]
+            .iter()
+            .rposition(|item| matches!(item, InlineItem::Text(_) | InlineItem::AtomicInline(_)))
+            .map(|index| start + index)
+    }
+
+    fn ends_in_forced_break(&self, line: Range<usize>) -> bool {
+        match self.last_text_item(line).map(|index| &self.items[index]) {
+            Some(InlineItem::Text(text)) => text.break_after == Some(LineBreak::Forced),
+            _ => false,
+        }
+    }
+
+    /// The inline size of the content of the given `line`, not counting the spaces at its end.
+    fn line_content_width(&self, line: Range<usize>) -> CSSFloat {
+        let last_text_item = self.last_text_item(line.clone());
+        line.map(|index| match &self.items[index] {
+            InlineItem::StartInlineBox { spacing, .. }
+            | InlineItem::EndInlineBox { spacing, .. } => *spacing,
+            InlineItem::Text(text) if Some(index) == last_text_item => {
+                text.width - text.trailing_space.width
+            }
+            InlineItem::Text(text) => text.width,
+            InlineItem::AtomicInline(atomic) => atomic.width,
+            InlineItem::OutOfFlow(_) => 0.,
+        })
+        .sum()
+    }
+
+    /// The `float_width` of each floated or absolutely positioned box on the given `line`.
+    fn line_float_widths(&self, line: Range<usize>) -> impl Iterator<Item = CSSFloat> + '_ {
+        self.items[line].iter().filter_map(|item| match item {
+            InlineItem::OutOfFlow(out_of_flow) => Some(out_of_flow.float_width),
+            _ => None,
+        })
+    }
+
+    /// The number of justification opportunities in the given `line`, not counting those in the
+    /// spaces at its end.
+    fn line_justification_opportunities(&self, line: Range<usize>) -> usize {
+        let last_text_item = self.last_text_item(line.clone());
+        line.map(|index| match &self.items[index] {
+            InlineItem::Text(text) if Some(index) == last_text_item => {
+                text.justification_opportunities - text.trailing_space.len
+            }
+            InlineItem::Text(text) => text.justification_opportunities,
+            _ => 0,
+        })
+        .sum()
+    }
+
+    /// Places the items of `line` along the inline axis, starting at `start_x` and adding
+    /// `justification_spacing` at each justification opportunity.  `open_boxes` holds the inline
+    /// boxes continued from the previous line, and is left holding those continued on the next.
+    fn place_line(
+        &self,
+        line: Range<usize>,
+        open_boxes: &mut Vec<usize>,
+        start_x: CSSFloat,
+        justification_spacing: CSSFloat,
+    ) -> PlacedLine {
+        let last_text_item = self.last_text_item(line.clone());
+        let mut placed = PlacedLine {
+            inline_boxes: Vec::new(),
+            text: Vec::new(),
+            atomic_inlines: Vec::new(),
+            out_of_flow: Vec::new(),
+        };
+        let mut x = start_x;
+        let mut box_starts = open_boxes
+            .iter()
+            .map(|&index| (index, x))
+            .collect::<Vec<_>>();
+        for index in line {
+            match &self.items[index] {
+                InlineItem::StartInlineBox { index, spacing } => {
+                    x += spacing;
+                    box_starts.push((*index, x));
+                }
+                InlineItem::EndInlineBox { index, spacing } => {
+                    let (started_index, start_x) = box_starts
+                        .pop()
+                        .expect("inline box ended without being started");
+                    debug_assert_eq!(started_index, *index);
+                    placed.inline_boxes.push((*index, start_x, x));
+                    x += spacing;
+                }
+                InlineItem::Text(text) => {
+                    let mut contents = text.text.as_str();
+                    let mut width = text.width;
+                    let mut justification_opportunities = text.justification_opportunities;
+                    if Some(index) == last_text_item {
+                        // Each trailing space is a justification opportunity.
+                        justification_opportunities -= text.trailing_space.len;
+                        if text.trailing_space.collapsible {
+                            contents = &contents[..contents.len() - text.trailing_space.len];
+                            width -= text.trailing_space.width;
+                        }
+                    }
+                    width += justification_spacing * justification_opportunities as CSSFloat;
+                    // Consecutive pieces of the same run on a line form a single fragment.
+                    match placed.text.last_mut() {
+                        Some(last) if last.run == text.run && last.end_x == x => {
+                            last.text.push_str(contents);
+                            last.end_x += width;
+                        }
+                        _ if contents.is_empty() => {}
+                        _ => placed.text.push(PlacedText {
+                            run: text.run,
+                            text: contents.to_owned(),
+                            start_x: x,
+                            end_x: x + width,
+                        }),
+                    }
+                    x += width;
+                }
+                InlineItem::AtomicInline(atomic) => {
+                    placed.atomic_inlines.push((atomic.index, x));
+                    x += atomic.width;
+                }
+                InlineItem::OutOfFlow(out_of_flow) => {
+                    placed.out_of_flow.push((out_of_flow.index, x))
+                }
+            }
+        }
+        // Inline boxes that haven't ended yet continue on the next line.
+        open_boxes.clear();
+        for (index, start_x) in box_starts {
+            placed.inline_boxes.push((index, start_x, x));
+            open_boxes.push(index);
+        }
+        placed
+    }
+}
+
+/// A run of items that can't be broken across lines, as found by `InlineContent::segment`.
+struct Segment {
+    /// The index after the last item of the segment.
+    end: usize,
+    width: CSSFloat,
+    /// The inline size of the spaces at the end of the segment's last text, which don't take up
+    /// space if the line ends after the segment.
+    trailing_space_width: CSSFloat,
+    break_after: Option<LineBreak>,
+}
+
+/// The fragments of inline boxes and text runs placed on a line, as yet only positioned along the
+/// inline axis.
+struct PlacedLine {
+    /// The index, start x-coordinate, and end x-coordinate of the content of each inline box on
+    /// the line.
+    inline_boxes: Vec<(usize, CSSFloat, CSSFloat)>,
+    text: Vec<PlacedText>,
+    /// The index and margin box start x-coordinate of each atomic inline on the line.
+    atomic_inlines: Vec<(usize, CSSFloat)>,
+    /// The index and x-coordinate of where each floated or absolutely positioned box was found on
+    /// the line.
+    out_of_flow: Vec<(usize, CSSFloat)>,
+}
+
+struct PlacedText {
+    run: usize,
+    text: String,
+    start_x: CSSFloat,
+    end_x: CSSFloat,
+}
+
+impl PlacedLine {
+    /// Aligns the fragments of this line on a shared baseline in a line box starting at
+    /// `line_top`, adding them to `fragments`, and returns the distance from the top of the line
+    /// box to the baseline, and the height of the line box.  The line box is tall enough to
+    /// contain the layout bounds of every inline box on it, including the `strut` of its root
+    /// inline box.
+    ///
+    /// https://www.w3.org/TR/CSS22/visudet.html#line-height
+    fn assign_fragments(
+        self,
+        content: &InlineContent,
+        strut: InlineMetrics,
+        line_top: CSSFloat,
+        fragments: &mut InlineFragments,
+    ) -> (CSSFloat, CSSFloat) {
+        let metrics = self
+            .inline_boxes
+            .iter()
+            .map(|&(index, _, _)| content.inline_boxes[index])
+            .chain(self.text.iter().map(|text| content.text_runs[text.run]))
+            .chain(
+                self.atomic_inlines
+                    .iter()
+                    .map(|&(index, _)| content.atomic_inlines[index]),
+            );
+        let (ascent, descent) = metrics.fold(
+            (strut.layout_ascent(), strut.layout_descent()),
+            |(ascent, descent), metrics| {
+                (
+                    metrics.layout_ascent().max(ascent),
+                    metrics.layout_descent().max(descent),
+                )
+            },
+        );
+        let baseline_y = line_top + ascent;
+        for (index, start_x, end_x) in self.inline_boxes {
+            fragments.inline_boxes[index]
+                .push(content.inline_boxes[index].content_area(start_x, end_x, baseline_y));
+        }
+        for text in self.text {
+            let rect =
+                content.text_runs[text.run].content_area(text.start_x, text.end_x, baseline_y);
+            fragments.text_runs[text.run].push(TextFragment {
+                text: text.text,
+                rect,
+            });
+        }
+        for (index, start_x) in self.atomic_inlines {
+            let start_y = baseline_y - content.atomic_inlines[index].ascent;
+            fragments.atomic_inlines[index] = (start_x, start_y);
+        }
+        (ascent, ascent + descent)
+    }
+}
+
+/// The fragments of each inline box and text run of an inline formatting context, in tree order.
+struct InlineFragments {
+    inline_boxes: Vec<Vec<PositionedRect>>,
+    text_runs: Vec<Vec<TextFragment>>,
+    /// The position of the margin box of each atomic inline.
+    atomic_inlines: Vec<(CSSFloat, CSSFloat)>,
+}
+
+/// The `InlineFragments` of an inline formatting context, as they're taken in tree order.
+struct InlineFragmentIters<'a> {
+    inline_boxes: &'a mut IntoIter<Vec<PositionedRect>>,
+    text_runs: &'a mut IntoIter<Vec<TextFragment>>,
+    atomic_inlines: &'a mut IntoIter<(CSSFloat, CSSFloat)>,
+}
+
+impl TextRunText {
+    fn text_item(
+        &self,
+        piece: &str,
+        break_after: Option<LineBreak>,
+        text_measurer: &dyn TextMeasurer,
+    ) -> TextItem {
+        let text = piece
+            .strip_suffix(FORCED_LINE_BREAK)
+            .unwrap_or(piece)
+            .to_owned();
+        let trailing_space =
+            if self.white_space.collapses_spaces() || self.white_space == WhiteSpace::PreWrap {
+                let len = text.len() - text.trim_end_matches(' ').len();
+                TrailingSpace {
+                    len,
+                    width: self.measure(&text[text.len() - len..], text_measurer),
+                    collapsible: self.white_space.collapses_spaces(),
+                }
+            } else {
+                TrailingSpace::default()
+            };
+        TextItem {
+            run: self.run,
+            width: self.measure(&text, text_measurer),
+            justification_opportunities: text.chars().filter(|&c| is_word_separator(c)).count(),
+            trailing_space,
+            break_after,
+            text,
+        }
+    }
+
+    /// The inline size of `text` set in this run's font, including its `letter-spacing` and
+    /// `word-spacing`.
+    fn measure(&self, text: &str, text_measurer: &dyn TextMeasurer) -> CSSFloat {
+        let characters = text.chars().count();
+        let word_separators = text.chars().filter(|&c| is_word_separator(c)).count();
+        text_measurer.advance_width(text, &self.font).px()
+            + self.letter_spacing * characters as CSSFloat
+            + self.word_spacing * word_separators as CSSFloat
+    }
+}
+
+/// Gives the inline boxes and text runs in `children` their fragments, taken in tree order, and
+/// sizes each to the bounding box of its fragments.  Root inline boxes span every line, so are
+/// given `lines_rect`, the area taken up by the line boxes.  Atomic inlines are moved to where
+/// they were placed.
+fn assign_fragments(
+    children: &mut [LayoutBox],
+    lines_rect: PositionedRect,
+    percentage_basis: CSSPixelLength,
+    fragments: &mut InlineFragmentIters,
+) {
+    for child in children {
+        match child {
+            LayoutBox::InlineLevel(InlineLevelContent::TextRun(tr)) => {
+                let run_fragments = fragments
+                    .text_runs
+                    .next()
+                    .expect("text run should have been laid out");
+                let bounds = bounding_rect(run_fragments.iter().map(|fragment| fragment.rect));
+                tr.dimensions_mut().content = bounds.unwrap_or(PositionedRect {
+                    rect: Rect::default(),
+                    ..lines_rect
+                });
+                tr.set_fragments(run_fragments);
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::InlineBox(ib),
+            )) => {
+                let box_fragments = fragments
+                    .inline_boxes
+                    .next()
+                    .expect("inline box should have been laid out");
+                // Margins and padding refer to the width of the containing block on every side.
+                let cvs = ib.computed_values();
+                let margin =
+                    |lp_or_auto: &LengthPercentageOrAuto| lp_or_auto.to_px(percentage_basis);
+                let padding = |lp: &LengthPercentage| lp.to_px(percentage_basis);
+                let edges = [
+                    (
+                        PhysicalSide::Left,
+                        margin(&cvs.margin_left.size),
+                        cvs.border_left_width.size,
+                        padding(&cvs.padding_left.size),
+                    ),
+                    (
+                        PhysicalSide::Right,
+                        margin(&cvs.margin_right.size),
+                        cvs.border_right_width.size,
+                        padding(&cvs.padding_right.size),
+                    ),
+                    (
+                        PhysicalSide::Top,
+                        margin(&cvs.margin_top.size),
+                        cvs.border_top_width.size,
+                        padding(&cvs.padding_top.size),
+                    ),
+                    (
+                        PhysicalSide::Bottom,
+                        margin(&cvs.margin_bottom.size),
+                        cvs.border_bottom_width.size,
+                        padding(&cvs.padding_bottom.size),
+                    ),
+                ];
+                drop(cvs);
+                let (split_before, split_after) = (ib.split_before(), ib.split_after());
+                let dimensions = ib.dimensions_mut();
+                for &(side, margin, border, padding) in &edges {
+                    // The sides a split inline box was split at have no edges.
+                    let zero = CSSPixelLength::new(0.);
+                    let (margin, border, padding) = match side {
+                        PhysicalSide::Left if split_before => (zero, zero, zero),
+                        PhysicalSide::Right if split_after => (zero, zero, zero),
+                        _ => (margin, border, padding),
+                    };
+                    dimensions.set_margin_physical(side, margin);
+                    dimensions.set_border_physical(side, border);
+                    dimensions.set_padding_physical(side, padding);
+                }
+                dimensions.content =
+                    bounding_rect(box_fragments.iter().copied()).unwrap_or(PositionedRect {
+                        rect: Rect::default(),
+                        ..lines_rect
+                    });
+                ib.set_fragments(box_fragments);
+                assign_fragments(ib.children_mut(), lines_rect, percentage_basis, fragments);
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AnonymousInline(aib),
+            )) => {
+                aib.dimensions_mut().content = lines_rect;
+                assign_fragments(aib.children_mut(), lines_rect, percentage_basis, fragments);
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AtomicInline(aib),
+            )) => {
+                let (x, y) = fragments
+                    .atomic_inlines
+                    .next()
+                    .expect("atomic inline should have been laid out");
+                let margin_box = aib.dimensions().margin_box();
+                aib.translate(x - margin_box.start_x, y - margin_box.start_y);
+            }
+            LayoutBox::BlockLevel(_) => {}
+        }
+    }
+}
+
+/// The smallest rectangle containing all of `rects`, or `None` if there are none.
+fn bounding_rect(rects: impl Iterator<Item = PositionedRect>) -> Option<PositionedRect> {
+    rects
+        .map(|rect| {
+            let end_x = rect.start_x + rect.width().px();
+            let end_y = rect.start_y + rect.height().px();
+            (rect.start_x, rect.start_y, end_x, end_y)
+        })
+        .reduce(|(start_x, start_y, end_x, end_y), rect| {
+            (
+                start_x.min(rect.0),
+                start_y.min(rect.1),
+                end_x.max(rect.2),
+                end_y.max(rect.3),
+            )
+        })
+        .map(|(start_x, start_y, end_x, end_y)| PositionedRect {
+            start_x,
+            start_y,
+            rect: Rect {
+                width: CSSPixelLength::new(end_x - start_x),
+                height: CSSPixelLength::new(end_y - start_y),
+            },
+        })
+}
@@ -0,0 +1,55 @@
+use primitives::units::CSSPixelLength;
+
+/// A set of adjoining margins, which collapse into a single margin.  The collapsed margin is the
+/// largest of the positive margins plus the most negative of the negative margins.
+///
+/// https://www.w3.org/TR/CSS22/box.html#collapsing-margins
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CollapsedMargin {
+    max_positive: CSSPixelLength,
+    min_negative: CSSPixelLength,
+}
+
+impl CollapsedMargin {
+    pub fn zero() -> Self {
+        CollapsedMargin::default()
+    }
+
+    pub fn new(margin: CSSPixelLength) -> Self {
+        let zero = CSSPixelLength::new(0.);
+        CollapsedMargin {
+            max_positive: margin.max(zero),
+            min_negative: margin.min(zero),
+        }
+    }
+
+    /// Collapses this margin with `other`, a margin adjoining it.
+    pub fn adjoin(self, other: CollapsedMargin) -> Self {
+        CollapsedMargin {
+            max_positive: self.max_positive.max(other.max_positive),
+            min_negative: self.min_negative.min(other.min_negative),
+        }
+    }
+
+    /// The used size of the collapsed margin.
+    pub fn resolve(self) -> CSSPixelLength {
+        self.max_positive + self.min_negative
+    }
+}
+
+/// The block-start and block-end margins of a block-level box, each collapsed with the margins
+/// of its descendants that adjoin it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollapsedBlockMargins {
+    pub start: CollapsedMargin,
+    pub end: CollapsedMargin,
+    /// Whether the box's own block-start and block-end margins adjoin, in which case all of its
+    /// margins (and those of its children) collapse through it, and `start` and `end` are the
+    /// same margin.
+    pub collapsed_through: bool,
+    /// Space added above the border box of the box to move it below the floats its `clear`
+    /// applies to.  Clearance separates the box's block-start margin from the margins before it.
+    ///
+    /// https://www.w3.org/TR/CSS22/visuren.html#flow-control
+    pub clearance: CSSPixelLength,
+}
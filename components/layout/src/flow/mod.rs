@@ -1,7 +1,10 @@
 pub mod block;
+pub mod float;
 pub mod inline;
+pub mod line;
+pub mod margin_collapse;
+pub mod white_space;
 
-use crate::apply_box_sizing_properties_base_box_passthrough_impls;
 use crate::behavior::{ApplyBoxSizingProperties, BaseLayoutBoxBehavior};
 use crate::containing_block::ContainingBlock;
 use crate::dimensions::Dimensions;
@@ -11,8 +14,9 @@ use crate::layout_box_behavior_base_box_passthrough_impls;
 use crate::DumpLayoutFormat;
 use accountable_refcell::Ref;
 use dom::tree::NodeRef;
+use primitives::rect::PositionedRect;
 use style::values::computed::ComputedValues;
-use style::values::computed::{Direction, WritingMode};
+use style::values::computed::{Direction, Display, WritingMode};
 
 /// A box that contains either contains only inline-level boxes participating in an inline
 /// formatting context, or contains only block-level boxes participating in a block formatting
@@ -24,6 +28,9 @@ use style::values::computed::{Direction, WritingMode};
 pub struct BlockContainer {
     base: BaseBox,
     children: Vec<LayoutBox>,
+    /// The areas the rules between the columns of this box are painted in, if it's a multi-column
+    /// container.  Set by multi-column layout.
+    column_rules: Vec<PositionedRect>,
 }
 
 impl BlockContainer {
@@ -31,6 +38,20 @@ impl BlockContainer {
         BlockContainer {
             base: BaseBox::new(node, fc),
             children: Vec::new(),
+            column_rules: Vec::new(),
+        }
+    }
+
+    /// Creates an anonymous block container with the given `display` for content starting at
+    /// `node`, like the anonymous table, row, and cell boxes generated around content that isn't
+    /// inside a table of the right structure.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+    pub fn new_anonymous(node: NodeRef, fc: FormattingContextRef, display: Display) -> Self {
+        BlockContainer {
+            base: BaseBox::new_anonymous_with_display(node, fc, display),
+            children: Vec::new(),
+            column_rules: Vec::new(),
         }
     }
 
@@ -45,6 +66,19 @@ impl BlockContainer {
     pub fn children_mut(&mut self) -> &mut Vec<LayoutBox> {
         &mut self.children
     }
+
+    pub fn column_rules(&self) -> &[PositionedRect] {
+        &self.column_rules
+    }
+
+    pub(crate) fn set_column_rules(&mut self, column_rules: Vec<PositionedRect>) {
+        self.column_rules = column_rules;
+    }
+
+    /// Whether this is an anonymous block container, which isn't generated by an element.
+    pub fn is_anonymous(&self) -> bool {
+        self.base.is_anonymous()
+    }
 }
 
 impl BaseLayoutBoxBehavior for BlockContainer {
@@ -52,11 +86,31 @@ impl BaseLayoutBoxBehavior for BlockContainer {
 }
 
 impl ApplyBoxSizingProperties for BlockContainer {
-    apply_box_sizing_properties_base_box_passthrough_impls!();
+    /// Tables are never smaller than their rows and columns need, so their sizing properties can
+    /// only make them bigger than they're laid out to be.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#width-layout
+    /// https://www.w3.org/TR/CSS22/tables.html#height-layout
+    fn apply_box_sizing_properties(&mut self, containing_block: ContainingBlock) {
+        let laid_out = self.dimensions().content;
+        self.base.apply_box_sizing_properties(containing_block);
+        if self.formatting_context().is_table_formatting_context() {
+            let content = self.dimensions().content;
+            self.dimensions_mut()
+                .set_width(content.width().max(laid_out.width()));
+            self.dimensions_mut()
+                .set_height(content.height().max(laid_out.height()));
+        }
+    }
 }
 
 impl DumpLayoutFormat for BlockContainer {
     fn dump_layout_format(&self) -> String {
+        // Anonymous block containers are not generated by an element of the DOM, so don't print
+        // the data of the node their content starts at.
+        if self.base.is_anonymous() {
+            return "AnonymousBlockContainer".to_string();
+        }
         let node_data = self.node().data().dump_layout_format();
         if node_data.is_empty() {
             "BlockContainer".to_string()
@@ -0,0 +1,200 @@
+use style::values::computed::WhiteSpace;
+
+/// The segment break that remains in processed text for each forced line break.
+pub const FORCED_LINE_BREAK: char = '\n';
+
+/// Tracks whether the text processed so far in an inline formatting context ends in a collapsible
+/// space.  A collapsible space immediately following another is removed, even when the two are in
+/// different text nodes (e.g. on either side of an inline element's boundary), so this state must
+/// be carried from one text node to the next.
+///
+/// https://drafts.csswg.org/css-text-3/#white-space-phase-1
+#[derive(Clone, Copy, Debug)]
+pub struct CollapsibleSpaceState {
+    after_collapsible_space: bool,
+}
+
+impl CollapsibleSpaceState {
+    /// The state at the start of a line, where collapsible spaces are removed.
+    ///
+    /// https://drafts.csswg.org/css-text-3/#white-space-phase-2
+    pub fn line_start() -> Self {
+        CollapsibleSpaceState {
+            after_collapsible_space: true,
+        }
+    }
+
+    /// The state following an atomic inline, which (like any character other than a collapsible
+    /// space) keeps a collapsible space after it from being removed.
+    pub fn after_atomic_inline() -> Self {
+        CollapsibleSpaceState {
+            after_collapsible_space: false,
+        }
+    }
+}
+
+/// Performs phase I of white space processing on the contents of a text node whose `white-space`
+/// is `white_space`, updating `state` with how the processed text ends.
+///
+/// Collapsible spaces at the start of a line or following another collapsible space are removed,
+/// and other sequences of spaces, tabs, and (unless preserved) segment breaks become a single
+/// space.  Segment breaks that are preserved remain as `FORCED_LINE_BREAK`s, which line layout
+/// must honour.
+///
+/// Removing the collapsible spaces at the end of each line is left to line layout, since where
+/// lines end isn't known until then.
+///
+/// https://drafts.csswg.org/css-text-3/#white-space-processing
+pub fn process_white_space(
+    text: &str,
+    white_space: WhiteSpace,
+    state: &mut CollapsibleSpaceState,
+) -> String {
+    let mut processed = String::with_capacity(text.len());
+    for c in text.chars() {
+        let is_segment_break = c == '\n';
+        if is_segment_break && white_space.preserves_segment_breaks() {
+            if white_space.collapses_spaces() {
+                // Collapsible spaces immediately preceding a segment break are removed.
+                let without_trailing_spaces = processed.trim_end_matches(' ').len();
+                processed.truncate(without_trailing_spaces);
+            }
+            processed.push(FORCED_LINE_BREAK);
+            // A forced line break starts a new line.
+            *state = CollapsibleSpaceState::line_start();
+        } else if white_space.collapses_spaces() && (is_segment_break || c == ' ' || c == '\t') {
+            if !state.after_collapsible_space {
+                processed.push(' ');
+                state.after_collapsible_space = true;
+            }
+        } else {
+            processed.push(c);
+            state.after_collapsible_space = false;
+        }
+    }
+    processed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Processes the contents of consecutive text nodes of an inline formatting context, starting
+    /// at the start of a line, all with the given `white_space`.
+    fn process(texts: &[&str], white_space: WhiteSpace) -> Vec<String> {
+        let mut state = CollapsibleSpaceState::line_start();
+        texts
+            .iter()
+            .map(|text| process_white_space(text, white_space, &mut state))
+            .collect()
+    }
+
+    #[test]
+    fn normal_collapses_spaces_tabs_and_segment_breaks() {
+        assert_eq!(
+            process(&["  one \t two\n\nthree  "], WhiteSpace::Normal),
+            ["one two three "]
+        );
+    }
+
+    #[test]
+    fn nowrap_collapses_like_normal() {
+        assert_eq!(
+            process(&["  one \t two\n\nthree  "], WhiteSpace::Nowrap),
+            ["one two three "]
+        );
+    }
+
+    #[test]
+    fn pre_preserves_spaces_tabs_and_segment_breaks() {
+        assert_eq!(
+            process(&["  one \t two\n\nthree  "], WhiteSpace::Pre),
+            ["  one \t two\n\nthree  "]
+        );
+    }
+
+    #[test]
+    fn pre_wrap_preserves_spaces_tabs_and_segment_breaks() {
+        assert_eq!(
+            process(&["  one \t two\n\nthree  "], WhiteSpace::PreWrap),
+            ["  one \t two\n\nthree  "]
+        );
+    }
+
+    #[test]
+    fn pre_line_collapses_spaces_but_preserves_segment_breaks() {
+        // Spaces on either side of a forced line break are removed, since they'd be at the end or
+        // start of a line.
+        assert_eq!(
+            process(&["  one \t two  \n  three  "], WhiteSpace::PreLine),
+            ["one two\nthree "]
+        );
+    }
+
+    #[test]
+    fn spaces_collapse_across_text_node_boundaries() {
+        // E.g. `one <span> two</span>`, whose text nodes are on either side of the start of an
+        // inline box.
+        assert_eq!(
+            process(&["one ", " two", " "], WhiteSpace::Normal),
+            ["one ", "two", " "]
+        );
+    }
+
+    #[test]
+    fn text_node_of_only_collapsed_spaces_is_empty() {
+        assert_eq!(
+            process(&["one ", " \n\t ", "two"], WhiteSpace::Normal),
+            ["one ", "", "two"]
+        );
+        assert_eq!(process(&["  \n  "], WhiteSpace::Normal), [""]);
+    }
+
+    #[test]
+    fn preserved_spaces_do_not_collapse_with_following_text_nodes() {
+        let mut state = CollapsibleSpaceState::line_start();
+        assert_eq!(
+            process_white_space("one ", WhiteSpace::Pre, &mut state),
+            "one "
+        );
+        assert_eq!(
+            process_white_space(" two", WhiteSpace::Normal, &mut state),
+            " two"
+        );
+    }
+
+    #[test]
+    fn space_after_atomic_inline_is_kept() {
+        // E.g. `<span style="display: inline-block"></span> one`.
+        let mut state = CollapsibleSpaceState::after_atomic_inline();
+        assert_eq!(
+            process_white_space(" one", WhiteSpace::Normal, &mut state),
+            " one"
+        );
+    }
+
+    #[test]
+    fn forced_line_break_starts_a_new_line_in_the_next_text_node() {
+        // E.g. `<span style="white-space: pre-line">one\n</span> two`, where the space after the
+        // forced line break is at the start of a line.
+        let mut state = CollapsibleSpaceState::line_start();
+        assert_eq!(
+            process_white_space("one\n", WhiteSpace::PreLine, &mut state),
+            "one\n"
+        );
+        assert_eq!(
+            process_white_space(" two", WhiteSpace::Normal, &mut state),
+            "two"
+        );
+    }
+
+    #[test]
+    fn segment_breaks_become_forced_line_breaks() {
+        for &white_space in &[WhiteSpace::Pre, WhiteSpace::PreWrap, WhiteSpace::PreLine] {
+            assert_eq!(
+                process(&["one\ntwo"], white_space),
+                [format!("one{}two", FORCED_LINE_BREAK)]
+            );
+        }
+    }
+}
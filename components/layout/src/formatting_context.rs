@@ -32,14 +32,48 @@ impl FormattingContextRef {
 
     pub fn new_independent_block() -> Self {
         FormattingContextRef(Rc::new(QualifiedFormattingContext::Independent(
-            FormattingContext::Inline,
+            FormattingContext::Block,
+        )))
+    }
+
+    pub fn new_independent_flex() -> Self {
+        FormattingContextRef(Rc::new(QualifiedFormattingContext::Independent(
+            FormattingContext::Flex,
+        )))
+    }
+
+    pub fn new_independent_grid() -> Self {
+        FormattingContextRef(Rc::new(QualifiedFormattingContext::Independent(
+            FormattingContext::Grid,
+        )))
+    }
+
+    pub fn new_independent_table() -> Self {
+        FormattingContextRef(Rc::new(QualifiedFormattingContext::Independent(
+            FormattingContext::Table,
         )))
     }
 
     pub fn is_inline_formatting_context(&self) -> bool {
+        self.formatting_context() == FormattingContext::Inline
+    }
+
+    pub fn is_flex_formatting_context(&self) -> bool {
+        self.formatting_context() == FormattingContext::Flex
+    }
+
+    pub fn is_grid_formatting_context(&self) -> bool {
+        self.formatting_context() == FormattingContext::Grid
+    }
+
+    pub fn is_table_formatting_context(&self) -> bool {
+        self.formatting_context() == FormattingContext::Table
+    }
+
+    fn formatting_context(&self) -> FormattingContext {
         match *self.0 {
             QualifiedFormattingContext::Independent(fc)
-            | QualifiedFormattingContext::Dependent(fc) => fc == FormattingContext::Inline,
+            | QualifiedFormattingContext::Dependent(fc) => fc,
         }
     }
 }
@@ -51,7 +85,16 @@ impl FormattingContextRef {
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum FormattingContext {
     Block,
+    /// The formatting context a flex container establishes for its flex items.
+    /// https://drafts.csswg.org/css-flexbox-1/#flex-formatting-context
+    Flex,
+    /// The formatting context a grid container establishes for its grid items.
+    /// https://drafts.csswg.org/css-grid-1/#grid-formatting-context
+    Grid,
     Inline,
+    /// The formatting context a table establishes for its rows, columns, and cells.
+    /// https://www.w3.org/TR/CSS22/tables.html#model
+    Table,
 }
 
 /// A formatting context can contain sub-formatting-contexts and boxes.
@@ -0,0 +1,1423 @@
+use crate::behavior::{ApplyBoxSizingProperties, BaseLayoutBoxBehavior};
+use crate::containing_block::ContainingBlock;
+use crate::dimensions::Dimensions;
+use crate::flex::{
+    definite_size, distribute, item_box, layout_item_contents, place, size_limits, FlowAxis,
+};
+use crate::flow::block::BlockLevelBox;
+use crate::flow::line::intrinsic_inline_size;
+use crate::layout_box::{
+    content_box_size, content_intrinsic_width, intrinsic_contribution, IntrinsicSize, LayoutBox,
+};
+use crate::text::TextMeasurer;
+use crate::{BoxComponent, LayoutContext};
+use primitives::sides::FlowSide;
+use primitives::units::{CSSFloat, CSSPixelLength};
+use std::collections::HashMap;
+use std::ops::Range;
+use style::values::computed::grid::{RepeatCount, TrackList, MAX_GRID_LINE};
+use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use style::values::computed::{
+    ComputedValues, ContentAlignment, Direction, GridAutoTracks, GridLine, GridTemplate,
+    GridTemplateAreas, ItemAlignment, TrackBreadth, TrackSize, WritingMode,
+};
+use style::values::used::ToPx;
+
+/// A minimum or maximum track sizing function, with any percentage in it resolved.
+///
+/// https://drafts.csswg.org/css-grid-1/#algo-terms
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Breadth {
+    Fixed(CSSPixelLength),
+    Flex(CSSFloat),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+impl Breadth {
+    /// The sizing function given by `breadth`, where `basis` is the size of the grid container's
+    /// content box along the axis of the track, if it's definite.  Percentages of an indefinite
+    /// size are treated as `auto`.
+    fn new(breadth: &TrackBreadth, basis: Option<CSSPixelLength>) -> Breadth {
+        match breadth {
+            TrackBreadth::Length(LengthPercentage::Length(length)) => Breadth::Fixed(*length),
+            TrackBreadth::Length(lp) => {
+                basis.map_or(Breadth::Auto, |basis| Breadth::Fixed(lp.to_px(basis)))
+            }
+            TrackBreadth::Flex(flex) => Breadth::Flex(*flex),
+            TrackBreadth::Auto => Breadth::Auto,
+            TrackBreadth::MinContent => Breadth::MinContent,
+            TrackBreadth::MaxContent => Breadth::MaxContent,
+        }
+    }
+
+    fn is_intrinsic(self) -> bool {
+        matches!(
+            self,
+            Breadth::Auto | Breadth::MinContent | Breadth::MaxContent
+        )
+    }
+}
+
+/// A row or column of a grid, and the sizes the track sizing algorithm resolves for it.
+///
+/// https://drafts.csswg.org/css-grid-1/#grid-track-concept
+#[derive(Clone, Debug)]
+struct Track {
+    min: Breadth,
+    max: Breadth,
+    base_size: CSSPixelLength,
+    /// The size the track can grow to, which is infinite until it's known.
+    growth_limit: CSSPixelLength,
+    /// Whether the track is an empty track repeated by `auto-fit`, which is sized to zero along
+    /// with the gutters on either side of it.
+    collapsed: bool,
+}
+
+impl Track {
+    fn new(size: &TrackSize, basis: Option<CSSPixelLength>) -> Track {
+        Track {
+            min: Breadth::new(&size.min(), basis),
+            max: Breadth::new(&size.max(), basis),
+            base_size: CSSPixelLength::new(0.),
+            growth_limit: CSSPixelLength::new(CSSFloat::INFINITY),
+            collapsed: false,
+        }
+    }
+
+    fn flex(&self) -> Option<CSSFloat> {
+        match self.max {
+            Breadth::Flex(flex) if !self.collapsed => Some(flex),
+            _ => None,
+        }
+    }
+}
+
+/// A grid item, and the zero-based range of the lines of the grid its area is between along each
+/// axis.
+///
+/// https://drafts.csswg.org/css-grid-1/#grid-items
+#[derive(Clone, Debug)]
+struct GridItem {
+    /// The index of the item among the children of the grid container.
+    index: usize,
+    columns: Range<usize>,
+    rows: Range<usize>,
+}
+
+/// The items of a grid container placed in its grid, and the tracks of the grid.
+#[derive(Clone, Debug)]
+struct Grid {
+    items: Vec<GridItem>,
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+}
+
+/// The placement of a grid item along one axis, either the range of the lines it's between or,
+/// if it's to be placed automatically, the number of tracks it spans.
+#[derive(Clone, Debug)]
+enum Placement<T> {
+    Definite(Range<T>),
+    Auto(usize),
+}
+
+/// The explicit grid along one axis.
+///
+/// https://drafts.csswg.org/css-grid-1/#explicit-grids
+#[derive(Clone, Debug)]
+struct ExplicitTracks {
+    /// The names of each line, one more than there are explicit tracks.
+    line_names: Vec<Vec<String>>,
+    /// The sizes of the tracks given by the grid template.  There can be more explicit tracks than
+    /// this if `grid-template-areas` adds them, and those are sized as implicit tracks are.
+    sizes: Vec<TrackSize>,
+    /// The tracks repeated by `auto-fit`, which are collapsed if they end up empty.
+    auto_fit: Option<Range<usize>>,
+}
+
+impl ExplicitTracks {
+    /// The explicit tracks given by `template`, and by the `area_count` tracks of
+    /// `grid-template-areas` along the same axis.  `space` is the size of the grid container's
+    /// content box along the axis if it's definite, which an automatic repetition is repeated to
+    /// fill.
+    fn new(
+        template: &GridTemplate,
+        area_count: usize,
+        space: Option<CSSPixelLength>,
+        gap: CSSPixelLength,
+    ) -> ExplicitTracks {
+        let (mut line_names, sizes, auto_fit) = match template {
+            GridTemplate::None => (vec![Vec::new()], Vec::new(), None),
+            GridTemplate::TrackList(list) => {
+                let repetitions = auto_repetitions(list, space, gap);
+                let auto_fit = list.auto_repeat.as_ref().and_then(|auto_repeat| {
+                    let start = auto_repeat.position;
+                    let end = start + auto_repeat.tracks.len() * repetitions;
+                    Some(start..end).filter(|_| auto_repeat.count == RepeatCount::AutoFit)
+                });
+                let (line_names, sizes) = list.expand(repetitions);
+                (line_names, sizes, auto_fit)
+            }
+        };
+        while line_names.len() <= area_count {
+            line_names.push(Vec::new());
+        }
+        ExplicitTracks {
+            line_names,
+            sizes,
+            auto_fit,
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.line_names.len() - 1
+    }
+}
+
+/// The number of times the automatic repetition of `list` is repeated: as many times as its
+/// tracks fit in `space` along with the other tracks of the list and the gaps between them, and at
+/// least once.  Tracks are measured by their fixed sizing functions.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-repeat
+fn auto_repetitions(list: &TrackList, space: Option<CSSPixelLength>, gap: CSSPixelLength) -> usize {
+    let (auto_repeat, space) = match (&list.auto_repeat, space) {
+        (Some(auto_repeat), Some(space)) => (auto_repeat, space),
+        (Some(_), None) => return 1,
+        (None, _) => return 0,
+    };
+    let zero = CSSPixelLength::new(0.);
+    let fixed_size = |track: &TrackSize| {
+        let breadth = |breadth: TrackBreadth| match breadth {
+            TrackBreadth::Length(lp) => Some(lp.to_px(space)),
+            _ => None,
+        };
+        match (breadth(track.min()), breadth(track.max())) {
+            (Some(min), Some(max)) => max.max(min),
+            (min, max) => max.or(min).unwrap_or(zero),
+        }
+    };
+    let fixed_tracks = list
+        .tracks
+        .iter()
+        .map(fixed_size)
+        .fold(zero, |sum, size| sum + size);
+    let repeated_tracks = auto_repeat
+        .tracks
+        .iter()
+        .map(fixed_size)
+        .fold(zero, |sum, size| sum + size);
+    let other_space = fixed_tracks + gap * (list.tracks.len() as CSSFloat - 1.);
+    let repetition_size = repeated_tracks + gap * auto_repeat.tracks.len() as CSSFloat;
+    if repetition_size <= 0. {
+        return 1;
+    }
+    let max_repetitions = MAX_GRID_LINE as usize / auto_repeat.tracks.len();
+    (((space - other_space).px() / repetition_size.px())
+        .floor()
+        .max(1.) as usize)
+        .min(max_repetitions.max(1))
+}
+
+/// Resolves the line the grid item edge `line` is on to a zero-based index among
+/// `line_names`, the names of the lines of the explicit grid, if it doesn't depend on the other
+/// edge of the item.  `side` is `"start"` or `"end"`, for the edge the line is for.
+///
+/// https://drafts.csswg.org/css-grid-1/#line-placement
+fn resolve_line(line: &GridLine, line_names: &[Vec<String>], side: &str) -> Option<i32> {
+    let explicit_count = line_names.len() as i32 - 1;
+    match line {
+        GridLine::Auto | GridLine::Span { .. } => None,
+        GridLine::Line { index, name: None } if *index > 0 => Some(index - 1),
+        GridLine::Line { index, name: None } => Some(explicit_count + 1 + index),
+        GridLine::Line {
+            index,
+            name: Some(name),
+        } => Some(nth_named_line(line_names, name, *index)),
+        GridLine::Name(name) => {
+            // The edge of a named area, or else the first line with the name.
+            let area_line = format!("{}-{}", name, side);
+            line_names
+                .iter()
+                .position(|names| names.contains(&area_line))
+                .map(|line| line as i32)
+                .or_else(|| Some(nth_named_line(line_names, name, 1)))
+        }
+    }
+}
+
+/// The line `index` lines with the name `name` from the start of the explicit grid, or from its
+/// end if `index` is negative.  If there aren't that many, all the implicit lines beyond the
+/// explicit grid are assumed to have the name.
+fn nth_named_line(line_names: &[Vec<String>], name: &str, index: i32) -> i32 {
+    let explicit_count = line_names.len() as i32 - 1;
+    let named = line_names
+        .iter()
+        .enumerate()
+        .filter(|(_, names)| names.iter().any(|line_name| line_name == name))
+        .map(|(line, _)| line as i32)
+        .collect::<Vec<_>>();
+    let count = named.len() as i32;
+    if index > 0 {
+        if index <= count {
+            named[(index - 1) as usize]
+        } else {
+            explicit_count.saturating_add(index - count)
+        }
+    } else if index.saturating_neg() <= count {
+        named[(count + index) as usize]
+    } else {
+        (index + count).max(-MAX_GRID_LINE)
+    }
+}
+
+/// The line a `span` edge of a grid item is on, counting `count` lines from the line `from` its
+/// other edge is on, forwards or backwards.  If `name` is given, only lines with that name are
+/// counted, and all implicit lines are assumed to have it.
+fn span_from(
+    line_names: &[Vec<String>],
+    from: i32,
+    count: u32,
+    name: &Option<String>,
+    forwards: bool,
+) -> i32 {
+    let step = if forwards { 1 } else { -1 };
+    let count = count.min(MAX_GRID_LINE as u32) as i32;
+    let name = match name {
+        Some(name) => name,
+        None => return from.saturating_add(step * count),
+    };
+    let mut line = from;
+    let mut remaining = count;
+    while remaining > 0 {
+        line += step;
+        let is_named = match line_names.get(line as usize) {
+            Some(names) if line >= 0 => names.contains(name),
+            _ => true,
+        };
+        if is_named {
+            remaining -= 1;
+        }
+    }
+    line
+}
+
+/// The placement of a grid item along an axis whose edges are given by `start` and `end`.
+/// Placements beyond `MAX_GRID_LINE` lines from the start of the explicit grid are clamped to
+/// that many, keeping at least one track.
+///
+/// https://drafts.csswg.org/css-grid-1/#line-placement
+/// https://drafts.csswg.org/css-grid-1/#overlarge-grids
+fn resolve_placement(
+    start: &GridLine,
+    end: &GridLine,
+    line_names: &[Vec<String>],
+) -> Placement<i32> {
+    match unclamped_placement(start, end, line_names) {
+        Placement::Definite(lines) => {
+            let start = lines.start.clamp(-MAX_GRID_LINE, MAX_GRID_LINE - 1);
+            Placement::Definite(start..lines.end.min(MAX_GRID_LINE).max(start + 1))
+        }
+        Placement::Auto(span) => Placement::Auto(span.min(MAX_GRID_LINE as usize)),
+    }
+}
+
+fn unclamped_placement(
+    start: &GridLine,
+    end: &GridLine,
+    line_names: &[Vec<String>],
+) -> Placement<i32> {
+    let definite_start = resolve_line(start, line_names, "start");
+    let definite_end = resolve_line(end, line_names, "end");
+    match (definite_start, definite_end) {
+        (Some(start), Some(end)) if start == end => Placement::Definite(start..start + 1),
+        (Some(start), Some(end)) => Placement::Definite(start.min(end)..start.max(end)),
+        (Some(start), None) => match end {
+            GridLine::Span { count, name } => {
+                Placement::Definite(start..span_from(line_names, start, *count, name, true))
+            }
+            _ => Placement::Definite(start..start + 1),
+        },
+        (None, Some(end)) => match start {
+            GridLine::Span { count, name } => {
+                Placement::Definite(span_from(line_names, end, *count, name, false)..end)
+            }
+            _ => Placement::Definite(end - 1..end),
+        },
+        // A span to a named line can't be resolved without a definite line to count from, so
+        // it's a span of one.
+        (None, None) => match (start, end) {
+            (GridLine::Span { count, name: None }, _)
+            | (GridLine::Auto, GridLine::Span { count, name: None }) => {
+                Placement::Auto(*count as usize)
+            }
+            _ => Placement::Auto(1),
+        },
+    }
+}
+
+/// Which cells of a grid are occupied by the items placed in it so far, by the line along the
+/// major axis of its auto-placement and then the line along the minor axis.
+#[derive(Clone, Debug, Default)]
+struct Occupancy(Vec<Vec<bool>>);
+
+impl Occupancy {
+    fn is_free(&self, major: Range<usize>, minor: Range<usize>) -> bool {
+        major.into_iter().all(|major| {
+            minor.clone().all(|minor| {
+                !matches!(
+                    self.0.get(major).and_then(|cells| cells.get(minor)),
+                    Some(true)
+                )
+            })
+        })
+    }
+
+    fn occupy(&mut self, major: Range<usize>, minor: Range<usize>) {
+        if self.0.len() < major.end {
+            self.0.resize(major.end, Vec::new());
+        }
+        for cells in &mut self.0[major] {
+            if cells.len() < minor.end {
+                cells.resize(minor.end, false);
+            }
+            for cell in &mut cells[minor.clone()] {
+                *cell = true;
+            }
+        }
+    }
+}
+
+/// Places the items with the given `placements` along the major and minor axes of the
+/// auto-placement in a grid with `minor_count` tracks along the minor axis, returning the area of
+/// each along those axes.  Items are given in order-modified document order.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-placement-algo
+fn auto_place(
+    placements: &[(Placement<usize>, Placement<usize>)],
+    mut minor_count: usize,
+    dense: bool,
+) -> Vec<(Range<usize>, Range<usize>)> {
+    let mut occupancy = Occupancy::default();
+    let mut areas = vec![None; placements.len()];
+
+    // Place the items that are already positioned along both axes.
+    for (area, placement) in areas.iter_mut().zip(placements) {
+        if let (Placement::Definite(major), Placement::Definite(minor)) = placement {
+            occupancy.occupy(major.clone(), minor.clone());
+            *area = Some((major.clone(), minor.clone()));
+        }
+    }
+
+    // Place the items locked to a line along the major axis, each past those placed on the same
+    // line before it unless packing densely.
+    let mut major_cursors = HashMap::new();
+    for (area, placement) in areas.iter_mut().zip(placements) {
+        if let (Placement::Definite(major), Placement::Auto(span)) = placement {
+            let cursor = major_cursors.entry(major.start).or_insert(0);
+            let mut minor = if dense { 0 } else { *cursor };
+            while !occupancy.is_free(major.clone(), minor..minor + span) {
+                minor += 1;
+            }
+            *cursor = minor + span;
+            minor_count = minor_count.max(minor + span);
+            occupancy.occupy(major.clone(), minor..minor + span);
+            *area = Some((major.clone(), minor..minor + span));
+        }
+    }
+
+    // Place the remaining items with a cursor that moves through the grid along the minor axis,
+    // and then along the major axis.
+    let (mut cursor_major, mut cursor_minor) = (0, 0);
+    for (area, placement) in areas.iter_mut().zip(placements) {
+        match placement {
+            (Placement::Auto(major_span), Placement::Definite(minor)) => {
+                if dense {
+                    cursor_major = 0;
+                } else if minor.start < cursor_minor {
+                    cursor_major += 1;
+                }
+                while !occupancy.is_free(cursor_major..cursor_major + major_span, minor.clone()) {
+                    cursor_major += 1;
+                }
+                cursor_minor = minor.start;
+                let major = cursor_major..cursor_major + major_span;
+                occupancy.occupy(major.clone(), minor.clone());
+                *area = Some((major, minor.clone()));
+            }
+            (Placement::Auto(major_span), Placement::Auto(minor_span)) => {
+                if dense {
+                    cursor_major = 0;
+                    cursor_minor = 0;
+                }
+                loop {
+                    if cursor_minor + minor_span > minor_count {
+                        cursor_major += 1;
+                        cursor_minor = 0;
+                        continue;
+                    }
+                    let major = cursor_major..cursor_major + major_span;
+                    let minor = cursor_minor..cursor_minor + minor_span;
+                    if occupancy.is_free(major.clone(), minor.clone()) {
+                        occupancy.occupy(major.clone(), minor.clone());
+                        *area = Some((major, minor));
+                        break;
+                    }
+                    cursor_minor += 1;
+                }
+                cursor_minor += minor_span;
+            }
+            _ => {}
+        }
+    }
+    areas.into_iter().map(Option::unwrap).collect()
+}
+
+/// Places the in-flow `children` of a grid container with the computed values `cvs` in its grid,
+/// and creates the tracks of the grid.  `inline_space` and `block_space` are the sizes of the
+/// container's content box, if they're definite.
+///
+/// https://drafts.csswg.org/css-grid-1/#placement
+fn place_items(
+    cvs: &ComputedValues,
+    children: &[LayoutBox],
+    inline_space: Option<CSSPixelLength>,
+    block_space: Option<CSSPixelLength>,
+    column_gap: CSSPixelLength,
+    row_gap: CSSPixelLength,
+) -> Grid {
+    let (area_rows, area_columns) = match &cvs.grid_template_areas {
+        GridTemplateAreas::None => (0, 0),
+        GridTemplateAreas::Areas(areas) => (areas.rows, areas.columns),
+    };
+    let mut explicit_columns = ExplicitTracks::new(
+        &cvs.grid_template_columns,
+        area_columns,
+        inline_space,
+        column_gap,
+    );
+    let mut explicit_rows =
+        ExplicitTracks::new(&cvs.grid_template_rows, area_rows, block_space, row_gap);
+    // Named areas name the lines at their edges.
+    if let GridTemplateAreas::Areas(areas) = &cvs.grid_template_areas {
+        for area in &areas.areas {
+            let start = format!("{}-start", area.name);
+            let end = format!("{}-end", area.name);
+            explicit_columns.line_names[area.columns.start].push(start.clone());
+            explicit_columns.line_names[area.columns.end].push(end.clone());
+            explicit_rows.line_names[area.rows.start].push(start);
+            explicit_rows.line_names[area.rows.end].push(end);
+        }
+    }
+
+    let mut placements = children
+        .iter()
+        .enumerate()
+        .filter(|(_, child)| !child.position().is_absolutely_positioned())
+        .map(|(index, child)| {
+            let cvs = child.computed_values();
+            let columns = resolve_placement(
+                &cvs.grid_column_start,
+                &cvs.grid_column_end,
+                &explicit_columns.line_names,
+            );
+            let rows = resolve_placement(
+                &cvs.grid_row_start,
+                &cvs.grid_row_end,
+                &explicit_rows.line_names,
+            );
+            (index, cvs.order, columns, rows)
+        })
+        .collect::<Vec<_>>();
+    placements.sort_by_key(|(_, order, _, _)| *order);
+
+    // Lines before the start of the explicit grid add implicit tracks there, so shift every line
+    // so that the first is at zero.
+    let offset = |axis_placements: &mut dyn Iterator<Item = &Placement<i32>>| {
+        -axis_placements
+            .filter_map(|placement| match placement {
+                Placement::Definite(lines) => Some(lines.start),
+                Placement::Auto(_) => None,
+            })
+            .fold(0, i32::min)
+    };
+    let column_offset = offset(&mut placements.iter().map(|(_, _, columns, _)| columns));
+    let row_offset = offset(&mut placements.iter().map(|(_, _, _, rows)| rows));
+    let shift = |placement: &Placement<i32>, offset: i32| match placement {
+        Placement::Definite(lines) => {
+            Placement::Definite((lines.start + offset) as usize..(lines.end + offset) as usize)
+        }
+        Placement::Auto(span) => Placement::Auto(*span),
+    };
+    let column_major = cvs.grid_auto_flow.column;
+    let (major_offset, minor_offset) = if column_major {
+        (column_offset, row_offset)
+    } else {
+        (row_offset, column_offset)
+    };
+    let minor_explicit_count = if column_major {
+        explicit_rows.count()
+    } else {
+        explicit_columns.count()
+    };
+    let major_minor = placements
+        .iter()
+        .map(|(_, _, columns, rows)| {
+            let (major, minor) = if column_major {
+                (columns, rows)
+            } else {
+                (rows, columns)
+            };
+            (shift(major, major_offset), shift(minor, minor_offset))
+        })
+        .collect::<Vec<_>>();
+    let minor_count = major_minor.iter().fold(
+        minor_explicit_count + minor_offset as usize,
+        |count, (_, minor)| match minor {
+            Placement::Definite(lines) => count.max(lines.end),
+            Placement::Auto(span) => count.max(*span),
+        },
+    );
+    let areas = auto_place(&major_minor, minor_count.max(1), cvs.grid_auto_flow.dense);
+
+    let items = placements
+        .iter()
+        .zip(areas)
+        .map(|((index, _, _, _), (major, minor))| {
+            let (columns, rows) = if column_major {
+                (major, minor)
+            } else {
+                (minor, major)
+            };
+            GridItem {
+                index: *index,
+                columns,
+                rows,
+            }
+        })
+        .collect::<Vec<_>>();
+    let column_count = items.iter().fold(
+        explicit_columns.count() + column_offset as usize,
+        |count, item| count.max(item.columns.end),
+    );
+    let row_count = items.iter().fold(
+        explicit_rows.count() + row_offset as usize,
+        |count, item| count.max(item.rows.end),
+    );
+    let columns = tracks(
+        &explicit_columns,
+        &cvs.grid_auto_columns,
+        column_offset as usize,
+        column_count,
+        inline_space,
+        items.iter().map(|item| item.columns.clone()),
+    );
+    let rows = tracks(
+        &explicit_rows,
+        &cvs.grid_auto_rows,
+        row_offset as usize,
+        row_count,
+        block_space,
+        items.iter().map(|item| item.rows.clone()),
+    );
+    Grid {
+        items,
+        columns,
+        rows,
+    }
+}
+
+/// The `count` tracks of a grid along one axis, where the explicit grid starts at the line
+/// `offset` and the rest are sized by `auto_tracks`.  `spans` are the ranges of the tracks the
+/// items of the grid span, which keep tracks repeated by `auto-fit` from being collapsed.
+///
+/// https://drafts.csswg.org/css-grid-1/#implicit-grids
+fn tracks(
+    explicit: &ExplicitTracks,
+    auto_tracks: &GridAutoTracks,
+    offset: usize,
+    count: usize,
+    basis: Option<CSSPixelLength>,
+    spans: impl Iterator<Item = Range<usize>>,
+) -> Vec<Track> {
+    let auto_tracks = &auto_tracks.0;
+    let mut tracks = (0..count)
+        .map(|track| {
+            let size = if track < offset {
+                // The implicit tracks before the explicit grid take the sizes given in reverse.
+                let before = offset - track - 1;
+                &auto_tracks[auto_tracks.len() - 1 - before % auto_tracks.len()]
+            } else if track - offset < explicit.sizes.len() {
+                &explicit.sizes[track - offset]
+            } else {
+                let after = track - offset - explicit.sizes.len();
+                &auto_tracks[after % auto_tracks.len()]
+            };
+            Track::new(size, basis)
+        })
+        .collect::<Vec<_>>();
+    if let Some(auto_fit) = &explicit.auto_fit {
+        let repeated = auto_fit.start + offset..auto_fit.end + offset;
+        for track in &mut tracks[repeated] {
+            track.collapsed = true;
+        }
+        for span in spans {
+            for track in &mut tracks[span] {
+                track.collapsed = false;
+            }
+        }
+    }
+    tracks
+}
+
+/// The space the tracks of a grid are sized to fill along one axis: a definite size, or a size
+/// that depends on that of the grid's contents.
+///
+/// https://drafts.csswg.org/css-grid-1/#algo-terms
+#[derive(Clone, Copy, Debug)]
+enum AvailableSpace {
+    Definite(CSSPixelLength),
+    Indefinite(IntrinsicSize),
+}
+
+/// The min-content and max-content contributions of a grid item to the size of the tracks it
+/// spans along one axis.
+#[derive(Clone, Debug)]
+struct Contribution {
+    span: Range<usize>,
+    min_content: CSSPixelLength,
+    max_content: CSSPixelLength,
+}
+
+/// The sum of the sizes of the gutters between `tracks`.
+fn gutters(tracks: &[Track], gap: CSSPixelLength) -> CSSPixelLength {
+    let count = tracks.iter().filter(|track| !track.collapsed).count();
+    gap * count.saturating_sub(1) as CSSFloat
+}
+
+fn sum_base_sizes(tracks: &[Track]) -> CSSPixelLength {
+    tracks
+        .iter()
+        .fold(CSSPixelLength::new(0.), |sum, track| sum + track.base_size)
+}
+
+/// Sizes `tracks`, the tracks of a grid along one axis, to fit the items with the given
+/// `contributions` in `space`.  `stretch` is whether the tracks with an `auto` maximum are
+/// stretched to fill any space left over, as they are with a `normal` or `stretch` content
+/// distribution.
+///
+/// https://drafts.csswg.org/css-grid-1/#algo-track-sizing
+fn size_tracks(
+    tracks: &mut [Track],
+    contributions: &[Contribution],
+    gap: CSSPixelLength,
+    space: AvailableSpace,
+    stretch: bool,
+) {
+    let zero = CSSPixelLength::new(0.);
+    let infinity = CSSPixelLength::new(CSSFloat::INFINITY);
+    for track in tracks.iter_mut() {
+        if track.collapsed {
+            track.base_size = zero;
+            track.growth_limit = zero;
+            continue;
+        }
+        track.base_size = match track.min {
+            Breadth::Fixed(size) => size,
+            _ => zero,
+        };
+        track.growth_limit = match track.max {
+            Breadth::Fixed(size) => size.max(track.base_size),
+            _ => infinity,
+        };
+    }
+
+    // Resolve the intrinsic track sizes, first for items spanning a single track, and then for
+    // items spanning more, fewest tracks first.
+    // https://drafts.csswg.org/css-grid-1/#algo-content
+    let (flexible, mut spanning): (Vec<_>, Vec<_>) =
+        contributions.iter().partition(|contribution| {
+            tracks[contribution.span.clone()]
+                .iter()
+                .any(|track| track.flex().is_some())
+        });
+    spanning.sort_by_key(|contribution| contribution.span.len());
+    for contribution in spanning {
+        let span = &mut tracks[contribution.span.clone()];
+        if let [track] = span {
+            if track.collapsed {
+                continue;
+            }
+            match track.min {
+                Breadth::Auto | Breadth::MinContent => {
+                    track.base_size.max_assign(contribution.min_content)
+                }
+                Breadth::MaxContent => track.base_size.max_assign(contribution.max_content),
+                _ => {}
+            }
+            let limit_contribution = match track.max {
+                Breadth::MinContent => Some(contribution.min_content),
+                Breadth::Auto | Breadth::MaxContent => Some(contribution.max_content),
+                _ => None,
+            };
+            if let Some(size) = limit_contribution {
+                track.growth_limit = if track.growth_limit == infinity {
+                    size
+                } else {
+                    track.growth_limit.max(size)
+                };
+            }
+            track.growth_limit.max_assign(track.base_size);
+            continue;
+        }
+
+        let gutters = gutters(span, gap);
+        let extra = contribution.min_content - sum_base_sizes(span) - gutters;
+        let intrinsic_min_count = span
+            .iter()
+            .filter(|track| !track.collapsed && track.min.is_intrinsic())
+            .count();
+        if extra > 0. && intrinsic_min_count > 0 {
+            let share = extra / intrinsic_min_count as CSSFloat;
+            for track in span.iter_mut() {
+                if !track.collapsed && track.min.is_intrinsic() {
+                    track.base_size += share;
+                }
+            }
+        }
+        let limit = |track: &Track| {
+            if track.growth_limit == infinity {
+                track.base_size
+            } else {
+                track.growth_limit
+            }
+        };
+        let extra = contribution.max_content
+            - span.iter().fold(zero, |sum, track| sum + limit(track))
+            - gutters;
+        let intrinsic_max_count = span
+            .iter()
+            .filter(|track| !track.collapsed && track.max.is_intrinsic())
+            .count();
+        for track in span.iter_mut() {
+            if !track.collapsed && track.max.is_intrinsic() && extra > 0. {
+                track.growth_limit = limit(track) + extra / intrinsic_max_count as CSSFloat;
+            }
+            track.growth_limit.max_assign(track.base_size);
+        }
+    }
+
+    // Items spanning flexible tracks only grow the base sizes of those tracks, in proportion to
+    // their flex factors.
+    for contribution in &flexible {
+        let span = &mut tracks[contribution.span.clone()];
+        let extra = contribution.min_content - sum_base_sizes(span) - gutters(span, gap);
+        let grows = |track: &Track| track.flex().is_some() && track.min.is_intrinsic();
+        let flex_sum = span
+            .iter()
+            .filter(|track| grows(track))
+            .filter_map(Track::flex)
+            .sum::<CSSFloat>();
+        let grown_count = span.iter().filter(|track| grows(track)).count();
+        if extra <= 0. || grown_count == 0 {
+            continue;
+        }
+        for track in span.iter_mut().filter(|track| grows(track)) {
+            let flex = track.flex().unwrap_or(0.);
+            track.base_size += if flex_sum > 0. {
+                extra * (flex / flex_sum)
+            } else {
+                extra / grown_count as CSSFloat
+            };
+            track.growth_limit.max_assign(track.base_size);
+        }
+    }
+    for track in tracks.iter_mut() {
+        if track.growth_limit == infinity {
+            track.growth_limit = track.base_size;
+        }
+    }
+
+    // Grow the tracks to their growth limits, sharing any free space equally between them.
+    // https://drafts.csswg.org/css-grid-1/#algo-grow-tracks
+    let all_gutters = gutters(tracks, gap);
+    match space {
+        AvailableSpace::Definite(size) => {
+            let mut free_space = size - sum_base_sizes(tracks) - all_gutters;
+            for _ in 0..tracks.len() {
+                let growing = tracks
+                    .iter()
+                    .filter(|track| track.base_size < track.growth_limit)
+                    .count();
+                if free_space <= 0. || growing == 0 {
+                    break;
+                }
+                let share = free_space / growing as CSSFloat;
+                for track in tracks.iter_mut() {
+                    let growth = (track.growth_limit - track.base_size).min(share);
+                    if growth > 0. {
+                        track.base_size += growth;
+                        free_space = free_space - growth;
+                    }
+                }
+            }
+        }
+        AvailableSpace::Indefinite(IntrinsicSize::MaxContent) => {
+            for track in tracks.iter_mut() {
+                track.base_size = track.growth_limit;
+            }
+        }
+        AvailableSpace::Indefinite(IntrinsicSize::MinContent) => {}
+    }
+
+    // Size the flexible tracks by the size of an `fr`.
+    // https://drafts.csswg.org/css-grid-1/#algo-flex-tracks
+    let fr_size = match space {
+        AvailableSpace::Definite(size) => {
+            if size - sum_base_sizes(tracks) - all_gutters > 0. {
+                find_fr_size(tracks, size - all_gutters)
+            } else {
+                0.
+            }
+        }
+        AvailableSpace::Indefinite(IntrinsicSize::MaxContent) => {
+            let track_fr_size = tracks
+                .iter()
+                .filter_map(|track| {
+                    let flex = track.flex()?;
+                    Some(if flex > 1. {
+                        track.base_size.px() / flex
+                    } else {
+                        track.base_size.px()
+                    })
+                })
+                .fold(0., CSSFloat::max);
+            flexible
+                .iter()
+                .map(|contribution| {
+                    let span = &tracks[contribution.span.clone()];
+                    find_fr_size(span, contribution.max_content - gutters(span, gap))
+                })
+                .fold(track_fr_size, CSSFloat::max)
+        }
+        AvailableSpace::Indefinite(IntrinsicSize::MinContent) => 0.,
+    };
+    for track in tracks.iter_mut() {
+        if let Some(flex) = track.flex() {
+            track
+                .base_size
+                .max_assign(CSSPixelLength::new(fr_size * flex));
+        }
+    }
+
+    // Stretch the tracks with an `auto` maximum to fill any space left over.
+    // https://drafts.csswg.org/css-grid-1/#algo-stretch
+    if let (AvailableSpace::Definite(size), true) = (space, stretch) {
+        let free_space = size - sum_base_sizes(tracks) - all_gutters;
+        let auto_count = tracks
+            .iter()
+            .filter(|track| !track.collapsed && track.max == Breadth::Auto)
+            .count();
+        if free_space > 0. && auto_count > 0 {
+            let share = free_space / auto_count as CSSFloat;
+            for track in tracks.iter_mut() {
+                if !track.collapsed && track.max == Breadth::Auto {
+                    track.base_size += share;
+                }
+            }
+        }
+    }
+}
+
+/// The size of an `fr` when the flexible ones among `tracks` share `space_to_fill` with the rest.
+/// Flexible tracks whose base size is larger than their share are treated as inflexible.
+///
+/// https://drafts.csswg.org/css-grid-1/#algo-find-fr-size
+fn find_fr_size(tracks: &[Track], space_to_fill: CSSPixelLength) -> CSSFloat {
+    let mut inflexible = tracks
+        .iter()
+        .map(|track| track.flex().is_none())
+        .collect::<Vec<_>>();
+    loop {
+        let mut leftover_space = space_to_fill;
+        let mut flex_sum = 0.;
+        for (track, inflexible) in tracks.iter().zip(&inflexible) {
+            if *inflexible {
+                leftover_space = leftover_space - track.base_size;
+            } else {
+                flex_sum += track.flex().unwrap_or(0.);
+            }
+        }
+        let fr_size = leftover_space.px() / CSSFloat::max(flex_sum, 1.);
+        let mut restart = false;
+        for (track, inflexible) in tracks.iter().zip(inflexible.iter_mut()) {
+            let flex = track.flex().unwrap_or(0.);
+            if !*inflexible && fr_size * flex < track.base_size.px() {
+                *inflexible = true;
+                restart = true;
+            }
+        }
+        if !restart {
+            return fr_size;
+        }
+    }
+}
+
+/// The offsets of the starts of `tracks` from the start of the grid container's content box
+/// along their axis, where `leading` is the space before the first track, and `between` is the
+/// space added to each gutter.
+fn track_offsets(
+    tracks: &[Track],
+    gap: CSSPixelLength,
+    leading: CSSPixelLength,
+    between: CSSPixelLength,
+) -> Vec<CSSPixelLength> {
+    let last_open = tracks.iter().rposition(|track| !track.collapsed);
+    let mut cursor = leading;
+    tracks
+        .iter()
+        .enumerate()
+        .map(|(index, track)| {
+            let offset = cursor;
+            cursor += track.base_size;
+            if !track.collapsed && last_open > Some(index) {
+                cursor += gap + between;
+            }
+            offset
+        })
+        .collect()
+}
+
+/// The offset and size of the area of the grid between the tracks in `span`.
+fn area(
+    tracks: &[Track],
+    offsets: &[CSSPixelLength],
+    span: Range<usize>,
+) -> (CSSPixelLength, CSSPixelLength) {
+    let last = span.end - 1;
+    let start = offsets[span.start];
+    (start, offsets[last] + tracks[last].base_size - start)
+}
+
+/// The size of all of `tracks` and the gutters between them.
+fn grid_size(tracks: &[Track], gap: CSSPixelLength) -> CSSPixelLength {
+    sum_base_sizes(tracks) + gutters(tracks, gap)
+}
+
+/// The contributions of grid `items` to the size of the columns they span.
+fn column_contributions(
+    items: &[GridItem],
+    children: &[LayoutBox],
+    text_measurer: &dyn TextMeasurer,
+) -> Vec<Contribution> {
+    let contribution = |child: &LayoutBox, size| match child {
+        LayoutBox::BlockLevel(BlockLevelBox::AnonymousBlock(abb)) => {
+            intrinsic_inline_size(abb.children(), size, text_measurer)
+        }
+        _ => intrinsic_contribution(child, size, text_measurer),
+    };
+    items
+        .iter()
+        .map(|item| {
+            let child = &children[item.index];
+            Contribution {
+                span: item.columns.clone(),
+                min_content: contribution(child, IntrinsicSize::MinContent),
+                max_content: contribution(child, IntrinsicSize::MaxContent),
+            }
+        })
+        .collect()
+}
+
+/// The intrinsic width `size` of the content box of the grid item `item`.
+fn content_width(
+    item: &BlockLevelBox,
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    match item {
+        BlockLevelBox::AnonymousBlock(abb) => {
+            intrinsic_inline_size(abb.children(), size, text_measurer)
+        }
+        BlockLevelBox::BlockContainer(_) => content_intrinsic_width(
+            &item.computed_values(),
+            &item.formatting_context(),
+            item.children(),
+            size,
+            text_measurer,
+        ),
+    }
+}
+
+/// A grid area of the given size, as a containing block for the item in it.  `container` is the
+/// dimensions of the grid container, which the area is placed within later.
+fn area_containing_block(
+    container: &Dimensions,
+    inline_size: CSSPixelLength,
+    block_size: CSSPixelLength,
+    writing_mode: WritingMode,
+    direction: Direction,
+) -> ContainingBlock {
+    let mut dimensions = *container;
+    dimensions.set_inline_size(inline_size, writing_mode);
+    dimensions.set_block_size(block_size, writing_mode);
+    ContainingBlock::new(dimensions.content, direction, writing_mode)
+}
+
+/// Lays out the children of the grid container `container` as grid items.  The content box inline
+/// size of the container must already be known, and its block size is set to the size of its rows
+/// if it's `auto`.  Absolutely positioned children aren't grid items, and are placed at the start
+/// of the container's content box.
+///
+/// https://drafts.csswg.org/css-grid-1/#layout-algorithm
+pub(crate) fn layout_grid_container(container: &mut BlockLevelBox, context: &LayoutContext) {
+    let zero = CSSPixelLength::new(0.);
+    let cvs = container.computed_values();
+    let writing_mode = cvs.writing_mode;
+    let direction = cvs.direction;
+    let mut dimensions = container.dimensions();
+    let inline_size = dimensions.get_inline_size(None, writing_mode);
+    let block_padding_border = dimensions.border_box_block_size(writing_mode)
+        - dimensions.content_box_block_size(writing_mode);
+    let cb_block_size = context.containing_block.block_size(writing_mode);
+    let block_limits = size_limits(
+        &cvs,
+        FlowAxis::Block,
+        writing_mode,
+        Some(cb_block_size),
+        block_padding_border,
+    );
+    let block_size = match cvs.block_size(writing_mode) {
+        LengthPercentageOrAuto::LengthPercentage(lp) => Some(block_limits.clamp(content_box_size(
+            lp.to_px(cb_block_size),
+            cvs.box_sizing,
+            block_padding_border,
+        ))),
+        LengthPercentageOrAuto::Auto => None,
+    };
+    let column_gap = cvs.column_gap.to_px(inline_size);
+    let row_gap = cvs.row_gap.to_px(block_size.unwrap_or(zero));
+    let justify_content = cvs.justify_content;
+    let align_content = cvs.align_content;
+    let justify_items = cvs.justify_items;
+    let align_items = cvs.align_items;
+    let grid = place_items(
+        &cvs,
+        container.children(),
+        Some(inline_size),
+        block_size,
+        column_gap,
+        row_gap,
+    );
+    drop(cvs);
+
+    // Absolutely positioned children get their static position from a containing block with no
+    // block size, so they're placed at the start of the container's content box.
+    dimensions.set_block_size(zero, writing_mode);
+    let static_position_context = LayoutContext::new(
+        ContainingBlock::new(dimensions.content, direction, writing_mode),
+        context.layout_viewport,
+        context.text_measurer,
+    );
+    let text_measurer = context.text_measurer;
+    let children = container.children_mut();
+    for child in children.iter_mut() {
+        if !child.position().is_absolutely_positioned() {
+            continue;
+        }
+        if let LayoutBox::BlockLevel(blb) = child {
+            blb.solve_and_set_inline_level_properties(&static_position_context);
+            blb.solve_and_set_block_level_properties(&static_position_context);
+        }
+    }
+
+    // Size the columns, and lay out each item in the columns it spans to find its contribution to
+    // the size of the rows.
+    let Grid {
+        items,
+        mut columns,
+        mut rows,
+    } = grid;
+    let contributions = column_contributions(&items, children, text_measurer);
+    size_tracks(
+        &mut columns,
+        &contributions,
+        column_gap,
+        AvailableSpace::Definite(inline_size),
+        matches!(
+            justify_content,
+            ContentAlignment::Normal | ContentAlignment::Stretch
+        ),
+    );
+    let open_columns = columns.iter().filter(|track| !track.collapsed).count();
+    let (leading, between) = distribute(
+        justify_content,
+        inline_size - grid_size(&columns, column_gap),
+        open_columns,
+        false,
+    );
+    let column_offsets = track_offsets(&columns, column_gap, leading, between);
+
+    let mut contributions = Vec::with_capacity(items.len());
+    for item in &items {
+        let item_box = item_box(children, item.index);
+        let (_, area_inline_size) = area(&columns, &column_offsets, item.columns.clone());
+        let containing_block =
+            area_containing_block(&dimensions, area_inline_size, zero, writing_mode, direction);
+        item_box.place_edges_at_origin(containing_block);
+        let item_dimensions = item_box.dimensions();
+        let content_inline_size = item_dimensions.get_inline_size(None, writing_mode);
+        let inline_edges =
+            item_dimensions.margin_box_inline_size(writing_mode) - content_inline_size;
+        let inline_padding_border =
+            item_dimensions.border_box_inline_size(writing_mode) - content_inline_size;
+
+        let cvs = item_box.computed_values();
+        let is_auto =
+            |side| cvs.margin_flow_relative(side, writing_mode) == LengthPercentageOrAuto::Auto;
+        let has_auto_margins = is_auto(FlowSide::InlineStart) || is_auto(FlowSide::InlineEnd);
+        let alignment = cvs.justify_self.resolve(justify_items);
+        let limits = size_limits(
+            &cvs,
+            FlowAxis::Inline,
+            writing_mode,
+            Some(area_inline_size),
+            inline_padding_border,
+        );
+        let definite_inline_size =
+            definite_size(&cvs.inline_size(writing_mode), Some(area_inline_size))
+                .map(|size| content_box_size(size, cvs.box_sizing, inline_padding_border));
+        drop(cvs);
+        let available = (area_inline_size - inline_edges).clamp_to_non_negative();
+        let item_inline_size = limits.clamp(match definite_inline_size {
+            Some(size) => size,
+            None if matches!(alignment, ItemAlignment::Normal | ItemAlignment::Stretch)
+                && !has_auto_margins =>
+            {
+                available
+            }
+            // Items that aren't stretched are sized to fit their content.
+            None => {
+                let min_content = content_width(item_box, IntrinsicSize::MinContent, text_measurer);
+                let max_content = content_width(item_box, IntrinsicSize::MaxContent, text_measurer);
+                max_content.min(available.max(min_content))
+            }
+        });
+
+        let item_context = LayoutContext::new(
+            containing_block,
+            context.layout_viewport,
+            context.text_measurer,
+        );
+        layout_item_contents(item_box, &item_context, item_inline_size, writing_mode);
+        item_box.apply_box_sizing_properties(containing_block);
+        item_box
+            .dimensions_mut()
+            .set_inline_size(item_inline_size, writing_mode);
+        let outer_block_size = item_box.dimensions().margin_box_block_size(writing_mode);
+        contributions.push(Contribution {
+            span: item.rows.clone(),
+            min_content: outer_block_size,
+            max_content: outer_block_size,
+        });
+    }
+
+    // Size the rows, and then the container if its block size is `auto`.
+    let row_space = match block_size {
+        Some(size) => AvailableSpace::Definite(size),
+        None => AvailableSpace::Indefinite(IntrinsicSize::MaxContent),
+    };
+    size_tracks(
+        &mut rows,
+        &contributions,
+        row_gap,
+        row_space,
+        matches!(
+            align_content,
+            ContentAlignment::Normal | ContentAlignment::Stretch
+        ),
+    );
+    let rows_size = grid_size(&rows, row_gap);
+    let container_block_size = block_size.unwrap_or_else(|| block_limits.clamp(rows_size));
+    container
+        .dimensions_mut()
+        .set_block_size(container_block_size, writing_mode);
+    let content = container.dimensions().content;
+    let open_rows = rows.iter().filter(|track| !track.collapsed).count();
+    let (leading, between) = distribute(
+        align_content,
+        container_block_size - rows_size,
+        open_rows,
+        false,
+    );
+    let row_offsets = track_offsets(&rows, row_gap, leading, between);
+
+    // Stretch and align each item within its grid area.
+    let children = container.children_mut();
+    for item in &items {
+        let item_box = item_box(children, item.index);
+        let (column_offset, area_inline_size) =
+            area(&columns, &column_offsets, item.columns.clone());
+        let (row_offset, area_block_size) = area(&rows, &row_offsets, item.rows.clone());
+
+        let cvs = item_box.computed_values();
+        let is_auto =
+            |side| cvs.margin_flow_relative(side, writing_mode) == LengthPercentageOrAuto::Auto;
+        let auto_inline_margins = (is_auto(FlowSide::InlineStart), is_auto(FlowSide::InlineEnd));
+        let auto_block_margins = (is_auto(FlowSide::BlockStart), is_auto(FlowSide::BlockEnd));
+        let justify = cvs.justify_self.resolve(justify_items);
+        let align = cvs.align_self.resolve(align_items);
+        let stretched = matches!(align, ItemAlignment::Normal | ItemAlignment::Stretch)
+            && cvs.block_size(writing_mode) == LengthPercentageOrAuto::Auto
+            && auto_block_margins == (false, false);
+        let item_dimensions = item_box.dimensions();
+        let content_block_size = item_dimensions.content_box_block_size(writing_mode);
+        let block_edges = item_dimensions.margin_box_block_size(writing_mode) - content_block_size;
+        let block_padding_border =
+            item_dimensions.border_box_block_size(writing_mode) - content_block_size;
+        let limits = size_limits(
+            &cvs,
+            FlowAxis::Block,
+            writing_mode,
+            Some(area_block_size),
+            block_padding_border,
+        );
+        drop(cvs);
+        if stretched {
+            let stretched_size =
+                limits.clamp((area_block_size - block_edges).clamp_to_non_negative());
+            item_box
+                .dimensions_mut()
+                .set_block_size(stretched_size, writing_mode);
+        }
+
+        let inline_offset = align_in_area(
+            item_box,
+            FlowAxis::Inline,
+            justify,
+            auto_inline_margins,
+            area_inline_size,
+        );
+        let block_offset = align_in_area(
+            item_box,
+            FlowAxis::Block,
+            align,
+            auto_block_margins,
+            area_block_size,
+        );
+        place(
+            item_box,
+            FlowAxis::Inline,
+            false,
+            content,
+            column_offset + inline_offset,
+            writing_mode,
+            direction,
+        );
+        place(
+            item_box,
+            FlowAxis::Block,
+            false,
+            content,
+            row_offset + block_offset,
+            writing_mode,
+            direction,
+        );
+    }
+}
+
+/// The offset of the margin box of `item` from the start of its grid area along `axis`, which is
+/// `area_size` long, when aligned with `alignment`.  If either of the item's margins along the
+/// axis is `auto`, as given by `auto_margins`, the free space goes to those margins instead.
+///
+/// https://drafts.csswg.org/css-align-3/#justify-self-property
+/// https://drafts.csswg.org/css-align-3/#align-self-property
+fn align_in_area(
+    item: &mut BlockLevelBox,
+    axis: FlowAxis,
+    alignment: ItemAlignment,
+    auto_margins: (bool, bool),
+    area_size: CSSPixelLength,
+) -> CSSPixelLength {
+    let zero = CSSPixelLength::new(0.);
+    let cvs = item.computed_values();
+    let writing_mode = cvs.writing_mode;
+    let direction = cvs.direction;
+    drop(cvs);
+    let dimensions = item.dimensions_mut();
+    let (outer_size, sides) = match axis {
+        FlowAxis::Inline => (
+            dimensions.margin_box_inline_size(writing_mode),
+            (FlowSide::InlineStart, FlowSide::InlineEnd),
+        ),
+        FlowAxis::Block => (
+            dimensions.margin_box_block_size(writing_mode),
+            (FlowSide::BlockStart, FlowSide::BlockEnd),
+        ),
+    };
+    let free_space = area_size - outer_size;
+    if auto_margins != (false, false) {
+        if free_space > 0. {
+            let share = match auto_margins {
+                (true, true) => free_space / 2.,
+                _ => free_space,
+            };
+            if auto_margins.0 {
+                dimensions.set(
+                    sides.0,
+                    BoxComponent::Margin,
+                    share,
+                    writing_mode,
+                    direction,
+                );
+            }
+            if auto_margins.1 {
+                dimensions.set(
+                    sides.1,
+                    BoxComponent::Margin,
+                    share,
+                    writing_mode,
+                    direction,
+                );
+            }
+        }
+        return zero;
+    }
+    match alignment {
+        // TODO: Baseline alignment of grid items, which are aligned as `start` for now.
+        ItemAlignment::Normal
+        | ItemAlignment::Stretch
+        | ItemAlignment::Start
+        | ItemAlignment::SelfStart
+        | ItemAlignment::FlexStart
+        | ItemAlignment::Baseline => zero,
+        ItemAlignment::End | ItemAlignment::SelfEnd | ItemAlignment::FlexEnd => free_space,
+        ItemAlignment::Center => free_space / 2.,
+    }
+}
+
+/// The intrinsic width `size` of a grid container with the computed values `container_values` and
+/// the given `children`: the size of its columns when sized by the contributions of its items
+/// with no definite space to fill, and the fixed gaps between them.
+///
+/// https://drafts.csswg.org/css-grid-1/#intrinsic-sizes
+pub(crate) fn intrinsic_width(
+    container_values: &ComputedValues,
+    children: &[LayoutBox],
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    let zero = CSSPixelLength::new(0.);
+    let column_gap = container_values.column_gap.to_px(zero);
+    let row_gap = container_values.row_gap.to_px(zero);
+    let mut grid = place_items(container_values, children, None, None, column_gap, row_gap);
+    let contributions = column_contributions(&grid.items, children, text_measurer);
+    size_tracks(
+        &mut grid.columns,
+        &contributions,
+        column_gap,
+        AvailableSpace::Indefinite(size),
+        false,
+    );
+    grid_size(&grid.columns, column_gap)
+}
@@ -2,21 +2,33 @@ use crate::behavior::ApplyBoxSizingProperties;
 use crate::behavior::BaseLayoutBoxBehavior;
 use crate::containing_block::ContainingBlock;
 use crate::dimensions::Dimensions;
+use crate::flex;
 use crate::flow::block::{AnonymousBlockBox, BlockLevelBox};
 use crate::flow::inline::{
-    AnonymousInlineBox, InlineBox, InlineLevelBox, InlineLevelContent, TextRun,
+    AnonymousInlineBox, AtomicInlineBox, InlineBox, InlineLevelBox, InlineLevelContent, TextRun,
 };
+use crate::flow::line::intrinsic_inline_size;
 use crate::formatting_context::FormattingContextRef;
+use crate::grid;
+use crate::multicol;
+use crate::table;
+use crate::text::TextMeasurer;
 use crate::DumpLayoutFormat;
 use crate::{DumpLayout, Layout, LayoutContext};
-use accountable_refcell::Ref;
+use accountable_refcell::{Ref, RefCell};
 use cli::DumpLayoutVerbosity;
 use dom::tree::{NodeData, NodeRef};
 use enum_dispatch::enum_dispatch;
+use primitives::rect::PositionedRect;
+use primitives::sides::PhysicalSide;
+use primitives::units::{CSSFloat, CSSPixelLength};
 use std::io::Write;
+use std::rc::Rc;
 use strum_macros::IntoStaticStr;
-use style::values::computed::length::LengthPercentageOrAuto;
-use style::values::computed::ComputedValues;
+use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use style::values::computed::{
+    compute_anonymous_values, BoxSizing, ComputedValues, Display, Float, MaxSize, Position,
+};
 use style::values::used::ToPx;
 
 /// The `LayoutBox` is Kosmonaut's representation of the box tree.  Note that, per-spec, the box
@@ -65,16 +77,70 @@ impl LayoutBox {
         }
     }
 
+    /// Returns the children of this layout box mutably, if there are any.
+    pub fn children_mut(&mut self) -> Option<&mut Vec<LayoutBox>> {
+        match self {
+            LayoutBox::BlockLevel(blb) => Some(blb.children_mut()),
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(ilb)) => {
+                Some(ilb.children_mut())
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::TextRun(_)) => None,
+        }
+    }
+
+    /// The positioning scheme of this box.  Anonymous boxes and text runs aren't generated by the
+    /// element whose computed values they use, so are always `static`.
+    pub fn position(&self) -> Position {
+        match self {
+            LayoutBox::BlockLevel(BlockLevelBox::BlockContainer(_))
+            | LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::InlineBox(_),
+            ))
+            | LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AtomicInline(_),
+            )) => self.computed_values().position,
+            _ => Position::Static,
+        }
+    }
+
+    /// The `float` of this box.  Like `position`, this is always `none` for anonymous boxes and
+    /// text runs, and it doesn't apply to absolutely positioned boxes either.
+    ///
+    /// https://www.w3.org/TR/CSS22/visuren.html#dis-pos-flo
+    pub fn float(&self) -> Float {
+        match self {
+            LayoutBox::BlockLevel(BlockLevelBox::BlockContainer(_))
+                if !self.position().is_absolutely_positioned() =>
+            {
+                self.computed_values().float
+            }
+            _ => Float::None,
+        }
+    }
+
+    /// Moves this box and all of its descendants by `dx` horizontally and `dy` vertically.
+    pub fn translate(&mut self, dx: CSSFloat, dy: CSSFloat) {
+        match self {
+            LayoutBox::BlockLevel(blb) => blb.translate(dx, dy),
+            LayoutBox::InlineLevel(ilc) => ilc.translate(dx, dy),
+        }
+    }
+
     /// Returns a box capable of containing inline children.  If `self` is already an inline-level
-    /// box, this will be `self`.  In other cases, we may need to get and or create a child box
-    /// capable of containing inline children.
+    /// box whose contents are inline, this will be `self`.  In other cases, we may need to get and
+    /// or create a child box capable of containing inline children.
     pub fn get_mut_inline_container(&mut self) -> Option<&mut LayoutBox> {
         match self {
             LayoutBox::BlockLevel(blb) => blb.get_mut_inline_container(),
-            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(ilb)) => Some(match ilb {
-                InlineLevelBox::AnonymousInline(_) => self,
-                InlineLevelBox::InlineBox(_) => self,
-            }),
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AtomicInline(aib),
+            )) => aib.get_mut_inline_container(),
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::AnonymousInline(_),
+            ))
+            | LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::InlineBox(_),
+            )) => Some(self),
             LayoutBox::InlineLevel(InlineLevelContent::TextRun(_)) => None,
         }
     }
@@ -109,6 +175,14 @@ impl From<InlineBox> for LayoutBox {
     }
 }
 
+impl From<AtomicInlineBox> for LayoutBox {
+    fn from(aib: AtomicInlineBox) -> Self {
+        LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+            InlineLevelBox::AtomicInline(aib),
+        ))
+    }
+}
+
 impl From<TextRun> for LayoutBox {
     fn from(tr: TextRun) -> Self {
         LayoutBox::InlineLevel(InlineLevelContent::TextRun(tr))
@@ -124,6 +198,163 @@ impl Layout for LayoutBox {
     }
 }
 
+/// The sizing properties of one physical axis of a box.
+struct ConstrainSizeInput {
+    preferred: LengthPercentageOrAuto,
+    min: LengthPercentageOrAuto,
+    max: MaxSize,
+    /// The size of the containing block along the same axis, which percentages refer to.
+    containing_size: CSSPixelLength,
+}
+
+/// The used content box size of one axis of a box: its preferred size (or `laid_out_size`, the
+/// size layout gave it, if that's `auto`), limited by its maximum and then its minimum size.  The
+/// minimum wins if the two conflict.  `to_content_box` converts sizes given by the sizing
+/// properties to content box sizes.
+fn constrain_size(
+    input: ConstrainSizeInput,
+    laid_out_size: CSSPixelLength,
+    to_content_box: impl Fn(CSSPixelLength) -> CSSPixelLength,
+) -> CSSPixelLength {
+    let containing_size = input.containing_size;
+    let mut size = match input.preferred {
+        LengthPercentageOrAuto::LengthPercentage(lp) => to_content_box(lp.to_px(containing_size)),
+        LengthPercentageOrAuto::Auto => laid_out_size,
+    };
+    if let Some(max) = input.max.to_px(containing_size) {
+        size = size.min(to_content_box(max));
+    }
+    size.max(to_content_box(input.min.to_px(containing_size)))
+}
+
+/// The content box size for a `size` given by a sizing property under `box_sizing`, where
+/// `padding_border` is the sum of the padding and border along the same axis.  Border box sizes
+/// smaller than their padding and border give an empty content box.
+///
+/// https://drafts.csswg.org/css-sizing-3/#box-sizing
+pub fn content_box_size(
+    size: CSSPixelLength,
+    box_sizing: BoxSizing,
+    padding_border: CSSPixelLength,
+) -> CSSPixelLength {
+    match box_sizing {
+        BoxSizing::ContentBox => size,
+        BoxSizing::BorderBox => (size - padding_border).clamp_to_non_negative(),
+    }
+}
+
+/// Which of the intrinsic sizes of a box to measure.
+///
+/// https://drafts.csswg.org/css-sizing-3/#intrinsic-sizes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum IntrinsicSize {
+    /// The narrowest a box can be without its content overflowing, which is where its inline
+    /// content is broken at every soft wrap opportunity.
+    MinContent,
+    /// The width a box would be given infinite space, which is where its inline content is only
+    /// broken at forced line breaks.
+    MaxContent,
+}
+
+/// An approximation of the max-content width of a box with the given `children`: the width of
+/// the widest margin box of its in-flow block-level children, where those without a fixed `width`
+/// are as wide as their own content, or of the widest line of its inline content were it only
+/// broken at forced line breaks.
+///
+/// https://drafts.csswg.org/css-sizing-3/#max-content
+pub(crate) fn max_content_width(
+    children: &[LayoutBox],
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    intrinsic_width(children, IntrinsicSize::MaxContent, text_measurer)
+}
+
+/// The intrinsic width of a box with the given `children`, measured like `max_content_width`, but
+/// for either intrinsic size.
+pub(crate) fn intrinsic_width(
+    children: &[LayoutBox],
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    children
+        .iter()
+        .filter(|child| !child.position().is_absolutely_positioned())
+        .map(|child| match child {
+            LayoutBox::BlockLevel(BlockLevelBox::BlockContainer(_)) => {
+                intrinsic_contribution(child, size, text_measurer)
+            }
+            LayoutBox::BlockLevel(BlockLevelBox::AnonymousBlock(abb)) => {
+                intrinsic_inline_size(abb.children(), size, text_measurer)
+            }
+            LayoutBox::InlineLevel(_) => CSSPixelLength::new(0.),
+        })
+        .fold(CSSPixelLength::new(0.), CSSPixelLength::max)
+}
+
+/// The width of the margin box of `layout_box` when sized by its max-content width.  Percentages
+/// can't be resolved until the width of the containing block is known, so percentage widths are
+/// treated as `auto`, and percentage margins and padding as zero.
+///
+/// https://drafts.csswg.org/css-sizing-3/#max-content-inline-size-contribution
+pub(crate) fn max_content_contribution(
+    layout_box: &LayoutBox,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    intrinsic_contribution(layout_box, IntrinsicSize::MaxContent, text_measurer)
+}
+
+/// The width of the margin box of `layout_box` when sized by the intrinsic width `size`, measured
+/// like `max_content_contribution`.
+///
+/// https://drafts.csswg.org/css-sizing-3/#contributions
+pub(crate) fn intrinsic_contribution(
+    layout_box: &LayoutBox,
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    let cvs = layout_box.computed_values();
+    let zero = CSSPixelLength::new(0.);
+    let margin = |lp_or_auto: &LengthPercentageOrAuto| lp_or_auto.to_px(zero);
+    let padding_border = cvs.padding_left.size.to_px(zero)
+        + cvs.padding_right.size.to_px(zero)
+        + cvs.border_left_width.size
+        + cvs.border_right_width.size;
+    let width = match &cvs.width.size {
+        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(length)) => {
+            content_box_size(*length, cvs.box_sizing, padding_border)
+        }
+        _ => layout_box.children().map_or(zero, |children| {
+            let formatting_context = layout_box.formatting_context();
+            content_intrinsic_width(&cvs, &formatting_context, children, size, text_measurer)
+        }),
+    };
+    margin(&cvs.margin_left.size) + padding_border + width + margin(&cvs.margin_right.size)
+}
+
+/// The intrinsic width `size` of the content box of a box with the computed values `cvs`, whose
+/// `children` are laid out in `formatting_context`.  Flex containers are measured by their
+/// max-content width either way, tables by their columns and captions, and multi-column
+/// containers by their columns and the gaps between them.
+pub(crate) fn content_intrinsic_width(
+    cvs: &ComputedValues,
+    formatting_context: &FormattingContextRef,
+    children: &[LayoutBox],
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    if formatting_context.is_flex_formatting_context() {
+        flex::max_content_width(cvs, children, text_measurer)
+    } else if formatting_context.is_grid_formatting_context() {
+        grid::intrinsic_width(cvs, children, size, text_measurer)
+    } else if formatting_context.is_table_formatting_context() {
+        table::intrinsic_width(cvs, children, size, text_measurer)
+    } else if cvs.is_multicol_container() {
+        multicol::intrinsic_width(cvs, children, size, text_measurer)
+    } else {
+        intrinsic_width(children, size, text_measurer)
+    }
+}
+
 pub fn get_anonymous_inline_layout_box(boxes: &mut Vec<LayoutBox>) -> Option<&mut LayoutBox> {
     boxes.iter_mut().find(|child| child.is_anonymous_inline())
 }
@@ -136,10 +367,13 @@ pub struct BaseBox {
     /// The formatting context this box participates in.
     formatting_context: FormattingContextRef,
     /// Reference to the closest non-anonymous node.  This distinction only matters for anonymous
-    /// boxes, since anonymous boxes are by definition not associated with a node, but need access
-    /// to a node to get computed values during layout.  If the box is a block, inline, or any other
-    /// non-anonymous box, this field is simply the actual DOM node associated with this box.
+    /// boxes, since anonymous boxes are by definition not associated with a node.  If the box is
+    /// a block, inline, or any other non-anonymous box, this field is simply the actual DOM node
+    /// associated with this box.
     node: NodeRef,
+    /// The computed values of an anonymous box, which can't use those of `node`, since they
+    /// include properties (like margins) that only apply to the boxes `node` generates itself.
+    anonymous_computed_values: Option<Rc<RefCell<ComputedValues>>>,
 }
 
 impl BaseBox {
@@ -148,29 +382,97 @@ impl BaseBox {
             dimensions: Dimensions::default(),
             formatting_context,
             node,
+            anonymous_computed_values: None,
         }
     }
 
-    /// Apply all box sizing properties to this box's dimensions.
+    /// Creates the base of an anonymous box generated for content starting at `node`, which
+    /// inherits from the parent of `node`: the element whose box contains the anonymous box.
     ///
-    /// https://www.w3.org/TR/css-sizing-3/#sizing-properties
-    pub fn apply_box_sizing_properties(&mut self, containing_block: ContainingBlock) {
-        let height = self.computed_values().height.size;
-        if let LengthPercentageOrAuto::LengthPercentage(lp) = height {
-            self.dimensions_mut()
-                .set_height(lp.to_px(containing_block.height()));
+    /// https://www.w3.org/TR/CSS22/visuren.html#anonymous-block-level
+    pub fn new_anonymous(node: NodeRef, formatting_context: FormattingContextRef) -> BaseBox {
+        let computed_values = node
+            .parent()
+            .map_or_else(ComputedValues::default, |parent| {
+                compute_anonymous_values(&parent.computed_values())
+            });
+        BaseBox {
+            anonymous_computed_values: Some(Rc::new(RefCell::new(computed_values))),
+            ..BaseBox::new(node, formatting_context)
         }
+    }
 
-        let width = self.computed_values().width.size;
-        if let LengthPercentageOrAuto::LengthPercentage(lp) = width {
-            self.dimensions_mut()
-                .set_width(lp.to_px(containing_block.width()));
+    /// Creates the base of an anonymous box like `new_anonymous`, but with the given `display`.
+    /// The anonymous boxes generated to complete the structure of a table are told apart by it.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+    pub fn new_anonymous_with_display(
+        node: NodeRef,
+        formatting_context: FormattingContextRef,
+        display: Display,
+    ) -> BaseBox {
+        let base = BaseBox::new_anonymous(node, formatting_context);
+        if let Some(computed_values) = &base.anonymous_computed_values {
+            computed_values.borrow_mut().display = display;
         }
+        base
     }
 
-    /// Retrieve the computed values of the node associated with this layout box.
+    /// Whether this is the base of an anonymous box, which isn't generated by an element.
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous_computed_values.is_some()
+    }
+
+    /// Apply all box sizing properties to this box's dimensions, constraining the size of each
+    /// axis by its `min-` and `max-` properties.
+    ///
+    /// https://www.w3.org/TR/css-sizing-3/#sizing-properties
+    /// https://www.w3.org/TR/CSS22/visudet.html#min-max-heights
+    pub fn apply_box_sizing_properties(&mut self, containing_block: ContainingBlock) {
+        let cvs = self.computed_values();
+        let box_sizing = cvs.box_sizing;
+        let width = ConstrainSizeInput {
+            preferred: cvs.width.size.clone(),
+            min: cvs.min_width.size.clone(),
+            max: cvs.max_width.clone(),
+            containing_size: containing_block.width(),
+        };
+        let height = ConstrainSizeInput {
+            preferred: cvs.height.size.clone(),
+            min: cvs.min_height.size.clone(),
+            max: cvs.max_height.clone(),
+            containing_size: containing_block.height(),
+        };
+        drop(cvs);
+
+        let dimensions = self.dimensions();
+        let padding_border_width = dimensions.get_padding_physical(PhysicalSide::Left)
+            + dimensions.get_padding_physical(PhysicalSide::Right)
+            + dimensions.get_border_physical(PhysicalSide::Left)
+            + dimensions.get_border_physical(PhysicalSide::Right);
+        let padding_border_height = dimensions.get_padding_physical(PhysicalSide::Top)
+            + dimensions.get_padding_physical(PhysicalSide::Bottom)
+            + dimensions.get_border_physical(PhysicalSide::Top)
+            + dimensions.get_border_physical(PhysicalSide::Bottom);
+        self.dimensions_mut().set_width(constrain_size(
+            width,
+            dimensions.content.width(),
+            |size| content_box_size(size, box_sizing, padding_border_width),
+        ));
+        self.dimensions_mut().set_height(constrain_size(
+            height,
+            dimensions.content.height(),
+            |size| content_box_size(size, box_sizing, padding_border_height),
+        ));
+    }
+
+    /// Retrieve the computed values of the node associated with this layout box, or those of the
+    /// box itself if it's anonymous.
     pub fn computed_values(&self) -> Ref<ComputedValues> {
-        self.node.computed_values()
+        match &self.anonymous_computed_values {
+            Some(computed_values) => computed_values.borrow(),
+            None => self.node.computed_values(),
+        }
     }
 
     pub fn dimensions(&self) -> Dimensions {
@@ -269,6 +571,45 @@ impl DumpLayout for LayoutBox {
         )
         .expect("error writing layout dump");
 
+        // Line layout places the content of an inline formatting context in line boxes, breaking
+        // inline boxes and text runs into a fragment per line.
+        let fragment_indent = indent_spaces + 2;
+        let write_fragment = |write_to: &mut W, name: String, rect: &PositionedRect| {
+            writeln!(
+                write_to,
+                "{:indent_spaces$}{} at ({}, {}) size {}x{}",
+                "",
+                name,
+                rect.start_x.dump_layout_format(),
+                rect.start_y.dump_layout_format(),
+                rect.width().dump_layout_format(),
+                rect.height().dump_layout_format(),
+                indent_spaces = fragment_indent,
+            )
+            .expect("error writing layout dump");
+        };
+        match self {
+            LayoutBox::BlockLevel(BlockLevelBox::AnonymousBlock(abb)) => {
+                for line_box in abb.line_boxes() {
+                    write_fragment(write_to, "LineBox".to_owned(), &line_box.rect);
+                }
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::InlineLevelBox(
+                InlineLevelBox::InlineBox(ib),
+            )) => {
+                for fragment in ib.fragments() {
+                    write_fragment(write_to, "InlineBoxFragment".to_owned(), fragment);
+                }
+            }
+            LayoutBox::InlineLevel(InlineLevelContent::TextRun(tr)) => {
+                for fragment in tr.fragments() {
+                    let name = format!("TextFragment \"{}\"", fragment.text);
+                    write_fragment(write_to, name, &fragment.rect);
+                }
+            }
+            _ => {}
+        }
+
         if let Some(children) = self.children() {
             let new_indent = indent_spaces + 2;
             children.iter().for_each(|child| {
@@ -2,9 +2,15 @@ pub mod behavior;
 pub mod box_tree;
 pub mod containing_block;
 pub mod dimensions;
+pub mod flex;
 pub mod flow;
 pub mod formatting_context;
+pub mod grid;
 pub mod layout_box;
+pub mod multicol;
+pub mod positioned;
+pub mod table;
+pub mod text;
 pub mod values;
 
 use crate::behavior::BaseLayoutBoxBehavior;
@@ -13,6 +19,7 @@ use crate::flow::block::BlockLevelBox;
 use crate::flow::inline::{InlineLevelBox, InlineLevelContent};
 use crate::flow::OriginRelativeProgression;
 use crate::layout_box::LayoutBox;
+use crate::text::TextMeasurer;
 use cli::DumpLayoutVerbosity;
 use dom::tree::NodeData;
 use enum_dispatch::enum_dispatch;
@@ -21,17 +28,20 @@ use primitives::rect::{PositionedRect, Rect};
 use primitives::units::{CSSFloat, CSSPixelLength};
 use std::io::Write;
 use style::values::computed::WritingMode;
+use style::viewport::ViewportSize;
 
 #[macro_use]
 extern crate html5ever;
 #[macro_use]
 extern crate strum_macros;
 
-/// Given a `window` and a `layout_root_box`, perform a layout with the dimensions of the `window`.
+/// Given a `window` and a `layout_root_box`, perform a layout with the dimensions of the `window`,
+/// measuring text with `text_measurer`.
 pub fn global_layout(
     layout_root_box: &mut LayoutBox,
     viewport: LayoutViewportDimensions,
     scale_factor: f32,
+    text_measurer: &dyn TextMeasurer,
 ) {
     let writing_mode = layout_root_box.computed_values().writing_mode;
     let direction = layout_root_box.computed_values().direction;
@@ -39,18 +49,28 @@ pub fn global_layout(
         width: CSSPixelLength::new(viewport.width().px() / scale_factor),
         height: CSSPixelLength::new(viewport.height().px() / scale_factor),
     };
+    let initial_containing_block = ContainingBlock::new(
+        PositionedRect {
+            start_x: 0.0,
+            start_y: 0.0,
+            rect: layout_viewport_rect,
+        },
+        direction,
+        writing_mode,
+    );
+    let layout_viewport = LayoutViewportDimensions::new(layout_viewport_rect)
+        .scrolled_to(viewport.scroll_x, viewport.scroll_y);
     layout_root_box.layout(LayoutContext::new(
-        ContainingBlock::new(
-            PositionedRect {
-                start_x: 0.0,
-                start_y: 0.0,
-                rect: layout_viewport_rect,
-            },
-            direction,
-            writing_mode,
-        ),
-        LayoutViewportDimensions::new(layout_viewport_rect),
+        initial_containing_block,
+        layout_viewport,
+        text_measurer,
     ));
+    positioned::layout_positioned_boxes(
+        layout_root_box,
+        initial_containing_block,
+        layout_viewport,
+        text_measurer,
+    );
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -60,20 +80,23 @@ pub enum BoxComponent {
     Padding,
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct LayoutContext {
+#[derive(Copy, Clone)]
+pub struct LayoutContext<'a> {
     containing_block: ContainingBlock,
     layout_viewport: LayoutViewportDimensions,
+    text_measurer: &'a dyn TextMeasurer,
 }
 
-impl LayoutContext {
+impl<'a> LayoutContext<'a> {
     pub fn new(
         containing_block: ContainingBlock,
         layout_viewport: LayoutViewportDimensions,
+        text_measurer: &'a dyn TextMeasurer,
     ) -> Self {
         LayoutContext {
             containing_block,
             layout_viewport,
+            text_measurer,
         }
     }
 
@@ -117,19 +140,48 @@ impl LayoutContext {
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct LayoutViewportDimensions {
     rect: Rect,
+    /// How far the document has been scrolled horizontally, i.e. the x-coordinate of the
+    /// top-left corner of this viewport relative to the document origin.
+    scroll_x: CSSFloat,
+    /// How far the document has been scrolled vertically.
+    scroll_y: CSSFloat,
 }
 
 impl LayoutViewportDimensions {
     pub fn new(rect: Rect) -> Self {
-        LayoutViewportDimensions { rect }
+        LayoutViewportDimensions {
+            rect,
+            scroll_x: 0.,
+            scroll_y: 0.,
+        }
     }
 
     pub fn from_px(width: CSSFloat, height: CSSFloat) -> Self {
+        LayoutViewportDimensions::new(Rect {
+            width: CSSPixelLength::new(width),
+            height: CSSPixelLength::new(height),
+        })
+    }
+
+    /// Returns these dimensions with the document scrolled to the given position.
+    pub fn scrolled_to(self, scroll_x: CSSFloat, scroll_y: CSSFloat) -> Self {
         LayoutViewportDimensions {
-            rect: Rect {
-                width: CSSPixelLength::new(width),
-                height: CSSPixelLength::new(height),
-            },
+            scroll_x,
+            scroll_y,
+            ..self
+        }
+    }
+
+    pub fn scroll_position(&self) -> (CSSFloat, CSSFloat) {
+        (self.scroll_x, self.scroll_y)
+    }
+
+    /// The area of the document visible through this viewport at its current scroll position.
+    pub fn scrollport(&self) -> PositionedRect {
+        PositionedRect {
+            start_x: self.scroll_x,
+            start_y: self.scroll_y,
+            rect: self.rect,
         }
     }
 
@@ -144,6 +196,12 @@ impl LayoutViewportDimensions {
     pub fn width_height_px(&self) -> (CSSFloat, CSSFloat) {
         (self.rect.width.px(), self.rect.height.px())
     }
+
+    /// The size of this viewport in CSS pixels given the `scale_factor`, which is what styles
+    /// (such as viewport-percentage lengths) are computed against.
+    pub fn style_viewport(&self, scale_factor: f32) -> ViewportSize {
+        ViewportSize::new(self.width() / scale_factor, self.height() / scale_factor)
+    }
 }
 
 impl From<PhysicalSize<u32>> for LayoutViewportDimensions {
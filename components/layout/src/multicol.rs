@@ -0,0 +1,494 @@
+use crate::behavior::BaseLayoutBoxBehavior;
+use crate::flex::definite_size;
+use crate::flow::block::BlockLevelBox;
+use crate::flow::inline::{InlineLevelBox, InlineLevelContent};
+use crate::layout_box::{content_box_size, intrinsic_width as flow_intrinsic_width};
+use crate::layout_box::{IntrinsicSize, LayoutBox};
+use crate::text::TextMeasurer;
+use crate::LayoutContext;
+use primitives::rect::{PositionedRect, Rect};
+use primitives::units::{CSSFloat, CSSPixelLength};
+use style::values::computed::{
+    ColumnCount, ColumnSpan, ColumnWidth, ComputedValues, Direction, Float, Gap,
+};
+use style::values::used::ToPx;
+
+/// How close two positions have to be to be treated as the same, so rounding doesn't open up
+/// breaks (or columns) where there are none.
+const EPSILON: CSSFloat = 0.01;
+
+/// The number of times the height of balanced columns is halved in on.
+const BALANCING_ITERATIONS: usize = 32;
+
+/// A place content can be broken between two columns.
+#[derive(Clone, Copy, Debug)]
+struct Break {
+    /// Where the content after the break starts.
+    position: CSSFloat,
+    /// Where the content before the break ends, which is as far as the column it ends is filled.
+    end_before: CSSFloat,
+}
+
+/// A column of a column set, and how far the content in it is moved from where it was laid out.
+#[derive(Clone, Copy, Debug)]
+struct Column {
+    /// Where the content of the column starts and ends in the strip it was laid out in.
+    start: CSSFloat,
+    end: CSSFloat,
+    dx: CSSFloat,
+    dy: CSSFloat,
+}
+
+impl Column {
+    fn is_empty(&self) -> bool {
+        self.end - self.start <= EPSILON
+    }
+}
+
+/// A run of children laid out in one column set, or a spanning child between the column sets.
+enum Segment {
+    Columns(Vec<LayoutBox>),
+    Spanner(LayoutBox),
+}
+
+/// The size of the gap between the columns of a multi-column container whose content box is
+/// `inline_size` wide.  `normal` is `1em`.
+///
+/// https://drafts.csswg.org/css-align-3/#column-row-gap
+fn column_gap(cvs: &ComputedValues, inline_size: CSSPixelLength) -> CSSPixelLength {
+    match &cvs.column_gap {
+        Gap::Normal => cvs.font_size.size,
+        Gap::LengthPercentage(lp) => lp.to_px(inline_size),
+    }
+    .clamp_to_non_negative()
+}
+
+/// The number and width of the columns of a multi-column container, given the size of its
+/// content box and the `gap` between its columns.
+///
+/// https://drafts.csswg.org/css-multicol-1/#pseudo-algorithm
+fn column_count_and_width(
+    cvs: &ComputedValues,
+    inline_size: CSSPixelLength,
+    gap: CSSPixelLength,
+) -> (usize, CSSPixelLength) {
+    let fitting = |width: CSSPixelLength| {
+        // Columns narrower than a pixel would make for an absurd number of them.
+        let column_and_gap = (width + gap).max(CSSPixelLength::new(1.));
+        (((inline_size + gap).px() / column_and_gap.px()).floor() as usize).max(1)
+    };
+    let count = match (cvs.column_width, cvs.column_count) {
+        (ColumnWidth::Auto, ColumnCount::Integer(count)) => count as usize,
+        (ColumnWidth::Length(width), ColumnCount::Auto) => fitting(width),
+        (ColumnWidth::Length(width), ColumnCount::Integer(count)) => {
+            fitting(width).min(count as usize)
+        }
+        (ColumnWidth::Auto, ColumnCount::Auto) => 1,
+    };
+    let width = (inline_size - gap * (count - 1) as CSSFloat) / count as CSSFloat;
+    (count, width.clamp_to_non_negative())
+}
+
+/// Whether `layout_box` is a spanning element, which is laid out across every column of its
+/// multi-column container.
+///
+/// https://drafts.csswg.org/css-multicol-1/#spanning-element
+fn is_spanner(layout_box: &LayoutBox) -> bool {
+    matches!(
+        layout_box,
+        LayoutBox::BlockLevel(BlockLevelBox::BlockContainer(_))
+    ) && is_in_flow(layout_box)
+        && layout_box.computed_values().column_span == ColumnSpan::All
+}
+
+fn is_in_flow(layout_box: &LayoutBox) -> bool {
+    !layout_box.position().is_absolutely_positioned() && layout_box.float() == Float::None
+}
+
+/// Whether content can't be broken between columns anywhere inside `blb`, either because it asks
+/// not to be with `break-inside`, or because it isn't laid out in a block formatting context.
+///
+/// https://drafts.csswg.org/css-break-3/#monolithic
+fn is_monolithic(blb: &BlockLevelBox) -> bool {
+    match blb {
+        BlockLevelBox::AnonymousBlock(_) => false,
+        BlockLevelBox::BlockContainer(_) => {
+            let formatting_context = blb.formatting_context();
+            let cvs = blb.computed_values();
+            cvs.break_inside.avoids_column_breaks()
+                || cvs.is_multicol_container()
+                || formatting_context.is_flex_formatting_context()
+                || formatting_context.is_grid_formatting_context()
+                || formatting_context.is_table_formatting_context()
+        }
+    }
+}
+
+/// Collects the places the in-flow content of `children` can be broken between columns, in the
+/// order they come in: between sibling block-level boxes, and between line boxes.
+///
+/// https://drafts.csswg.org/css-break-3/#possible-breaks
+fn collect_breaks(children: &[LayoutBox], breaks: &mut Vec<Break>) {
+    let mut previous_end = None;
+    for child in children.iter().filter(|child| is_in_flow(child)) {
+        let blb = match child {
+            LayoutBox::BlockLevel(blb) => blb,
+            LayoutBox::InlineLevel(_) => continue,
+        };
+        let border_box = blb.dimensions().border_box();
+        if let Some(end_before) = previous_end {
+            breaks.push(Break {
+                position: border_box.start_y,
+                end_before,
+            });
+        }
+        previous_end = Some(border_box.start_y + border_box.height().px());
+        if is_monolithic(blb) {
+            continue;
+        }
+        match blb {
+            BlockLevelBox::AnonymousBlock(abb) => {
+                for lines in abb.line_boxes().windows(2) {
+                    breaks.push(Break {
+                        position: lines[1].rect.start_y,
+                        end_before: lines[0].rect.start_y + lines[0].rect.height().px(),
+                    });
+                }
+            }
+            BlockLevelBox::BlockContainer(bc) => collect_breaks(bc.children(), breaks),
+        }
+    }
+}
+
+/// Fills columns `height` tall with the content of a strip from `start` to `end`, returning where
+/// the content of each column starts and ends.  Each column is broken at the last break that lets
+/// its content fit, or the first one after its start if none does, leaving the column to
+/// overflow.
+fn fill_columns(
+    breaks: &[Break],
+    start: CSSFloat,
+    end: CSSFloat,
+    height: CSSFloat,
+) -> Vec<(CSSFloat, CSSFloat)> {
+    let mut columns = Vec::new();
+    let mut column_start = start;
+    let mut next_break = 0;
+    loop {
+        if end - column_start <= height + EPSILON {
+            columns.push((column_start, end));
+            return columns;
+        }
+        let mut chosen = None;
+        for (index, candidate) in breaks.iter().enumerate().skip(next_break) {
+            if candidate.position <= column_start + EPSILON {
+                continue;
+            }
+            if candidate.end_before - column_start <= height + EPSILON {
+                chosen = Some(index);
+            } else {
+                if chosen.is_none() {
+                    chosen = Some(index);
+                }
+                break;
+            }
+        }
+        match chosen {
+            Some(index) => {
+                let chosen = breaks[index];
+                columns.push((column_start, chosen.end_before.max(column_start)));
+                column_start = chosen.position;
+                next_break = index + 1;
+            }
+            None => {
+                columns.push((column_start, end));
+                return columns;
+            }
+        }
+    }
+}
+
+/// Breaks the content of a strip from `start` to `end` into at most `count` columns, as short as
+/// they can be while still fitting, or into as many columns as it takes to fit in
+/// `available_height` if that's shorter.
+///
+/// https://drafts.csswg.org/css-multicol-1/#cf
+fn balance_columns(
+    breaks: &[Break],
+    start: CSSFloat,
+    end: CSSFloat,
+    count: usize,
+    available_height: Option<CSSFloat>,
+) -> Vec<(CSSFloat, CSSFloat)> {
+    let total = end - start;
+    let (mut low, mut high) = (total / count as CSSFloat, total);
+    if count > 1 && total > EPSILON {
+        for _ in 0..BALANCING_ITERATIONS {
+            let middle = (low + high) / 2.;
+            if fill_columns(breaks, start, end, middle).len() <= count {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+    }
+    let height = match available_height {
+        Some(available_height) if available_height < high => available_height,
+        _ => high,
+    };
+    fill_columns(breaks, start, end, height)
+}
+
+/// The column the content at `y` in the strip ends up in.
+fn column_at(columns: &[Column], y: CSSFloat) -> &Column {
+    columns
+        .iter()
+        .rev()
+        .find(|column| column.start <= y + EPSILON)
+        .unwrap_or(&columns[0])
+}
+
+/// Whether content from `top` to `bottom` in the strip is broken across columns.
+fn straddles(columns: &[Column], top: CSSFloat, bottom: CSSFloat) -> bool {
+    columns
+        .iter()
+        .skip(1)
+        .any(|column| column.start > top + EPSILON && column.start < bottom - EPSILON)
+}
+
+fn move_rect(rect: &mut PositionedRect, column: &Column) {
+    rect.start_x += column.dx;
+    rect.start_y += column.dy;
+}
+
+/// Moves the content of `layout_box` from the strip it was laid out in into the `columns` it was
+/// broken into.  Boxes broken across columns stay in the column they start in, and are cut off
+/// at its end, but the line boxes and text they contain move on to the columns after it.
+// TODO: Boxes broken across columns should be split into fragments in each column, like inline
+// boxes are split across lines.
+fn relocate(layout_box: &mut LayoutBox, columns: &[Column]) {
+    let border_box = layout_box.dimensions().border_box();
+    let top = border_box.start_y;
+    let bottom = top + border_box.height().px();
+    let column = *column_at(columns, top);
+    if !straddles(columns, top, bottom) {
+        layout_box.translate(column.dx, column.dy);
+        return;
+    }
+    match layout_box {
+        LayoutBox::BlockLevel(blb) => {
+            if let BlockLevelBox::AnonymousBlock(abb) = blb {
+                let mut line_boxes = abb.line_boxes().to_vec();
+                for line_box in &mut line_boxes {
+                    let line_column = *column_at(columns, line_box.rect.start_y);
+                    move_rect(&mut line_box.rect, &line_column);
+                }
+                abb.set_line_boxes(line_boxes);
+            }
+            for child in blb.children_mut() {
+                relocate(child, columns);
+            }
+            let dimensions = blb.dimensions_mut();
+            move_rect(&mut dimensions.content, &column);
+            let overflow = bottom - column.end;
+            if overflow > 0. {
+                let height = dimensions.content.height() - overflow;
+                dimensions.set_height(height.clamp_to_non_negative());
+            }
+        }
+        LayoutBox::InlineLevel(ilc) => relocate_inline(ilc, columns),
+    }
+}
+
+/// Moves the fragments of inline-level content broken across `columns` into the column the middle
+/// of each of them is in.
+fn relocate_inline(ilc: &mut InlineLevelContent, columns: &[Column]) {
+    let middle_column =
+        |rect: &PositionedRect| *column_at(columns, rect.start_y + rect.height().px() / 2.);
+    let top = ilc.dimensions().content.start_y;
+    let column = *column_at(columns, top);
+    match ilc {
+        InlineLevelContent::TextRun(tr) => {
+            let mut fragments = tr.fragments().to_vec();
+            for fragment in &mut fragments {
+                let fragment_column = middle_column(&fragment.rect);
+                move_rect(&mut fragment.rect, &fragment_column);
+            }
+            tr.set_fragments(fragments);
+        }
+        InlineLevelContent::InlineLevelBox(InlineLevelBox::AtomicInline(aib)) => {
+            let margin_box = aib.dimensions().margin_box();
+            let atomic_column = middle_column(&margin_box);
+            aib.translate(atomic_column.dx, atomic_column.dy);
+            return;
+        }
+        InlineLevelContent::InlineLevelBox(ilb) => {
+            if let InlineLevelBox::InlineBox(ib) = ilb {
+                let mut fragments = ib.fragments().to_vec();
+                for fragment in &mut fragments {
+                    let fragment_column = middle_column(fragment);
+                    move_rect(fragment, &fragment_column);
+                }
+                ib.set_fragments(fragments);
+            }
+            for child in ilb.children_mut() {
+                relocate(child, columns);
+            }
+        }
+    }
+    move_rect(&mut ilc.dimensions_mut().content, &column);
+}
+
+/// Lays out the children of a multi-column container in columns, which are the width of its
+/// content box divided between `column-count` columns, or as many `column-width` wide columns as
+/// fit, with `column-gap` between them.
+///
+/// The children are laid out in a new block formatting context as a single column (a strip) that
+/// is then broken into columns between block-level boxes and lines, balancing the height of the
+/// columns.  Spanning elements split the content into a column set before them and one after
+/// them, and the rules between columns with content are recorded to be painted.
+///
+/// https://drafts.csswg.org/css-multicol-1/#the-multi-column-model
+/// https://drafts.csswg.org/css-multicol-1/#column-span
+// TODO: Columns are only laid out in horizontal writing modes, and only spanning elements that
+// are children of the multi-column container span its columns.
+pub(crate) fn layout_multicol_container(container: &mut BlockLevelBox, context: &LayoutContext) {
+    let zero = CSSPixelLength::new(0.);
+    let cvs = container.computed_values();
+    let writing_mode = cvs.writing_mode;
+    let direction = cvs.direction;
+    let dimensions = container.dimensions();
+    let content = dimensions.content;
+    let inline_size = content.width();
+    let gap = column_gap(&cvs, inline_size);
+    let (count, column_width) = column_count_and_width(&cvs, inline_size, gap);
+    let rule_width = cvs.column_rule_width.size;
+    let block_padding_border = dimensions.border_box_block_size(writing_mode)
+        - dimensions.content_box_block_size(writing_mode);
+    let cb_block_size = context.containing_block.block_size(writing_mode);
+    let block_size = definite_size(&cvs.block_size(writing_mode), Some(cb_block_size))
+        .map(|size| content_box_size(size, cvs.box_sizing, block_padding_border));
+    drop(cvs);
+
+    let mut segments: Vec<Segment> = Vec::new();
+    for child in std::mem::take(container.children_mut()) {
+        if is_spanner(&child) {
+            segments.push(Segment::Spanner(child));
+            continue;
+        }
+        match segments.last_mut() {
+            Some(Segment::Columns(children)) => children.push(child),
+            _ => segments.push(Segment::Columns(vec![child])),
+        }
+    }
+    let has_spanners = segments
+        .iter()
+        .any(|segment| matches!(segment, Segment::Spanner(_)));
+    // Columns only fill up and overflow into more of them when their height is known up front.
+    let available_height = block_size.filter(|_| !has_spanners).map(|size| size.px());
+
+    let column_x = |index: usize| {
+        let offset = (column_width + gap).px() * index as CSSFloat;
+        match direction {
+            Direction::Ltr => content.start_x + offset,
+            Direction::Rtl => content.start_x + (inline_size - column_width).px() - offset,
+        }
+    };
+    let mut block_offset = content.start_y;
+    let mut laid_out = Vec::new();
+    let mut column_rules = Vec::new();
+    for segment in segments {
+        let (children, spanner) = match segment {
+            Segment::Columns(children) => (children, false),
+            Segment::Spanner(spanner) => (vec![spanner], true),
+        };
+        *container.children_mut() = children;
+        let dimensions = container.dimensions_mut();
+        dimensions.content.start_y = block_offset;
+        dimensions.set_width(if spanner { inline_size } else { column_width });
+        dimensions.set_height(zero);
+        container.layout_children_in_new_block_formatting_context(context);
+        let strip_height = container.dimensions().content.height().px();
+        if spanner {
+            block_offset += strip_height;
+            laid_out.append(container.children_mut());
+            continue;
+        }
+
+        let mut breaks = Vec::new();
+        collect_breaks(container.children(), &mut breaks);
+        let strip_end = block_offset + strip_height;
+        let columns: Vec<Column> =
+            balance_columns(&breaks, block_offset, strip_end, count, available_height)
+                .into_iter()
+                .enumerate()
+                .map(|(index, (start, end))| Column {
+                    start,
+                    end,
+                    dx: column_x(index) - content.start_x,
+                    dy: block_offset - start,
+                })
+                .collect();
+        for child in container.children_mut() {
+            relocate(child, &columns);
+        }
+        let column_height = columns
+            .iter()
+            .map(|column| column.end - column.start)
+            .fold(0., CSSFloat::max);
+        if rule_width > zero {
+            for index in 1..columns.len() {
+                if columns[index - 1].is_empty() || columns[index].is_empty() {
+                    continue;
+                }
+                let gap_start = column_x(index - 1).min(column_x(index)) + column_width.px();
+                column_rules.push(PositionedRect {
+                    start_x: gap_start + (gap - rule_width).px() / 2.,
+                    start_y: block_offset,
+                    rect: Rect {
+                        width: rule_width,
+                        height: CSSPixelLength::new(column_height),
+                    },
+                });
+            }
+        }
+        block_offset += column_height;
+        laid_out.append(container.children_mut());
+    }
+
+    *container.children_mut() = laid_out;
+    let dimensions = container.dimensions_mut();
+    dimensions.content.start_y = content.start_y;
+    dimensions.set_width(inline_size);
+    dimensions.set_height(CSSPixelLength::new(block_offset - content.start_y));
+    if let BlockLevelBox::BlockContainer(bc) = container {
+        bc.set_column_rules(column_rules);
+    }
+}
+
+/// The intrinsic width `size` of the content box of a multi-column container with the computed
+/// values `cvs` and the given `children`: enough for `column-count` columns as wide as its content
+/// (or its `column-width`), and the gaps between them.
+///
+/// https://drafts.csswg.org/css-multicol-1/#cw
+pub(crate) fn intrinsic_width(
+    cvs: &ComputedValues,
+    children: &[LayoutBox],
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    let content = flow_intrinsic_width(children, size, text_measurer);
+    let column =
+        match (cvs.column_width, size) {
+            (ColumnWidth::Auto, _) => content,
+            (ColumnWidth::Length(width), IntrinsicSize::MinContent) => width.min(content),
+            (ColumnWidth::Length(width), IntrinsicSize::MaxContent) => width.max(
+                flow_intrinsic_width(children, IntrinsicSize::MinContent, text_measurer),
+            ),
+        };
+    let count = match cvs.column_count {
+        ColumnCount::Integer(count) => count as CSSFloat,
+        ColumnCount::Auto => 1.,
+    };
+    let gap = column_gap(cvs, CSSPixelLength::new(0.));
+    column * count + gap * (count - 1.)
+}
@@ -0,0 +1,594 @@
+use crate::behavior::BaseLayoutBoxBehavior;
+use crate::containing_block::ContainingBlock;
+use crate::flow::block::BlockLevelBox;
+use crate::layout_box::{content_box_size, max_content_width, LayoutBox};
+use crate::text::TextMeasurer;
+use crate::{LayoutContext, LayoutViewportDimensions};
+use primitives::sides::PhysicalSide;
+use primitives::units::{CSSFloat, CSSPixelLength};
+use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use style::values::computed::{ComputedValues, Direction, Position};
+use style::values::used::ToPx;
+
+/// The containing blocks the descendants of a box are positioned against, depending on their
+/// `position`.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#containing-block-details
+#[derive(Clone, Copy, Debug)]
+struct PositionedContainingBlocks {
+    /// The content box of the parent box, which in-flow boxes are laid out in, and which limits
+    /// how far stickily positioned boxes can move.
+    in_flow: ContainingBlock,
+    /// The padding box of the nearest positioned ancestor, or the initial containing block if
+    /// there is none.
+    absolute: ContainingBlock,
+    /// The viewport at its current scroll position.
+    fixed: ContainingBlock,
+}
+
+/// Positions the positioned boxes of the tree rooted at `root`, which normal flow layout must
+/// already have been performed on.  Relatively and stickily positioned boxes are offset from
+/// where normal flow put them, and absolutely positioned boxes, which normal flow skips, are laid
+/// out against their containing block.
+///
+/// This happens after normal flow layout since the containing block of an absolutely positioned
+/// box is often sized by in-flow content laid out after the box itself.
+pub fn layout_positioned_boxes(
+    root: &mut LayoutBox,
+    initial_containing_block: ContainingBlock,
+    layout_viewport: LayoutViewportDimensions,
+    text_measurer: &dyn TextMeasurer,
+) {
+    let containing_blocks = PositionedContainingBlocks {
+        in_flow: initial_containing_block,
+        absolute: initial_containing_block,
+        fixed: ContainingBlock::new(
+            layout_viewport.scrollport(),
+            initial_containing_block.direction(),
+            initial_containing_block.writing_mode(),
+        ),
+    };
+    position_subtree(root, containing_blocks, layout_viewport, text_measurer);
+}
+
+fn position_subtree(
+    layout_box: &mut LayoutBox,
+    containing_blocks: PositionedContainingBlocks,
+    layout_viewport: LayoutViewportDimensions,
+    text_measurer: &dyn TextMeasurer,
+) {
+    let position = layout_box.position();
+    match position {
+        Position::Static => {}
+        Position::Relative => {
+            let (dx, dy) =
+                relative_offset(&layout_box.computed_values(), containing_blocks.in_flow);
+            layout_box.translate(dx, dy);
+        }
+        Position::Sticky => {
+            let (dx, dy) = sticky_offset(layout_box, containing_blocks.in_flow, layout_viewport);
+            layout_box.translate(dx, dy);
+        }
+        Position::Absolute | Position::Fixed => {
+            let containing_block = if position == Position::Fixed {
+                containing_blocks.fixed
+            } else {
+                containing_blocks.absolute
+            };
+            if let LayoutBox::BlockLevel(blb) = layout_box {
+                layout_absolutely_positioned(blb, containing_block, layout_viewport, text_measurer);
+            }
+        }
+    }
+
+    let cvs = layout_box.computed_values();
+    let (direction, writing_mode) = (cvs.direction, cvs.writing_mode);
+    drop(cvs);
+    let dimensions = layout_box.dimensions();
+    let child_containing_blocks = PositionedContainingBlocks {
+        in_flow: ContainingBlock::new(dimensions.content, direction, writing_mode),
+        absolute: if position.is_positioned() {
+            ContainingBlock::new(dimensions.padding_box(), direction, writing_mode)
+        } else {
+            containing_blocks.absolute
+        },
+        fixed: containing_blocks.fixed,
+    };
+    if let Some(children) = layout_box.children_mut() {
+        for child in children {
+            position_subtree(
+                child,
+                child_containing_blocks,
+                layout_viewport,
+                text_measurer,
+            );
+        }
+    }
+}
+
+/// The offset of a relatively positioned box from its position in flow.  If both insets along an
+/// axis are given, `left` wins over `right` when the containing block is left-to-right (and the
+/// reverse when it's right-to-left), and `top` always wins over `bottom`.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#relative-positioning
+fn relative_offset(
+    computed_values: &ComputedValues,
+    containing_block: ContainingBlock,
+) -> (CSSFloat, CSSFloat) {
+    let inset = |side: PhysicalSide, containing_size: CSSPixelLength| {
+        resolve_auto(&computed_values.inset(side), containing_size)
+    };
+    let (width, height) = (containing_block.width(), containing_block.height());
+    let left = inset(PhysicalSide::Left, width);
+    let right = inset(PhysicalSide::Right, width);
+    let dx = match (left, right, containing_block.direction()) {
+        (Some(left), Some(_), Direction::Ltr) | (Some(left), None, _) => left.px(),
+        (_, Some(right), _) => -right.px(),
+        (None, None, _) => 0.,
+    };
+    let dy = match (
+        inset(PhysicalSide::Top, height),
+        inset(PhysicalSide::Bottom, height),
+    ) {
+        (Some(top), _) => top.px(),
+        (None, Some(bottom)) => -bottom.px(),
+        (None, None) => 0.,
+    };
+    (dx, dy)
+}
+
+/// The offset of a stickily positioned box from its position in flow, which keeps its border box
+/// within the scrollport (the viewport at its current scroll position) inset by the box's
+/// non-`auto` insets, for as long as that doesn't take its margin box outside its containing
+/// block.  Percentage insets refer to the size of the scrollport.
+///
+/// https://drafts.csswg.org/css-position-3/#stickypos-insets
+fn sticky_offset(
+    layout_box: &LayoutBox,
+    containing_block: ContainingBlock,
+    layout_viewport: LayoutViewportDimensions,
+) -> (CSSFloat, CSSFloat) {
+    let cvs = layout_box.computed_values();
+    let scrollport = layout_viewport.scrollport();
+    let inset = |side: PhysicalSide, scrollport_size: CSSPixelLength| {
+        resolve_auto(&cvs.inset(side), scrollport_size).map(|inset| inset.px())
+    };
+    let dimensions = layout_box.dimensions();
+    let border_box = dimensions.border_box();
+    let margin_box = dimensions.margin_box();
+    let limits = containing_block.positioned_rect();
+
+    let dx = sticky_axis_offset(StickyAxisInput {
+        border_box_start: border_box.start_x,
+        border_box_end: border_box.start_x + border_box.width().px(),
+        margin_box_start: margin_box.start_x,
+        margin_box_end: margin_box.start_x + margin_box.width().px(),
+        view_start: inset(PhysicalSide::Left, scrollport.width())
+            .map(|left| scrollport.start_x + left),
+        view_end: inset(PhysicalSide::Right, scrollport.width())
+            .map(|right| scrollport.start_x + scrollport.width().px() - right),
+        containing_block_start: limits.start_x,
+        containing_block_end: limits.start_x + limits.width().px(),
+    });
+    let dy = sticky_axis_offset(StickyAxisInput {
+        border_box_start: border_box.start_y,
+        border_box_end: border_box.start_y + border_box.height().px(),
+        margin_box_start: margin_box.start_y,
+        margin_box_end: margin_box.start_y + margin_box.height().px(),
+        view_start: inset(PhysicalSide::Top, scrollport.height())
+            .map(|top| scrollport.start_y + top),
+        view_end: inset(PhysicalSide::Bottom, scrollport.height())
+            .map(|bottom| scrollport.start_y + scrollport.height().px() - bottom),
+        containing_block_start: limits.start_y,
+        containing_block_end: limits.start_y + limits.height().px(),
+    });
+    (dx, dy)
+}
+
+/// The edges of a stickily positioned box, its sticky view rectangle, and its containing block
+/// along one physical axis.
+struct StickyAxisInput {
+    border_box_start: CSSFloat,
+    border_box_end: CSSFloat,
+    margin_box_start: CSSFloat,
+    margin_box_end: CSSFloat,
+    /// The start edge of the scrollport inset by the start inset, or `None` if that's `auto`.
+    view_start: Option<CSSFloat>,
+    /// The end edge of the scrollport inset by the end inset, or `None` if that's `auto`.
+    view_end: Option<CSSFloat>,
+    containing_block_start: CSSFloat,
+    containing_block_end: CSSFloat,
+}
+
+/// The sticky offset along one axis.  If the box can't fit within the sticky view rectangle, the
+/// start inset wins.
+fn sticky_axis_offset(input: StickyAxisInput) -> CSSFloat {
+    if let Some(view_start) = input.view_start {
+        if input.border_box_start < view_start {
+            let room = (input.containing_block_end - input.margin_box_end).max(0.);
+            return (view_start - input.border_box_start).min(room);
+        }
+    }
+    if let Some(view_end) = input.view_end {
+        if input.border_box_end > view_end {
+            let room = (input.margin_box_start - input.containing_block_start).max(0.);
+            return -(input.border_box_end - view_end).min(room);
+        }
+    }
+    0.
+}
+
+/// Lays out an absolutely positioned box against `containing_block`, the padding box of its
+/// nearest positioned ancestor (or the viewport for fixed positioned boxes).  Normal flow layout
+/// must have already given the box its static position.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#abs-non-replaced-width
+/// https://www.w3.org/TR/CSS22/visudet.html#abs-non-replaced-height
+// TODO: This follows CSS 2.1, which only considers horizontal writing modes.  Solve the insets in
+// flow-relative terms to support vertical writing modes.
+fn layout_absolutely_positioned(
+    blb: &mut BlockLevelBox,
+    containing_block: ContainingBlock,
+    layout_viewport: LayoutViewportDimensions,
+    text_measurer: &dyn TextMeasurer,
+) {
+    let static_margin_box = blb.dimensions().margin_box();
+    let cb_rect = *containing_block.positioned_rect();
+    let (cb_width, cb_height) = (containing_block.width(), containing_block.height());
+
+    let cvs = blb.computed_values();
+    // Margins and padding refer to the width of the containing block on every side.
+    let padding = |lp: &LengthPercentage| lp.to_px(cb_width);
+    let (padding_left, padding_right, padding_top, padding_bottom) = (
+        padding(&cvs.padding_left.size),
+        padding(&cvs.padding_right.size),
+        padding(&cvs.padding_top.size),
+        padding(&cvs.padding_bottom.size),
+    );
+    let (border_left, border_right, border_top, border_bottom) = (
+        cvs.border_left_width.size,
+        cvs.border_right_width.size,
+        cvs.border_top_width.size,
+        cvs.border_bottom_width.size,
+    );
+    let box_sizing = cvs.box_sizing;
+    let padding_border_width = padding_left + padding_right + border_left + border_right;
+    let padding_border_height = padding_top + padding_bottom + border_top + border_bottom;
+    let content_width = |size| content_box_size(size, box_sizing, padding_border_width);
+    let content_height = |size| content_box_size(size, box_sizing, padding_border_height);
+
+    let horizontal_input = AbsoluteAxisInput {
+        containing_size: cb_width,
+        inset_start: resolve_auto(&cvs.left.size, cb_width),
+        inset_end: resolve_auto(&cvs.right.size, cb_width),
+        margin_start: resolve_auto(&cvs.margin_left.size, cb_width),
+        margin_end: resolve_auto(&cvs.margin_right.size, cb_width),
+        padding_border: padding_border_width,
+        size: resolve_auto(&cvs.width.size, cb_width).map(content_width),
+        min_size: content_width(cvs.min_width.size.to_px(cb_width)),
+        max_size: cvs.max_width.to_px(cb_width).map(content_width),
+        static_start: CSSPixelLength::new(static_margin_box.start_x - cb_rect.start_x),
+        static_end: CSSPixelLength::new(
+            cb_rect.start_x + cb_width.px()
+                - (static_margin_box.start_x + static_margin_box.width().px()),
+        ),
+        // The shrink-to-fit width is never wider than the preferred width, which is what content
+        // is measured by for now.
+        auto_size: max_content_width(blb.children(), text_measurer),
+        start_wins: containing_block.direction() == Direction::Ltr,
+        clamp_negative_auto_margins: true,
+    };
+    let vertical_input = AbsoluteAxisInput {
+        containing_size: cb_height,
+        inset_start: resolve_auto(&cvs.top.size, cb_height),
+        inset_end: resolve_auto(&cvs.bottom.size, cb_height),
+        margin_start: resolve_auto(&cvs.margin_top.size, cb_width),
+        margin_end: resolve_auto(&cvs.margin_bottom.size, cb_width),
+        padding_border: padding_border_height,
+        size: resolve_auto(&cvs.height.size, cb_height).map(content_height),
+        min_size: content_height(cvs.min_height.size.to_px(cb_height)),
+        max_size: cvs.max_height.to_px(cb_height).map(content_height),
+        static_start: CSSPixelLength::new(static_margin_box.start_y - cb_rect.start_y),
+        static_end: CSSPixelLength::new(
+            cb_rect.start_y + cb_height.px()
+                - (static_margin_box.start_y + static_margin_box.height().px()),
+        ),
+        // Filled in once the box's content has been laid out.
+        auto_size: CSSPixelLength::new(0.),
+        start_wins: true,
+        clamp_negative_auto_margins: false,
+    };
+    drop(cvs);
+
+    let horizontal = solve_absolute_axis(horizontal_input);
+    let dimensions = blb.dimensions_mut();
+    dimensions.set_margin_physical(PhysicalSide::Left, horizontal.margin_start);
+    dimensions.set_margin_physical(PhysicalSide::Right, horizontal.margin_end);
+    dimensions.set_border_physical(PhysicalSide::Left, border_left);
+    dimensions.set_border_physical(PhysicalSide::Right, border_right);
+    dimensions.set_border_physical(PhysicalSide::Top, border_top);
+    dimensions.set_border_physical(PhysicalSide::Bottom, border_bottom);
+    dimensions.set_padding_physical(PhysicalSide::Left, padding_left);
+    dimensions.set_padding_physical(PhysicalSide::Right, padding_right);
+    dimensions.set_padding_physical(PhysicalSide::Top, padding_top);
+    dimensions.set_padding_physical(PhysicalSide::Bottom, padding_bottom);
+    dimensions.set_width(horizontal.size);
+    dimensions.set_start_x(
+        cb_rect.start_x
+            + (horizontal.inset_start + horizontal.margin_start + border_left + padding_left).px(),
+    );
+    // The height of the box can depend on its content, so lay that out first, starting from a
+    // block-start coordinate of zero that the whole box is then moved from.
+    dimensions.set_start_y(0.);
+    dimensions.set_height(CSSPixelLength::new(0.));
+    blb.layout_children_in_new_formatting_context(&LayoutContext::new(
+        containing_block,
+        layout_viewport,
+        text_measurer,
+    ));
+
+    let vertical = solve_absolute_axis(AbsoluteAxisInput {
+        auto_size: blb.dimensions().content.height(),
+        ..vertical_input
+    });
+    let dimensions = blb.dimensions_mut();
+    dimensions.set_margin_physical(PhysicalSide::Top, vertical.margin_start);
+    dimensions.set_margin_physical(PhysicalSide::Bottom, vertical.margin_end);
+    dimensions.set_width(horizontal.size);
+    dimensions.set_height(vertical.size);
+    let start_y = cb_rect.start_y
+        + (vertical.inset_start + vertical.margin_start + border_top + padding_top).px();
+    blb.translate(0., start_y);
+}
+
+/// The constraints on an absolutely positioned box along one physical axis.  `None` represents
+/// `auto`, and `size`, `min_size`, and `max_size` are content box sizes.
+#[derive(Clone, Copy, Debug)]
+struct AbsoluteAxisInput {
+    containing_size: CSSPixelLength,
+    inset_start: Option<CSSPixelLength>,
+    inset_end: Option<CSSPixelLength>,
+    margin_start: Option<CSSPixelLength>,
+    margin_end: Option<CSSPixelLength>,
+    /// The sum of the padding and border along this axis.
+    padding_border: CSSPixelLength,
+    size: Option<CSSPixelLength>,
+    min_size: CSSPixelLength,
+    max_size: Option<CSSPixelLength>,
+    /// The distance from the start of the containing block to the start of the margin box of the
+    /// hypothetical box the element would have generated were it `position: static`.
+    static_start: CSSPixelLength,
+    /// The distance from the end of the hypothetical box's margin box to the end of the
+    /// containing block.
+    static_end: CSSPixelLength,
+    /// The size of the content box when `size` is `auto` and not given by the other constraints:
+    /// the shrink-to-fit width horizontally, and the height of the content vertically.
+    auto_size: CSSPixelLength,
+    /// Whether the start inset is the one set to the static position when both insets are
+    /// `auto`, and the one kept when the constraints are over-constrained.  This is true for the
+    /// vertical axis, and for the horizontal axis when the containing block is left-to-right.
+    start_wins: bool,
+    /// Whether `auto` margins that would be equal and negative are instead zero on the start side
+    /// (or end side, if `!start_wins`).  This only applies to the horizontal axis.
+    clamp_negative_auto_margins: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct AbsoluteAxisOutput {
+    inset_start: CSSPixelLength,
+    margin_start: CSSPixelLength,
+    margin_end: CSSPixelLength,
+    size: CSSPixelLength,
+}
+
+/// Solves the used insets, margins, and size of an absolutely positioned box along one axis,
+/// first tentatively, then again with its maximum size if the tentative size is larger, then
+/// again with its minimum size if the result is smaller.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#min-max-widths
+/// https://www.w3.org/TR/CSS22/visudet.html#min-max-heights
+fn solve_absolute_axis(input: AbsoluteAxisInput) -> AbsoluteAxisOutput {
+    let mut output = solve_absolute_axis_to_spec(input);
+    if let Some(max_size) = input.max_size {
+        if output.size > max_size {
+            output = solve_absolute_axis_to_spec(AbsoluteAxisInput {
+                size: Some(max_size),
+                ..input
+            });
+        }
+    }
+    if output.size < input.min_size {
+        output = solve_absolute_axis_to_spec(AbsoluteAxisInput {
+            size: Some(input.min_size),
+            ..input
+        });
+    }
+    output
+}
+
+/// Solves the constraint that the insets, margins, padding, border, and size of an absolutely
+/// positioned box along one axis sum to the size of its containing block.
+///
+/// Corresponds to CSS 2.1 sections 10.3.7 (horizontally) and 10.6.4 (vertically).
+fn solve_absolute_axis_to_spec(input: AbsoluteAxisInput) -> AbsoluteAxisOutput {
+    let zero = CSSPixelLength::new(0.);
+    let mut inset_start = input.inset_start;
+    let mut inset_end = input.inset_end;
+    let mut size = input.size;
+    let (mut margin_start, mut margin_end) = (input.margin_start, input.margin_end);
+    // The space left over for whichever value is solved for, given the others (treating any
+    // that are still `auto` as zero).
+    let remaining = |values: [Option<CSSPixelLength>; 5]| {
+        values.iter().fold(
+            input.containing_size - input.padding_border,
+            |remaining, value| remaining - value.unwrap_or(zero),
+        )
+    };
+
+    if inset_start.is_none() && size.is_none() && inset_end.is_none() {
+        // If all three are `auto`, set any `auto` margins to zero, then set the inset that wins to
+        // its static position and size the box to fit its content.
+        margin_start = margin_start.or(Some(zero));
+        margin_end = margin_end.or(Some(zero));
+        if input.start_wins {
+            inset_start = Some(input.static_start);
+        } else {
+            inset_end = Some(input.static_end);
+        }
+        size = Some(input.auto_size);
+    } else if inset_start.is_some() && size.is_some() && inset_end.is_some() {
+        match (margin_start, margin_end) {
+            (None, None) => {
+                // Both margins are `auto`, so they take equal shares of the remaining space.
+                let space = remaining([inset_start, size, inset_end, None, None]);
+                let half = space / 2.;
+                let (start, end) = if input.clamp_negative_auto_margins && half < 0. {
+                    if input.start_wins {
+                        (zero, space)
+                    } else {
+                        (space, zero)
+                    }
+                } else {
+                    (half, half)
+                };
+                margin_start = Some(start);
+                margin_end = Some(end);
+            }
+            (None, Some(_)) => {
+                margin_start = Some(remaining([inset_start, size, inset_end, None, margin_end]))
+            }
+            (Some(_), None) => {
+                margin_end = Some(remaining([
+                    inset_start,
+                    size,
+                    inset_end,
+                    margin_start,
+                    None,
+                ]))
+            }
+            // The values are over-constrained, so ignore the inset that doesn't win.
+            (Some(_), Some(_)) => {
+                if input.start_wins {
+                    inset_end = None;
+                } else {
+                    inset_start = None;
+                }
+            }
+        }
+    } else {
+        // Otherwise, any `auto` margins are zero, and the box is sized to fit its content unless
+        // the two insets determine its size.
+        margin_start = margin_start.or(Some(zero));
+        margin_end = margin_end.or(Some(zero));
+        match (inset_start, size, inset_end) {
+            (Some(_), None, Some(_)) => {}
+            (None, Some(_), None) => {
+                if input.start_wins {
+                    inset_start = Some(input.static_start);
+                } else {
+                    inset_end = Some(input.static_end);
+                }
+            }
+            _ => size = size.or(Some(input.auto_size)),
+        }
+    }
+
+    // At most one value is still `auto`, and its used value follows from the constraint.
+    let remaining = remaining([inset_start, size, inset_end, margin_start, margin_end]);
+    if size.is_none() {
+        size = Some(remaining);
+    } else if inset_start.is_none() {
+        inset_start = Some(remaining);
+    }
+    AbsoluteAxisOutput {
+        inset_start: inset_start.unwrap_or(zero),
+        margin_start: margin_start.unwrap_or(zero),
+        margin_end: margin_end.unwrap_or(zero),
+        size: size.unwrap_or(zero),
+    }
+}
+
+/// Resolves `lp_or_auto` against `containing_size`, or `None` if it's `auto`.
+fn resolve_auto(
+    lp_or_auto: &LengthPercentageOrAuto,
+    containing_size: CSSPixelLength,
+) -> Option<CSSPixelLength> {
+    match lp_or_auto {
+        LengthPercentageOrAuto::LengthPercentage(lp) => Some(lp.to_px(containing_size)),
+        LengthPercentageOrAuto::Auto => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A box whose border and margin boxes span 100px to 150px, in a containing block spanning
+    /// 50px to 400px, against a scrollport 300px long scrolled to `scroll` with the given insets.
+    fn sticky_input(
+        scroll: CSSFloat,
+        start_inset: Option<CSSFloat>,
+        end_inset: Option<CSSFloat>,
+    ) -> StickyAxisInput {
+        StickyAxisInput {
+            border_box_start: 100.,
+            border_box_end: 150.,
+            margin_box_start: 100.,
+            margin_box_end: 150.,
+            view_start: start_inset.map(|inset| scroll + inset),
+            view_end: end_inset.map(|inset| scroll + 300. - inset),
+            containing_block_start: 50.,
+            containing_block_end: 400.,
+        }
+    }
+
+    #[test]
+    fn sticky_box_stays_in_flow_while_within_the_scrollport() {
+        assert_eq!(sticky_axis_offset(sticky_input(0., Some(10.), None)), 0.);
+        assert_eq!(sticky_axis_offset(sticky_input(90., Some(10.), None)), 0.);
+    }
+
+    #[test]
+    fn sticky_box_sticks_to_start_of_scrolled_scrollport() {
+        // The scrollport's start edge, inset by 10px, is at 210px.
+        assert_eq!(
+            sticky_axis_offset(sticky_input(200., Some(10.), None)),
+            110.
+        );
+    }
+
+    #[test]
+    fn sticky_box_is_limited_by_containing_block_end() {
+        // Sticking would put the box at 410px, but its margin box can only move down to 400px.
+        assert_eq!(
+            sticky_axis_offset(sticky_input(400., Some(10.), None)),
+            250.
+        );
+    }
+
+    #[test]
+    fn sticky_box_sticks_to_end_of_scrolled_scrollport() {
+        // The scrollport's end edge, inset by 10px, is at 120px.
+        assert_eq!(
+            sticky_axis_offset(sticky_input(-170., None, Some(10.))),
+            -30.
+        );
+    }
+
+    #[test]
+    fn sticky_box_is_limited_by_containing_block_start() {
+        // Sticking would put the box's end at 90px, but its margin box can only move up to 50px.
+        assert_eq!(
+            sticky_axis_offset(sticky_input(-200., None, Some(10.))),
+            -50.
+        );
+    }
+
+    #[test]
+    fn sticky_start_inset_wins_when_box_cannot_fit() {
+        assert_eq!(
+            sticky_axis_offset(sticky_input(200., Some(10.), Some(280.))),
+            110.
+        );
+    }
+}
@@ -0,0 +1,1439 @@
+use crate::behavior::{ApplyBoxSizingProperties, BaseLayoutBoxBehavior};
+use crate::box_tree::internal_display;
+use crate::containing_block::ContainingBlock;
+use crate::dimensions::Dimensions;
+use crate::flex::{definite_size, layout_item_contents, place, FlowAxis};
+use crate::flow::block::BlockLevelBox;
+use crate::flow::OriginRelativeProgression;
+use crate::layout_box::{content_box_size, content_intrinsic_width, IntrinsicSize, LayoutBox};
+use crate::text::TextMeasurer;
+use crate::LayoutContext;
+use primitives::rect::EdgeSizes;
+use primitives::sides::{FlowSide, PhysicalSide};
+use primitives::units::{CSSFloat, CSSPixelLength};
+use std::ops::Range;
+use style::values::computed::display::InternalDisplay;
+use style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use style::values::computed::{
+    BorderCollapse, BorderSpacing, BoxSizing, CaptionSide, ComputedValues, Direction, Display,
+    LineStyle, TableLayout, WritingMode,
+};
+use style::values::used::ToPx;
+
+/// Where a row or column box is: among the children of the table, or among those of the row group
+/// or column group at `parent` among the children of the table.
+#[derive(Clone, Copy, Debug)]
+struct BoxPath {
+    parent: Option<usize>,
+    index: usize,
+}
+
+/// A row group, or a run of rows that aren't in one, and the rows in it.
+#[derive(Clone, Debug)]
+struct RowGroup {
+    /// The index of the row group among the children of the table, or `None` for rows that
+    /// aren't in a row group.
+    index: Option<usize>,
+    rows: Range<usize>,
+}
+
+/// A column or column group box, and the columns it spans.
+#[derive(Clone, Debug)]
+struct ColumnBox {
+    path: BoxPath,
+    columns: Range<usize>,
+}
+
+/// A cell, and the slots of the table it spans.
+#[derive(Clone, Debug)]
+struct Cell {
+    /// The row the cell is in, and its index among the children of that row.
+    row: usize,
+    index: usize,
+    columns: Range<usize>,
+    rows: Range<usize>,
+}
+
+/// The structure of a table: its captions, and the grid of slots its cells span.  Rows are in the
+/// order they're laid out, where the first header group goes before every other row, and the
+/// first footer group after every other row.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#table-layout
+#[derive(Clone, Debug, Default)]
+struct TableStructure {
+    /// The indices of the captions among the children of the table.
+    captions: Vec<usize>,
+    row_groups: Vec<RowGroup>,
+    rows: Vec<BoxPath>,
+    /// The column and column group boxes, where each column group comes before its columns.
+    column_boxes: Vec<ColumnBox>,
+    cells: Vec<Cell>,
+    column_count: usize,
+}
+
+impl TableStructure {
+    fn new(children: &[LayoutBox]) -> TableStructure {
+        let mut structure = TableStructure::default();
+        // The row groups and runs of rows that aren't in one, before they're put in order.
+        let mut groups: Vec<(Option<usize>, Vec<BoxPath>)> = Vec::new();
+        let mut header = None;
+        let mut footer = None;
+        let mut column = 0;
+        for (index, child) in children.iter().enumerate() {
+            let internal = match internal_display(child) {
+                Some(internal) => internal,
+                None => continue,
+            };
+            match internal {
+                InternalDisplay::TableCaption => structure.captions.push(index),
+                InternalDisplay::TableColumn => {
+                    let span = span_attribute(table_box(children, index));
+                    structure.column_boxes.push(ColumnBox {
+                        path: BoxPath {
+                            parent: None,
+                            index,
+                        },
+                        columns: column..column + span,
+                    });
+                    column += span;
+                }
+                InternalDisplay::TableColumnGroup => {
+                    let group = table_box(children, index);
+                    let group_entry = structure.column_boxes.len();
+                    let start = column;
+                    structure.column_boxes.push(ColumnBox {
+                        path: BoxPath {
+                            parent: None,
+                            index,
+                        },
+                        columns: start..start,
+                    });
+                    // A column group without columns spans columns of its own.
+                    if group.children().is_empty() {
+                        column += span_attribute(group);
+                    }
+                    for column_index in 0..group.children().len() {
+                        let span = span_attribute(table_box(group.children(), column_index));
+                        structure.column_boxes.push(ColumnBox {
+                            path: BoxPath {
+                                parent: Some(index),
+                                index: column_index,
+                            },
+                            columns: column..column + span,
+                        });
+                        column += span;
+                    }
+                    structure.column_boxes[group_entry].columns = start..column;
+                }
+                InternalDisplay::TableRow => {
+                    let path = BoxPath {
+                        parent: None,
+                        index,
+                    };
+                    // Runs of rows that aren't in a row group are grouped like those that are.
+                    let continues_run = index > 0
+                        && internal_display(&children[index - 1])
+                            == Some(InternalDisplay::TableRow);
+                    match groups.last_mut() {
+                        Some((None, rows)) if continues_run => rows.push(path),
+                        _ => groups.push((None, vec![path])),
+                    }
+                }
+                InternalDisplay::TableRowGroup
+                | InternalDisplay::TableHeaderGroup
+                | InternalDisplay::TableFooterGroup => {
+                    if internal == InternalDisplay::TableHeaderGroup && header.is_none() {
+                        header = Some(groups.len());
+                    } else if internal == InternalDisplay::TableFooterGroup && footer.is_none() {
+                        footer = Some(groups.len());
+                    }
+                    let rows = (0..table_box(children, index).children().len())
+                        .map(|row_index| BoxPath {
+                            parent: Some(index),
+                            index: row_index,
+                        })
+                        .collect();
+                    groups.push((Some(index), rows));
+                }
+                InternalDisplay::TableCell => {
+                    unreachable!("cells should have been wrapped in an anonymous row")
+                }
+            }
+        }
+
+        // Put the header group first and the footer group last, and then find the slots spanned
+        // by each cell.
+        let order = header
+            .into_iter()
+            .chain(
+                (0..groups.len()).filter(|&group| Some(group) != header && Some(group) != footer),
+            )
+            .chain(footer);
+        let mut occupied: Vec<Vec<bool>> = Vec::new();
+        for group in order {
+            let (index, rows) = &groups[group];
+            let first_row = structure.rows.len();
+            let group_end = first_row + rows.len();
+            structure.row_groups.push(RowGroup {
+                index: *index,
+                rows: first_row..group_end,
+            });
+            for (row, path) in (first_row..).zip(rows.iter().copied()) {
+                structure.rows.push(path);
+                occupied.resize(group_end, Vec::new());
+                let mut column = 0;
+                for (cell_index, cell) in row_box(children, path).children().iter().enumerate() {
+                    let cell = match cell {
+                        LayoutBox::BlockLevel(blb) => blb,
+                        LayoutBox::InlineLevel(_) => continue,
+                    };
+                    while occupied[row].get(column) == Some(&true) {
+                        column += 1;
+                    }
+                    let colspan = integer_attribute(cell, local_name!("colspan"))
+                        .unwrap_or(1)
+                        .clamp(1, 1000);
+                    // A `rowspan` of zero spans the rest of the row group, which no cell can
+                    // span past.
+                    let row_end = match integer_attribute(cell, local_name!("rowspan")) {
+                        Some(0) => group_end,
+                        rowspan => (row + rowspan.unwrap_or(1).min(65534)).min(group_end),
+                    };
+                    let columns = column..column + colspan;
+                    for slots in &mut occupied[row..row_end] {
+                        if slots.len() < columns.end {
+                            slots.resize(columns.end, false);
+                        }
+                        for slot in &mut slots[columns.clone()] {
+                            *slot = true;
+                        }
+                    }
+                    structure.cells.push(Cell {
+                        row,
+                        index: cell_index,
+                        columns,
+                        rows: row..row_end,
+                    });
+                    column += colspan;
+                }
+            }
+        }
+        structure.column_count = occupied
+            .iter()
+            .map(Vec::len)
+            .chain(std::iter::once(column))
+            .max()
+            .unwrap_or(0);
+        structure
+    }
+}
+
+/// The child at `index` among `children`, which are the children of a table, row group, row, or
+/// column group.
+fn table_box(children: &[LayoutBox], index: usize) -> &BlockLevelBox {
+    match &children[index] {
+        LayoutBox::BlockLevel(blb) => blb,
+        LayoutBox::InlineLevel(_) => panic!("the internal boxes of a table should be block-level"),
+    }
+}
+
+/// Like `table_box`, but mutable.
+fn table_box_mut(children: &mut [LayoutBox], index: usize) -> &mut BlockLevelBox {
+    match &mut children[index] {
+        LayoutBox::BlockLevel(blb) => blb,
+        LayoutBox::InlineLevel(_) => panic!("the internal boxes of a table should be block-level"),
+    }
+}
+
+/// The row or column box at `path` among the `children` of a table.
+fn row_box(children: &[LayoutBox], path: BoxPath) -> &BlockLevelBox {
+    match path.parent {
+        Some(parent) => table_box(table_box(children, parent).children(), path.index),
+        None => table_box(children, path.index),
+    }
+}
+
+/// Like `row_box`, but mutable.
+fn row_box_mut(children: &mut [LayoutBox], path: BoxPath) -> &mut BlockLevelBox {
+    match path.parent {
+        Some(parent) => table_box_mut(table_box_mut(children, parent).children_mut(), path.index),
+        None => table_box_mut(children, path.index),
+    }
+}
+
+fn cell_box<'a>(
+    children: &'a [LayoutBox],
+    structure: &TableStructure,
+    cell: &Cell,
+) -> &'a BlockLevelBox {
+    table_box(
+        row_box(children, structure.rows[cell.row]).children(),
+        cell.index,
+    )
+}
+
+fn cell_box_mut<'a>(
+    children: &'a mut [LayoutBox],
+    structure: &TableStructure,
+    cell: &Cell,
+) -> &'a mut BlockLevelBox {
+    table_box_mut(
+        row_box_mut(children, structure.rows[cell.row]).children_mut(),
+        cell.index,
+    )
+}
+
+/// The value of the attribute `name` of the element that generated `layout_box`, parsed as a
+/// non-negative integer, or `None` if the box is anonymous or the attribute is missing or isn't
+/// a number.
+///
+/// https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-non-negative-integers
+fn integer_attribute(layout_box: &BlockLevelBox, name: html5ever::LocalName) -> Option<usize> {
+    let block_container = match layout_box {
+        BlockLevelBox::BlockContainer(bc) if !bc.is_anonymous() => bc,
+        _ => return None,
+    };
+    let node = block_container.node();
+    let attributes = node.as_element()?.attributes.borrow();
+    let digits: String = attributes
+        .get(name)?
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// The number of columns spanned by the column or column group `column_box`.
+fn span_attribute(column_box: &BlockLevelBox) -> usize {
+    integer_attribute(column_box, local_name!("span"))
+        .unwrap_or(1)
+        .clamp(1, 1000)
+}
+
+/// The spacing between cells along each axis of a table with the computed values `cvs`, as
+/// `(inline, block)`.  There is none in the collapsing border model.
+fn spacing(cvs: &ComputedValues) -> (CSSPixelLength, CSSPixelLength) {
+    let BorderSpacing {
+        horizontal,
+        vertical,
+    } = match cvs.border_collapse {
+        BorderCollapse::Separate => cvs.border_spacing,
+        BorderCollapse::Collapse => BorderSpacing::initial_value(),
+    };
+    if cvs.writing_mode.is_horizontal() {
+        (horizontal, vertical)
+    } else {
+        (vertical, horizontal)
+    }
+}
+
+/// The size of `tracks` (rows or columns) laid out with `spacing` between each of them, and
+/// between them and the edges of the table.
+fn tracks_size(tracks: &[CSSPixelLength], spacing: CSSPixelLength) -> CSSPixelLength {
+    if tracks.is_empty() {
+        return CSSPixelLength::new(0.);
+    }
+    tracks
+        .iter()
+        .fold(spacing, |size, &track| size + track + spacing)
+}
+
+/// The offset of each of `tracks` from the start of the table when laid out with `spacing` between
+/// them, and between them and the edges of the table.
+fn track_offsets(tracks: &[CSSPixelLength], spacing: CSSPixelLength) -> Vec<CSSPixelLength> {
+    let mut offset = spacing;
+    tracks
+        .iter()
+        .map(|&track| {
+            let track_offset = offset;
+            offset = offset + track + spacing;
+            track_offset
+        })
+        .collect()
+}
+
+/// The offset and size of the area spanning `span` of `tracks`.
+fn span_area(
+    tracks: &[CSSPixelLength],
+    offsets: &[CSSPixelLength],
+    span: Range<usize>,
+    spacing: CSSPixelLength,
+) -> (CSSPixelLength, CSSPixelLength) {
+    let size = tracks[span.clone()]
+        .iter()
+        .fold(CSSPixelLength::new(0.), |size, &track| size + track);
+    let inner_spacing = spacing * (span.len().saturating_sub(1) as CSSFloat);
+    (offsets[span.start], size + inner_spacing)
+}
+
+/// Grows `tracks` so that together, with the `spacing` between them, they're at least `size`.
+/// The extra space is distributed in proportion to their sizes, or equally if they're all empty.
+fn grow_to(tracks: &mut [CSSPixelLength], size: CSSPixelLength, spacing: CSSPixelLength) {
+    let inner_spacing = spacing * (tracks.len().saturating_sub(1) as CSSFloat);
+    let total = tracks
+        .iter()
+        .fold(CSSPixelLength::new(0.), |total, &track| total + track);
+    let extra = size - total - inner_spacing;
+    if extra <= 0. || tracks.is_empty() {
+        return;
+    }
+    let count = tracks.len() as CSSFloat;
+    for track in tracks.iter_mut() {
+        *track = if total > 0. {
+            *track + extra * (track.px() / total.px())
+        } else {
+            *track + extra / count
+        };
+    }
+}
+
+/// The fixed `width` of `layout_box` as a border box width, if it has one.  `padding_border` is the
+/// sum of its horizontal padding and borders.
+fn fixed_width(
+    layout_box: &BlockLevelBox,
+    padding_border: CSSPixelLength,
+) -> Option<CSSPixelLength> {
+    let cvs = layout_box.computed_values();
+    match cvs.width.size {
+        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(width)) => {
+            Some(content_box_size(width, cvs.box_sizing, padding_border) + padding_border)
+        }
+        // TODO: Percentage widths of cells and columns, which are treated as `auto`.
+        _ => None,
+    }
+}
+
+/// The sum of the horizontal padding and borders of the cell `cell`.  In the collapsing border
+/// model, its borders are the `collapsed_borders` it has inside it.
+fn cell_padding_border(
+    cell: &BlockLevelBox,
+    collapsed_borders: Option<EdgeSizes>,
+) -> CSSPixelLength {
+    let cvs = cell.computed_values();
+    let zero = CSSPixelLength::new(0.);
+    let borders = match collapsed_borders {
+        Some(borders) => borders.left + borders.right,
+        None => cvs.border_left_width.size + cvs.border_right_width.size,
+    };
+    cvs.padding_left.size.to_px(zero) + cvs.padding_right.size.to_px(zero) + borders
+}
+
+/// The minimum and maximum widths of the border box of the cell `cell`: its min-content and
+/// max-content widths, or its fixed `width` if it has one, unless its content needs more.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+fn cell_widths(
+    cell: &BlockLevelBox,
+    collapsed_borders: Option<EdgeSizes>,
+    text_measurer: &dyn TextMeasurer,
+) -> (CSSPixelLength, CSSPixelLength) {
+    let padding_border = cell_padding_border(cell, collapsed_borders);
+    let content_width = |size| {
+        content_intrinsic_width(
+            &cell.computed_values(),
+            &cell.formatting_context(),
+            cell.children(),
+            size,
+            text_measurer,
+        ) + padding_border
+    };
+    let min_content = content_width(IntrinsicSize::MinContent);
+    match fixed_width(cell, padding_border) {
+        Some(width) => {
+            let width = width.max(min_content);
+            (width, width)
+        }
+        None => (min_content, content_width(IntrinsicSize::MaxContent)),
+    }
+}
+
+/// The minimum and maximum widths of each column of a table with the given `structure` and
+/// `children`, given by the widths of its column boxes and of the cells in it.  The widths of cells
+/// spanning several columns are distributed over them once those of the cells spanning only one
+/// are known.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+fn column_widths(
+    structure: &TableStructure,
+    children: &[LayoutBox],
+    collapsed_borders: Option<&CollapsedBorders>,
+    spacing: CSSPixelLength,
+    text_measurer: &dyn TextMeasurer,
+) -> (Vec<CSSPixelLength>, Vec<CSSPixelLength>) {
+    let zero = CSSPixelLength::new(0.);
+    let mut min = vec![zero; structure.column_count];
+    let mut max = vec![zero; structure.column_count];
+    for column_box in &structure.column_boxes {
+        let layout_box = row_box(children, column_box.path);
+        if internal_display_of(layout_box) != Some(InternalDisplay::TableColumn) {
+            continue;
+        }
+        if let Some(width) = fixed_width(layout_box, zero) {
+            for column in column_box.columns.clone() {
+                min[column] = min[column].max(width);
+                max[column] = max[column].max(width);
+            }
+        }
+    }
+    let mut spanning = Vec::new();
+    for cell in &structure.cells {
+        let widths = cell_widths(
+            cell_box(children, structure, cell),
+            collapsed_borders.map(|borders| borders.cell(cell)),
+            text_measurer,
+        );
+        if cell.columns.len() == 1 {
+            let column = cell.columns.start;
+            min[column] = min[column].max(widths.0);
+            max[column] = max[column].max(widths.1);
+        } else {
+            spanning.push((cell.columns.clone(), widths));
+        }
+    }
+    spanning.sort_by_key(|(columns, _)| columns.len());
+    for (columns, (cell_min, cell_max)) in spanning {
+        grow_to(&mut min[columns.clone()], cell_min, spacing);
+        grow_to(&mut max[columns], cell_max, spacing);
+    }
+    for (max, &min) in max.iter_mut().zip(&min) {
+        *max = max.max(min);
+    }
+    (min, max)
+}
+
+/// The widths of the columns of a table under the automatic table layout algorithm, when the
+/// columns have `available` space to fill.  Each column gets its minimum width, and then the rest
+/// of the space goes towards its maximum width in proportion to how much more that is, until every
+/// column has its maximum width.  Any space left after that is distributed in proportion to those.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+fn auto_column_widths(
+    min: &[CSSPixelLength],
+    max: &[CSSPixelLength],
+    available: CSSPixelLength,
+) -> Vec<CSSPixelLength> {
+    let sum = |widths: &[CSSPixelLength]| {
+        widths
+            .iter()
+            .fold(CSSPixelLength::new(0.), |total, &width| total + width)
+    };
+    let (min_sum, max_sum) = (sum(min), sum(max));
+    if available <= min_sum {
+        return min.to_vec();
+    }
+    if available < max_sum {
+        let ratio = (available - min_sum).px() / (max_sum - min_sum).px();
+        return min
+            .iter()
+            .zip(max)
+            .map(|(&min, &max)| min + (max - min) * ratio)
+            .collect();
+    }
+    let mut widths = max.to_vec();
+    grow_to(&mut widths, available, CSSPixelLength::new(0.));
+    widths
+}
+
+/// The widths of the columns of a table under the fixed table layout algorithm, when the columns
+/// have `available` space to fill.  Columns get the widths of their column boxes, or otherwise of
+/// the cells in the first row, and the rest of the space is split equally between the columns
+/// with neither.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#fixed-table-layout
+fn fixed_column_widths(
+    structure: &TableStructure,
+    children: &[LayoutBox],
+    collapsed_borders: Option<&CollapsedBorders>,
+    spacing: CSSPixelLength,
+    available: CSSPixelLength,
+) -> Vec<CSSPixelLength> {
+    let zero = CSSPixelLength::new(0.);
+    let mut widths: Vec<Option<CSSPixelLength>> = vec![None; structure.column_count];
+    for column_box in &structure.column_boxes {
+        let layout_box = row_box(children, column_box.path);
+        if internal_display_of(layout_box) != Some(InternalDisplay::TableColumn) {
+            continue;
+        }
+        if let Some(width) = fixed_width(layout_box, zero) {
+            for column in column_box.columns.clone() {
+                widths[column] = Some(width);
+            }
+        }
+    }
+    for cell in structure.cells.iter().filter(|cell| cell.row == 0) {
+        let cell_box = cell_box(children, structure, cell);
+        let padding_border = cell_padding_border(
+            cell_box,
+            collapsed_borders.map(|borders| borders.cell(cell)),
+        );
+        let width = match fixed_width(cell_box, padding_border) {
+            Some(width) => width,
+            None => continue,
+        };
+        let unset: Vec<usize> = cell
+            .columns
+            .clone()
+            .filter(|&column| widths[column].is_none())
+            .collect();
+        let inner_spacing = spacing * (cell.columns.len() - 1) as CSSFloat;
+        let share =
+            (width - inner_spacing).clamp_to_non_negative() / unset.len().max(1) as CSSFloat;
+        for column in unset {
+            widths[column] = Some(share);
+        }
+    }
+
+    let set_width = widths
+        .iter()
+        .flatten()
+        .fold(zero, |total, &width| total + width);
+    let unset_count = widths.iter().filter(|width| width.is_none()).count();
+    let remaining = (available - set_width).clamp_to_non_negative();
+    let mut widths: Vec<CSSPixelLength> = widths
+        .into_iter()
+        .map(|width| width.unwrap_or_else(|| remaining / unset_count as CSSFloat))
+        .collect();
+    if unset_count == 0 {
+        grow_to(&mut widths, available, zero);
+    }
+    widths
+}
+
+/// The layout-internal display type of the internal table box `layout_box`.
+fn internal_display_of(layout_box: &BlockLevelBox) -> Option<InternalDisplay> {
+    match layout_box.computed_values().display {
+        Display::Internal(internal_display) => Some(internal_display),
+        Display::Full(_) | Display::Box(_) => None,
+    }
+}
+
+/// The origin of a border in the collapsing border model, in order of increasing precedence.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum BorderOrigin {
+    Table,
+    ColumnGroup,
+    Column,
+    RowGroup,
+    Row,
+    Cell,
+}
+
+/// One of the borders that meet at an edge of the grid of a table in the collapsing border model.
+#[derive(Clone, Copy, Debug)]
+struct CandidateBorder {
+    width: CSSPixelLength,
+    style: LineStyle,
+    origin: BorderOrigin,
+}
+
+impl CandidateBorder {
+    /// The border on the `side` of a box with the computed values `cvs`.
+    fn new(cvs: &ComputedValues, side: PhysicalSide, origin: BorderOrigin) -> CandidateBorder {
+        let width = match side {
+            PhysicalSide::Top => cvs.border_top_width.size,
+            PhysicalSide::Right => cvs.border_right_width.size,
+            PhysicalSide::Bottom => cvs.border_bottom_width.size,
+            PhysicalSide::Left => cvs.border_left_width.size,
+        };
+        CandidateBorder {
+            width,
+            style: cvs.border_style(side),
+            origin,
+        }
+    }
+
+    /// The precedence of this border in a conflict, where greater wins: `hidden` borders win and
+    /// `none` borders lose, and otherwise wider borders win, then those of a more eye-catching
+    /// style, and then those of a box with a higher `BorderOrigin`.
+    fn precedence(&self) -> (u8, CSSFloat, u8, BorderOrigin) {
+        let (visibility, style) = match self.style {
+            LineStyle::None => (0, 0),
+            LineStyle::Hidden => (2, 0),
+            LineStyle::Inset => (1, 1),
+            LineStyle::Groove => (1, 2),
+            LineStyle::Outset => (1, 3),
+            LineStyle::Ridge => (1, 4),
+            LineStyle::Dotted => (1, 5),
+            LineStyle::Dashed => (1, 6),
+            LineStyle::Solid => (1, 7),
+            LineStyle::Double => (1, 8),
+        };
+        (visibility, self.width.px(), style, self.origin)
+    }
+}
+
+/// The width of the border that wins the conflict between the `candidates` that meet at an edge.
+/// Of borders with equal precedence, the first wins, so those of boxes further towards the start
+/// of the table must come first.  A `hidden` border wins with a width of zero, as does the lack of
+/// any border.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#border-conflict-resolution
+fn resolve_border_conflict(candidates: Vec<CandidateBorder>) -> CSSPixelLength {
+    candidates
+        .into_iter()
+        .reduce(|winner, candidate| {
+            if candidate.precedence() > winner.precedence() {
+                candidate
+            } else {
+                winner
+            }
+        })
+        .map_or(CSSPixelLength::new(0.), |winner| winner.width)
+}
+
+/// The physical side of the boxes of a table with the given `writing_mode` and `direction` that
+/// the flow-relative `side` maps to.
+///
+/// https://drafts.csswg.org/css-writing-modes-4/#logical-to-physical
+fn physical_side(side: FlowSide, writing_mode: WritingMode, direction: Direction) -> PhysicalSide {
+    let away_from_origin = |progression| progression == OriginRelativeProgression::AwayFromOrigin;
+    let block_away = away_from_origin(
+        OriginRelativeProgression::block_start_origin_relative_direction(writing_mode),
+    );
+    let inline_away = away_from_origin(
+        OriginRelativeProgression::inline_start_origin_relative_direction(writing_mode, direction),
+    );
+    let (start, end, is_start, away) = match side {
+        FlowSide::BlockStart | FlowSide::BlockEnd if writing_mode.is_horizontal() => (
+            PhysicalSide::Top,
+            PhysicalSide::Bottom,
+            side == FlowSide::BlockStart,
+            block_away,
+        ),
+        FlowSide::BlockStart | FlowSide::BlockEnd => (
+            PhysicalSide::Left,
+            PhysicalSide::Right,
+            side == FlowSide::BlockStart,
+            block_away,
+        ),
+        FlowSide::InlineStart | FlowSide::InlineEnd if writing_mode.is_horizontal() => (
+            PhysicalSide::Left,
+            PhysicalSide::Right,
+            side == FlowSide::InlineStart,
+            inline_away,
+        ),
+        FlowSide::InlineStart | FlowSide::InlineEnd => (
+            PhysicalSide::Top,
+            PhysicalSide::Bottom,
+            side == FlowSide::InlineStart,
+            inline_away,
+        ),
+    };
+    if is_start == away {
+        start
+    } else {
+        end
+    }
+}
+
+/// The size of the edge on the given `side` of `edges`.
+fn edge_size(edges: EdgeSizes, side: PhysicalSide) -> CSSPixelLength {
+    match side {
+        PhysicalSide::Top => edges.top,
+        PhysicalSide::Right => edges.right,
+        PhysicalSide::Bottom => edges.bottom,
+        PhysicalSide::Left => edges.left,
+    }
+}
+
+/// The borders of a table in the collapsing border model, where the borders of the table, its
+/// cells, and its rows, columns, and groups of them that meet at each edge of its grid resolve to
+/// the single border that wins the conflict between them.  Each cell has half of the borders
+/// around it inside it.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#collapsing-borders
+#[derive(Clone, Debug)]
+struct CollapsedBorders {
+    /// The physical sides of the table's boxes that its block-start, block-end, inline-start, and
+    /// inline-end sides map to.
+    sides: [PhysicalSide; 4],
+    /// The width of the border along the block-start edge of each slot in each row, followed by
+    /// the block-end edges of the slots in the last row.
+    block_edges: Vec<Vec<CSSPixelLength>>,
+    /// The width of the border along the inline-start edge of each slot in each row, followed by
+    /// the inline-end edge of the last slot in the row.
+    inline_edges: Vec<Vec<CSSPixelLength>>,
+    /// The part of the collapsed borders at the table's edges inside the table: half of the widest
+    /// along its block-start and block-end edges, and half of those at either end of its first
+    /// row.
+    table: EdgeSizes,
+}
+
+impl CollapsedBorders {
+    fn new(
+        table_values: &ComputedValues,
+        children: &[LayoutBox],
+        structure: &TableStructure,
+    ) -> CollapsedBorders {
+        let (writing_mode, direction) = (table_values.writing_mode, table_values.direction);
+        let side = |side| physical_side(side, writing_mode, direction);
+        let sides = [
+            side(FlowSide::BlockStart),
+            side(FlowSide::BlockEnd),
+            side(FlowSide::InlineStart),
+            side(FlowSide::InlineEnd),
+        ];
+        let [block_start, block_end, inline_start, inline_end] = sides;
+        let row_count = structure.rows.len();
+        let column_count = structure.column_count;
+
+        // The cell in each slot of the grid, if any.
+        let mut slots = vec![vec![None; column_count]; row_count];
+        for (index, cell) in structure.cells.iter().enumerate() {
+            for row in cell.rows.clone() {
+                for column in cell.columns.clone() {
+                    slots[row][column] = Some(index);
+                }
+            }
+        }
+        let cell_border = |cell: usize, side| {
+            let cell_box = cell_box(children, structure, &structure.cells[cell]);
+            CandidateBorder::new(&cell_box.computed_values(), side, BorderOrigin::Cell)
+        };
+        let row_border = |row: usize, side| {
+            let row_box = row_box(children, structure.rows[row]);
+            CandidateBorder::new(&row_box.computed_values(), side, BorderOrigin::Row)
+        };
+        // The border of the row group that `row` is the first (or last) row of.
+        let row_group_border = |row: usize, side| {
+            let group = structure
+                .row_groups
+                .iter()
+                .find(|group| group.rows.contains(&row))?;
+            let is_at_edge = if side == block_start {
+                group.rows.start == row
+            } else if side == block_end {
+                group.rows.end == row + 1
+            } else {
+                true
+            };
+            let group_box = table_box(children, group.index.filter(|_| is_at_edge)?);
+            Some(CandidateBorder::new(
+                &group_box.computed_values(),
+                side,
+                BorderOrigin::RowGroup,
+            ))
+        };
+        let column_border = |column_box: &ColumnBox, side| {
+            let layout_box = row_box(children, column_box.path);
+            let origin = match internal_display_of(layout_box) {
+                Some(InternalDisplay::TableColumn) => BorderOrigin::Column,
+                _ => BorderOrigin::ColumnGroup,
+            };
+            CandidateBorder::new(&layout_box.computed_values(), side, origin)
+        };
+        let column_boxes = || {
+            structure
+                .column_boxes
+                .iter()
+                .filter(|column_box| !column_box.columns.is_empty())
+        };
+        let table_border = |side| CandidateBorder::new(table_values, side, BorderOrigin::Table);
+
+        let block_edges: Vec<Vec<CSSPixelLength>> = (0..=row_count)
+            .map(|row| {
+                (0..column_count)
+                    .map(|column| {
+                        let before = row.checked_sub(1).and_then(|row| slots[row][column]);
+                        let after = slots.get(row).and_then(|slots| slots[column]);
+                        if before.is_some() && before == after {
+                            // The edge is inside a cell spanning the rows on either side of it.
+                            return CSSPixelLength::new(0.);
+                        }
+                        let mut candidates = Vec::new();
+                        candidates.extend(before.map(|cell| cell_border(cell, block_end)));
+                        candidates.extend(after.map(|cell| cell_border(cell, block_start)));
+                        if let Some(row) = row.checked_sub(1) {
+                            candidates.push(row_border(row, block_end));
+                            candidates.extend(row_group_border(row, block_end));
+                        }
+                        if row < row_count {
+                            candidates.push(row_border(row, block_start));
+                            candidates.extend(row_group_border(row, block_start));
+                        }
+                        let grid_sides = [(0, block_start), (row_count, block_end)];
+                        for &(_, side) in grid_sides.iter().filter(|&&(edge, _)| edge == row) {
+                            candidates.extend(
+                                column_boxes()
+                                    .filter(|column_box| column_box.columns.contains(&column))
+                                    .map(|column_box| column_border(column_box, side)),
+                            );
+                            candidates.push(table_border(side));
+                        }
+                        resolve_border_conflict(candidates)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let inline_edges: Vec<Vec<CSSPixelLength>> = (0..row_count)
+            .map(|row| {
+                (0..=column_count)
+                    .map(|column| {
+                        let before = column.checked_sub(1).and_then(|column| slots[row][column]);
+                        let after = slots[row].get(column).copied().flatten();
+                        if before.is_some() && before == after {
+                            // The edge is inside a cell spanning the columns on either side of it.
+                            return CSSPixelLength::new(0.);
+                        }
+                        let mut candidates = Vec::new();
+                        candidates.extend(before.map(|cell| cell_border(cell, inline_end)));
+                        candidates.extend(after.map(|cell| cell_border(cell, inline_start)));
+                        let grid_sides = [(0, inline_start), (column_count, inline_end)];
+                        for &(_, side) in grid_sides.iter().filter(|&&(edge, _)| edge == column) {
+                            candidates.push(row_border(row, side));
+                            candidates.extend(row_group_border(row, side));
+                        }
+                        for column_box in column_boxes() {
+                            if column_box.columns.end == column {
+                                candidates.push(column_border(column_box, inline_end));
+                            }
+                        }
+                        for column_box in column_boxes() {
+                            if column_box.columns.start == column {
+                                candidates.push(column_border(column_box, inline_start));
+                            }
+                        }
+                        for &(_, side) in grid_sides.iter().filter(|&&(edge, _)| edge == column) {
+                            candidates.push(table_border(side));
+                        }
+                        resolve_border_conflict(candidates)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Without a grid, the table keeps its own borders.
+        let widest = |edges: &[CSSPixelLength], side| {
+            edges
+                .iter()
+                .copied()
+                .reduce(CSSPixelLength::max)
+                .unwrap_or_else(|| table_border(side).width)
+        };
+        let first_row = inline_edges.first();
+        let table_edges = [
+            widest(&block_edges[0], block_start),
+            widest(&block_edges[row_count], block_end),
+            first_row.map_or(table_border(inline_start).width, |edges| edges[0]),
+            first_row.map_or(table_border(inline_end).width, |edges| edges[column_count]),
+        ];
+        let mut collapsed_borders = CollapsedBorders {
+            sides,
+            block_edges,
+            inline_edges,
+            table: EdgeSizes::default(),
+        };
+        collapsed_borders.table = collapsed_borders.halves(table_edges);
+        collapsed_borders
+    }
+
+    /// The part of the collapsed borders around the cell `cell` inside it: half of the widest
+    /// along each of its sides.
+    fn cell(&self, cell: &Cell) -> EdgeSizes {
+        let widest = |edges: &mut dyn Iterator<Item = CSSPixelLength>| {
+            edges.fold(CSSPixelLength::new(0.), CSSPixelLength::max)
+        };
+        let block_edges = |row: usize| {
+            widest(
+                &mut cell
+                    .columns
+                    .clone()
+                    .map(|column| self.block_edges[row][column]),
+            )
+        };
+        let inline_edges = |column: usize| {
+            widest(&mut cell.rows.clone().map(|row| self.inline_edges[row][column]))
+        };
+        self.halves([
+            block_edges(cell.rows.start),
+            block_edges(cell.rows.end),
+            inline_edges(cell.columns.start),
+            inline_edges(cell.columns.end),
+        ])
+    }
+
+    /// Half of each of the given block-start, block-end, inline-start, and inline-end borders, as
+    /// physical edges.
+    fn halves(&self, flow_relative: [CSSPixelLength; 4]) -> EdgeSizes {
+        let mut edges = EdgeSizes::default();
+        for (&side, &width) in self.sides.iter().zip(&flow_relative) {
+            let edge = match side {
+                PhysicalSide::Top => &mut edges.top,
+                PhysicalSide::Right => &mut edges.right,
+                PhysicalSide::Bottom => &mut edges.bottom,
+                PhysicalSide::Left => &mut edges.left,
+            };
+            *edge = width / 2.;
+        }
+        edges
+    }
+}
+
+/// Sets the edges of a table in the collapsing border model, where it has no padding, and only
+/// its `collapsed_borders` are inside it.  The border box of the table stays where it is, so its
+/// content box grows by what its edges lose.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#collapsing-borders
+fn collapse_table_edges(dimensions: &mut Dimensions, collapsed_borders: EdgeSizes) {
+    for side in [
+        PhysicalSide::Top,
+        PhysicalSide::Right,
+        PhysicalSide::Bottom,
+        PhysicalSide::Left,
+    ] {
+        let border = dimensions.get_border_physical(side);
+        let collapsed_border = edge_size(collapsed_borders, side);
+        let lost = dimensions.get_padding_physical(side) + border - collapsed_border;
+        dimensions.set_border_physical(side, collapsed_border);
+        dimensions.set_padding_physical(side, CSSPixelLength::new(0.));
+        match side {
+            PhysicalSide::Top => dimensions.content.start_y -= lost.px(),
+            PhysicalSide::Left => dimensions.content.start_x -= lost.px(),
+            PhysicalSide::Bottom | PhysicalSide::Right => {}
+        }
+        match side {
+            PhysicalSide::Top | PhysicalSide::Bottom => {
+                let height = dimensions.content.height();
+                dimensions.set_height(height + lost);
+            }
+            PhysicalSide::Left | PhysicalSide::Right => {
+                let width = dimensions.content.width();
+                dimensions.set_width(width + lost);
+            }
+        }
+    }
+}
+
+/// Sets the edges of the cell `cell` for the table it's in: margins don't apply to cells, and in
+/// the collapsing border model, their borders are the `collapsed_borders` inside them.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#collapsing-borders
+fn set_cell_edges(
+    cell: &mut BlockLevelBox,
+    containing_block: ContainingBlock,
+    collapsed_borders: Option<EdgeSizes>,
+) {
+    cell.place_edges_at_origin(containing_block);
+    let dimensions = cell.dimensions_mut();
+    for side in [
+        PhysicalSide::Top,
+        PhysicalSide::Right,
+        PhysicalSide::Bottom,
+        PhysicalSide::Left,
+    ] {
+        dimensions.set_margin_physical(side, CSSPixelLength::new(0.));
+        if let Some(collapsed_borders) = collapsed_borders {
+            dimensions.set_border_physical(side, edge_size(collapsed_borders, side));
+        }
+    }
+}
+
+/// Resets the dimensions of the row, row group, column, or column group `layout_box` to a content
+/// box of the given size at the origin.  Margins, borders, and padding don't apply to them.
+fn size_internal_box(
+    layout_box: &mut BlockLevelBox,
+    inline_size: CSSPixelLength,
+    block_size: CSSPixelLength,
+    writing_mode: WritingMode,
+) {
+    let dimensions = layout_box.dimensions_mut();
+    *dimensions = Dimensions::default();
+    dimensions.set_inline_size(inline_size, writing_mode);
+    dimensions.set_block_size(block_size, writing_mode);
+}
+
+/// Places `layout_box`, whose margin box is at the origin, `inline_offset` and `block_offset`
+/// from the start of `content`.
+fn place_at(
+    layout_box: &mut BlockLevelBox,
+    content: primitives::rect::PositionedRect,
+    inline_offset: CSSPixelLength,
+    block_offset: CSSPixelLength,
+    writing_mode: WritingMode,
+    direction: Direction,
+) {
+    place(
+        layout_box,
+        FlowAxis::Inline,
+        false,
+        content,
+        inline_offset,
+        writing_mode,
+        direction,
+    );
+    place(
+        layout_box,
+        FlowAxis::Block,
+        false,
+        content,
+        block_offset,
+        writing_mode,
+        direction,
+    );
+}
+
+/// Lays out the children of the table `table`: its captions, and the rows, columns, and cells of
+/// its table grid.  The content box inline size of the table must already be known, and grows to
+/// fit its columns if they need more.  Its block size is set to fit its captions and rows.  The
+/// table box doubles as its table wrapper box, so captions are placed inside its content box,
+/// before or after the rows according to their `caption-side`.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#model
+/// https://www.w3.org/TR/CSS22/tables.html#width-layout
+/// https://www.w3.org/TR/CSS22/tables.html#height-layout
+// TODO: Cells are aligned to the top of their rows, since `vertical-align` isn't supported.
+pub(crate) fn layout_table(table: &mut BlockLevelBox, context: &LayoutContext) {
+    let zero = CSSPixelLength::new(0.);
+    let text_measurer = context.text_measurer;
+    let cvs = table.computed_values();
+    let writing_mode = cvs.writing_mode;
+    let direction = cvs.direction;
+    let (inline_spacing, block_spacing) = spacing(&cvs);
+    // The fixed table layout algorithm only applies to tables whose width isn't `auto`.
+    let fixed = cvs.table_layout == TableLayout::Fixed
+        && cvs.inline_size(writing_mode) != LengthPercentageOrAuto::Auto;
+    let structure = TableStructure::new(table.children());
+    let collapsed_borders = match cvs.border_collapse {
+        BorderCollapse::Separate => None,
+        BorderCollapse::Collapse => Some(CollapsedBorders::new(&cvs, table.children(), &structure)),
+    };
+    drop(cvs);
+    if let Some(collapsed_borders) = &collapsed_borders {
+        collapse_table_edges(table.dimensions_mut(), collapsed_borders.table);
+    }
+    let cvs = table.computed_values();
+    let dimensions = table.dimensions();
+    let block_padding_border = dimensions.border_box_block_size(writing_mode)
+        - dimensions.content_box_block_size(writing_mode);
+    let cb_block_size = context.containing_block.block_size(writing_mode);
+    let block_size = definite_size(&cvs.block_size(writing_mode), Some(cb_block_size))
+        .map(|size| content_box_size(size, cvs.box_sizing, block_padding_border));
+    drop(cvs);
+
+    // Size the columns, and widen the table if they don't fit.
+    let mut inline_size = dimensions.get_inline_size(None, writing_mode);
+    let column_count = structure.column_count as CSSFloat;
+    let available = (inline_size - inline_spacing * (column_count + 1.)).clamp_to_non_negative();
+    let columns = if fixed {
+        fixed_column_widths(
+            &structure,
+            table.children(),
+            collapsed_borders.as_ref(),
+            inline_spacing,
+            available,
+        )
+    } else {
+        let (min, max) = column_widths(
+            &structure,
+            table.children(),
+            collapsed_borders.as_ref(),
+            inline_spacing,
+            text_measurer,
+        );
+        auto_column_widths(&min, &max, available)
+    };
+    let grid_inline_size = tracks_size(&columns, inline_spacing);
+    if grid_inline_size > inline_size {
+        inline_size = grid_inline_size;
+        table
+            .dimensions_mut()
+            .set_inline_size(inline_size, writing_mode);
+    }
+    let column_offsets = track_offsets(&columns, inline_spacing);
+
+    // Lay out each cell in the columns it spans to find the height of the rows, which are at
+    // least as tall as their own `height`.
+    let mut table_dimensions = table.dimensions();
+    table_dimensions.set_block_size(zero, writing_mode);
+    let containing_block = ContainingBlock::new(table_dimensions.content, direction, writing_mode);
+    let cell_context = LayoutContext::new(
+        containing_block,
+        context.layout_viewport,
+        context.text_measurer,
+    );
+    let children = table.children_mut();
+    let mut rows: Vec<CSSPixelLength> = structure
+        .rows
+        .iter()
+        .map(|&path| {
+            let row = row_box(children, path);
+            let cvs = row.computed_values();
+            definite_size(&cvs.block_size(writing_mode), None).unwrap_or(zero)
+        })
+        .collect();
+    let mut spanning = Vec::new();
+    for cell in &structure.cells {
+        let (_, area_inline_size) = span_area(
+            &columns,
+            &column_offsets,
+            cell.columns.clone(),
+            inline_spacing,
+        );
+        let cell_box = cell_box_mut(children, &structure, cell);
+        let cell_borders = collapsed_borders
+            .as_ref()
+            .map(|collapsed_borders| collapsed_borders.cell(cell));
+        set_cell_edges(cell_box, containing_block, cell_borders);
+        let cell_dimensions = cell_box.dimensions();
+        let inline_edges = cell_dimensions.border_box_inline_size(writing_mode)
+            - cell_dimensions.get_inline_size(None, writing_mode);
+        let content_inline_size = (area_inline_size - inline_edges).clamp_to_non_negative();
+        layout_item_contents(cell_box, &cell_context, content_inline_size, writing_mode);
+        cell_box.apply_box_sizing_properties(containing_block);
+        cell_box
+            .dimensions_mut()
+            .set_inline_size(content_inline_size, writing_mode);
+        let outer_block_size = cell_box.dimensions().border_box_block_size(writing_mode);
+        if cell.rows.len() == 1 {
+            rows[cell.row] = rows[cell.row].max(outer_block_size);
+        } else {
+            spanning.push((cell.rows.clone(), outer_block_size));
+        }
+    }
+    spanning.sort_by_key(|(span, _)| span.len());
+    for (span, outer_block_size) in spanning {
+        grow_to(&mut rows[span], outer_block_size, block_spacing);
+    }
+
+    // Lay out the captions across the table.
+    let mut captions_before = zero;
+    let mut captions_after = zero;
+    let mut caption_sizes = Vec::with_capacity(structure.captions.len());
+    for &index in &structure.captions {
+        let caption = table_box_mut(children, index);
+        caption.place_edges_at_origin(containing_block);
+        let caption_dimensions = caption.dimensions();
+        let inline_edges = caption_dimensions.margin_box_inline_size(writing_mode)
+            - caption_dimensions.get_inline_size(None, writing_mode);
+        let content_inline_size = (inline_size - inline_edges).clamp_to_non_negative();
+        layout_item_contents(caption, &cell_context, content_inline_size, writing_mode);
+        caption.apply_box_sizing_properties(containing_block);
+        caption
+            .dimensions_mut()
+            .set_inline_size(content_inline_size, writing_mode);
+        let outer_block_size = caption.dimensions().margin_box_block_size(writing_mode);
+        let caption_side = caption.computed_values().caption_side;
+        caption_sizes.push((caption_side, outer_block_size));
+        match caption_side {
+            CaptionSide::Top => captions_before += outer_block_size,
+            CaptionSide::Bottom => captions_after += outer_block_size,
+        }
+    }
+
+    // A table with a definite block size taller than its rows and captions stretches its rows
+    // to fill it.
+    if let Some(block_size) = block_size {
+        grow_to(
+            &mut rows,
+            block_size - captions_before - captions_after - block_spacing * 2.,
+            block_spacing,
+        );
+    }
+    let grid_block_size = tracks_size(&rows, block_spacing);
+    let table_block_size = captions_before + grid_block_size + captions_after;
+    let row_offsets: Vec<CSSPixelLength> = track_offsets(&rows, block_spacing)
+        .into_iter()
+        .map(|offset| offset + captions_before)
+        .collect();
+    let grid_inline_size = tracks_size(&columns, inline_spacing);
+    let rows_inline_size = (grid_inline_size - inline_spacing * 2.).clamp_to_non_negative();
+    let rows_block_size = (grid_block_size - block_spacing * 2.).clamp_to_non_negative();
+
+    table
+        .dimensions_mut()
+        .set_block_size(table_block_size, writing_mode);
+    let content = table.dimensions().content;
+    let children = table.children_mut();
+
+    // Place the captions before and after the rows, in order.
+    let mut before_offset = zero;
+    let mut after_offset = captions_before + grid_block_size;
+    for (&index, &(caption_side, outer_block_size)) in structure.captions.iter().zip(&caption_sizes)
+    {
+        let offset = match caption_side {
+            CaptionSide::Top => &mut before_offset,
+            CaptionSide::Bottom => &mut after_offset,
+        };
+        place_at(
+            table_box_mut(children, index),
+            content,
+            zero,
+            *offset,
+            writing_mode,
+            direction,
+        );
+        *offset += outer_block_size;
+    }
+
+    // Place the row groups and rows across the columns, and the column groups and columns down
+    // the rows.  Groups are placed before the rows and columns in them, which move with them.
+    let grid_inline_start = if columns.is_empty() {
+        zero
+    } else {
+        inline_spacing
+    };
+    let grid_block_start = captions_before + if rows.is_empty() { zero } else { block_spacing };
+    for group in &structure.row_groups {
+        let index = match group.index {
+            Some(index) => index,
+            None => continue,
+        };
+        let (offset, block_size) = if group.rows.is_empty() {
+            (grid_block_start, zero)
+        } else {
+            span_area(&rows, &row_offsets, group.rows.clone(), block_spacing)
+        };
+        let group_box = table_box_mut(children, index);
+        size_internal_box(group_box, rows_inline_size, block_size, writing_mode);
+        place_at(
+            group_box,
+            content,
+            grid_inline_start,
+            offset,
+            writing_mode,
+            direction,
+        );
+    }
+    for (row, &path) in structure.rows.iter().enumerate() {
+        let row_box = row_box_mut(children, path);
+        size_internal_box(row_box, rows_inline_size, rows[row], writing_mode);
+        place_at(
+            row_box,
+            content,
+            grid_inline_start,
+            row_offsets[row],
+            writing_mode,
+            direction,
+        );
+    }
+    for column_box in &structure.column_boxes {
+        let (offset, inline_size) = if column_box.columns.is_empty() {
+            (grid_inline_start, zero)
+        } else {
+            span_area(
+                &columns,
+                &column_offsets,
+                column_box.columns.clone(),
+                inline_spacing,
+            )
+        };
+        let layout_box = row_box_mut(children, column_box.path);
+        size_internal_box(layout_box, inline_size, rows_block_size, writing_mode);
+        place_at(
+            layout_box,
+            content,
+            offset,
+            grid_block_start,
+            writing_mode,
+            direction,
+        );
+    }
+
+    // Stretch each cell to the height of the rows it spans, and place it in them.
+    for cell in &structure.cells {
+        let (inline_offset, _) = span_area(
+            &columns,
+            &column_offsets,
+            cell.columns.clone(),
+            inline_spacing,
+        );
+        let (block_offset, area_block_size) =
+            span_area(&rows, &row_offsets, cell.rows.clone(), block_spacing);
+        let cell_box = cell_box_mut(children, &structure, cell);
+        let cell_dimensions = cell_box.dimensions();
+        let block_edges = cell_dimensions.border_box_block_size(writing_mode)
+            - cell_dimensions.content_box_block_size(writing_mode);
+        cell_box.dimensions_mut().set_block_size(
+            (area_block_size - block_edges).clamp_to_non_negative(),
+            writing_mode,
+        );
+        place_at(
+            cell_box,
+            content,
+            inline_offset,
+            block_offset,
+            writing_mode,
+            direction,
+        );
+    }
+}
+
+/// The inline size of the table `table`, whose own is `auto`, when laid out in `containing_block`:
+/// its max-content width, limited to the space its margins, borders, and padding leave it, but no
+/// narrower than its min-content width.  It's given as a sizing property value, so under the
+/// table's `box-sizing`, and before the table's edges are collapsed.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+// TODO: Like the intrinsic widths it's measured by, this is only correct in horizontal writing
+// modes.
+pub(crate) fn auto_inline_size(
+    table: &BlockLevelBox,
+    containing_block: ContainingBlock,
+    text_measurer: &dyn TextMeasurer,
+) -> LengthPercentageOrAuto {
+    let cvs = table.computed_values();
+    let cb_width = containing_block.width();
+    let padding = cvs.padding_left.size.to_px(cb_width) + cvs.padding_right.size.to_px(cb_width);
+    let borders = cvs.border_left_width.size + cvs.border_right_width.size;
+    let margins = cvs.margin_left.size.to_px(cb_width) + cvs.margin_right.size.to_px(cb_width);
+    // The content box of a table in the collapsing border model takes in its padding and the part
+    // of its borders outside its collapsed borders.
+    let collapsed_edges = match cvs.border_collapse {
+        BorderCollapse::Separate => CSSPixelLength::new(0.),
+        BorderCollapse::Collapse => {
+            let structure = TableStructure::new(table.children());
+            let collapsed = CollapsedBorders::new(&cvs, table.children(), &structure).table;
+            padding + borders - collapsed.left - collapsed.right
+        }
+    };
+    let available = cb_width - margins - padding - borders + collapsed_edges;
+    let width = |size| intrinsic_width(&cvs, table.children(), size, text_measurer);
+    let width = width(IntrinsicSize::MaxContent)
+        .min(available)
+        .max(width(IntrinsicSize::MinContent));
+    let width = (width - collapsed_edges).clamp_to_non_negative();
+    let width = match cvs.box_sizing {
+        BoxSizing::ContentBox => width,
+        BoxSizing::BorderBox => width + padding + borders,
+    };
+    LengthPercentageOrAuto::new_len_px(width)
+}
+
+/// The intrinsic width `size` of a table with the computed values `table_values` and the given
+/// `children`: the minimum or maximum widths of its columns and the spacing around them, or the
+/// width of its widest caption if that's more.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+pub(crate) fn intrinsic_width(
+    table_values: &ComputedValues,
+    children: &[LayoutBox],
+    size: IntrinsicSize,
+    text_measurer: &dyn TextMeasurer,
+) -> CSSPixelLength {
+    let (inline_spacing, _) = spacing(table_values);
+    let structure = TableStructure::new(children);
+    let collapsed_borders = match table_values.border_collapse {
+        BorderCollapse::Separate => None,
+        BorderCollapse::Collapse => Some(CollapsedBorders::new(table_values, children, &structure)),
+    };
+    let (min, max) = column_widths(
+        &structure,
+        children,
+        collapsed_borders.as_ref(),
+        inline_spacing,
+        text_measurer,
+    );
+    let columns = match size {
+        IntrinsicSize::MinContent => min,
+        IntrinsicSize::MaxContent => max,
+    };
+    structure
+        .captions
+        .iter()
+        .map(|&index| {
+            crate::layout_box::intrinsic_contribution(&children[index], size, text_measurer)
+        })
+        .fold(tracks_size(&columns, inline_spacing), CSSPixelLength::max)
+}
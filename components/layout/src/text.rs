@@ -0,0 +1,61 @@
+use primitives::units::{CSSFloat, CSSPixelLength};
+use style::values::computed::font::FontDescription;
+
+/// The vertical metrics of a font at a particular size, which line layout sizes the content areas
+/// of inline boxes and the line boxes containing them with.
+///
+/// https://www.w3.org/TR/CSS22/visudet.html#inline-box-height
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontMetrics {
+    /// The distance from the baseline to the top of the font's content area.
+    pub ascent: CSSPixelLength,
+    /// The distance from the baseline to the bottom of the font's content area, as a positive
+    /// length.
+    pub descent: CSSPixelLength,
+    /// The space the font recommends leaving between lines, which `line-height: normal` adds.
+    pub line_gap: CSSPixelLength,
+}
+
+impl FontMetrics {
+    /// The height of the font's content area.
+    pub fn content_height(&self) -> CSSPixelLength {
+        self.ascent + self.descent
+    }
+}
+
+/// Measures text for layout.  Layout only knows fonts by their computed description, so it's up
+/// to the implementor to load the font faces that text is actually painted with.
+pub trait TextMeasurer {
+    /// The metrics of the font described by `font` at its size.
+    fn font_metrics(&self, font: &FontDescription) -> FontMetrics;
+
+    /// The advance width of `text` set in the font described by `font`, not including any
+    /// `letter-spacing` or `word-spacing`.
+    fn advance_width(&self, text: &str, font: &FontDescription) -> CSSPixelLength;
+}
+
+/// A `TextMeasurer` that approximates the metrics of every font from its size alone, for when no
+/// fonts are available.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApproximateTextMeasurer;
+
+impl ApproximateTextMeasurer {
+    const ASCENT: CSSFloat = 0.8;
+    const DESCENT: CSSFloat = 0.2;
+    const ADVANCE: CSSFloat = 0.5;
+}
+
+impl TextMeasurer for ApproximateTextMeasurer {
+    fn font_metrics(&self, font: &FontDescription) -> FontMetrics {
+        let size = font.size.size;
+        FontMetrics {
+            ascent: size * Self::ASCENT,
+            descent: size * Self::DESCENT,
+            line_gap: CSSPixelLength::new(0.),
+        }
+    }
+
+    fn advance_width(&self, text: &str, font: &FontDescription) -> CSSPixelLength {
+        font.size.size * Self::ADVANCE * text.chars().count() as CSSFloat
+    }
+}
@@ -0,0 +1,291 @@
+//! Custom properties (e.g. `--brand-color: #3355ff`) and the `var()` function, which substitutes
+//! the value of a custom property into the value of another property.
+//!
+//! Values containing `var()` can't be parsed until the custom properties they reference have been
+//! computed, so both custom property values and any other values referencing them are kept as raw
+//! CSS text until computed-value time.
+//!
+//! https://drafts.csswg.org/css-variables/
+
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, ParserInput, SourcePosition, Token};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// The name of a custom property, without its leading `--`.
+pub type Name = String;
+
+/// The computed values of every custom property applying to an element, by name.  Custom
+/// properties that are absent have their initial value, the guaranteed-invalid value.
+///
+/// https://drafts.csswg.org/css-variables/#guaranteed-invalid
+pub type CustomPropertiesMap = HashMap<Name, String>;
+
+/// Parses the name of a custom property (e.g. `--brand-color`), returning it without its leading
+/// `--`.  Returns `None` if `prop_name` isn't a custom property name.
+///
+/// https://drafts.csswg.org/css-variables/#typedef-custom-property-name
+pub fn parse_name(prop_name: &str) -> Option<&str> {
+    if prop_name.len() > 2 && prop_name.starts_with("--") {
+        Some(&prop_name[2..])
+    } else {
+        None
+    }
+}
+
+/// The value of a custom property, or of any other property whose value contains `var()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableValue {
+    /// The CSS text of the value.
+    pub css: String,
+    /// The names of the custom properties referenced by `var()` functions in this value.
+    pub references: HashSet<Name>,
+}
+
+impl VariableValue {
+    /// Whether or not this value contains any `var()` functions.
+    pub fn has_references(&self) -> bool {
+        !self.references.is_empty()
+    }
+}
+
+/// Parses the remainder of `input` as a `<declaration-value>`, collecting any `var()` references
+/// along the way.
+///
+/// https://drafts.csswg.org/css-syntax-3/#typedef-declaration-value
+pub fn parse_declaration_value<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<VariableValue, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let start = input.position();
+    let mut references = HashSet::new();
+    parse_declaration_value_block(input, &mut references)?;
+    Ok(VariableValue {
+        css: input.slice_from(start).trim().to_owned(),
+        references,
+    })
+}
+
+fn parse_declaration_value_block<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    references: &mut HashSet<Name>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    loop {
+        let token = match input.next_including_whitespace_and_comments() {
+            Ok(token) => token.clone(),
+            Err(_) => return Ok(()),
+        };
+        match token {
+            Token::BadUrl(url) => {
+                return Err(
+                    input.new_custom_error(StyleParseErrorKind::BadUrlInDeclarationValueBlock(url))
+                )
+            }
+            Token::BadString(string) => {
+                return Err(input.new_custom_error(
+                    StyleParseErrorKind::BadStringInDeclarationValueBlock(string),
+                ))
+            }
+            Token::CloseParenthesis => {
+                return Err(input.new_custom_error(
+                    StyleParseErrorKind::UnbalancedCloseParenthesisInDeclarationValueBlock,
+                ))
+            }
+            Token::CloseSquareBracket => {
+                return Err(input.new_custom_error(
+                    StyleParseErrorKind::UnbalancedCloseSquareBracketInDeclarationValueBlock,
+                ))
+            }
+            Token::CloseCurlyBracket => {
+                return Err(input.new_custom_error(
+                    StyleParseErrorKind::UnbalancedCloseCurlyBracketInDeclarationValueBlock,
+                ))
+            }
+            Token::Function(ref name) if name.eq_ignore_ascii_case("var") => {
+                input.parse_nested_block(|i| parse_var_function(i, references))?;
+            }
+            Token::Function(_)
+            | Token::ParenthesisBlock
+            | Token::CurlyBracketBlock
+            | Token::SquareBracketBlock => {
+                input.parse_nested_block(|i| parse_declaration_value_block(i, references))?;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parses the arguments of `var( <custom-property-name> [, <declaration-value>? ]? )`.
+///
+/// https://drafts.csswg.org/css-variables/#funcdef-var
+fn parse_var_function<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    references: &mut HashSet<Name>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let name = input.expect_ident_cloned()?;
+    match parse_name(&name) {
+        Some(name) => references.insert(name.to_owned()),
+        None => {
+            return Err(location.new_unexpected_token_error(Token::Ident(name)));
+        }
+    };
+    if input.try_parse(|i| i.expect_comma()).is_ok() {
+        // The fallback may itself reference other custom properties.
+        parse_declaration_value_block(input, references)?;
+    }
+    Ok(())
+}
+
+/// Computes the custom properties of an element from the custom properties declared on it (after
+/// the cascade, so each name maps to the winning declaration) and those it inherits from its parent.
+///
+/// https://drafts.csswg.org/css-variables/#defining-variables
+pub fn compute_custom_properties(
+    declared: &HashMap<&str, &VariableValue>,
+    inherited: &Arc<CustomPropertiesMap>,
+) -> Arc<CustomPropertiesMap> {
+    if declared.is_empty() {
+        return inherited.clone();
+    }
+    let mut resolver = CustomPropertiesResolver {
+        declared,
+        inherited,
+        resolved: HashMap::new(),
+        resolving: Vec::new(),
+    };
+    let mut computed = (**inherited).clone();
+    for name in declared.keys() {
+        match resolver.resolve(name) {
+            Some(value) => computed.insert((*name).to_owned(), value),
+            // Custom properties that are invalid at computed-value time compute to the
+            // guaranteed-invalid value, their initial value.
+            None => computed.remove(*name),
+        };
+    }
+    Arc::new(computed)
+}
+
+struct CustomPropertiesResolver<'a> {
+    declared: &'a HashMap<&'a str, &'a VariableValue>,
+    inherited: &'a CustomPropertiesMap,
+    /// Declared custom properties whose `var()`s have been substituted, or `None` for those that
+    /// are invalid at computed-value time.
+    resolved: HashMap<Name, Option<String>>,
+    /// The custom properties currently being resolved, used to detect reference cycles.
+    resolving: Vec<Name>,
+}
+
+impl CustomPropertiesResolver<'_> {
+    fn resolve(&mut self, name: &str) -> Option<String> {
+        if let Some(resolved) = self.resolved.get(name) {
+            return resolved.clone();
+        }
+        let specified = match self.declared.get(name) {
+            Some(specified) => *specified,
+            None => return self.inherited.get(name).cloned(),
+        };
+        if let Some(cycle_start) = self.resolving.iter().position(|n| n == name) {
+            // Every custom property in a reference cycle is invalid at computed-value time, even
+            // if the `var()` closing the cycle has a fallback.
+            // https://drafts.csswg.org/css-variables/#cycles
+            for in_cycle in &self.resolving[cycle_start..] {
+                self.resolved.insert(in_cycle.clone(), None);
+            }
+            return None;
+        }
+
+        self.resolving.push(name.to_owned());
+        let value = if specified.has_references() {
+            substitute_with(&specified.css, &mut |referenced| self.resolve(referenced))
+        } else {
+            Some(specified.css.clone())
+        };
+        self.resolving.pop();
+        // If this property turned out to be part of a cycle, it has already been marked invalid.
+        self.resolved
+            .entry(name.to_owned())
+            .or_insert(value)
+            .clone()
+    }
+}
+
+/// Substitutes every `var()` in `value` with the computed value of the custom property it
+/// references, returning `None` if the value is invalid at computed-value time (i.e. it references
+/// a custom property with the guaranteed-invalid value, and the `var()` has no fallback).
+///
+/// https://drafts.csswg.org/css-variables/#substitute-a-var
+pub fn substitute_variables(
+    value: &VariableValue,
+    custom_properties: &CustomPropertiesMap,
+) -> Option<String> {
+    substitute_with(&value.css, &mut |name| custom_properties.get(name).cloned())
+}
+
+fn substitute_with(css: &str, lookup: &mut dyn FnMut(&str) -> Option<String>) -> Option<String> {
+    let mut parser_input = ParserInput::new(css);
+    let mut input = Parser::new(&mut parser_input);
+    let mut substituted = String::new();
+    let mut last_copied = input.position();
+    substitute_block(&mut input, &mut substituted, &mut last_copied, lookup).ok()?;
+    substituted.push_str(input.slice_from(last_copied));
+    Some(substituted)
+}
+
+/// Copies the input to `substituted` up to the start of each `var()` in this block, followed by the
+/// value of the `var()`.  `last_copied` is the position in the input up to which the input has
+/// been copied.
+fn substitute_block<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    substituted: &mut String,
+    last_copied: &mut SourcePosition,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<(), ParseError<'i, ()>> {
+    loop {
+        let token_start = input.position();
+        let token = match input.next_including_whitespace_and_comments() {
+            Ok(token) => token.clone(),
+            Err(_) => return Ok(()),
+        };
+        match token {
+            Token::Function(ref name) if name.eq_ignore_ascii_case("var") => {
+                substituted.push_str(input.slice(*last_copied..token_start));
+                let value = input.parse_nested_block(|i| substitute_var_function(i, lookup))?;
+                substituted.push_str(&value);
+                *last_copied = input.position();
+            }
+            Token::Function(_)
+            | Token::ParenthesisBlock
+            | Token::CurlyBracketBlock
+            | Token::SquareBracketBlock => {
+                input.parse_nested_block(|i| {
+                    substitute_block(i, substituted, last_copied, lookup)
+                })?;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn substitute_var_function<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    lookup: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<String, ParseError<'i, ()>> {
+    let name = input.expect_ident_cloned()?;
+    let value = parse_name(&name).and_then(&mut *lookup);
+    let has_fallback = input.try_parse(|i| i.expect_comma()).is_ok();
+    match value {
+        Some(value) => {
+            // Skip past the unused fallback.
+            while input.next_including_whitespace_and_comments().is_ok() {}
+            Ok(value)
+        }
+        None if has_fallback => {
+            let mut fallback = String::new();
+            let mut last_copied = input.position();
+            substitute_block(input, &mut fallback, &mut last_copied, lookup)?;
+            fallback.push_str(input.slice_from(last_copied));
+            Ok(fallback.trim().to_owned())
+        }
+        None => Err(input.new_custom_error(())),
+    }
+}
@@ -0,0 +1,199 @@
+//! Diagnostics about CSS that Kosmonaut ignores, either because it couldn't be parsed or because
+//! Kosmonaut doesn't support it.
+
+use std::fmt;
+
+use cssparser::{
+    BasicParseErrorKind, ParseError, ParseErrorKind, Parser, ParserInput, SourceLocation,
+};
+
+use crate::StyleParseErrorKind;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiagnosticKind {
+    /// A declaration that couldn't be parsed at all, e.g. one missing its colon.
+    DroppedDeclaration,
+    /// A declaration of a property Kosmonaut doesn't know about.
+    UnknownProperty,
+    /// A declaration of a known property whose value is invalid or unsupported.
+    InvalidValue,
+    /// An at-rule Kosmonaut doesn't support.
+    UnsupportedAtRule,
+    /// A rule that couldn't be parsed, e.g. one with an invalid selector.  Only the rule itself is
+    /// dropped.
+    InvalidRule,
+    /// An `@import` rule whose stylesheet couldn't be loaded.
+    FailedImport,
+    /// A stylesheet that couldn't be loaded at all, e.g. because its file couldn't be read.
+    FailedLoad,
+}
+
+impl DiagnosticKind {
+    /// A stable name for this kind of diagnostic, suitable for machine-readable output.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticKind::DroppedDeclaration => "dropped-declaration",
+            DiagnosticKind::UnknownProperty => "unknown-property",
+            DiagnosticKind::InvalidValue => "invalid-value",
+            DiagnosticKind::UnsupportedAtRule => "unsupported-at-rule",
+            DiagnosticKind::InvalidRule => "invalid-rule",
+            DiagnosticKind::FailedImport => "failed-import",
+            DiagnosticKind::FailedLoad => "failed-load",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            DiagnosticKind::DroppedDeclaration => "dropped declaration",
+            DiagnosticKind::UnknownProperty => "unknown property",
+            DiagnosticKind::InvalidValue => "invalid value",
+            DiagnosticKind::UnsupportedAtRule => "unsupported at-rule",
+            DiagnosticKind::InvalidRule => "invalid rule",
+            DiagnosticKind::FailedImport => "failed import",
+            DiagnosticKind::FailedLoad => "failed load",
+        })
+    }
+}
+
+/// A piece of CSS that Kosmonaut ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// The name of the stylesheet the CSS was found in.
+    pub sheet_name: String,
+    /// Where in the stylesheet the CSS was found.  Note that cssparser's line numbers are
+    /// zero-based, while its column numbers are one-based.
+    pub location: SourceLocation,
+    /// The ignored CSS.
+    pub text: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.sheet_name,
+            self.location.line + 1,
+            self.location.column,
+            self.kind,
+            self.text
+        )
+    }
+}
+
+/// Somewhere to send diagnostics to as CSS is parsed.
+pub trait DiagnosticSink {
+    fn report(&mut self, diagnostic: Diagnostic);
+}
+
+impl DiagnosticSink for Vec<Diagnostic> {
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.push(diagnostic);
+    }
+}
+
+/// A sink that drops every diagnostic sent to it.
+pub struct IgnoreDiagnostics;
+
+impl DiagnosticSink for IgnoreDiagnostics {
+    fn report(&mut self, _diagnostic: Diagnostic) {}
+}
+
+/// Reports diagnostics about a single stylesheet to a `DiagnosticSink`.
+pub struct DiagnosticReporter<'a> {
+    sheet_name: &'a str,
+    sink: &'a mut dyn DiagnosticSink,
+}
+
+impl<'a> DiagnosticReporter<'a> {
+    pub fn new(sheet_name: &'a str, sink: &'a mut dyn DiagnosticSink) -> Self {
+        DiagnosticReporter { sheet_name, sink }
+    }
+
+    /// A reporter for the same stylesheet as this one, e.g. for use by a nested parser.
+    pub fn reborrow(&mut self) -> DiagnosticReporter<'_> {
+        DiagnosticReporter {
+            sheet_name: self.sheet_name,
+            sink: &mut *self.sink,
+        }
+    }
+
+    /// A reporter for another stylesheet that sends diagnostics to the same sink as this one,
+    /// e.g. for a stylesheet imported by this one.
+    pub fn for_sheet<'b>(&'b mut self, sheet_name: &'b str) -> DiagnosticReporter<'b> {
+        DiagnosticReporter {
+            sheet_name,
+            sink: &mut *self.sink,
+        }
+    }
+
+    pub fn report(&mut self, kind: DiagnosticKind, location: SourceLocation, text: &str) {
+        self.sink.report(Diagnostic {
+            kind,
+            sheet_name: self.sheet_name.to_owned(),
+            location,
+            text: text.trim().to_owned(),
+        })
+    }
+
+    /// Reports that the stylesheet this reporter is for couldn't be loaded at all, because of the
+    /// error described by `message`.
+    pub fn report_failed_load(&mut self, message: &str) {
+        let start = SourceLocation { line: 0, column: 1 };
+        self.report(DiagnosticKind::FailedLoad, start, message);
+    }
+
+    /// Reports the error that caused the declaration `text`, found at `location`, to be dropped.
+    pub(crate) fn report_declaration_error(
+        &mut self,
+        error: &ParseError<StyleParseErrorKind>,
+        location: SourceLocation,
+        text: &str,
+    ) {
+        let kind = match &error.kind {
+            ParseErrorKind::Custom(StyleParseErrorKind::UnknownProperty(_)) => {
+                DiagnosticKind::UnknownProperty
+            }
+            ParseErrorKind::Basic(BasicParseErrorKind::AtRuleInvalid(_))
+            | ParseErrorKind::Basic(BasicParseErrorKind::AtRuleBodyInvalid) => {
+                DiagnosticKind::UnsupportedAtRule
+            }
+            // Any other error from a declaration that starts with a name and a colon came from
+            // parsing its value.
+            _ if starts_with_name_and_colon(text) => DiagnosticKind::InvalidValue,
+            _ => DiagnosticKind::DroppedDeclaration,
+        };
+        self.report(kind, location, text.trim().trim_end_matches(';'));
+    }
+
+    /// Reports the error that caused the rule `text` to be dropped.
+    pub(crate) fn report_rule_error(
+        &mut self,
+        error: &ParseError<StyleParseErrorKind>,
+        text: &str,
+    ) {
+        let kind = match &error.kind {
+            ParseErrorKind::Custom(StyleParseErrorKind::UnsupportedAtRule(_))
+            | ParseErrorKind::Basic(BasicParseErrorKind::AtRuleInvalid(_))
+            | ParseErrorKind::Basic(BasicParseErrorKind::AtRuleBodyInvalid) => {
+                DiagnosticKind::UnsupportedAtRule
+            }
+            _ => DiagnosticKind::InvalidRule,
+        };
+        self.report(kind, error.location, text);
+    }
+}
+
+fn starts_with_name_and_colon(declaration: &str) -> bool {
+    let mut parser_input = ParserInput::new(declaration);
+    let mut parser = Parser::new(&mut parser_input);
+    parser
+        .try_parse(|input| -> Result<_, ParseError<StyleParseErrorKind>> {
+            input.expect_ident()?;
+            Ok(input.expect_colon()?)
+        })
+        .is_ok()
+}
@@ -1,12 +1,16 @@
 use std::convert::From;
 
 use cssparser::{
-    AtRuleParser, CowRcStr, ParseError, Parser, ParserInput, QualifiedRuleParser, RuleListParser,
-    SourceLocation, Token,
+    AtRuleParser, AtRuleType, CowRcStr, ParseError, Parser, ParserInput, QualifiedRuleParser,
+    RuleListParser, SourceLocation, Token,
 };
 use selectors::parser::SelectorParseErrorKind;
 
+use crate::diagnostics::DiagnosticReporter;
+use crate::media_queries::{MediaList, MediaRule};
 use crate::properties::{parse_property_declaration_list, PropertyDeclarationBlock};
+use crate::stylesheet::{ImportLayer, ImportRule};
+use crate::supports::{SupportsCondition, SupportsRule};
 use kosmonaut_selectors::Selectors;
 
 #[macro_use]
@@ -18,15 +22,23 @@ mod macros;
 #[macro_use]
 extern crate strum_macros;
 
+pub mod custom_properties;
+pub mod diagnostics;
+pub mod media_queries;
 pub mod properties;
 pub mod stylesheet;
+pub mod supports;
 pub mod test_utils;
 pub mod values;
+pub mod viewport;
 
 // TODO: Servo supports many different types of rules, but we won't support those yet.  https://github.com/servo/servo/blob/d2856ce8aeca11e543bc4d9f869400d73451374e/components/style/stylesheets/mod.rs#L236
 #[derive(Clone, Debug)]
 pub enum CssRule {
     Style(StyleRule),
+    Media(MediaRule),
+    Import(ImportRule),
+    Supports(SupportsRule),
     None,
 }
 
@@ -54,6 +66,17 @@ pub enum CssOrigin {
     Sheet(StylesheetOrigin),
 }
 
+impl CssOrigin {
+    /// The cascade origin CSS from this origin belongs to.  Styles from the document itself are
+    /// author styles.
+    pub fn cascade_origin(&self) -> CascadeOrigin {
+        match self {
+            CssOrigin::Inline | CssOrigin::Embedded => CascadeOrigin::Author,
+            CssOrigin::Sheet(sheet_origin) => sheet_origin.cascade_origin,
+        }
+    }
+}
+
 /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#cascading-origins
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum CascadeOrigin {
@@ -62,6 +85,24 @@ pub enum CascadeOrigin {
     UserAgent,
 }
 
+impl CascadeOrigin {
+    /// Whether this origin comes before `other` in the cascade, regardless of importance:
+    /// user-agent styles come before user styles, which come before author styles.  This is the
+    /// order `revert` rolls the cascade back through.
+    ///
+    /// https://drafts.csswg.org/css-cascade-4/#valdef-all-revert
+    pub fn precedes(self, other: CascadeOrigin) -> bool {
+        fn rank(origin: CascadeOrigin) -> u8 {
+            match origin {
+                CascadeOrigin::UserAgent => 0,
+                CascadeOrigin::User => 1,
+                CascadeOrigin::Author => 2,
+            }
+        }
+        rank(self) < rank(other)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct StylesheetOrigin {
     pub sheet_name: String,
@@ -82,36 +123,142 @@ pub struct Color {
     a: u8,
 }
 
-pub fn parse_css_to_rules(
-    css_str: &mut str,
-) -> Result<Vec<CssRule>, (ParseError<StyleParseErrorKind>, &str)> {
+/// Parses `css_str` into its rules.  Rules that are invalid or unsupported are reported to
+/// `reporter` and skipped, and parsing carries on with the rest of the stylesheet.
+///
+/// https://drafts.csswg.org/css-syntax-3/#consume-list-of-rules
+pub fn parse_css_to_rules(css_str: &mut str, reporter: &mut DiagnosticReporter) -> Vec<CssRule> {
     let input = &mut ParserInput::new(css_str);
     let parser = &mut Parser::new(input);
-    let rule_parser = RuleListParser::new_for_stylesheet(parser, TopLevelRuleParser {});
+    let mut rule_parser = RuleListParser::new_for_stylesheet(
+        parser,
+        TopLevelRuleParser {
+            allow_imports: true,
+            reporter: reporter.reborrow(),
+        },
+    );
     let mut rules = Vec::new();
-    for rule in rule_parser {
-        rules.push(rule?)
+    while let Some(rule) = rule_parser.next() {
+        match rule {
+            Ok(rule) => rules.push(rule),
+            Err((err, css)) => {
+                rule_parser.parser.reporter.report_rule_error(&err, css);
+            }
+        }
     }
-    Ok(rules)
+    rules
 }
 
 /// Parser for top-level CSS rules.
-pub struct TopLevelRuleParser {}
+pub struct TopLevelRuleParser<'a> {
+    /// Whether or not an `@import` rule would be valid here.  `@import` rules must come before
+    /// every other rule in a stylesheet (except `@charset`), and can't be nested in other rules.
+    allow_imports: bool,
+    /// Where to report CSS that is dropped while parsing.
+    reporter: DiagnosticReporter<'a>,
+}
 
-// TODO: Support @ rules
-pub enum AtRuleNonBlockPrelude {}
+// TODO: Support more @ rules
+pub enum AtRuleNonBlockPrelude {
+    Import(String, Option<ImportLayer>, MediaList),
+}
 
-pub enum AtRuleBlockPrelude {}
+pub enum AtRuleBlockPrelude {
+    Media(MediaList),
+    Supports(SupportsCondition),
+}
 
-/// Kosmonaut currently does not support @rules, so fall back to the default @rule error impl.
-impl<'i> AtRuleParser<'i> for TopLevelRuleParser {
+impl<'i> AtRuleParser<'i> for TopLevelRuleParser<'_> {
     type PreludeNoBlock = AtRuleNonBlockPrelude;
     type PreludeBlock = AtRuleBlockPrelude;
     type AtRule = CssRule;
     type Error = StyleParseErrorKind<'i>;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<AtRuleType<Self::PreludeNoBlock, Self::PreludeBlock>, ParseError<'i, Self::Error>>
+    {
+        if !name.eq_ignore_ascii_case("import") {
+            self.allow_imports = false;
+        }
+        match_ignore_ascii_case! { &*name,
+            "import" => {
+                if !self.allow_imports {
+                    return Err(input.new_custom_error(StyleParseErrorKind::UnexpectedImportRule));
+                }
+                let url = input.expect_url_or_string()?.as_ref().to_owned();
+                let layer = input.try_parse(ImportLayer::parse).ok();
+                let media_queries = MediaList::parse(input);
+                Ok(AtRuleType::WithoutBlock(AtRuleNonBlockPrelude::Import(url, layer, media_queries)))
+            },
+            "media" => Ok(AtRuleType::WithBlock(AtRuleBlockPrelude::Media(MediaList::parse(input)))),
+            "supports" => Ok(AtRuleType::WithBlock(AtRuleBlockPrelude::Supports(SupportsCondition::parse(input)?))),
+            _ => Err(input.new_custom_error(StyleParseErrorKind::UnsupportedAtRule(name.clone()))),
+        }
+    }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::PreludeNoBlock,
+        source_location: SourceLocation,
+    ) -> CssRule {
+        match prelude {
+            AtRuleNonBlockPrelude::Import(url, layer, media_queries) => {
+                CssRule::Import(ImportRule {
+                    url,
+                    layer,
+                    media_queries,
+                    source_location,
+                })
+            }
+        }
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::PreludeBlock,
+        source_location: SourceLocation,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<CssRule, ParseError<'i, Self::Error>> {
+        match prelude {
+            AtRuleBlockPrelude::Media(media_queries) => Ok(CssRule::Media(MediaRule {
+                media_queries,
+                rules: parse_nested_rules(input, self.reporter.reborrow()),
+                source_location,
+            })),
+            AtRuleBlockPrelude::Supports(condition) => Ok(CssRule::Supports(SupportsRule {
+                enabled: condition.evaluate(),
+                condition,
+                rules: parse_nested_rules(input, self.reporter.reborrow()),
+                source_location,
+            })),
+        }
+    }
 }
 
-impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser {
+/// Parses the rules within the block of a conditional group rule, such as `@media`.  Like at the
+/// top level, rules that are invalid or unsupported are reported and skipped.
+fn parse_nested_rules(input: &mut Parser, reporter: DiagnosticReporter) -> Vec<CssRule> {
+    let mut rules = Vec::new();
+    let nested_rule_parser = TopLevelRuleParser {
+        allow_imports: false,
+        reporter,
+    };
+    let mut rule_parser = RuleListParser::new_for_nested_rule(input, nested_rule_parser);
+    while let Some(rule) = rule_parser.next() {
+        match rule {
+            Ok(rule) => rules.push(rule),
+            Err((err, css)) => {
+                rule_parser.parser.reporter.report_rule_error(&err, css);
+            }
+        }
+    }
+    rules
+}
+
+impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser<'_> {
     type Prelude = Selectors;
     type QualifiedRule = CssRule;
     type Error = StyleParseErrorKind<'i>;
@@ -121,6 +268,7 @@ impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser {
         &mut self,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        self.allow_imports = false;
         Selectors::compile(input).map_err(|err| err.into())
     }
 
@@ -133,7 +281,7 @@ impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser {
     ) -> Result<CssRule, ParseError<'i, Self::Error>> {
         Ok(CssRule::Style(StyleRule {
             selectors,
-            block: parse_property_declaration_list(input),
+            block: parse_property_declaration_list(input, &mut self.reporter),
             source_location,
         }))
     }
@@ -225,6 +373,7 @@ pub enum ValueParseErrorKind<'i> {
 
 /// Value computations common to all CSS properties.
 /// https://www.w3.org/TR/css3-values/#common-keywords
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum CssWideKeywords {
     /// Represents the value specified as the property’s initial value.
     Initial,
@@ -232,4 +381,21 @@ pub enum CssWideKeywords {
     Inherit,
     /// Acts as either inherit or initial, depending on whether the property is inherited or not.
     Unset,
+    /// Rolls back the cascade to the value the property would have had if there were no
+    /// declarations from the current cascade origin.
+    /// https://drafts.csswg.org/css-cascade-4/#default
+    Revert,
+}
+
+impl CssWideKeywords {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "initial" => Ok(CssWideKeywords::Initial),
+            "inherit" => Ok(CssWideKeywords::Inherit),
+            "unset" => Ok(CssWideKeywords::Unset),
+            "revert" => Ok(CssWideKeywords::Revert),
+        }
+    }
 }
@@ -14,3 +14,397 @@ macro_rules! try_match_ident_ignore_ascii_case {
         }
     }}
 }
+
+/// Generates the property plumbing — `LonghandId`, `ShorthandId`, `PropertyDeclaration`,
+/// `ComputedValues`, and the parsing, computing, and defaulting code for each longhand — from the
+/// declarative table in `properties/registry.rs`.  See that file for what each field means.
+macro_rules! define_properties {
+    (
+        longhands {
+            $(
+                $(#[$longhand_meta:meta])*
+                $Longhand:ident($longhand_name:literal) {
+                    field: $field:ident,
+                    specified: $Specified:ty,
+                    computed: $Computed:ty,
+                    inherited: $inherited:literal,
+                    initial: |$initial_ctx:tt| $initial:expr,
+                    $(early: $early:ident,)?
+                    $(group: $group:ident,)?
+                }
+            )+
+        }
+        logical_longhands {
+            $(
+                $(#[$logical_meta:meta])*
+                $Logical:ident($logical_name:literal) {
+                    specified: $LogicalSpecified:ty,
+                    group: $logical_group:ident,
+                    horizontal: $LogicalHorizontal:ident,
+                    vertical: $LogicalVertical:ident,
+                }
+            )*
+        }
+        shorthands {
+            $(
+                $(#[$shorthand_meta:meta])*
+                $Shorthand:ident($shorthand_name:literal) {
+                    longhands: [$($ShorthandLonghand:ident),+ $(,)?],
+                    parse: $parse:ident $(($($parse_arg:expr),*))?,
+                }
+            )+
+        }
+    ) => {
+        /// An identifier for a given longhand property.
+        #[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub enum LonghandId {
+            $(
+                $(#[$longhand_meta])*
+                $Longhand,
+            )+
+            $(
+                $(#[$logical_meta])*
+                $Logical,
+            )*
+        }
+
+        impl LonghandId {
+            pub fn from_name(name: &str) -> Option<LonghandId> {
+                match name {
+                    $($longhand_name => Some(LonghandId::$Longhand),)+
+                    $($logical_name => Some(LonghandId::$Logical),)*
+                    _ => None,
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(LonghandId::$Longhand => $longhand_name,)+
+                    $(LonghandId::$Logical => $logical_name,)*
+                }
+            }
+
+            /// Whether or not this property is inherited, meaning it takes its parent's computed
+            /// value when the cascade doesn't provide one.
+            pub fn inherited(self) -> bool {
+                match self {
+                    $(LonghandId::$Longhand => $inherited,)+
+                    $(LonghandId::$Logical => LonghandId::$LogicalHorizontal.inherited(),)*
+                }
+            }
+
+            /// The group of physical longhands this longhand belongs to, if any.
+            pub fn logical_group(self) -> Option<LogicalGroup> {
+                match self {
+                    $(LonghandId::$Longhand => logical_group!($($group)?),)+
+                    $(LonghandId::$Logical => Some(LogicalGroup::$logical_group),)*
+                }
+            }
+
+            /// Whether this is a logical longhand, which maps onto a physical longhand of its
+            /// group depending on the writing mode.
+            ///
+            /// https://drafts.csswg.org/css-logical/#logical-property
+            pub fn is_logical(self) -> bool {
+                match self {
+                    $(LonghandId::$Logical => true,)*
+                    _ => false,
+                }
+            }
+
+            /// The physical longhand this longhand maps onto in `writing_mode`, which is itself
+            /// unless this is a logical longhand.
+            pub fn to_physical(self, writing_mode: WritingMode) -> LonghandId {
+                match self {
+                    $(
+                        LonghandId::$Logical => if writing_mode.is_horizontal() {
+                            LonghandId::$LogicalHorizontal
+                        } else {
+                            LonghandId::$LogicalVertical
+                        },
+                    )*
+                    physical => physical,
+                }
+            }
+
+            /// Sets this longhand to its value default, for when the cascade doesn't provide a
+            /// value.
+            ///
+            /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#defaulting
+            pub fn value_default(
+                self,
+                cv_builder: &mut ComputedValuesBuilder,
+                ctx: &ComputeContext,
+            ) {
+                if self.inherited() {
+                    self.inherit_value(cv_builder, ctx)
+                } else {
+                    self.initial_value(cv_builder, ctx)
+                }
+            }
+
+            pub fn initial_value(
+                self,
+                cv_builder: &mut ComputedValuesBuilder,
+                ctx: &ComputeContext,
+            ) {
+                let initial_context = InitialValueContext::from(ctx);
+                match self {
+                    $(
+                        LonghandId::$Longhand => {
+                            let $initial_ctx = &initial_context;
+                            cv_builder.$field($initial);
+                        }
+                    )+
+                    $(
+                        LonghandId::$Logical => {
+                            unreachable!("{}", crate::macros::LOGICAL_UNREACHABLE)
+                        }
+                    )*
+                }
+            }
+
+            pub fn inherit_value(
+                self,
+                cv_builder: &mut ComputedValuesBuilder,
+                ctx: &ComputeContext,
+            ) {
+                let parent = ctx.parent_computed_values;
+                match self {
+                    $(
+                        LonghandId::$Longhand => {
+                            cv_builder.$field(parent.$field.clone());
+                        }
+                    )+
+                    $(
+                        LonghandId::$Logical => {
+                            unreachable!("{}", crate::macros::LOGICAL_UNREACHABLE)
+                        }
+                    )*
+                }
+            }
+        }
+
+        /// An identifier for a given shorthand property.
+        #[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
+        pub enum ShorthandId {
+            $(
+                $(#[$shorthand_meta])*
+                $Shorthand,
+            )+
+        }
+
+        impl ShorthandId {
+            pub fn from_name(name: &str) -> Option<ShorthandId> {
+                match name {
+                    $($shorthand_name => Some(ShorthandId::$Shorthand),)+
+                    _ => None,
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(ShorthandId::$Shorthand => $shorthand_name,)+
+                }
+            }
+
+            pub fn longhands(self) -> &'static [LonghandId] {
+                match self {
+                    $(ShorthandId::$Shorthand => &[$(LonghandId::$ShorthandLonghand),+],)+
+                }
+            }
+        }
+
+        #[derive(Clone, Debug)]
+        pub enum PropertyDeclaration {
+            $(
+                $(#[$longhand_meta])*
+                $Longhand($Specified),
+            )+
+            $(
+                $(#[$logical_meta])*
+                $Logical($LogicalSpecified),
+            )*
+            /// A longhand whose value contains `var()`, which is parsed at computed-value time.
+            WithVariables(VariableDeclaration),
+            /// A custom property, e.g. `--brand-color`.
+            Custom(CustomDeclaration),
+            /// A longhand set to a CSS-wide keyword, e.g. `margin-top: inherit`.
+            CssWideKeyword(WideKeywordDeclaration),
+        }
+
+        impl PropertyDeclaration {
+            pub(super) fn parse_into_longhand<'i, 't>(
+                declarations: &mut Vec<PropertyDeclaration>,
+                id: LonghandId,
+                input: &mut Parser<'i, 't>,
+            ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+                match id {
+                    $(
+                        LonghandId::$Longhand => declarations.push(
+                            PropertyDeclaration::$Longhand(
+                                <$Specified as CssValueParse>::parse(input)?,
+                            ),
+                        ),
+                    )+
+                    $(
+                        LonghandId::$Logical => declarations.push(
+                            PropertyDeclaration::$Logical(
+                                <$LogicalSpecified as CssValueParse>::parse(input)?,
+                            ),
+                        ),
+                    )*
+                }
+                Ok(())
+            }
+
+            pub(super) fn parse_into_shorthand<'i, 't>(
+                declarations: &mut Vec<PropertyDeclaration>,
+                id: ShorthandId,
+                input: &mut Parser<'i, 't>,
+            ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+                match id {
+                    $(
+                        ShorthandId::$Shorthand => {
+                            $parse($($($parse_arg,)*)? declarations, input)?
+                        }
+                    )+
+                }
+                Ok(())
+            }
+
+            /// Computes the value of this declaration and sets it on `cv_builder`.
+            pub fn compute_into(
+                &self,
+                cv_builder: &mut ComputedValuesBuilder,
+                context: &ComputeContext,
+            ) {
+                match self {
+                    $(
+                        PropertyDeclaration::$Longhand(specified) => {
+                            cv_builder.$field(computed_value!(specified, context $(, $early)?));
+                        }
+                    )+
+                    $(
+                        PropertyDeclaration::$Logical(_) => {
+                            unreachable!("{}", crate::macros::LOGICAL_UNREACHABLE)
+                        }
+                    )*
+                    PropertyDeclaration::CssWideKeyword(decl) => {
+                        decl.id.apply_css_wide_keyword(decl.keyword, cv_builder, context);
+                    }
+                    PropertyDeclaration::WithVariables(_) | PropertyDeclaration::Custom(_) => {
+                        unreachable!("declarations with variables are substituted before computing")
+                    }
+                }
+            }
+        }
+
+        impl PropertyDeclaration {
+            /// This declaration with any logical longhand it's for mapped onto the physical
+            /// longhand it corresponds to in `writing_mode`.
+            ///
+            /// https://drafts.csswg.org/css-logical/#box
+            pub fn to_physical(&self, writing_mode: WritingMode) -> PropertyDeclaration {
+                match self {
+                    $(
+                        PropertyDeclaration::$Logical(specified) => {
+                            if writing_mode.is_horizontal() {
+                                PropertyDeclaration::$LogicalHorizontal(specified.clone())
+                            } else {
+                                PropertyDeclaration::$LogicalVertical(specified.clone())
+                            }
+                        }
+                    )*
+                    PropertyDeclaration::WithVariables(decl) => {
+                        PropertyDeclaration::WithVariables(VariableDeclaration {
+                            id: decl.id.to_physical(writing_mode),
+                            value: decl.value.clone(),
+                        })
+                    }
+                    PropertyDeclaration::CssWideKeyword(decl) => {
+                        PropertyDeclaration::CssWideKeyword(WideKeywordDeclaration {
+                            id: decl.id.to_physical(writing_mode),
+                            keyword: decl.keyword,
+                        })
+                    }
+                    physical => physical.clone(),
+                }
+            }
+        }
+
+        impl From<&PropertyDeclaration> for LonghandId {
+            fn from(prop_decl: &PropertyDeclaration) -> Self {
+                match prop_decl {
+                    $(PropertyDeclaration::$Longhand(_) => LonghandId::$Longhand,)+
+                    $(PropertyDeclaration::$Logical(_) => LonghandId::$Logical,)*
+                    PropertyDeclaration::WithVariables(decl) => decl.id,
+                    PropertyDeclaration::CssWideKeyword(decl) => decl.id,
+                    PropertyDeclaration::Custom(decl) => panic!(
+                        "custom property declaration `--{}` has no longhand id",
+                        decl.name
+                    ),
+                }
+            }
+        }
+
+        /// A finalized set of computed values.
+        ///
+        /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#computed-value
+        #[derive(Debug, Clone, Builder)]
+        pub struct ComputedValues {
+            $(
+                $(#[$longhand_meta])*
+                pub $field: $Computed,
+            )+
+            /// The computed values of custom properties, which are all inherited.  This is shared
+            /// with the parent's computed values unless this element declares custom properties of
+            /// its own.
+            pub custom_properties: Arc<CustomPropertiesMap>,
+        }
+
+        /// Create a default set of computed values.  Likely most useful for the case in which
+        /// we're working with the root node of a DOM, which has no parent to inherit from.
+        impl Default for ComputedValues {
+            fn default() -> Self {
+                let initial_context = InitialValueContext::initial();
+                ComputedValues {
+                    $(
+                        $field: {
+                            let $initial_ctx = &initial_context;
+                            $initial
+                        },
+                    )+
+                    custom_properties: Arc::new(CustomPropertiesMap::new()),
+                }
+            }
+        }
+    };
+}
+
+/// Logical longhands are mapped onto physical longhands before values are computed, so never have
+/// values of their own.
+pub(crate) const LOGICAL_UNREACHABLE: &str =
+    "logical longhands are mapped to physical longhands before computing";
+
+/// The computed value of a specified value in `define_properties!`.  Early properties have already
+/// been computed into the `ComputeContext`, so are taken from there instead.
+macro_rules! computed_value {
+    ($specified:ident, $context:ident) => {
+        $specified.compute_value_with_context($context)
+    };
+    ($specified:ident, $context:ident, $early:ident) => {{
+        let _ = $specified;
+        $context.$early()
+    }};
+}
+
+/// The `LogicalGroup` of a longhand in `define_properties!`.
+macro_rules! logical_group {
+    () => {
+        None
+    };
+    ($group:ident) => {
+        Some(LogicalGroup::$group)
+    };
+}
@@ -0,0 +1,740 @@
+//! Media queries, which test characteristics of the device a document is displayed on, and the
+//! `@media` rule, which only applies the rules it contains when its media queries match.
+//!
+//! https://drafts.csswg.org/mediaqueries-4/
+
+use cssparser::{CowRcStr, Delimiter, ParseError, Parser, SourceLocation, Token};
+use primitives::units::CSSFloat;
+
+use crate::values::specified::{AbsoluteLength, NoCalcLength, FONT_MEDIUM_PX};
+use crate::viewport::ViewportSize;
+use crate::{CssRule, StyleParseErrorKind};
+
+/// An `@media` rule, e.g. `@media (min-width: 600px) { ... }`.
+///
+/// https://drafts.csswg.org/css-conditional-3/#at-media
+#[derive(Clone, Debug)]
+pub struct MediaRule {
+    /// The media queries that must match for `rules` to apply.
+    pub media_queries: MediaList,
+    /// The rules nested within this rule.
+    pub rules: Vec<CssRule>,
+    /// The location in the sheet where it was found.
+    pub source_location: SourceLocation,
+}
+
+/// The device a document is being styled for, which media queries are evaluated against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Device {
+    /// The size of the viewport in CSS pixels.
+    pub viewport: ViewportSize,
+    /// The number of device pixels per CSS pixel.
+    pub scale_factor: CSSFloat,
+    pub media_type: MediaType,
+    /// The color scheme the user has asked for.
+    pub color_scheme: ColorScheme,
+}
+
+impl Device {
+    /// A screen with the given viewport and scale factor, displaying a light color scheme.
+    pub fn screen(viewport: ViewportSize, scale_factor: CSSFloat) -> Self {
+        Device {
+            viewport,
+            scale_factor,
+            media_type: MediaType::Screen,
+            color_scheme: ColorScheme::Light,
+        }
+    }
+
+    /// This device, but displaying `media_type`, e.g. for styling a document to be printed.
+    pub fn with_media_type(self, media_type: MediaType) -> Self {
+        Device { media_type, ..self }
+    }
+
+    /// This device, but with the user asking for `color_scheme`.
+    pub fn with_color_scheme(self, color_scheme: ColorScheme) -> Self {
+        Device {
+            color_scheme,
+            ..self
+        }
+    }
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#media-types
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaType {
+    Print,
+    Screen,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-color-scheme
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#orientation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+/// A comma-separated list of media queries, which matches if any one of its queries matches.
+///
+/// https://drafts.csswg.org/mediaqueries-4/#typedef-media-query-list
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MediaList {
+    pub queries: Vec<MediaQuery>,
+}
+
+impl MediaList {
+    /// Parses a `<media-query-list>`.  Media queries that fail to parse are replaced with
+    /// `not all`, rather than invalidating the entire list.
+    ///
+    /// https://drafts.csswg.org/mediaqueries-4/#error-handling
+    pub fn parse(input: &mut Parser) -> MediaList {
+        let mut queries = Vec::new();
+        if input.is_exhausted() {
+            return MediaList { queries };
+        }
+        loop {
+            queries.push(
+                input
+                    .parse_until_before(Delimiter::Comma, MediaQuery::parse)
+                    .unwrap_or_else(|_| MediaQuery::never_matching()),
+            );
+            if input.next().is_err() {
+                // The only token that `parse_until_before` can leave is the comma, so the list
+                // must be finished.
+                return MediaList { queries };
+            }
+        }
+    }
+
+    /// Whether or not this list matches `device`.  An empty list matches every device.
+    pub fn evaluate(&self, device: &Device) -> bool {
+        self.queries.is_empty() || self.queries.iter().any(|query| query.evaluate(device))
+    }
+}
+
+/// A single media query, e.g. `screen and (min-width: 600px)`.
+///
+/// https://drafts.csswg.org/mediaqueries-4/#typedef-media-query
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaQuery {
+    pub qualifier: Option<Qualifier>,
+    pub media_type: MediaQueryType,
+    pub condition: Option<MediaCondition>,
+}
+
+/// The `only` and `not` keywords that can precede the media type of a query.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Qualifier {
+    /// Has no effect on the result of the query.  Hides the query from legacy user agents.
+    Only,
+    /// Negates the result of the query.
+    Not,
+}
+
+impl Qualifier {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "only" => Ok(Qualifier::Only),
+            "not" => Ok(Qualifier::Not),
+        }
+    }
+}
+
+/// The media type a query applies to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaQueryType {
+    All,
+    Known(MediaType),
+    /// A media type Kosmonaut doesn't know about (e.g. `tv`), which never matches.
+    Unknown,
+}
+
+impl MediaQuery {
+    /// The `not all` query, which never matches.
+    pub fn never_matching() -> Self {
+        MediaQuery {
+            qualifier: Some(Qualifier::Not),
+            media_type: MediaQueryType::All,
+            condition: None,
+        }
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(condition) = input.try_parse(MediaCondition::parse) {
+            return Ok(MediaQuery {
+                qualifier: None,
+                media_type: MediaQueryType::All,
+                condition: Some(condition),
+            });
+        }
+
+        let qualifier = input.try_parse(Qualifier::parse).ok();
+        let location = input.current_source_location();
+        let media_type = input.expect_ident_cloned()?;
+        let media_type = match_ignore_ascii_case! { &media_type,
+            "all" => MediaQueryType::All,
+            "print" => MediaQueryType::Known(MediaType::Print),
+            "screen" => MediaQueryType::Known(MediaType::Screen),
+            // These keywords can't be used as media types.
+            "only" | "not" | "and" | "or" | "layer" => {
+                return Err(location.new_unexpected_token_error(Token::Ident(media_type.clone())))
+            },
+            _ => MediaQueryType::Unknown,
+        };
+        let condition = if input.try_parse(|i| i.expect_ident_matching("and")).is_ok() {
+            Some(MediaCondition::parse_without_or(input)?)
+        } else {
+            None
+        };
+        Ok(MediaQuery {
+            qualifier,
+            media_type,
+            condition,
+        })
+    }
+
+    pub fn evaluate(&self, device: &Device) -> bool {
+        let media_type_matches = match self.media_type {
+            MediaQueryType::All => true,
+            MediaQueryType::Known(media_type) => media_type == device.media_type,
+            MediaQueryType::Unknown => false,
+        };
+        let matches = media_type_matches
+            && match &self.condition {
+                Some(condition) => condition.evaluate(device),
+                None => true,
+            };
+        match self.qualifier {
+            Some(Qualifier::Not) => !matches,
+            Some(Qualifier::Only) | None => matches,
+        }
+    }
+}
+
+/// A condition made up of media features, combined with `not`, `and`, and `or`.
+///
+/// https://drafts.csswg.org/mediaqueries-4/#typedef-media-condition
+#[derive(Clone, Debug, PartialEq)]
+pub enum MediaCondition {
+    Feature(MediaFeatureExpression),
+    Not(Box<MediaCondition>),
+    And(Vec<MediaCondition>),
+    Or(Vec<MediaCondition>),
+}
+
+impl MediaCondition {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        MediaCondition::parse_with_or(input, true)
+    }
+
+    /// Parses a `<media-condition-without-or>`, which is what may follow the media type of a
+    /// query.
+    pub fn parse_without_or<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        MediaCondition::parse_with_or(input, false)
+    }
+
+    fn parse_with_or<'i, 't>(
+        input: &mut Parser<'i, 't>,
+        allow_or: bool,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("not")).is_ok() {
+            return Ok(MediaCondition::Not(Box::new(
+                MediaCondition::parse_in_parens(input)?,
+            )));
+        }
+        let first = MediaCondition::parse_in_parens(input)?;
+        let is_and = match input.try_parse(|i| -> Result<_, ParseError<StyleParseErrorKind>> {
+            let location = i.current_source_location();
+            let ident = i.expect_ident_cloned()?;
+            match_ignore_ascii_case! { &ident,
+                "and" => Ok(true),
+                "or" if allow_or => Ok(false),
+                _ => Err(location.new_unexpected_token_error(Token::Ident(ident.clone()))),
+            }
+        }) {
+            Ok(is_and) => is_and,
+            Err(_) => return Ok(first),
+        };
+        // `and` and `or` can't be mixed without parentheses.
+        let operator = if is_and { "and" } else { "or" };
+        let mut conditions = vec![first, MediaCondition::parse_in_parens(input)?];
+        while input
+            .try_parse(|i| i.expect_ident_matching(operator))
+            .is_ok()
+        {
+            conditions.push(MediaCondition::parse_in_parens(input)?);
+        }
+        Ok(if is_and {
+            MediaCondition::And(conditions)
+        } else {
+            MediaCondition::Or(conditions)
+        })
+    }
+
+    /// Parses a `<media-in-parens>`, either a nested condition or a single media feature.
+    fn parse_in_parens<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input.expect_parenthesis_block()?;
+        input.parse_nested_block(|i| {
+            if let Ok(condition) = i.try_parse(MediaCondition::parse) {
+                return Ok(condition);
+            }
+            MediaFeatureExpression::parse(i).map(MediaCondition::Feature)
+        })
+    }
+
+    pub fn evaluate(&self, device: &Device) -> bool {
+        match self {
+            MediaCondition::Feature(expression) => expression.evaluate(device),
+            MediaCondition::Not(condition) => !condition.evaluate(device),
+            MediaCondition::And(conditions) => conditions.iter().all(|c| c.evaluate(device)),
+            MediaCondition::Or(conditions) => conditions.iter().any(|c| c.evaluate(device)),
+        }
+    }
+}
+
+/// The media features Kosmonaut supports.
+///
+/// https://drafts.csswg.org/mediaqueries-4/#mq-features
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaFeature {
+    Width,
+    Height,
+    Orientation,
+    Resolution,
+    PrefersColorScheme,
+}
+
+/// The `min-` or `max-` prefix of a media feature name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum RangePrefix {
+    Min,
+    Max,
+}
+
+impl MediaFeature {
+    /// Parses the name of a media feature, along with its `min-` or `max-` prefix, if any.
+    fn from_name(name: &str) -> Option<(MediaFeature, Option<RangePrefix>)> {
+        let lowercase_name = name.to_ascii_lowercase();
+        let (prefix, unprefixed_name) = if let Some(stripped) = lowercase_name.strip_prefix("min-")
+        {
+            (Some(RangePrefix::Min), stripped)
+        } else if let Some(stripped) = lowercase_name.strip_prefix("max-") {
+            (Some(RangePrefix::Max), stripped)
+        } else {
+            (None, lowercase_name.as_str())
+        };
+        let feature = match unprefixed_name {
+            "width" => MediaFeature::Width,
+            "height" => MediaFeature::Height,
+            "orientation" => MediaFeature::Orientation,
+            "resolution" => MediaFeature::Resolution,
+            "prefers-color-scheme" => MediaFeature::PrefersColorScheme,
+            _ => return None,
+        };
+        // Only range features can be prefixed.
+        if prefix.is_some() && !feature.is_range() {
+            return None;
+        }
+        Some((feature, prefix))
+    }
+
+    /// Whether or not this feature can be compared with `<` and `>`, and prefixed with `min-`
+    /// and `max-`.
+    ///
+    /// https://drafts.csswg.org/mediaqueries-4/#mq-range-context
+    fn is_range(self) -> bool {
+        match self {
+            MediaFeature::Width | MediaFeature::Height | MediaFeature::Resolution => true,
+            MediaFeature::Orientation | MediaFeature::PrefersColorScheme => false,
+        }
+    }
+
+    fn parse_value<'i, 't>(
+        self,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<MediaFeatureValue, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let value = match self {
+            MediaFeature::Width | MediaFeature::Height | MediaFeature::Resolution => {
+                MediaFeatureValue::parse_range_value(input)?
+            }
+            MediaFeature::Orientation => {
+                MediaFeatureValue::Orientation(try_match_ident_ignore_ascii_case! { input,
+                    "portrait" => Orientation::Portrait,
+                    "landscape" => Orientation::Landscape,
+                })
+            }
+            MediaFeature::PrefersColorScheme => {
+                MediaFeatureValue::ColorScheme(try_match_ident_ignore_ascii_case! { input,
+                    "light" => ColorScheme::Light,
+                    "dark" => ColorScheme::Dark,
+                })
+            }
+        };
+        self.check_value(value, input)
+    }
+
+    /// Checks that `value` is of the right type for this feature.
+    fn check_value<'i, 't>(
+        self,
+        value: MediaFeatureValue,
+        input: &Parser<'i, 't>,
+    ) -> Result<MediaFeatureValue, ParseError<'i, StyleParseErrorKind<'i>>> {
+        match (self, &value) {
+            (MediaFeature::Width, MediaFeatureValue::Length(_))
+            | (MediaFeature::Height, MediaFeatureValue::Length(_))
+            | (MediaFeature::Resolution, MediaFeatureValue::Resolution(_))
+            | (MediaFeature::Orientation, MediaFeatureValue::Orientation(_))
+            | (MediaFeature::PrefersColorScheme, MediaFeatureValue::ColorScheme(_)) => Ok(value),
+            _ => Err(input.new_custom_error(StyleParseErrorKind::MediaQueryExpectedFeatureValue)),
+        }
+    }
+
+    /// The value of this feature for `device`.
+    fn value_for(self, device: &Device) -> MediaFeatureValue {
+        match self {
+            MediaFeature::Width => MediaFeatureValue::Length(NoCalcLength::Absolute(
+                AbsoluteLength::Px(device.viewport.width.px()),
+            )),
+            MediaFeature::Height => MediaFeatureValue::Length(NoCalcLength::Absolute(
+                AbsoluteLength::Px(device.viewport.height.px()),
+            )),
+            MediaFeature::Orientation => MediaFeatureValue::Orientation(
+                if device.viewport.height.px() >= device.viewport.width.px() {
+                    Orientation::Portrait
+                } else {
+                    Orientation::Landscape
+                },
+            ),
+            MediaFeature::Resolution => MediaFeatureValue::Resolution(device.scale_factor),
+            MediaFeature::PrefersColorScheme => MediaFeatureValue::ColorScheme(device.color_scheme),
+        }
+    }
+}
+
+/// The value of a media feature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MediaFeatureValue {
+    Length(NoCalcLength),
+    /// A `<resolution>`, in dots per CSS pixel (`dppx`).
+    Resolution(CSSFloat),
+    Orientation(Orientation),
+    ColorScheme(ColorScheme),
+}
+
+impl MediaFeatureValue {
+    /// Parses the value of a range feature, either a `<length>` or a `<resolution>`.
+    fn parse_range_value<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let token = input.next()?.clone();
+        match token {
+            Token::Dimension {
+                value, ref unit, ..
+            } => {
+                if let Ok(length) = NoCalcLength::parse_dimension(value, unit) {
+                    return Ok(MediaFeatureValue::Length(length));
+                }
+                // https://drafts.csswg.org/css-values-4/#resolution
+                let dppx = match_ignore_ascii_case! { unit,
+                    "dppx" | "x" => value,
+                    "dpi" => value / 96.,
+                    "dpcm" => value * 2.54 / 96.,
+                    _ => return Err(location.new_unexpected_token_error(token.clone())),
+                };
+                Ok(MediaFeatureValue::Resolution(dppx))
+            }
+            Token::Number { value, .. } => NoCalcLength::parse_unitless_zero(value)
+                .map(MediaFeatureValue::Length)
+                .map_err(|()| location.new_unexpected_token_error(token.clone())),
+            _ => Err(location.new_unexpected_token_error(token.clone())),
+        }
+    }
+
+    /// This value as a number that can be compared to other values of the same range feature.
+    /// Relative lengths are resolved against the initial font size and the viewport.
+    ///
+    /// https://drafts.csswg.org/mediaqueries-4/#units
+    fn to_comparable(self, device: &Device) -> Option<CSSFloat> {
+        match self {
+            MediaFeatureValue::Length(length) => Some(match length {
+                NoCalcLength::Absolute(absolute) => absolute.to_px(),
+                NoCalcLength::FontRelative(font_relative) => {
+                    font_relative.to_px(FONT_MEDIUM_PX as CSSFloat, FONT_MEDIUM_PX as CSSFloat)
+                }
+                NoCalcLength::ViewportPercentage(viewport_percentage) => {
+                    viewport_percentage.to_px(device.viewport)
+                }
+            }),
+            MediaFeatureValue::Resolution(dppx) => Some(dppx),
+            MediaFeatureValue::Orientation(_) | MediaFeatureValue::ColorScheme(_) => None,
+        }
+    }
+}
+
+/// An operator comparing the value of a media feature to a specified value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl Comparison {
+    /// Parses one of `=`, `<`, `<=`, `>`, or `>=`.
+    ///
+    /// https://drafts.csswg.org/mediaqueries-4/#typedef-mf-comparison
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let (less_than, or_equal) = match input.next()? {
+            Token::Delim('=') => return Ok(Comparison::Equal),
+            Token::Delim('<') => (true, Comparison::parse_trailing_equals(input)),
+            Token::Delim('>') => (false, Comparison::parse_trailing_equals(input)),
+            _ => {
+                return Err(
+                    location.new_custom_error(StyleParseErrorKind::MediaQueryUnexpectedOperator)
+                )
+            }
+        };
+        Ok(match (less_than, or_equal) {
+            (true, false) => Comparison::LessThan,
+            (true, true) => Comparison::LessThanOrEqual,
+            (false, false) => Comparison::GreaterThan,
+            (false, true) => Comparison::GreaterThanOrEqual,
+        })
+    }
+
+    /// Whether or not the `<` or `>` just parsed is immediately followed by `=`.
+    fn parse_trailing_equals(input: &mut Parser) -> bool {
+        input
+            .try_parse(|i| match i.next_including_whitespace() {
+                Ok(Token::Delim('=')) => Ok(()),
+                _ => Err(()),
+            })
+            .is_ok()
+    }
+
+    /// The comparison with its operands swapped, e.g. `600px < width` is `width > 600px`.
+    fn flip(self) -> Self {
+        match self {
+            Comparison::Equal => Comparison::Equal,
+            Comparison::LessThan => Comparison::GreaterThan,
+            Comparison::LessThanOrEqual => Comparison::GreaterThanOrEqual,
+            Comparison::GreaterThan => Comparison::LessThan,
+            Comparison::GreaterThanOrEqual => Comparison::LessThanOrEqual,
+        }
+    }
+
+    fn is_less_than(self) -> bool {
+        match self {
+            Comparison::LessThan | Comparison::LessThanOrEqual => true,
+            Comparison::Equal | Comparison::GreaterThan | Comparison::GreaterThanOrEqual => false,
+        }
+    }
+
+    fn is_greater_than(self) -> bool {
+        match self {
+            Comparison::GreaterThan | Comparison::GreaterThanOrEqual => true,
+            Comparison::Equal | Comparison::LessThan | Comparison::LessThanOrEqual => false,
+        }
+    }
+
+    fn evaluate(self, feature_value: CSSFloat, specified_value: CSSFloat) -> bool {
+        match self {
+            Comparison::Equal => feature_value == specified_value,
+            Comparison::LessThan => feature_value < specified_value,
+            Comparison::LessThanOrEqual => feature_value <= specified_value,
+            Comparison::GreaterThan => feature_value > specified_value,
+            Comparison::GreaterThanOrEqual => feature_value >= specified_value,
+        }
+    }
+}
+
+/// A test of a single media feature, e.g. `(orientation: portrait)` or `(400px < width <= 700px)`.
+///
+/// https://drafts.csswg.org/mediaqueries-4/#typedef-media-feature
+#[derive(Clone, Debug, PartialEq)]
+pub enum MediaFeatureExpression {
+    /// A feature evaluated in a boolean context, e.g. `(width)`, which matches if the value of
+    /// the feature isn't zero.
+    Boolean(MediaFeature),
+    /// A feature compared with one or two values, each in the form `<feature> <comparison>
+    /// <value>`.  `(min-width: 600px)`, `(width >= 600px)`, and `(600px <= width)` are all
+    /// represented as `width >= 600px`.
+    Comparisons(MediaFeature, Vec<(Comparison, MediaFeatureValue)>),
+}
+
+impl MediaFeatureExpression {
+    /// Parses the contents of the parentheses around a media feature.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        match input.try_parse(|i| i.expect_ident_cloned()) {
+            Ok(name) => MediaFeatureExpression::parse_after_name(name, input),
+            Err(_) => MediaFeatureExpression::parse_value_first(input),
+        }
+    }
+
+    /// Parses `(<feature>)`, `(<feature>: <value>)`, and `(<feature> <comparison> <value>)`.
+    fn parse_after_name<'i, 't>(
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let (feature, prefix) = MediaFeature::from_name(&name).ok_or_else(|| {
+            input.new_custom_error(StyleParseErrorKind::MediaQueryExpectedFeatureName(
+                name.clone(),
+            ))
+        })?;
+        if input.is_exhausted() {
+            if prefix.is_some() {
+                return Err(
+                    input.new_custom_error(StyleParseErrorKind::RangedExpressionWithNoValue)
+                );
+            }
+            return Ok(MediaFeatureExpression::Boolean(feature));
+        }
+        if input.try_parse(|i| i.expect_colon()).is_ok() {
+            let comparison = match prefix {
+                None => Comparison::Equal,
+                Some(RangePrefix::Min) => Comparison::GreaterThanOrEqual,
+                Some(RangePrefix::Max) => Comparison::LessThanOrEqual,
+            };
+            let value = feature.parse_value(input)?;
+            return Ok(MediaFeatureExpression::Comparisons(
+                feature,
+                vec![(comparison, value)],
+            ));
+        }
+        if prefix.is_some() || !feature.is_range() {
+            return Err(input.new_custom_error(StyleParseErrorKind::MediaQueryUnexpectedOperator));
+        }
+        let comparison = Comparison::parse(input)?;
+        let value = feature.parse_value(input)?;
+        Ok(MediaFeatureExpression::Comparisons(
+            feature,
+            vec![(comparison, value)],
+        ))
+    }
+
+    /// Parses `(<value> <comparison> <feature>)` and
+    /// `(<value> <comparison> <feature> <comparison> <value>)`.
+    fn parse_value_first<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let first_value = MediaFeatureValue::parse_range_value(input)?;
+        let first_comparison = Comparison::parse(input)?.flip();
+        let location = input.current_source_location();
+        let name = input.expect_ident_cloned()?;
+        let feature = match MediaFeature::from_name(&name) {
+            Some((feature, None)) if feature.is_range() => feature,
+            _ => {
+                return Err(location.new_custom_error(
+                    StyleParseErrorKind::MediaQueryExpectedFeatureName(name.clone()),
+                ))
+            }
+        };
+        let mut comparisons = vec![(first_comparison, feature.check_value(first_value, input)?)];
+        if !input.is_exhausted() {
+            let second_comparison = Comparison::parse(input)?;
+            // Both comparisons of a range must point in the same direction, e.g.
+            // `(400px < width < 700px)`.
+            let same_direction = (first_comparison.is_greater_than()
+                && second_comparison.is_less_than())
+                || (first_comparison.is_less_than() && second_comparison.is_greater_than());
+            if !same_direction {
+                return Err(
+                    input.new_custom_error(StyleParseErrorKind::MediaQueryUnexpectedOperator)
+                );
+            }
+            comparisons.push((second_comparison, feature.parse_value(input)?));
+        }
+        Ok(MediaFeatureExpression::Comparisons(feature, comparisons))
+    }
+
+    pub fn evaluate(&self, device: &Device) -> bool {
+        match self {
+            MediaFeatureExpression::Boolean(feature) => {
+                match feature.value_for(device).to_comparable(device) {
+                    Some(value) => value != 0.,
+                    // Discrete features are only false in a boolean context when their value is
+                    // `none`, which none of the supported features can be.
+                    None => true,
+                }
+            }
+            MediaFeatureExpression::Comparisons(feature, comparisons) => {
+                let feature_value = feature.value_for(device);
+                comparisons.iter().all(|(comparison, specified_value)| {
+                    match (
+                        feature_value.to_comparable(device),
+                        specified_value.to_comparable(device),
+                    ) {
+                        (Some(feature_value), Some(specified_value)) => {
+                            comparison.evaluate(feature_value, specified_value)
+                        }
+                        _ => feature_value == *specified_value,
+                    }
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cssparser::ParserInput;
+
+    fn screen() -> Device {
+        Device::screen(ViewportSize::from_px(800., 600.), 1.)
+    }
+
+    fn matches(media_query_list: &str, device: &Device) -> bool {
+        let mut parser_input = ParserInput::new(media_query_list);
+        MediaList::parse(&mut Parser::new(&mut parser_input)).evaluate(device)
+    }
+
+    #[test]
+    fn media_type_queries_match_device_media_type() {
+        let print = screen().with_media_type(MediaType::Print);
+        assert!(matches("print", &print));
+        assert!(matches("only print and (min-width: 100px)", &print));
+        assert!(!matches("screen", &print));
+        assert!(!matches("not print", &print));
+
+        assert!(!matches("print", &screen()));
+        assert!(matches("not print", &screen()));
+        assert!(matches("all", &print));
+    }
+
+    #[test]
+    fn prefers_color_scheme_matches_device_color_scheme() {
+        let dark = screen().with_color_scheme(ColorScheme::Dark);
+        assert!(matches("(prefers-color-scheme: dark)", &dark));
+        assert!(!matches("(prefers-color-scheme: light)", &dark));
+        assert!(matches("print, (prefers-color-scheme: dark)", &dark));
+
+        assert!(!matches("(prefers-color-scheme: dark)", &screen()));
+        assert!(matches("(prefers-color-scheme: light)", &screen()));
+    }
+}
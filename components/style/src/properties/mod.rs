@@ -1,41 +1,48 @@
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::mem;
+use std::sync::Arc;
 
 use cssparser::{
     parse_important, AtRuleParser, CowRcStr, DeclarationListParser, DeclarationParser, Delimiter,
-    ParseError, Parser, SourceLocation,
+    ParseError, Parser, ParserInput, SourceLocation,
 };
 use smallbitvec::SmallBitVec;
 
-use crate::properties::id::{LonghandId, PropertyId, ShorthandId};
-use crate::values::computed::direction::WritingMode;
-use crate::values::computed::{Direction, Display, LineStyle};
-use crate::values::specified::border::{
-    parse_border_color_shorthand_into, parse_border_shorthand_into,
-    parse_border_side_shorthand_into, parse_border_style_shorthand_into,
-    parse_border_width_shorthand_into, BorderBottomWidth, BorderColor, BorderLeftWidth,
-    BorderRightWidth, BorderTopWidth,
+use crate::custom_properties::{self, CustomPropertiesMap, VariableValue};
+use crate::diagnostics::DiagnosticReporter;
+use crate::properties::id::{
+    LogicalGroup, LonghandId, PropertyDeclarationId, PropertyId, ShorthandId,
 };
-use crate::values::specified::margin::parse_margin_shorthand_into;
-use crate::values::specified::padding::parse_padding_shorthand_into;
-use crate::values::specified::{BackgroundColor, Color, FontSize, Height, Margin, Padding, Width};
-use crate::values::CssValueParse;
+use crate::values::computed::WritingMode;
 use crate::CascadeOrigin;
-use crate::{CssOrigin, StyleParseErrorKind};
+use crate::{CssOrigin, CssWideKeywords, StyleParseErrorKind};
 use kosmonaut_selectors::Specificity;
-use primitives::sides::PhysicalSide;
 
 pub mod id;
+pub(crate) mod registry;
+
+pub use registry::PropertyDeclaration;
 
 /// Parses raw parser input into a block of property declarations.
-pub fn parse_property_declaration_list(input: &mut Parser) -> PropertyDeclarationBlock {
+pub fn parse_property_declaration_list(
+    input: &mut Parser,
+    reporter: &mut DiagnosticReporter,
+) -> PropertyDeclarationBlock {
     let mut block = PropertyDeclarationBlock::new();
     let prop_parser = PropertyDeclarationParser {
         declarations: Vec::new(),
     };
     let mut decl_iter = DeclarationListParser::new(input, prop_parser);
-    while let Some(declaration) = decl_iter.next() {
+    loop {
+        // Dropped declarations are reported at the location they start, rather than the location
+        // of the error that caused them to be dropped.
+        decl_iter.input.skip_whitespace();
+        let location = decl_iter.input.current_source_location();
+        let declaration = match decl_iter.next() {
+            Some(declaration) => declaration,
+            None => break,
+        };
         match declaration {
             Ok(importance) => {
                 let decls: Vec<PropertyDeclaration> =
@@ -44,8 +51,11 @@ pub fn parse_property_declaration_list(input: &mut Parser) -> PropertyDeclaratio
                     block.add_declaration(decl.clone(), importance);
                 }
             }
-            Err(parse_err) => {
-                dbg!(parse_err);
+            Err((parse_err, css)) => {
+                // A declaration can fail after its value has been parsed, e.g. because of trailing
+                // junk, so throw away anything it left behind.
+                decl_iter.parser.declarations.clear();
+                reporter.report_declaration_error(&parse_err, location, css);
             }
         }
     }
@@ -118,7 +128,7 @@ impl PropertyDeclarationBlock {
     ) {
         let mut swap_index = None;
         for (i, existing_decl) in self.declarations.iter().enumerate() {
-            if mem::discriminant(existing_decl) == mem::discriminant(&new_decl) {
+            if existing_decl.id() == new_decl.id() {
                 // the props are the same "type", e.g. both `font-size, both `display`, etc
                 // take the `new_decl`, since the latest/newest prop should always be taken
                 swap_index = Some(i);
@@ -161,6 +171,30 @@ impl PropertyDeclaration {
         id: PropertyId,
         input: &mut Parser<'i, 't>,
     ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let PropertyId::Custom(name) = id {
+            declarations.push(PropertyDeclaration::Custom(CustomDeclaration {
+                name,
+                value: Arc::new(custom_properties::parse_declaration_value(input)?),
+            }));
+            return Ok(());
+        }
+        let start = input.state();
+        if let Ok(keyword) = input.try_parse(CssWideKeywords::parse) {
+            if input.expect_exhausted().is_ok() {
+                PropertyDeclaration::push_css_wide_keyword(declarations, id, keyword);
+                return Ok(());
+            }
+            input.reset(&start);
+        }
+        // Values containing `var()` can't be parsed until the referenced custom properties are
+        // computed, so keep them unparsed until then.
+        match custom_properties::parse_declaration_value(input) {
+            Ok(variable_value) if variable_value.has_references() => {
+                PropertyDeclaration::push_with_variables(declarations, id, variable_value);
+                return Ok(());
+            }
+            _ => input.reset(&start),
+        }
         match id {
             PropertyId::Longhand(longhand) => {
                 PropertyDeclaration::parse_into_longhand(declarations, longhand, input)?
@@ -168,168 +202,125 @@ impl PropertyDeclaration {
             PropertyId::Shorthand(shorthand) => {
                 PropertyDeclaration::parse_into_shorthand(declarations, shorthand, input)?
             }
+            PropertyId::Custom(_) => unreachable!("custom properties are parsed above"),
         }
         Ok(())
     }
 
-    #[allow(unreachable_patterns)]
-    fn parse_into_longhand<'i, 't>(
+    /// Pushes a declaration for each longhand set by `id` that will be parsed once the `var()`s
+    /// in `variable_value` have been substituted.
+    fn push_with_variables(
         declarations: &mut Vec<PropertyDeclaration>,
-        id: LonghandId,
-        input: &mut Parser<'i, 't>,
-    ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
-        match id {
-            LonghandId::BackgroundColor => declarations.push(PropertyDeclaration::BackgroundColor(
-                BackgroundColor::parse(input)?,
-            )),
-            LonghandId::BorderBottomColor => declarations.push(
-                PropertyDeclaration::BorderBottomColor(BorderColor::parse(input)?),
-            ),
-            LonghandId::BorderLeftColor => declarations.push(PropertyDeclaration::BorderLeftColor(
-                BorderColor::parse(input)?,
-            )),
-            LonghandId::BorderRightColor => declarations.push(
-                PropertyDeclaration::BorderRightColor(BorderColor::parse(input)?),
-            ),
-            LonghandId::BorderTopColor => declarations.push(PropertyDeclaration::BorderTopColor(
-                BorderColor::parse(input)?,
-            )),
-            LonghandId::BorderBottomStyle => declarations.push(
-                PropertyDeclaration::BorderBottomStyle(LineStyle::parse(input)?),
-            ),
-            LonghandId::BorderLeftStyle => declarations.push(PropertyDeclaration::BorderLeftStyle(
-                LineStyle::parse(input)?,
-            )),
-            LonghandId::BorderRightStyle => declarations.push(
-                PropertyDeclaration::BorderRightStyle(LineStyle::parse(input)?),
-            ),
-            LonghandId::BorderTopStyle => declarations.push(PropertyDeclaration::BorderTopStyle(
-                LineStyle::parse(input)?,
-            )),
-            LonghandId::BorderBottomWidth => declarations.push(
-                PropertyDeclaration::BorderBottomWidth(BorderBottomWidth::parse(input)?),
-            ),
-            LonghandId::BorderLeftWidth => declarations.push(PropertyDeclaration::BorderLeftWidth(
-                BorderLeftWidth::parse(input)?,
-            )),
-            LonghandId::BorderRightWidth => declarations.push(
-                PropertyDeclaration::BorderRightWidth(BorderRightWidth::parse(input)?),
-            ),
-            LonghandId::BorderTopWidth => declarations.push(PropertyDeclaration::BorderTopWidth(
-                BorderTopWidth::parse(input)?,
-            )),
-            LonghandId::Color => {
-                declarations.push(PropertyDeclaration::Color(Color::parse(input)?))
-            }
-            LonghandId::Direction => {
-                declarations.push(PropertyDeclaration::Direction(Direction::parse(input)?))
-            }
-            LonghandId::Display => {
-                declarations.push(PropertyDeclaration::Display(Display::parse(input)?))
-            }
-            LonghandId::FontSize => {
-                declarations.push(PropertyDeclaration::FontSize(FontSize::parse(input)?));
-            }
-            LonghandId::Height => {
-                declarations.push(PropertyDeclaration::Height(Height::parse(input)?));
-            }
-            LonghandId::MarginBottom => {
-                declarations.push(PropertyDeclaration::MarginBottom(Margin::parse(input)?));
-            }
-            LonghandId::MarginLeft => {
-                declarations.push(PropertyDeclaration::MarginLeft(Margin::parse(input)?));
-            }
-            LonghandId::MarginRight => {
-                declarations.push(PropertyDeclaration::MarginRight(Margin::parse(input)?));
-            }
-            LonghandId::MarginTop => {
-                declarations.push(PropertyDeclaration::MarginTop(Margin::parse(input)?));
-            }
-            LonghandId::PaddingBottom => {
-                declarations.push(PropertyDeclaration::PaddingBottom(Padding::parse(input)?));
-            }
-            LonghandId::PaddingLeft => {
-                declarations.push(PropertyDeclaration::PaddingLeft(Padding::parse(input)?));
-            }
-            LonghandId::PaddingRight => {
-                declarations.push(PropertyDeclaration::PaddingRight(Padding::parse(input)?));
-            }
-            LonghandId::PaddingTop => {
-                declarations.push(PropertyDeclaration::PaddingTop(Padding::parse(input)?));
-            }
-            LonghandId::Width => {
-                declarations.push(PropertyDeclaration::Width(Width::parse(input)?));
-            }
-            LonghandId::WritingMode => {
-                declarations.push(PropertyDeclaration::WritingMode(WritingMode::parse(input)?));
-            }
-            _ => unimplemented!("{}", format!("value default by longhand for id: {:?}", id)),
+        id: PropertyId,
+        variable_value: VariableValue,
+    ) {
+        let (longhands, from_shorthand) = match id {
+            PropertyId::Longhand(longhand) => (vec![longhand], None),
+            PropertyId::Shorthand(shorthand) => (shorthand.longhands().to_vec(), Some(shorthand)),
+            PropertyId::Custom(_) => unreachable!("custom properties are never unparsed longhands"),
         };
-        Ok(())
+        let value = Arc::new(UnparsedValue {
+            variable_value,
+            from_shorthand,
+        });
+        for longhand in longhands {
+            declarations.push(PropertyDeclaration::WithVariables(VariableDeclaration {
+                id: longhand,
+                value: value.clone(),
+            }));
+        }
     }
 
-    #[allow(unreachable_patterns)]
-    fn parse_into_shorthand<'i, 't>(
+    /// Pushes a declaration of `keyword` for each longhand set by `id`.  Shorthands set to a
+    /// CSS-wide keyword set all of their longhands to it.
+    fn push_css_wide_keyword(
         declarations: &mut Vec<PropertyDeclaration>,
-        id: ShorthandId,
-        input: &mut Parser<'i, 't>,
-    ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
-        match id {
-            ShorthandId::BorderColor => parse_border_color_shorthand_into(declarations, input)?,
-            ShorthandId::BorderStyle => parse_border_style_shorthand_into(declarations, input)?,
-            ShorthandId::BorderWidth => parse_border_width_shorthand_into(declarations, input)?,
-            ShorthandId::BorderTop => {
-                parse_border_side_shorthand_into(PhysicalSide::Top, declarations, input)?
-            }
-            ShorthandId::BorderRight => {
-                parse_border_side_shorthand_into(PhysicalSide::Right, declarations, input)?
-            }
-            ShorthandId::BorderBottom => {
-                parse_border_side_shorthand_into(PhysicalSide::Bottom, declarations, input)?
-            }
-            ShorthandId::BorderLeft => {
-                parse_border_side_shorthand_into(PhysicalSide::Left, declarations, input)?
-            }
-            ShorthandId::Border => parse_border_shorthand_into(declarations, input)?,
-            ShorthandId::Margin => parse_margin_shorthand_into(declarations, input)?,
-            ShorthandId::Padding => parse_padding_shorthand_into(declarations, input)?,
-            _ => unimplemented!("{}", format!("parse shorthand with id: {:?}", id)),
+        id: PropertyId,
+        keyword: CssWideKeywords,
+    ) {
+        let longhands = match id {
+            PropertyId::Longhand(longhand) => vec![longhand],
+            PropertyId::Shorthand(shorthand) => shorthand.longhands().to_vec(),
+            PropertyId::Custom(_) => unreachable!("custom properties never take CSS-wide keywords"),
         };
-        Ok(())
+        for longhand in longhands {
+            declarations.push(PropertyDeclaration::CssWideKeyword(
+                WideKeywordDeclaration {
+                    id: longhand,
+                    keyword,
+                },
+            ));
+        }
+    }
+
+    /// The property this declaration applies to.
+    pub fn id(&self) -> PropertyDeclarationId<'_> {
+        match self {
+            PropertyDeclaration::Custom(decl) => PropertyDeclarationId::Custom(&decl.name),
+            _ => PropertyDeclarationId::Longhand(LonghandId::from(self)),
+        }
+    }
+}
+
+/// A declaration of a longhand whose value is a CSS-wide keyword.
+///
+/// https://drafts.csswg.org/css-cascade-4/#defaulting-keywords
+#[derive(Clone, Copy, Debug)]
+pub struct WideKeywordDeclaration {
+    pub id: LonghandId,
+    pub keyword: CssWideKeywords,
+}
+
+/// A declaration of a longhand whose value can't be parsed until the `var()`s it contains have
+/// been substituted.
+#[derive(Clone, Debug)]
+pub struct VariableDeclaration {
+    pub id: LonghandId,
+    pub value: Arc<UnparsedValue>,
+}
+
+/// The unparsed value of a declaration containing `var()`.
+#[derive(Clone, Debug)]
+pub struct UnparsedValue {
+    pub variable_value: VariableValue,
+    /// The shorthand this value was declared for, if any.  Once `var()`s are substituted, the
+    /// value must be parsed as this shorthand and the relevant longhand extracted from the result.
+    pub from_shorthand: Option<ShorthandId>,
+}
+
+impl VariableDeclaration {
+    /// Substitutes the `var()`s in this declaration and parses the result, returning `None` if the
+    /// declaration is invalid at computed-value time.
+    ///
+    /// https://drafts.csswg.org/css-variables/#invalid-at-computed-value-time
+    pub fn substitute_variables(
+        &self,
+        custom_properties: &CustomPropertiesMap,
+    ) -> Option<PropertyDeclaration> {
+        let css =
+            custom_properties::substitute_variables(&self.value.variable_value, custom_properties)?;
+        let id = match self.value.from_shorthand {
+            Some(shorthand) => PropertyId::Shorthand(shorthand),
+            None => PropertyId::Longhand(self.id),
+        };
+        let mut parser_input = ParserInput::new(&css);
+        let mut parser = Parser::new(&mut parser_input);
+        let mut declarations = Vec::new();
+        parser
+            .parse_entirely(|input| PropertyDeclaration::parse_into(&mut declarations, id, input))
+            .ok()?;
+        declarations
+            .into_iter()
+            .find(|decl| decl.id() == PropertyDeclarationId::Longhand(self.id))
     }
 }
 
+/// A declaration of a custom property.
 #[derive(Clone, Debug)]
-#[repr(u16)]
-pub enum PropertyDeclaration {
-    BackgroundColor(crate::values::specified::BackgroundColor),
-    BorderBottomColor(crate::values::specified::BorderColor),
-    BorderLeftColor(crate::values::specified::BorderColor),
-    BorderRightColor(crate::values::specified::BorderColor),
-    BorderTopColor(crate::values::specified::BorderColor),
-    BorderBottomStyle(crate::values::computed::LineStyle),
-    BorderLeftStyle(crate::values::computed::LineStyle),
-    BorderRightStyle(crate::values::computed::LineStyle),
-    BorderTopStyle(crate::values::computed::LineStyle),
-    BorderBottomWidth(crate::values::specified::BorderBottomWidth),
-    BorderLeftWidth(crate::values::specified::BorderLeftWidth),
-    BorderRightWidth(crate::values::specified::BorderRightWidth),
-    BorderTopWidth(crate::values::specified::BorderTopWidth),
-    Color(crate::values::specified::Color),
-    Direction(crate::values::computed::Direction),
-    Display(crate::values::computed::Display),
-    FontSize(crate::values::specified::FontSize),
-    Height(crate::values::specified::Height),
-    MarginBottom(crate::values::specified::Margin),
-    MarginLeft(crate::values::specified::Margin),
-    MarginRight(crate::values::specified::Margin),
-    MarginTop(crate::values::specified::Margin),
-    PaddingBottom(crate::values::specified::Padding),
-    PaddingLeft(crate::values::specified::Padding),
-    PaddingRight(crate::values::specified::Padding),
-    PaddingTop(crate::values::specified::Padding),
-    Width(crate::values::specified::Width),
-    WritingMode(crate::values::computed::WritingMode),
+pub struct CustomDeclaration {
+    /// The name of the custom property, without its leading `--`.
+    pub name: custom_properties::Name,
+    pub value: Arc<VariableValue>,
 }
 
 pub struct ComputedPropertyDeclarations {}
@@ -358,6 +349,12 @@ pub struct ContextualPropertyDeclarations {
     /// will be found later in the `Vec`.
     /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#cascading
     is_sorted: bool,
+    /// Whether or not any of the declarations are longhands containing `var()`.
+    has_variables: bool,
+    /// Whether or not any of the declarations are longhands set to `revert`.
+    has_revert: bool,
+    /// Whether or not any of the declarations are for logical longhands.
+    has_logical: bool,
 }
 
 impl ContextualPropertyDeclarations {
@@ -372,9 +369,15 @@ impl ContextualPropertyDeclarations {
     /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#cascading
     #[inline]
     pub fn cascade_sort(&mut self) {
-        // ContextualPropertyDeclarations override `Ord`, so this will sort by origin, importance,
-        // specificity.
-        self.decls.as_mut_slice().sort();
+        // Group declarations by property, or by logical group for properties that are part of
+        // one, then sort each group by origin, importance, and specificity.  Logical and physical
+        // properties of a group share one order, so whichever of them sets a physical longhand
+        // last wins once logical properties are mapped onto physical ones.
+        self.decls.as_mut_slice().sort_by(|a, b| {
+            cascade_group(a)
+                .cmp(&cascade_group(b))
+                .then_with(|| a.cmp_precedence(b))
+        });
         // After sorting by origin, importance, and specificity, rules must also be sorted by order
         // of appearance.  However, we don't need to do anything to uphold that variant, since later
         // rules are naturally pushed to the end of the Vec.
@@ -395,17 +398,146 @@ impl ContextualPropertyDeclarations {
             self.decls
                 .iter()
                 .rev()
-                .find(|decl| LonghandId::from(*decl).eq(&longhand))
+                .find(|decl| decl.inner_decl.id() == PropertyDeclarationId::Longhand(longhand))
         }
     }
 
     #[inline]
     pub fn add(&mut self, new_decl: ContextualPropertyDeclaration) {
-        self.longhands
-            .insert(LonghandId::from(&new_decl.inner_decl));
+        match new_decl.inner_decl.id() {
+            PropertyDeclarationId::Longhand(longhand) => {
+                self.longhands.insert(longhand);
+                self.has_logical |= longhand.is_logical();
+            }
+            PropertyDeclarationId::Custom(_) => {}
+        }
+        match new_decl.inner_decl {
+            PropertyDeclaration::WithVariables(_) => self.has_variables = true,
+            PropertyDeclaration::CssWideKeyword(WideKeywordDeclaration {
+                keyword: CssWideKeywords::Revert,
+                ..
+            }) => self.has_revert = true,
+            _ => {}
+        }
         self.decls.push(new_decl);
         self.is_sorted = false;
     }
+
+    /// Whether or not any of these declarations are longhands containing `var()`.
+    #[inline]
+    pub fn has_variables(&self) -> bool {
+        self.has_variables
+    }
+
+    /// Whether or not the winning declarations must be resolved by `resolve_winners` before
+    /// values can be computed from them.
+    #[inline]
+    pub fn needs_resolution(&self) -> bool {
+        self.has_variables || self.has_revert
+    }
+
+    /// Whether or not any of these declarations are for logical longhands, which must be mapped
+    /// onto physical longhands by `to_physical` before values can be computed from them.
+    #[inline]
+    pub fn has_logical(&self) -> bool {
+        self.has_logical
+    }
+
+    /// These declarations with those for logical longhands mapped onto the physical longhands
+    /// they correspond to in `writing_mode`, keeping their cascade order.
+    ///
+    /// https://drafts.csswg.org/css-logical/#box
+    pub fn to_physical(&self, writing_mode: WritingMode) -> ContextualPropertyDeclarations {
+        let mut physical = ContextualPropertyDeclarations::new();
+        for decl in &self.decls {
+            physical.add(ContextualPropertyDeclaration {
+                inner_decl: decl.inner_decl.to_physical(writing_mode),
+                ..decl.clone()
+            });
+        }
+        physical.is_sorted = self.is_sorted;
+        physical
+    }
+
+    /// The value of each declared custom property that wins the cascade, by name.
+    pub fn custom_properties(&self) -> HashMap<&str, &VariableValue> {
+        let mut custom_properties = HashMap::new();
+        // Declarations that win the cascade come last, so they'll overwrite the losers here.
+        for decl in &self.decls {
+            if let PropertyDeclaration::Custom(custom_decl) = &decl.inner_decl {
+                custom_properties.insert(custom_decl.name.as_str(), &*custom_decl.value);
+            }
+        }
+        custom_properties
+    }
+
+    /// Returns the declarations that win the cascade for each longhand, with `revert` rolled back
+    /// to the previous cascade origin and the `var()`s in their values substituted.  Longhands that
+    /// are invalid at computed-value time are left out, which leaves them to be defaulted as if
+    /// they were `unset`.
+    ///
+    /// https://drafts.csswg.org/css-cascade-4/#valdef-all-revert
+    /// https://drafts.csswg.org/css-variables/#invalid-at-computed-value-time
+    pub fn resolve_winners(
+        &self,
+        custom_properties: &CustomPropertiesMap,
+    ) -> ContextualPropertyDeclarations {
+        let mut substituted = ContextualPropertyDeclarations::new();
+        let mut winners = self
+            .longhands
+            .iter()
+            .map(|longhand| self.reverted_winner(*longhand))
+            .collect::<Vec<_>>();
+        // Keep the winners in cascade order, which decides between logical and physical longhands
+        // of the same group.
+        winners.sort_by_key(|(index, _)| *index);
+        for (_, winner) in winners {
+            match &winner.inner_decl {
+                PropertyDeclaration::WithVariables(decl) => {
+                    if let Some(inner_decl) = decl.substitute_variables(custom_properties) {
+                        substituted.add(ContextualPropertyDeclaration {
+                            inner_decl,
+                            ..winner.clone()
+                        });
+                    }
+                }
+                _ => substituted.add(winner.clone()),
+            }
+        }
+        substituted.is_sorted = self.is_sorted;
+        substituted
+    }
+
+    /// The declaration that wins the cascade for `longhand` once any `revert`s are rolled back.
+    /// A `revert` rolls back to the winning declaration among those from origins before its own,
+    /// whatever their importance, so a user `!important` `revert` skips any author declarations.
+    /// A `revert` with no declaration from an earlier origin to roll back to is left in place, and
+    /// acts like `unset`.
+    /// Returned alongside the declaration is its index in the cascade order.
+    fn reverted_winner(&self, longhand: LonghandId) -> (usize, &ContextualPropertyDeclaration) {
+        let mut candidates =
+            self.decls.iter().enumerate().rev().filter(|(_, decl)| {
+                decl.inner_decl.id() == PropertyDeclarationId::Longhand(longhand)
+            });
+        let mut winner = candidates
+            .next()
+            .expect("longhand should have at least one declaration");
+        while let PropertyDeclaration::CssWideKeyword(WideKeywordDeclaration {
+            keyword: CssWideKeywords::Revert,
+            ..
+        }) = winner.1.inner_decl
+        {
+            let reverted_origin = winner.1.origin.cascade_origin();
+            match candidates
+                .by_ref()
+                .find(|(_, decl)| decl.origin.cascade_origin().precedes(reverted_origin))
+            {
+                Some(candidate) => winner = candidate,
+                None => break,
+            }
+        }
+        winner
+    }
 }
 
 impl Default for ContextualPropertyDeclarations {
@@ -414,10 +546,32 @@ impl Default for ContextualPropertyDeclarations {
             decls: Vec::default(),
             longhands: HashSet::default(),
             is_sorted: true,
+            has_variables: false,
+            has_revert: false,
+            has_logical: false,
         }
     }
 }
 
+/// The declarations `cascade_sort` orders against each other: those for the same property, or for
+/// properties in the same logical group.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum CascadeGroup<'a> {
+    Logical(LogicalGroup),
+    Property(PropertyDeclarationId<'a>),
+}
+
+fn cascade_group(decl: &ContextualPropertyDeclaration) -> CascadeGroup<'_> {
+    let id = decl.inner_decl.id();
+    match id {
+        PropertyDeclarationId::Longhand(longhand) => match longhand.logical_group() {
+            Some(group) => CascadeGroup::Logical(group),
+            None => CascadeGroup::Property(id),
+        },
+        PropertyDeclarationId::Custom(_) => CascadeGroup::Property(id),
+    }
+}
+
 /// Much of Kosmonaut's cascade algorithm is in this implementation — namely, the first two top-level
 /// bullet points.  The final deciding factor in the cascade, order of appearance, can't possibly
 /// be exercised here.
@@ -445,28 +599,54 @@ impl Default for ContextualPropertyDeclarations {
 ///         * Declarations from style attributes are ordered according to the document order of the element the style attribute appears on, and are all placed after any style sheets.
 impl Ord for ContextualPropertyDeclaration {
     fn cmp(&self, other: &Self) -> Ordering {
-        if mem::discriminant(&self.inner_decl) == mem::discriminant(&other.inner_decl) {
-            if self.important && !other.important {
-                return Ordering::Greater;
-            } else if !self.important && other.important {
-                return Ordering::Less;
-            } else if self.important && other.important {
-                match cmp_important_origins(&self.origin, &other.origin) {
-                    Ordering::Greater => return Ordering::Greater,
-                    Ordering::Less => return Ordering::Less,
-                    Ordering::Equal => return self.specificity.cmp(&other.specificity),
-                }
-            } else if !self.important && !other.important {
-                // When both declarations are not important, run the same routine as the one to
-                // compare important declarations but flip the result.
-                return match cmp_important_origins(&self.origin, &other.origin) {
-                    Ordering::Less => Ordering::Greater,
-                    Ordering::Greater => Ordering::Less,
-                    Ordering::Equal => return self.specificity.cmp(&other.specificity),
-                };
+        if self.inner_decl.id() == other.inner_decl.id() {
+            self.cmp_precedence(other)
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+impl ContextualPropertyDeclaration {
+    /// Compares the origin, importance, and specificity of two declarations without regard to the
+    /// property they're for.  Declarations of different properties in the same logical group must
+    /// be ordered against each other too, since they set the same physical longhands.
+    ///
+    /// https://drafts.csswg.org/css-logical/#logical-property-group
+    fn cmp_precedence(&self, other: &Self) -> Ordering {
+        if self.important && !other.important {
+            return Ordering::Greater;
+        } else if !self.important && other.important {
+            return Ordering::Less;
+        } else if self.important && other.important {
+            return match cmp_important_origins(&self.origin, &other.origin) {
+                Ordering::Equal => cmp_style_attr_then_specificity(self, other),
+                ordering => ordering,
+            };
+        }
+        // When both declarations are not important, run the same routine as the one to compare
+        // important declarations but flip the result.
+        return match cmp_important_origins(&self.origin, &other.origin) {
+            Ordering::Less => Ordering::Greater,
+            Ordering::Greater => Ordering::Less,
+            Ordering::Equal => cmp_style_attr_then_specificity(self, other),
+        };
+
+        /// Declarations from style attributes behave as if they had a specificity higher than any
+        /// selector, so they win over every other declaration of the same origin and importance.
+        ///
+        /// https://www.w3.org/TR/css-style-attr/#interpret
+        fn cmp_style_attr_then_specificity(
+            a: &ContextualPropertyDeclaration,
+            b: &ContextualPropertyDeclaration,
+        ) -> Ordering {
+            match (&a.origin, &b.origin) {
+                (CssOrigin::Inline, CssOrigin::Inline) => a.specificity.cmp(&b.specificity),
+                (CssOrigin::Inline, _) => Ordering::Greater,
+                (_, CssOrigin::Inline) => Ordering::Less,
+                _ => a.specificity.cmp(&b.specificity),
             }
         }
-        return Ordering::Equal;
 
         fn cmp_important_origins(a: &CssOrigin, b: &CssOrigin) -> Ordering {
             match (a, b) {
@@ -519,8 +699,7 @@ impl Eq for ContextualPropertyDeclaration {}
 
 impl PartialEq for ContextualPropertyDeclaration {
     fn eq(&self, other: &Self) -> bool {
-        mem::discriminant(&self.inner_decl) == mem::discriminant(&other.inner_decl)
-            && self.origin == other.origin
+        self.inner_decl.id() == other.inner_decl.id() && self.origin == other.origin
     }
 }
 
@@ -552,9 +731,29 @@ mod tests {
     use crate::test_utils::{display_by_type, font_size_px, font_size_px_or_panic};
 
     use super::*;
+    use crate::diagnostics::IgnoreDiagnostics;
+    use crate::values::computed::display::{InnerDisplay, InternalDisplay, OuterDisplay};
+    use crate::values::computed::font::{FamilyName, GenericFontFamily};
+    use crate::values::computed::grid::{
+        GridAutoFlow, NamedArea, RepeatCount, TemplateAreas, TrackList, MAX_GRID_LINE,
+    };
+    use crate::values::computed::length::{self, LengthPercentageOrAuto};
+    use crate::values::computed::text::TextTransformCase;
     use crate::values::computed::Display;
-    use crate::values::specified::{AbsoluteLength, LengthPercentage, NoCalcLength};
+    use crate::values::computed::{
+        compute_anonymous_values, compute_values, BorderCollapse, BorderSpacing, BoxSizing,
+        BreakInside, CaptionSide, Clear, ColumnCount, ColumnSpan, ColumnWidth, ComputedValues,
+        ContentAlignment, FlexBasis, FlexDirection, FlexFactor, FlexWrap, Float, FontFamily,
+        FontStretch, FontStyle, FontWeight, GridLine, GridTemplate, GridTemplateAreas, LineHeight,
+        LineStyle, MaxSize, MinSize, Order, Percentage, Position, Spacing, TableLayout, TextAlign,
+        TextTransform, TrackBreadth, TrackSize, ZIndex,
+    };
+    use crate::values::specified::{AbsoluteLength, FontSize, LengthPercentage, NoCalcLength};
+    use crate::values::used::ToPx;
+    use crate::viewport::ViewportSize;
     use crate::StylesheetOrigin;
+    use cssparser::RGBA;
+    use primitives::units::CSSPixelLength;
     use std::clone::Clone;
 
     #[test]
@@ -678,6 +877,44 @@ mod tests {
         assert_eq!(author_decl.cmp(&author_decl.clone()), Ordering::Equal);
     }
 
+    #[test]
+    fn inline_decl_preferred_over_more_specific_author_decls() {
+        let inline_decl = ContextualPropertyDeclaration {
+            inner_decl: PropertyDeclaration::FontSize(FontSize::Length(LengthPercentage::Length(
+                NoCalcLength::Absolute(AbsoluteLength::Px(12.0)),
+            ))),
+            important: false,
+            origin: CssOrigin::Inline,
+            source_location: None,
+            specificity: Specificity::new(0),
+        };
+        let mut author_decl = inline_decl.clone();
+        author_decl.origin = CssOrigin::Sheet(StylesheetOrigin {
+            sheet_name: "author_sheet.css".to_owned(),
+            cascade_origin: CascadeOrigin::Author,
+        });
+        author_decl.specificity = Specificity::new(2049);
+        let mut embedded_decl = author_decl.clone();
+        embedded_decl.origin = CssOrigin::Embedded;
+
+        assert!(inline_decl > author_decl);
+        assert!(inline_decl > embedded_decl);
+
+        let mut imp_inline_decl = inline_decl.clone();
+        imp_inline_decl.important = true;
+        let mut imp_author_decl = author_decl.clone();
+        imp_author_decl.important = true;
+        assert!(imp_inline_decl > imp_author_decl);
+
+        // Important user declarations still beat important inline declarations.
+        let mut imp_user_decl = imp_author_decl.clone();
+        imp_user_decl.origin = CssOrigin::Sheet(StylesheetOrigin {
+            sheet_name: "user_sheet.css".to_owned(),
+            cascade_origin: CascadeOrigin::User,
+        });
+        assert!(imp_user_decl > imp_inline_decl);
+    }
+
     #[test]
     fn author_sheet_rule_preferred_over_user_agent_sheet_rule_both_unimportant() {
         let ua_decl = ContextualPropertyDeclaration {
@@ -712,6 +949,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn revert_rolls_back_to_earlier_origins_only() {
+        let sheet_origin = |cascade_origin| {
+            CssOrigin::Sheet(StylesheetOrigin {
+                sheet_name: "file.css".to_owned(),
+                cascade_origin,
+            })
+        };
+        let font_size = |px| {
+            PropertyDeclaration::FontSize(FontSize::Length(LengthPercentage::Length(
+                NoCalcLength::Absolute(AbsoluteLength::Px(px)),
+            )))
+        };
+        let ua_decl = ContextualPropertyDeclaration {
+            inner_decl: font_size(12.0),
+            important: false,
+            origin: sheet_origin(CascadeOrigin::UserAgent),
+            source_location: None,
+            specificity: Specificity::new(0),
+        };
+        let author_decl = ContextualPropertyDeclaration {
+            inner_decl: font_size(16.0),
+            important: true,
+            origin: sheet_origin(CascadeOrigin::Author),
+            ..ua_decl.clone()
+        };
+        let user_revert_decl = ContextualPropertyDeclaration {
+            inner_decl: PropertyDeclaration::CssWideKeyword(WideKeywordDeclaration {
+                id: LonghandId::FontSize,
+                keyword: CssWideKeywords::Revert,
+            }),
+            important: true,
+            origin: sheet_origin(CascadeOrigin::User),
+            ..ua_decl.clone()
+        };
+        let mut decls = ContextualPropertyDeclarations::new();
+        decls.add(ua_decl);
+        decls.add(author_decl);
+        decls.add(user_revert_decl);
+        decls.cascade_sort();
+
+        // Important user declarations beat important author ones, and the user `revert` rolls back
+        // past the author origin to the user-agent one.
+        let winners = decls.resolve_winners(&CustomPropertiesMap::new());
+        let winner = winners
+            .get_by_longhand(LonghandId::FontSize)
+            .expect("font-size should have a winner");
+        assert_eq!(winner.origin.cascade_origin(), CascadeOrigin::UserAgent);
+        assert_eq!(&12.0, font_size_px_or_panic(&winner.inner_decl));
+    }
+
     #[test]
     fn decl_sort_order_of_appearance() {
         let mut decls = ContextualPropertyDeclarations::new();
@@ -787,4 +1075,623 @@ mod tests {
         assert_eq!(decl_block.declarations.len(), 1);
         assert_eq!(&24.0, font_size_px_or_panic(&decl_block.declarations[0]));
     }
+
+    #[test]
+    fn property_names_round_trip() {
+        use strum::IntoEnumIterator;
+        for longhand in LonghandId::iter() {
+            assert_eq!(
+                PropertyId::parse(longhand.name()),
+                Some(PropertyId::Longhand(longhand))
+            );
+        }
+        for shorthand in ShorthandId::iter() {
+            assert_eq!(
+                PropertyId::parse(shorthand.name()),
+                Some(PropertyId::Shorthand(shorthand))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_background_shorthand() {
+        let mut declarations = Vec::new();
+        let mut parser_input = ParserInput::new("red");
+        let mut parser = Parser::new(&mut parser_input);
+        PropertyDeclaration::parse_into(
+            &mut declarations,
+            PropertyId::Shorthand(ShorthandId::Background),
+            &mut parser,
+        )
+        .unwrap();
+        let ids: Vec<_> = declarations.iter().map(LonghandId::from).collect();
+        assert_eq!(ids, ShorthandId::Background.longhands());
+    }
+
+    #[test]
+    fn declarations_with_trailing_junk_are_dropped() {
+        let mut parser_input = ParserInput::new("width: 10px junk; color: blue !important");
+        let mut parser = Parser::new(&mut parser_input);
+        let mut diagnostics = Vec::new();
+        let block = parse_property_declaration_list(
+            &mut parser,
+            &mut DiagnosticReporter::new("", &mut diagnostics),
+        );
+        assert_eq!(block.declarations().len(), 1);
+        assert!(matches!(
+            block.declarations()[0],
+            PropertyDeclaration::Color(_)
+        ));
+        assert_eq!(block.declarations_importance().get(0), Some(true));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].text, "width: 10px junk");
+    }
+
+    /// Computes the values of an element with the style attribute `css`, whose parent has the
+    /// computed values `parent`.
+    fn compute_style_attribute(css: &str, parent: &ComputedValues) -> ComputedValues {
+        let mut parser_input = ParserInput::new(css);
+        let mut parser = Parser::new(&mut parser_input);
+        let mut sink = IgnoreDiagnostics;
+        let block = parse_property_declaration_list(
+            &mut parser,
+            &mut DiagnosticReporter::new("", &mut sink),
+        );
+        let mut decls = ContextualPropertyDeclarations::new();
+        for decl in block.declarations() {
+            decls.add(ContextualPropertyDeclaration {
+                inner_decl: decl.clone(),
+                important: false,
+                origin: CssOrigin::Inline,
+                source_location: None,
+                specificity: Specificity::new(0),
+            });
+        }
+        decls.cascade_sort();
+        compute_values(&decls, parent, None, ViewportSize::from_px(800., 600.))
+    }
+
+    #[test]
+    fn font_shorthand_sets_every_font_longhand() {
+        let computed = compute_style_attribute(
+            r#"font-weight: 900; line-height: 3; font: italic bold 12px/1.5 "Helvetica Neue", Arial Black, sans-serif"#,
+            &ComputedValues::default(),
+        );
+        assert_eq!(computed.font_style, FontStyle::Italic);
+        assert_eq!(computed.font_weight, FontWeight::bold());
+        assert_eq!(computed.font_stretch, FontStretch::initial_value());
+        assert_eq!(computed.font_size.size.px(), 12.);
+        assert_eq!(computed.line_height, LineHeight::Number(1.5));
+        assert_eq!(
+            computed.font_family,
+            FontFamily(vec![
+                FamilyName::Specific("Helvetica Neue".to_owned()),
+                FamilyName::Specific("Arial Black".to_owned()),
+                FamilyName::Generic(GenericFontFamily::SansSerif),
+            ])
+        );
+
+        // Longhands left out of the shorthand are reset to their initial values.
+        let computed = compute_style_attribute(
+            "font-weight: 900; line-height: 3; font: 12px serif",
+            &ComputedValues::default(),
+        );
+        assert_eq!(computed.font_weight, FontWeight::initial_value());
+        assert_eq!(computed.line_height, LineHeight::initial_value());
+    }
+
+    #[test]
+    fn relative_font_weights_depend_on_parent_weight() {
+        let parent = compute_style_attribute("font-weight: 300", &ComputedValues::default());
+        let bolder = compute_style_attribute("font-weight: bolder", &parent);
+        assert_eq!(bolder.font_weight, FontWeight::normal());
+        assert_eq!(
+            compute_style_attribute("font-weight: bolder", &bolder).font_weight,
+            FontWeight::bold()
+        );
+        assert_eq!(
+            compute_style_attribute("font-weight: lighter", &parent).font_weight,
+            FontWeight(100.)
+        );
+        // Weights are inherited as numbers.
+        assert_eq!(
+            compute_style_attribute("", &bolder).font_weight,
+            FontWeight::normal()
+        );
+    }
+
+    #[test]
+    fn line_height_percentages_resolve_against_own_font_size() {
+        let parent = compute_style_attribute("line-height: 150%", &ComputedValues::default());
+        assert_eq!(
+            parent.line_height,
+            LineHeight::Length(CSSPixelLength::new(24.))
+        );
+        // The resolved length is inherited, rather than the percentage...
+        let child = compute_style_attribute("font-size: 10px", &parent);
+        assert_eq!(
+            child.line_height,
+            LineHeight::Length(CSSPixelLength::new(24.))
+        );
+        // ...while numbers are inherited as-is.
+        let parent = compute_style_attribute("line-height: 1.5", &ComputedValues::default());
+        let child = compute_style_attribute("font-size: 10px", &parent);
+        assert_eq!(child.line_height, LineHeight::Number(1.5));
+    }
+
+    #[test]
+    fn text_align_match_parent_resolves_against_parent_direction() {
+        let parent = compute_style_attribute(
+            "direction: rtl; text-align: start",
+            &ComputedValues::default(),
+        );
+        let child = compute_style_attribute("direction: ltr; text-align: match-parent", &parent);
+        assert_eq!(child.text_align, TextAlign::Right);
+        // `text-align` is inherited as the keyword, so `start` follows the child's direction.
+        let child = compute_style_attribute("direction: ltr", &parent);
+        assert_eq!(child.text_align, TextAlign::Start);
+    }
+
+    #[test]
+    fn parses_text_transform() {
+        let transform = compute_style_attribute(
+            "text-transform: full-width uppercase",
+            &ComputedValues::default(),
+        )
+        .text_transform;
+        assert_eq!(transform.case, TextTransformCase::Uppercase);
+        assert!(transform.full_width);
+        assert_eq!(transform.transform("a b"), "\u{FF21}\u{3000}\u{FF22}");
+        // A case mapping may only be given once, so this is dropped.
+        let transform = compute_style_attribute(
+            "text-transform: lowercase; text-transform: uppercase capitalize",
+            &ComputedValues::default(),
+        )
+        .text_transform;
+        assert_eq!(transform.case, TextTransformCase::Lowercase);
+        assert_eq!(
+            TextTransform {
+                case: TextTransformCase::Capitalize,
+                full_width: false,
+            }
+            .transform("don't stop-believing"),
+            "Don't Stop-Believing"
+        );
+    }
+
+    #[test]
+    fn spacing_resolves_against_own_font_size() {
+        let parent = compute_style_attribute(
+            "font-size: 20px; letter-spacing: 0.1em; word-spacing: 50%",
+            &ComputedValues::default(),
+        );
+        assert_eq!(parent.letter_spacing, Spacing(CSSPixelLength::new(2.)));
+        assert_eq!(parent.word_spacing, Spacing(CSSPixelLength::new(10.)));
+        // The resolved lengths are inherited.
+        let child = compute_style_attribute("font-size: 10px; letter-spacing: normal", &parent);
+        assert_eq!(child.letter_spacing, Spacing::normal());
+        assert_eq!(child.word_spacing, Spacing(CSSPixelLength::new(10.)));
+    }
+
+    #[test]
+    fn parses_sizing_properties() {
+        let computed = compute_style_attribute(
+            "box-sizing: border-box; min-height: 10px; max-height: 20px; max-width: none",
+            &ComputedValues::default(),
+        );
+        assert_eq!(computed.box_sizing, BoxSizing::BorderBox);
+        assert_eq!(
+            computed.min_height,
+            MinSize {
+                size: LengthPercentageOrAuto::new_len(10.)
+            }
+        );
+        assert_eq!(computed.min_width, MinSize::initial_value());
+        assert_eq!(
+            computed.max_height.to_px(CSSPixelLength::new(0.)),
+            Some(CSSPixelLength::new(20.))
+        );
+        assert_eq!(computed.max_width, MaxSize::None);
+    }
+
+    #[test]
+    fn negative_math_function_results_are_clamped_to_property_range() {
+        let computed = compute_style_attribute(
+            "width: calc(10px - 50px); height: max(-5px, -1em); padding-left: calc(10% - 50px); \
+             min-width: calc(50% - 1000px); margin-left: calc(10px - 50px)",
+            &ComputedValues::default(),
+        );
+        assert_eq!(computed.width.size, LengthPercentageOrAuto::new_len(0.));
+        assert_eq!(computed.height.size, LengthPercentageOrAuto::new_len(0.));
+        // Math functions with percentages are clamped once the percentages are resolved.
+        let padding = &computed.padding_left.size;
+        assert_eq!(padding.to_px(CSSPixelLength::new(100.)).px(), 0.);
+        assert_eq!(padding.to_px(CSSPixelLength::new(1000.)).px(), 50.);
+        let min_width = &computed.min_width.size;
+        assert_eq!(min_width.to_px(CSSPixelLength::new(1000.)).px(), 0.);
+        // Margins can be negative.
+        assert_eq!(
+            computed.margin_left.size,
+            LengthPercentageOrAuto::new_len(-40.)
+        );
+    }
+
+    #[test]
+    fn negative_literals_are_rejected_by_non_negative_properties() {
+        // The negative declarations are invalid, so the earlier declarations apply instead.
+        let computed = compute_style_attribute(
+            "width: 20px; width: -10px; height: 10%; height: -10%; padding-left: 5px; \
+             padding-left: -5px; padding: 1px -2px; min-width: 3px; min-width: -3px; \
+             max-height: -1px",
+            &ComputedValues::default(),
+        );
+        assert_eq!(computed.width.size, LengthPercentageOrAuto::new_len(20.));
+        assert_eq!(
+            computed.height.size,
+            LengthPercentageOrAuto::LengthPercentage(length::LengthPercentage::Percentage(
+                Percentage(0.1)
+            ))
+        );
+        assert_eq!(
+            computed.padding_left.size,
+            length::LengthPercentage::new_len(5.)
+        );
+        assert_eq!(
+            computed.padding_top.size,
+            length::LengthPercentage::new_len(0.)
+        );
+        assert_eq!(computed.min_width.size, LengthPercentageOrAuto::new_len(3.));
+        assert_eq!(computed.max_height, MaxSize::None);
+    }
+
+    #[test]
+    fn logical_and_physical_longhands_cascade_by_order_of_appearance() {
+        let computed = compute_style_attribute(
+            "min-inline-size: 10px; min-width: 20px; max-height: 5px; max-block-size: 30px",
+            &ComputedValues::default(),
+        );
+        assert_eq!(
+            computed.min_width.size,
+            LengthPercentageOrAuto::new_len(20.)
+        );
+        assert_eq!(
+            computed.max_height.to_px(CSSPixelLength::new(0.)),
+            Some(CSSPixelLength::new(30.))
+        );
+
+        // In vertical writing modes, the inline axis is vertical.
+        let computed = compute_style_attribute(
+            "min-height: 20px; min-inline-size: 10px; writing-mode: vertical-rl",
+            &ComputedValues::default(),
+        );
+        assert_eq!(
+            computed.min_height.size,
+            LengthPercentageOrAuto::new_len(10.)
+        );
+        assert_eq!(computed.min_width, MinSize::initial_value());
+
+        // `writing-mode` is inherited, and so decides how an element's logical longhands map even
+        // when it isn't declared on the element itself.
+        let child = compute_style_attribute("min-block-size: inherit", &computed);
+        assert_eq!(child.min_width.size, LengthPercentageOrAuto::Auto);
+        assert_eq!(child.min_height.size, LengthPercentageOrAuto::Auto);
+        let child = compute_style_attribute("max-block-size: 40px", &computed);
+        assert_eq!(
+            child.max_width.to_px(CSSPixelLength::new(0.)),
+            Some(CSSPixelLength::new(40.))
+        );
+    }
+
+    #[test]
+    fn parses_positioning_properties() {
+        let computed = compute_style_attribute(
+            "position: absolute; inset: 10px 20px; bottom: auto; z-index: -3",
+            &ComputedValues::default(),
+        );
+        assert_eq!(computed.position, Position::Absolute);
+        assert_eq!(computed.top.size, LengthPercentageOrAuto::new_len(10.));
+        assert_eq!(computed.right.size, LengthPercentageOrAuto::new_len(20.));
+        assert_eq!(computed.bottom.size, LengthPercentageOrAuto::Auto);
+        assert_eq!(computed.left.size, LengthPercentageOrAuto::new_len(20.));
+        assert_eq!(computed.z_index, ZIndex::Integer(-3));
+
+        // None of these properties are inherited.
+        let child = compute_style_attribute("", &computed);
+        assert_eq!(child.position, Position::Static);
+        assert_eq!(child.top.size, LengthPercentageOrAuto::Auto);
+        assert_eq!(child.z_index, ZIndex::Auto);
+
+        // A non-integer z-index is invalid.
+        let computed = compute_style_attribute("z-index: 1.5", &ComputedValues::default());
+        assert_eq!(computed.z_index, ZIndex::Auto);
+    }
+
+    #[test]
+    fn parses_float_and_clear() {
+        let computed =
+            compute_style_attribute("float: inline-end; clear: both", &ComputedValues::default());
+        assert_eq!(computed.float, Float::InlineEnd);
+        assert_eq!(computed.clear, Clear::Both);
+
+        let child = compute_style_attribute("float: center; clear: start", &computed);
+        assert_eq!(child.float, Float::None);
+        assert_eq!(child.clear, Clear::None);
+    }
+
+    #[test]
+    fn parses_flex_properties() {
+        let container = compute_style_attribute(
+            "display: flex; flex-flow: column wrap; gap: 5px; justify-content: space-between",
+            &ComputedValues::default(),
+        );
+        assert_eq!(
+            container.display,
+            Display::new_full_display(OuterDisplay::Block, InnerDisplay::Flex)
+        );
+        assert_eq!(container.flex_direction, FlexDirection::Column);
+        assert_eq!(container.flex_wrap, FlexWrap::Wrap);
+        assert_eq!(container.row_gap, container.column_gap);
+        assert_eq!(container.justify_content, ContentAlignment::SpaceBetween);
+
+        // Flex items are blockified, and `flex: <number>` has a zero basis.
+        let item = compute_style_attribute("display: inline; flex: 2; order: -1", &container);
+        assert_eq!(item.display, Display::new_block());
+        assert_eq!(item.flex_grow, FlexFactor::new(2.));
+        assert_eq!(item.flex_shrink, FlexFactor::new(1.));
+        assert_eq!(
+            item.flex_basis,
+            FlexBasis::Width(LengthPercentageOrAuto::new_len(0.))
+        );
+        assert_eq!(item.order, Order::new(-1));
+
+        let item = compute_style_attribute("flex: none; flex-grow: -1", &container);
+        assert_eq!(item.flex_grow, FlexFactor::new(0.));
+        assert_eq!(item.flex_shrink, FlexFactor::new(0.));
+        assert_eq!(
+            item.flex_basis,
+            FlexBasis::Width(LengthPercentageOrAuto::Auto)
+        );
+    }
+
+    #[test]
+    fn clamps_overlarge_grids() {
+        let container = compute_style_attribute(
+            "display: grid; grid-template-columns: repeat(100000000, 10px)",
+            &ComputedValues::default(),
+        );
+        match &container.grid_template_columns {
+            GridTemplate::TrackList(list) => {
+                assert_eq!(list.tracks.len(), MAX_GRID_LINE as usize);
+                assert_eq!(list.line_names.len(), MAX_GRID_LINE as usize + 1);
+            }
+            GridTemplate::None => panic!("expected a track list"),
+        }
+        let item = compute_style_attribute(
+            "grid-row: 100000000 / -2147483648; grid-column: span 3000000000",
+            &container,
+        );
+        assert_eq!(
+            item.grid_row_start,
+            GridLine::Line {
+                index: MAX_GRID_LINE,
+                name: None
+            }
+        );
+        assert_eq!(
+            item.grid_row_end,
+            GridLine::Line {
+                index: -MAX_GRID_LINE,
+                name: None
+            }
+        );
+        assert_eq!(
+            item.grid_column_start,
+            GridLine::Span {
+                count: MAX_GRID_LINE as u32,
+                name: None
+            }
+        );
+    }
+
+    #[test]
+    fn parses_grid_properties() {
+        let px = |px| TrackBreadth::Length(length::LengthPercentage::new_len(px));
+        let container = compute_style_attribute(
+            "display: inline-grid; grid-template-columns: [a] 100px repeat(2, minmax(10px, 1fr)) \
+             [b]; grid-template-rows: repeat(auto-fill, 50px); grid-auto-flow: column dense; \
+             grid-template-areas: 'a a' 'b c'",
+            &ComputedValues::default(),
+        );
+        assert_eq!(
+            container.display,
+            Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Grid)
+        );
+        let fractional = TrackSize::MinMax(px(10.), TrackBreadth::Flex(1.));
+        assert_eq!(
+            container.grid_template_columns,
+            GridTemplate::TrackList(TrackList {
+                line_names: vec![
+                    vec!["a".to_owned()],
+                    Vec::new(),
+                    Vec::new(),
+                    vec!["b".to_owned()]
+                ],
+                tracks: vec![TrackSize::Breadth(px(100.)), fractional.clone(), fractional],
+                auto_repeat: None,
+            })
+        );
+        match &container.grid_template_rows {
+            GridTemplate::TrackList(list) => {
+                let auto_repeat = list.auto_repeat.as_ref().unwrap();
+                assert_eq!(auto_repeat.count, RepeatCount::AutoFill);
+                assert_eq!(auto_repeat.tracks, vec![TrackSize::Breadth(px(50.))]);
+            }
+            GridTemplate::None => panic!("expected a track list"),
+        }
+        assert_eq!(
+            container.grid_auto_flow,
+            GridAutoFlow {
+                column: true,
+                dense: true
+            }
+        );
+        assert_eq!(
+            container.grid_template_areas,
+            GridTemplateAreas::Areas(TemplateAreas {
+                rows: 2,
+                columns: 2,
+                areas: vec![
+                    NamedArea {
+                        name: "a".to_owned(),
+                        rows: 0..1,
+                        columns: 0..2
+                    },
+                    NamedArea {
+                        name: "b".to_owned(),
+                        rows: 1..2,
+                        columns: 0..1
+                    },
+                    NamedArea {
+                        name: "c".to_owned(),
+                        rows: 1..2,
+                        columns: 1..2
+                    },
+                ]
+            })
+        );
+
+        // Grid items are blockified, and lines omitted from `grid-area` are taken from the others.
+        let item =
+            compute_style_attribute("display: inline; grid-area: a / 2 / span 3", &container);
+        assert_eq!(item.display, Display::new_block());
+        assert_eq!(item.grid_row_start, GridLine::Name("a".to_owned()));
+        assert_eq!(
+            item.grid_column_start,
+            GridLine::Line {
+                index: 2,
+                name: None
+            }
+        );
+        assert_eq!(
+            item.grid_row_end,
+            GridLine::Span {
+                count: 3,
+                name: None
+            }
+        );
+        assert_eq!(item.grid_column_end, GridLine::Auto);
+
+        // Areas that aren't rectangles are invalid.
+        let invalid = compute_style_attribute(
+            "grid-template-areas: 'a b' 'b a'; grid-column: 0",
+            &ComputedValues::default(),
+        );
+        assert_eq!(invalid.grid_template_areas, GridTemplateAreas::None);
+        assert_eq!(invalid.grid_column_start, GridLine::Auto);
+    }
+
+    #[test]
+    fn parses_table_properties() {
+        let table = compute_style_attribute(
+            "display: inline-table; border-collapse: collapse; border-spacing: 2px; \
+             table-layout: fixed; caption-side: bottom",
+            &ComputedValues::default(),
+        );
+        assert_eq!(
+            table.display,
+            Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Table)
+        );
+        assert_eq!(table.border_collapse, BorderCollapse::Collapse);
+        assert_eq!(
+            table.border_spacing,
+            BorderSpacing {
+                horizontal: CSSPixelLength::new(2.),
+                vertical: CSSPixelLength::new(2.),
+            }
+        );
+        assert_eq!(table.table_layout, TableLayout::Fixed);
+        assert_eq!(table.caption_side, CaptionSide::Bottom);
+
+        // The spacing and borders model of tables are inherited by their cells, unlike the
+        // algorithm used to lay them out.
+        let cell = compute_style_attribute(
+            "display: table-cell; border-spacing: 1px 3px; border-spacing: -1px",
+            &table,
+        );
+        assert_eq!(cell.display, Display::Internal(InternalDisplay::TableCell));
+        assert_eq!(cell.border_collapse, BorderCollapse::Collapse);
+        assert_eq!(
+            cell.border_spacing,
+            BorderSpacing {
+                horizontal: CSSPixelLength::new(1.),
+                vertical: CSSPixelLength::new(3.),
+            }
+        );
+        assert_eq!(cell.table_layout, TableLayout::Auto);
+        assert_eq!(cell.caption_side, CaptionSide::Bottom);
+
+        // Internal table boxes are blockified when they're flex items.
+        let flex = compute_style_attribute("display: flex", &ComputedValues::default());
+        let item = compute_style_attribute("display: table-row", &flex);
+        assert_eq!(item.display, Display::new_block());
+    }
+
+    #[test]
+    fn parses_multicol_properties() {
+        let container = compute_style_attribute(
+            "columns: 12em 3; column-gap: 10px; column-rule: 2px solid red",
+            &ComputedValues::default(),
+        );
+        assert!(container.is_multicol_container());
+        assert_eq!(
+            container.column_width,
+            ColumnWidth::Length(CSSPixelLength::new(192.))
+        );
+        assert_eq!(container.column_count, ColumnCount::Integer(3));
+        assert_eq!(container.column_rule_width.size, CSSPixelLength::new(2.));
+        assert_eq!(container.column_rule_style, LineStyle::Solid);
+        assert_eq!(container.column_rule_color.rgba, RGBA::new(255, 0, 0, 255));
+
+        // Either longhand of `columns` can be omitted, and `column-count` must be positive.
+        let count = compute_style_attribute("columns: 2; column-count: 0", &container);
+        assert_eq!(count.column_width, ColumnWidth::Auto);
+        assert_eq!(count.column_count, ColumnCount::Integer(2));
+        let auto = compute_style_attribute("columns: auto", &container);
+        assert!(!auto.is_multicol_container());
+
+        let rule = compute_style_attribute("column-rule: dotted thick", &container);
+        assert_eq!(rule.column_rule_width.size, CSSPixelLength::new(5.));
+        assert_eq!(rule.column_rule_style, LineStyle::Dotted);
+
+        // Like border widths, the rule is no wider than zero without a style.
+        let unstyled = compute_style_attribute("column-rule-width: 4px", &container);
+        assert_eq!(unstyled.column_rule_width.size, CSSPixelLength::new(0.));
+
+        let child =
+            compute_style_attribute("column-span: all; break-inside: avoid-column", &container);
+        assert_eq!(child.column_span, ColumnSpan::All);
+        assert_eq!(child.break_inside, BreakInside::AvoidColumn);
+        assert!(child.break_inside.avoids_column_breaks());
+        assert!(!child.is_multicol_container());
+    }
+
+    #[test]
+    fn anonymous_values_only_inherit_inherited_properties() {
+        let parent = compute_style_attribute(
+            "font-size: 20px; text-align: center; margin: 5px; padding: 3px; width: 50px",
+            &ComputedValues::default(),
+        );
+        let anonymous = compute_anonymous_values(&parent);
+        assert_eq!(anonymous.font_size, parent.font_size);
+        assert_eq!(anonymous.text_align, TextAlign::Center);
+        assert_eq!(
+            anonymous.margin_left.size,
+            LengthPercentageOrAuto::new_len(0.)
+        );
+        assert_eq!(
+            anonymous.padding_top.size,
+            ComputedValues::default().padding_top.size
+        );
+        assert_eq!(anonymous.width.size, LengthPercentageOrAuto::Auto);
+    }
 }
@@ -0,0 +1,951 @@
+//! The registry of every property Kosmonaut supports.  `LonghandId`, `ShorthandId`,
+//! `PropertyDeclaration`, `ComputedValues`, and the code to parse, compute, and default each
+//! property are all generated from the table below by `define_properties!`, so adding a property
+//! means adding an entry here (and the value types it refers to).
+//!
+//! Each longhand gives:
+//!
+//!   * `field` — the name of its field in `ComputedValues` (and its setter in
+//!     `ComputedValuesBuilder`).
+//!   * `specified` — the type it's parsed into, which must implement `CssValueParse`.
+//!   * `computed` — the type of its computed value.  `specified` must implement
+//!     `ComputeValueWithContext` with this as its `ComputedValue`, unless the property is early.
+//!   * `inherited` — whether it takes its parent's value when the cascade doesn't provide one.
+//!   * `initial` — its initial value, given the `InitialValueContext` it depends on.
+//!   * `early` (optional) — for properties other properties depend on to compute, the
+//!     `ComputeContext` method that returns its already computed value.
+//!   * `group` (optional) — the `LogicalGroup` of physical longhands it belongs to.
+//!
+//! Each logical longhand gives the type it's parsed into, its `LogicalGroup`, and the physical
+//! longhands of that group it maps onto in horizontal and vertical writing modes.  Logical
+//! longhands have no `ComputedValues` field of their own; their declarations are mapped onto the
+//! physical longhand before values are computed.
+//!
+//! Each shorthand gives the longhands it sets and the function that parses it into declarations
+//! of them, along with any leading arguments to pass that function.
+
+use std::sync::Arc;
+
+use cssparser::{ParseError, Parser};
+use primitives::sides::PhysicalSide;
+
+use crate::custom_properties::CustomPropertiesMap;
+use crate::properties::id::LogicalGroup;
+use crate::properties::{CustomDeclaration, VariableDeclaration, WideKeywordDeclaration};
+use crate::values::computed::{
+    ComputeContext, ComputeValueWithContext, InitialValueContext, WritingMode,
+};
+use crate::values::specified::align::parse_gap_shorthand_into;
+use crate::values::specified::background::parse_background_shorthand_into;
+use crate::values::specified::border::{
+    parse_border_color_shorthand_into, parse_border_shorthand_into,
+    parse_border_side_shorthand_into, parse_border_style_shorthand_into,
+    parse_border_width_shorthand_into,
+};
+use crate::values::specified::flex::{parse_flex_flow_shorthand_into, parse_flex_shorthand_into};
+use crate::values::specified::font::parse_font_shorthand_into;
+use crate::values::specified::grid::{
+    parse_grid_area_shorthand_into, parse_grid_line_shorthand_into,
+    parse_grid_template_shorthand_into,
+};
+use crate::values::specified::margin::parse_margin_shorthand_into;
+use crate::values::specified::multicol::{
+    parse_column_rule_shorthand_into, parse_columns_shorthand_into,
+};
+use crate::values::specified::padding::parse_padding_shorthand_into;
+use crate::values::specified::position::parse_inset_shorthand_into;
+use crate::values::{computed, specified, CssValueParse};
+use crate::StyleParseErrorKind;
+
+define_properties! {
+    longhands {
+        /// align-content
+        AlignContent("align-content") {
+            field: align_content,
+            specified: computed::ContentAlignment,
+            computed: computed::ContentAlignment,
+            inherited: false,
+            initial: |_| computed::ContentAlignment::initial_value(),
+        }
+        /// align-items
+        AlignItems("align-items") {
+            field: align_items,
+            specified: computed::ItemAlignment,
+            computed: computed::ItemAlignment,
+            inherited: false,
+            initial: |_| computed::ItemAlignment::initial_value(),
+        }
+        /// align-self
+        AlignSelf("align-self") {
+            field: align_self,
+            specified: computed::AlignSelf,
+            computed: computed::AlignSelf,
+            inherited: false,
+            initial: |_| computed::AlignSelf::initial_value(),
+        }
+        /// background-color
+        BackgroundColor("background-color") {
+            field: background_color,
+            specified: specified::BackgroundColor,
+            computed: computed::BackgroundColor,
+            inherited: false,
+            initial: |ctx| computed::BackgroundColor::initial_value(ctx.color.rgba()),
+        }
+        /// border-bottom-color
+        BorderBottomColor("border-bottom-color") {
+            field: border_bottom_color,
+            specified: specified::BorderColor,
+            computed: computed::BorderColor,
+            inherited: false,
+            initial: |ctx| computed::BorderColor::initial_value(ctx.color.rgba()),
+            group: BorderColor,
+        }
+        /// border-collapse
+        BorderCollapse("border-collapse") {
+            field: border_collapse,
+            specified: computed::BorderCollapse,
+            computed: computed::BorderCollapse,
+            inherited: true,
+            initial: |_| computed::BorderCollapse::initial_value(),
+        }
+        /// border-left-color
+        BorderLeftColor("border-left-color") {
+            field: border_left_color,
+            specified: specified::BorderColor,
+            computed: computed::BorderColor,
+            inherited: false,
+            initial: |ctx| computed::BorderColor::initial_value(ctx.color.rgba()),
+            group: BorderColor,
+        }
+        /// border-right-color
+        BorderRightColor("border-right-color") {
+            field: border_right_color,
+            specified: specified::BorderColor,
+            computed: computed::BorderColor,
+            inherited: false,
+            initial: |ctx| computed::BorderColor::initial_value(ctx.color.rgba()),
+            group: BorderColor,
+        }
+        /// border-spacing
+        BorderSpacing("border-spacing") {
+            field: border_spacing,
+            specified: specified::BorderSpacing,
+            computed: computed::BorderSpacing,
+            inherited: true,
+            initial: |_| computed::BorderSpacing::initial_value(),
+        }
+        /// border-top-color
+        BorderTopColor("border-top-color") {
+            field: border_top_color,
+            specified: specified::BorderColor,
+            computed: computed::BorderColor,
+            inherited: false,
+            initial: |ctx| computed::BorderColor::initial_value(ctx.color.rgba()),
+            group: BorderColor,
+        }
+        /// border-bottom-style
+        BorderBottomStyle("border-bottom-style") {
+            field: border_bottom_style,
+            specified: computed::LineStyle,
+            computed: computed::LineStyle,
+            inherited: false,
+            initial: |_| computed::border_side_initial_style(),
+            early: border_bottom_style,
+            group: BorderStyle,
+        }
+        /// border-left-style
+        BorderLeftStyle("border-left-style") {
+            field: border_left_style,
+            specified: computed::LineStyle,
+            computed: computed::LineStyle,
+            inherited: false,
+            initial: |_| computed::border_side_initial_style(),
+            early: border_left_style,
+            group: BorderStyle,
+        }
+        /// border-right-style
+        BorderRightStyle("border-right-style") {
+            field: border_right_style,
+            specified: computed::LineStyle,
+            computed: computed::LineStyle,
+            inherited: false,
+            initial: |_| computed::border_side_initial_style(),
+            early: border_right_style,
+            group: BorderStyle,
+        }
+        /// border-top-style
+        BorderTopStyle("border-top-style") {
+            field: border_top_style,
+            specified: computed::LineStyle,
+            computed: computed::LineStyle,
+            inherited: false,
+            initial: |_| computed::border_side_initial_style(),
+            early: border_top_style,
+            group: BorderStyle,
+        }
+        /// border-bottom-width
+        BorderBottomWidth("border-bottom-width") {
+            field: border_bottom_width,
+            specified: specified::BorderBottomWidth,
+            computed: computed::BorderWidth,
+            inherited: false,
+            initial: |ctx| computed::BorderWidth::initial_value(ctx.border_styles.bottom),
+            group: BorderWidth,
+        }
+        /// border-left-width
+        BorderLeftWidth("border-left-width") {
+            field: border_left_width,
+            specified: specified::BorderLeftWidth,
+            computed: computed::BorderWidth,
+            inherited: false,
+            initial: |ctx| computed::BorderWidth::initial_value(ctx.border_styles.left),
+            group: BorderWidth,
+        }
+        /// border-right-width
+        BorderRightWidth("border-right-width") {
+            field: border_right_width,
+            specified: specified::BorderRightWidth,
+            computed: computed::BorderWidth,
+            inherited: false,
+            initial: |ctx| computed::BorderWidth::initial_value(ctx.border_styles.right),
+            group: BorderWidth,
+        }
+        /// border-top-width
+        BorderTopWidth("border-top-width") {
+            field: border_top_width,
+            specified: specified::BorderTopWidth,
+            computed: computed::BorderWidth,
+            inherited: false,
+            initial: |ctx| computed::BorderWidth::initial_value(ctx.border_styles.top),
+            group: BorderWidth,
+        }
+        /// bottom
+        Bottom("bottom") {
+            field: bottom,
+            specified: specified::Inset,
+            computed: computed::Inset,
+            inherited: false,
+            initial: |_| computed::Inset::initial_value(),
+            group: Inset,
+        }
+        /// box-sizing
+        BoxSizing("box-sizing") {
+            field: box_sizing,
+            specified: computed::BoxSizing,
+            computed: computed::BoxSizing,
+            inherited: false,
+            initial: |_| computed::BoxSizing::initial_value(),
+        }
+        /// break-inside
+        BreakInside("break-inside") {
+            field: break_inside,
+            specified: computed::BreakInside,
+            computed: computed::BreakInside,
+            inherited: false,
+            initial: |_| computed::BreakInside::initial_value(),
+        }
+        /// caption-side
+        CaptionSide("caption-side") {
+            field: caption_side,
+            specified: computed::CaptionSide,
+            computed: computed::CaptionSide,
+            inherited: true,
+            initial: |_| computed::CaptionSide::initial_value(),
+        }
+        /// clear
+        Clear("clear") {
+            field: clear,
+            specified: computed::Clear,
+            computed: computed::Clear,
+            inherited: false,
+            initial: |_| computed::Clear::initial_value(),
+        }
+        /// color
+        Color("color") {
+            field: color,
+            specified: specified::Color,
+            computed: computed::Color,
+            inherited: true,
+            initial: |_| computed::Color::initial_value(),
+            early: color,
+        }
+        /// column-count
+        ColumnCount("column-count") {
+            field: column_count,
+            specified: computed::ColumnCount,
+            computed: computed::ColumnCount,
+            inherited: false,
+            initial: |_| computed::ColumnCount::initial_value(),
+        }
+        /// column-gap
+        ColumnGap("column-gap") {
+            field: column_gap,
+            specified: specified::Gap,
+            computed: computed::Gap,
+            inherited: false,
+            initial: |_| computed::Gap::initial_value(),
+        }
+        /// column-rule-color
+        ColumnRuleColor("column-rule-color") {
+            field: column_rule_color,
+            specified: specified::BorderColor,
+            computed: computed::BorderColor,
+            inherited: false,
+            initial: |ctx| computed::BorderColor::initial_value(ctx.color.rgba()),
+        }
+        /// column-rule-style
+        ColumnRuleStyle("column-rule-style") {
+            field: column_rule_style,
+            specified: computed::LineStyle,
+            computed: computed::LineStyle,
+            inherited: false,
+            initial: |_| computed::border_side_initial_style(),
+            early: column_rule_style,
+        }
+        /// column-rule-width
+        ColumnRuleWidth("column-rule-width") {
+            field: column_rule_width,
+            specified: specified::ColumnRuleWidth,
+            computed: computed::BorderWidth,
+            inherited: false,
+            initial: |ctx| computed::BorderWidth::initial_value(ctx.column_rule_style),
+        }
+        /// column-span
+        ColumnSpan("column-span") {
+            field: column_span,
+            specified: computed::ColumnSpan,
+            computed: computed::ColumnSpan,
+            inherited: false,
+            initial: |_| computed::ColumnSpan::initial_value(),
+        }
+        /// column-width
+        ColumnWidth("column-width") {
+            field: column_width,
+            specified: specified::ColumnWidth,
+            computed: computed::ColumnWidth,
+            inherited: false,
+            initial: |_| computed::ColumnWidth::initial_value(),
+        }
+        /// direction
+        Direction("direction") {
+            field: direction,
+            specified: computed::Direction,
+            computed: computed::Direction,
+            inherited: true,
+            initial: |_| computed::Direction::initial_value(),
+        }
+        /// display
+        Display("display") {
+            field: display,
+            specified: computed::Display,
+            computed: computed::Display,
+            inherited: false,
+            initial: |_| computed::Display::initial_value(),
+        }
+        /// flex-basis
+        FlexBasis("flex-basis") {
+            field: flex_basis,
+            specified: specified::FlexBasis,
+            computed: computed::FlexBasis,
+            inherited: false,
+            initial: |_| computed::FlexBasis::initial_value(),
+        }
+        /// flex-direction
+        FlexDirection("flex-direction") {
+            field: flex_direction,
+            specified: computed::FlexDirection,
+            computed: computed::FlexDirection,
+            inherited: false,
+            initial: |_| computed::FlexDirection::initial_value(),
+        }
+        /// flex-grow
+        FlexGrow("flex-grow") {
+            field: flex_grow,
+            specified: computed::FlexFactor,
+            computed: computed::FlexFactor,
+            inherited: false,
+            initial: |_| computed::FlexFactor::new(0.),
+        }
+        /// flex-shrink
+        FlexShrink("flex-shrink") {
+            field: flex_shrink,
+            specified: computed::FlexFactor,
+            computed: computed::FlexFactor,
+            inherited: false,
+            initial: |_| computed::FlexFactor::new(1.),
+        }
+        /// flex-wrap
+        FlexWrap("flex-wrap") {
+            field: flex_wrap,
+            specified: computed::FlexWrap,
+            computed: computed::FlexWrap,
+            inherited: false,
+            initial: |_| computed::FlexWrap::initial_value(),
+        }
+        /// float
+        Float("float") {
+            field: float,
+            specified: computed::Float,
+            computed: computed::Float,
+            inherited: false,
+            initial: |_| computed::Float::initial_value(),
+        }
+        /// font-family
+        FontFamily("font-family") {
+            field: font_family,
+            specified: computed::FontFamily,
+            computed: computed::FontFamily,
+            inherited: true,
+            initial: |_| computed::FontFamily::initial_value(),
+        }
+        /// font-size
+        FontSize("font-size") {
+            field: font_size,
+            specified: specified::FontSize,
+            computed: computed::FontSize,
+            inherited: true,
+            initial: |_| computed::FontSize::initial_value(),
+            early: font_size,
+        }
+        /// font-stretch
+        FontStretch("font-stretch") {
+            field: font_stretch,
+            specified: computed::FontStretch,
+            computed: computed::FontStretch,
+            inherited: true,
+            initial: |_| computed::FontStretch::initial_value(),
+        }
+        /// font-style
+        FontStyle("font-style") {
+            field: font_style,
+            specified: computed::FontStyle,
+            computed: computed::FontStyle,
+            inherited: true,
+            initial: |_| computed::FontStyle::initial_value(),
+        }
+        /// font-weight
+        FontWeight("font-weight") {
+            field: font_weight,
+            specified: specified::FontWeight,
+            computed: computed::FontWeight,
+            inherited: true,
+            initial: |_| computed::FontWeight::initial_value(),
+        }
+        /// grid-auto-columns
+        GridAutoColumns("grid-auto-columns") {
+            field: grid_auto_columns,
+            specified: specified::GridAutoTracks,
+            computed: computed::GridAutoTracks,
+            inherited: false,
+            initial: |_| computed::GridAutoTracks::initial_value(),
+        }
+        /// grid-auto-flow
+        GridAutoFlow("grid-auto-flow") {
+            field: grid_auto_flow,
+            specified: computed::GridAutoFlow,
+            computed: computed::GridAutoFlow,
+            inherited: false,
+            initial: |_| computed::GridAutoFlow::initial_value(),
+        }
+        /// grid-auto-rows
+        GridAutoRows("grid-auto-rows") {
+            field: grid_auto_rows,
+            specified: specified::GridAutoTracks,
+            computed: computed::GridAutoTracks,
+            inherited: false,
+            initial: |_| computed::GridAutoTracks::initial_value(),
+        }
+        /// grid-column-end
+        GridColumnEnd("grid-column-end") {
+            field: grid_column_end,
+            specified: computed::GridLine,
+            computed: computed::GridLine,
+            inherited: false,
+            initial: |_| computed::GridLine::initial_value(),
+        }
+        /// grid-column-start
+        GridColumnStart("grid-column-start") {
+            field: grid_column_start,
+            specified: computed::GridLine,
+            computed: computed::GridLine,
+            inherited: false,
+            initial: |_| computed::GridLine::initial_value(),
+        }
+        /// grid-row-end
+        GridRowEnd("grid-row-end") {
+            field: grid_row_end,
+            specified: computed::GridLine,
+            computed: computed::GridLine,
+            inherited: false,
+            initial: |_| computed::GridLine::initial_value(),
+        }
+        /// grid-row-start
+        GridRowStart("grid-row-start") {
+            field: grid_row_start,
+            specified: computed::GridLine,
+            computed: computed::GridLine,
+            inherited: false,
+            initial: |_| computed::GridLine::initial_value(),
+        }
+        /// grid-template-areas
+        GridTemplateAreas("grid-template-areas") {
+            field: grid_template_areas,
+            specified: computed::GridTemplateAreas,
+            computed: computed::GridTemplateAreas,
+            inherited: false,
+            initial: |_| computed::GridTemplateAreas::initial_value(),
+        }
+        /// grid-template-columns
+        GridTemplateColumns("grid-template-columns") {
+            field: grid_template_columns,
+            specified: specified::GridTemplate,
+            computed: computed::GridTemplate,
+            inherited: false,
+            initial: |_| computed::GridTemplate::initial_value(),
+        }
+        /// grid-template-rows
+        GridTemplateRows("grid-template-rows") {
+            field: grid_template_rows,
+            specified: specified::GridTemplate,
+            computed: computed::GridTemplate,
+            inherited: false,
+            initial: |_| computed::GridTemplate::initial_value(),
+        }
+        /// height
+        Height("height") {
+            field: height,
+            specified: specified::Height,
+            computed: computed::height::Height,
+            inherited: false,
+            initial: |_| computed::height::Height::initial_value(),
+            group: Size,
+        }
+        /// justify-content
+        JustifyContent("justify-content") {
+            field: justify_content,
+            specified: computed::ContentAlignment,
+            computed: computed::ContentAlignment,
+            inherited: false,
+            initial: |_| computed::ContentAlignment::initial_value(),
+        }
+        /// justify-items
+        JustifyItems("justify-items") {
+            field: justify_items,
+            specified: computed::ItemAlignment,
+            computed: computed::ItemAlignment,
+            inherited: false,
+            initial: |_| computed::ItemAlignment::initial_value(),
+        }
+        /// justify-self
+        JustifySelf("justify-self") {
+            field: justify_self,
+            specified: computed::AlignSelf,
+            computed: computed::AlignSelf,
+            inherited: false,
+            initial: |_| computed::AlignSelf::initial_value(),
+        }
+        /// left
+        Left("left") {
+            field: left,
+            specified: specified::Inset,
+            computed: computed::Inset,
+            inherited: false,
+            initial: |_| computed::Inset::initial_value(),
+            group: Inset,
+        }
+        /// letter-spacing
+        LetterSpacing("letter-spacing") {
+            field: letter_spacing,
+            specified: specified::Spacing,
+            computed: computed::Spacing,
+            inherited: true,
+            initial: |_| computed::Spacing::initial_value(),
+        }
+        /// line-height
+        LineHeight("line-height") {
+            field: line_height,
+            specified: specified::LineHeight,
+            computed: computed::LineHeight,
+            inherited: true,
+            initial: |_| computed::LineHeight::initial_value(),
+        }
+        /// margin-bottom
+        MarginBottom("margin-bottom") {
+            field: margin_bottom,
+            specified: specified::Margin,
+            computed: computed::Margin,
+            inherited: false,
+            initial: |_| computed::Margin::initial_value(),
+            group: Margin,
+        }
+        /// margin-left
+        MarginLeft("margin-left") {
+            field: margin_left,
+            specified: specified::Margin,
+            computed: computed::Margin,
+            inherited: false,
+            initial: |_| computed::Margin::initial_value(),
+            group: Margin,
+        }
+        /// margin-right
+        MarginRight("margin-right") {
+            field: margin_right,
+            specified: specified::Margin,
+            computed: computed::Margin,
+            inherited: false,
+            initial: |_| computed::Margin::initial_value(),
+            group: Margin,
+        }
+        /// margin-top
+        MarginTop("margin-top") {
+            field: margin_top,
+            specified: specified::Margin,
+            computed: computed::Margin,
+            inherited: false,
+            initial: |_| computed::Margin::initial_value(),
+            group: Margin,
+        }
+        /// max-height
+        MaxHeight("max-height") {
+            field: max_height,
+            specified: specified::MaxSize,
+            computed: computed::MaxSize,
+            inherited: false,
+            initial: |_| computed::MaxSize::initial_value(),
+            group: MaxSize,
+        }
+        /// max-width
+        MaxWidth("max-width") {
+            field: max_width,
+            specified: specified::MaxSize,
+            computed: computed::MaxSize,
+            inherited: false,
+            initial: |_| computed::MaxSize::initial_value(),
+            group: MaxSize,
+        }
+        /// min-height
+        MinHeight("min-height") {
+            field: min_height,
+            specified: specified::MinSize,
+            computed: computed::MinSize,
+            inherited: false,
+            initial: |_| computed::MinSize::initial_value(),
+            group: MinSize,
+        }
+        /// min-width
+        MinWidth("min-width") {
+            field: min_width,
+            specified: specified::MinSize,
+            computed: computed::MinSize,
+            inherited: false,
+            initial: |_| computed::MinSize::initial_value(),
+            group: MinSize,
+        }
+        /// order
+        Order("order") {
+            field: order,
+            specified: computed::Order,
+            computed: computed::Order,
+            inherited: false,
+            initial: |_| computed::Order::initial_value(),
+        }
+        /// padding-bottom
+        PaddingBottom("padding-bottom") {
+            field: padding_bottom,
+            specified: specified::Padding,
+            computed: computed::Padding,
+            inherited: false,
+            initial: |_| computed::Padding::initial_value(),
+            group: Padding,
+        }
+        /// padding-left
+        PaddingLeft("padding-left") {
+            field: padding_left,
+            specified: specified::Padding,
+            computed: computed::Padding,
+            inherited: false,
+            initial: |_| computed::Padding::initial_value(),
+            group: Padding,
+        }
+        /// padding-right
+        PaddingRight("padding-right") {
+            field: padding_right,
+            specified: specified::Padding,
+            computed: computed::Padding,
+            inherited: false,
+            initial: |_| computed::Padding::initial_value(),
+            group: Padding,
+        }
+        /// padding-top
+        PaddingTop("padding-top") {
+            field: padding_top,
+            specified: specified::Padding,
+            computed: computed::Padding,
+            inherited: false,
+            initial: |_| computed::Padding::initial_value(),
+            group: Padding,
+        }
+        /// position
+        Position("position") {
+            field: position,
+            specified: computed::Position,
+            computed: computed::Position,
+            inherited: false,
+            initial: |_| computed::Position::initial_value(),
+        }
+        /// right
+        Right("right") {
+            field: right,
+            specified: specified::Inset,
+            computed: computed::Inset,
+            inherited: false,
+            initial: |_| computed::Inset::initial_value(),
+            group: Inset,
+        }
+        /// row-gap
+        RowGap("row-gap") {
+            field: row_gap,
+            specified: specified::Gap,
+            computed: computed::Gap,
+            inherited: false,
+            initial: |_| computed::Gap::initial_value(),
+        }
+        /// table-layout
+        TableLayout("table-layout") {
+            field: table_layout,
+            specified: computed::TableLayout,
+            computed: computed::TableLayout,
+            inherited: false,
+            initial: |_| computed::TableLayout::initial_value(),
+        }
+        /// text-align
+        TextAlign("text-align") {
+            field: text_align,
+            specified: specified::TextAlign,
+            computed: computed::TextAlign,
+            inherited: true,
+            initial: |_| computed::TextAlign::initial_value(),
+        }
+        /// text-indent
+        TextIndent("text-indent") {
+            field: text_indent,
+            specified: specified::TextIndent,
+            computed: computed::TextIndent,
+            inherited: true,
+            initial: |_| computed::TextIndent::initial_value(),
+        }
+        /// text-transform
+        TextTransform("text-transform") {
+            field: text_transform,
+            specified: computed::TextTransform,
+            computed: computed::TextTransform,
+            inherited: true,
+            initial: |_| computed::TextTransform::initial_value(),
+        }
+        /// top
+        Top("top") {
+            field: top,
+            specified: specified::Inset,
+            computed: computed::Inset,
+            inherited: false,
+            initial: |_| computed::Inset::initial_value(),
+            group: Inset,
+        }
+        /// white-space
+        WhiteSpace("white-space") {
+            field: white_space,
+            specified: computed::WhiteSpace,
+            computed: computed::WhiteSpace,
+            inherited: true,
+            initial: |_| computed::WhiteSpace::initial_value(),
+        }
+        /// width
+        Width("width") {
+            field: width,
+            specified: specified::Width,
+            computed: computed::width::Width,
+            inherited: false,
+            initial: |_| computed::width::Width::initial_value(),
+            group: Size,
+        }
+        /// word-spacing
+        WordSpacing("word-spacing") {
+            field: word_spacing,
+            specified: specified::Spacing,
+            computed: computed::Spacing,
+            inherited: true,
+            initial: |_| computed::Spacing::initial_value(),
+        }
+        /// writing-mode
+        WritingMode("writing-mode") {
+            field: writing_mode,
+            specified: computed::WritingMode,
+            computed: computed::WritingMode,
+            inherited: true,
+            initial: |_| computed::WritingMode::initial_value(),
+        }
+        /// z-index
+        ZIndex("z-index") {
+            field: z_index,
+            specified: computed::ZIndex,
+            computed: computed::ZIndex,
+            inherited: false,
+            initial: |_| computed::ZIndex::initial_value(),
+        }
+    }
+    logical_longhands {
+        /// max-block-size
+        MaxBlockSize("max-block-size") {
+            specified: specified::MaxSize,
+            group: MaxSize,
+            horizontal: MaxHeight,
+            vertical: MaxWidth,
+        }
+        /// max-inline-size
+        MaxInlineSize("max-inline-size") {
+            specified: specified::MaxSize,
+            group: MaxSize,
+            horizontal: MaxWidth,
+            vertical: MaxHeight,
+        }
+        /// min-block-size
+        MinBlockSize("min-block-size") {
+            specified: specified::MinSize,
+            group: MinSize,
+            horizontal: MinHeight,
+            vertical: MinWidth,
+        }
+        /// min-inline-size
+        MinInlineSize("min-inline-size") {
+            specified: specified::MinSize,
+            group: MinSize,
+            horizontal: MinWidth,
+            vertical: MinHeight,
+        }
+    }
+    shorthands {
+        /// background
+        Background("background") {
+            longhands: [BackgroundColor],
+            parse: parse_background_shorthand_into,
+        }
+        /// border-color
+        BorderColor("border-color") {
+            longhands: [BorderTopColor, BorderRightColor, BorderBottomColor, BorderLeftColor],
+            parse: parse_border_color_shorthand_into,
+        }
+        /// border-style
+        BorderStyle("border-style") {
+            longhands: [BorderTopStyle, BorderRightStyle, BorderBottomStyle, BorderLeftStyle],
+            parse: parse_border_style_shorthand_into,
+        }
+        /// border-width
+        BorderWidth("border-width") {
+            longhands: [BorderTopWidth, BorderRightWidth, BorderBottomWidth, BorderLeftWidth],
+            parse: parse_border_width_shorthand_into,
+        }
+        /// border-top
+        BorderTop("border-top") {
+            longhands: [BorderTopColor, BorderTopStyle, BorderTopWidth],
+            parse: parse_border_side_shorthand_into(PhysicalSide::Top),
+        }
+        /// border-right
+        BorderRight("border-right") {
+            longhands: [BorderRightColor, BorderRightStyle, BorderRightWidth],
+            parse: parse_border_side_shorthand_into(PhysicalSide::Right),
+        }
+        /// border-bottom
+        BorderBottom("border-bottom") {
+            longhands: [BorderBottomColor, BorderBottomStyle, BorderBottomWidth],
+            parse: parse_border_side_shorthand_into(PhysicalSide::Bottom),
+        }
+        /// border-left
+        BorderLeft("border-left") {
+            longhands: [BorderLeftColor, BorderLeftStyle, BorderLeftWidth],
+            parse: parse_border_side_shorthand_into(PhysicalSide::Left),
+        }
+        /// border
+        Border("border") {
+            longhands: [
+                BorderTopColor,
+                BorderRightColor,
+                BorderBottomColor,
+                BorderLeftColor,
+                BorderTopStyle,
+                BorderRightStyle,
+                BorderBottomStyle,
+                BorderLeftStyle,
+                BorderTopWidth,
+                BorderRightWidth,
+                BorderBottomWidth,
+                BorderLeftWidth,
+            ],
+            parse: parse_border_shorthand_into,
+        }
+        /// column-rule
+        ColumnRule("column-rule") {
+            longhands: [ColumnRuleWidth, ColumnRuleStyle, ColumnRuleColor],
+            parse: parse_column_rule_shorthand_into,
+        }
+        /// columns
+        Columns("columns") {
+            longhands: [ColumnWidth, ColumnCount],
+            parse: parse_columns_shorthand_into,
+        }
+        /// flex
+        Flex("flex") {
+            longhands: [FlexGrow, FlexShrink, FlexBasis],
+            parse: parse_flex_shorthand_into,
+        }
+        /// flex-flow
+        FlexFlow("flex-flow") {
+            longhands: [FlexDirection, FlexWrap],
+            parse: parse_flex_flow_shorthand_into,
+        }
+        /// font
+        Font("font") {
+            longhands: [FontStyle, FontWeight, FontStretch, FontSize, LineHeight, FontFamily],
+            parse: parse_font_shorthand_into,
+        }
+        /// gap
+        Gap("gap") {
+            longhands: [RowGap, ColumnGap],
+            parse: parse_gap_shorthand_into,
+        }
+        /// grid-area
+        GridArea("grid-area") {
+            longhands: [GridRowStart, GridColumnStart, GridRowEnd, GridColumnEnd],
+            parse: parse_grid_area_shorthand_into,
+        }
+        /// grid-column
+        GridColumn("grid-column") {
+            longhands: [GridColumnStart, GridColumnEnd],
+            parse: parse_grid_line_shorthand_into(true),
+        }
+        /// grid-row
+        GridRow("grid-row") {
+            longhands: [GridRowStart, GridRowEnd],
+            parse: parse_grid_line_shorthand_into(false),
+        }
+        /// grid-template
+        GridTemplate("grid-template") {
+            longhands: [GridTemplateRows, GridTemplateColumns, GridTemplateAreas],
+            parse: parse_grid_template_shorthand_into,
+        }
+        /// margin
+        Margin("margin") {
+            longhands: [MarginTop, MarginRight, MarginBottom, MarginLeft],
+            parse: parse_margin_shorthand_into,
+        }
+        /// padding
+        Padding("padding") {
+            longhands: [PaddingTop, PaddingRight, PaddingBottom, PaddingLeft],
+            parse: parse_padding_shorthand_into,
+        }
+        /// inset
+        Inset("inset") {
+            longhands: [Top, Right, Bottom, Left],
+            parse: parse_inset_shorthand_into,
+        }
+    }
+}
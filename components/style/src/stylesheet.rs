@@ -1,27 +1,207 @@
-use std::mem::discriminant;
+use std::path::{Path, PathBuf};
 
-use cssparser::ParseError;
+use cssparser::{ParseError, Parser, SourceLocation};
 
+use crate::diagnostics::{DiagnosticKind, DiagnosticReporter, DiagnosticSink};
+use crate::media_queries::{MediaList, MediaRule};
 use crate::{
     parse_css_to_rules, CascadeOrigin, CssOrigin, CssRule, StyleParseErrorKind, StylesheetOrigin,
 };
 
-/// Parses string containing CSS into StyleRules.
+/// Parses string containing CSS into StyleRules, reporting any CSS that is dropped to `sink`.
 pub fn parse_css_to_stylesheet(
     sheet_name: Option<String>,
     css_str: &mut str,
-) -> Result<Stylesheet, (ParseError<StyleParseErrorKind>, &str)> {
+    sink: &mut dyn DiagnosticSink,
+) -> Stylesheet {
     let mut sheet = if let Some(name) = sheet_name {
         Stylesheet::new_with_name(name)
     } else {
         Stylesheet::new()
     };
-    for rule in parse_css_to_rules(css_str)? {
+    let rules = parse_css_to_rules(css_str, &mut DiagnosticReporter::new(&sheet.name, sink));
+    for rule in rules {
+        sheet.add_rule(rule);
+    }
+    sheet
+}
+
+/// Reads the stylesheet at `path` from the file system and parses it, replacing its `@import`
+/// rules with the rules of the stylesheets they import.  Any CSS that is dropped from these
+/// stylesheets is reported to `sink`.
+pub fn load_stylesheet<P: AsRef<Path>>(
+    path: P,
+    sink: &mut dyn DiagnosticSink,
+) -> Result<Stylesheet, String> {
+    let path = path.as_ref();
+    let mut sheet = Stylesheet::new_with_name(path.to_string_lossy().into_owned());
+    let rules = load_rules(
+        path,
+        &mut Vec::new(),
+        &mut DiagnosticReporter::new(&sheet.name, sink),
+    )?;
+    for rule in rules {
         sheet.add_rule(rule);
     }
     Ok(sheet)
 }
 
+/// Replaces each `@import` rule in `rules` with the rules of the stylesheet it imports, read from
+/// the file system relative to `importer_path`, the path of the stylesheet or document `rules`
+/// were found in.
+///
+/// Imports that can't be read or parsed, and imports that would create a cycle, are dropped and
+/// reported to `reporter`, the reporter of the importing stylesheet.
+///
+/// https://drafts.csswg.org/css-cascade-4/#at-import
+pub fn resolve_imports(
+    rules: Vec<CssRule>,
+    importer_path: &Path,
+    reporter: &mut DiagnosticReporter,
+) -> Vec<CssRule> {
+    resolve_imports_from(rules, importer_path, &mut Vec::new(), reporter)
+}
+
+/// `importers` holds the stylesheets currently being loaded, outermost first, which is used to
+/// detect import cycles.
+fn resolve_imports_from(
+    rules: Vec<CssRule>,
+    importer_path: &Path,
+    importers: &mut Vec<Importer>,
+    reporter: &mut DiagnosticReporter,
+) -> Vec<CssRule> {
+    let mut resolved = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let import_rule = match rule {
+            CssRule::Import(import_rule) => import_rule,
+            rule => {
+                resolved.push(rule);
+                continue;
+            }
+        };
+        let path = importer_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&import_rule.url);
+        let imported_rules = match load_rules(&path, importers, reporter) {
+            Ok(imported_rules) => imported_rules,
+            Err(err) => {
+                reporter.report(
+                    DiagnosticKind::FailedImport,
+                    import_rule.source_location,
+                    &format!("'{}': {}", import_rule.url, err),
+                );
+                continue;
+            }
+        };
+        // The imported rules take the place of the `@import` rule, so they come before any of the
+        // importing stylesheet's own rules in the cascade.
+        if import_rule.media_queries.queries.is_empty() {
+            resolved.extend(imported_rules);
+        } else {
+            resolved.push(CssRule::Media(MediaRule {
+                media_queries: import_rule.media_queries,
+                rules: imported_rules,
+                source_location: import_rule.source_location,
+            }));
+        }
+    }
+    resolved
+}
+
+/// A stylesheet that's being loaded, along with the stylesheets it imports.
+struct Importer {
+    canonical_path: PathBuf,
+    /// The path the stylesheet was loaded from, for naming it in diagnostics.
+    path: PathBuf,
+}
+
+/// Diagnostics for the stylesheet at `path` are sent to the same sink as `reporter`'s.
+fn load_rules(
+    path: &Path,
+    importers: &mut Vec<Importer>,
+    reporter: &mut DiagnosticReporter,
+) -> Result<Vec<CssRule>, String> {
+    let canonical_path = path
+        .canonicalize()
+        .map_err(|err| format!("couldn't resolve '{}': {}", path.display(), err))?;
+    if let Some(cycle_start) = importers
+        .iter()
+        .position(|importer| importer.canonical_path == canonical_path)
+    {
+        let cycle: Vec<_> = importers[cycle_start..]
+            .iter()
+            .map(|importer| importer.path.display().to_string())
+            .chain(std::iter::once(path.display().to_string()))
+            .collect();
+        return Err(format!("import cycle: {}", cycle.join(" -> ")));
+    }
+    let mut css = std::fs::read_to_string(path)
+        .map_err(|err| format!("couldn't read '{}': {}", path.display(), err))?;
+    let sheet_name = path.display().to_string();
+    let mut reporter = reporter.for_sheet(&sheet_name);
+    let rules = parse_css_to_rules(&mut css, &mut reporter);
+    importers.push(Importer {
+        canonical_path,
+        path: path.to_owned(),
+    });
+    let rules = resolve_imports_from(rules, path, importers, &mut reporter);
+    importers.pop();
+    Ok(rules)
+}
+
+/// An `@import` rule, e.g. `@import url("theme.css") screen;`.  These are replaced with the
+/// rules of the stylesheet they import by `resolve_imports`.
+///
+/// https://drafts.csswg.org/css-cascade-4/#at-import
+#[derive(Clone, Debug)]
+pub struct ImportRule {
+    /// The URL of the imported stylesheet, relative to the importing stylesheet.
+    pub url: String,
+    /// The cascade layer the imported rules are placed in, if any.
+    pub layer: Option<ImportLayer>,
+    /// The media queries that must match for the imported rules to apply.
+    pub media_queries: MediaList,
+    /// The location in the sheet where it was found.
+    pub source_location: SourceLocation,
+}
+
+/// The `layer` or `layer(<layer-name>)` condition of an `@import` rule.
+///
+/// Kosmonaut doesn't support cascade layers yet, so rules imported into a layer are treated as if
+/// they weren't in one.
+///
+/// https://drafts.csswg.org/css-cascade-5/#at-import
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportLayer {
+    Anonymous,
+    /// A named layer, e.g. `layer(framework.components)`.
+    Named(String),
+}
+
+impl ImportLayer {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input
+            .try_parse(|i| i.expect_ident_matching("layer"))
+            .is_ok()
+        {
+            return Ok(ImportLayer::Anonymous);
+        }
+        input.expect_function_matching("layer")?;
+        input.parse_nested_block(|i| {
+            // https://drafts.csswg.org/css-cascade-5/#typedef-layer-name
+            let mut name = i.expect_ident()?.as_ref().to_owned();
+            while i.try_parse(|i| i.expect_delim('.')).is_ok() {
+                name.push('.');
+                name.push_str(i.expect_ident()?);
+            }
+            Ok(ImportLayer::Named(name))
+        })
+    }
+}
+
 #[derive(Debug)]
 pub enum StylesheetParseErr<'i> {
     Io(std::io::Error),
@@ -86,7 +266,7 @@ impl Stylesheet {
                                     existing_style.block.declarations().iter().enumerate()
                                 {
                                     for new_prop in new_style.block.declarations() {
-                                        if discriminant(new_prop) == discriminant(existing_prop) {
+                                        if new_prop.id() == existing_prop.id() {
                                             // the props are the same "type", e.g. both `font-size, both `display`, etc
                                             // take the `new_prop`, since the latest/newest prop should always be taken
                                             obsolete_prop_indices.push(prop_index);
@@ -103,7 +283,10 @@ impl Stylesheet {
                                 }
                             }
                         }
-                        CssRule::None => {}
+                        CssRule::Media(_)
+                        | CssRule::Import(_)
+                        | CssRule::Supports(_)
+                        | CssRule::None => {}
                     }
                 }
 
@@ -111,7 +294,7 @@ impl Stylesheet {
                     self.rules.remove(index);
                 }
             }
-            CssRule::None => {}
+            CssRule::Media(_) | CssRule::Import(_) | CssRule::Supports(_) | CssRule::None => {}
         }
         self.rules.push(new_rule);
     }
@@ -119,6 +302,7 @@ impl Stylesheet {
 
 #[cfg(test)]
 mod tests {
+    use crate::diagnostics::{Diagnostic, IgnoreDiagnostics};
     use crate::test_utils::font_size_px_or_panic;
 
     // Note this useful idiom: importing names from outer (for mod tests) scope.
@@ -127,11 +311,17 @@ mod tests {
     #[test]
     // TODO: Create integration test that exercises this as well
     fn selects_last_rules_prop_in_dupes_across_rules() {
-        let mut sheet_a = parse_css_to_stylesheet(None, &mut ".a { font-size: 12px; }".to_owned())
-            .expect("failed getting sheet_a for cross-block deduping test");
+        let mut sheet_a = parse_css_to_stylesheet(
+            None,
+            &mut ".a { font-size: 12px; }".to_owned(),
+            &mut IgnoreDiagnostics,
+        );
         // We won't actually use this sheet — just extract the `font-size` rule from it
-        let mut sheet_b = parse_css_to_stylesheet(None, &mut ".a { font-size: 16px; }".to_owned())
-            .expect("failed getting sheet_b for cross-block deduping test");
+        let mut sheet_b = parse_css_to_stylesheet(
+            None,
+            &mut ".a { font-size: 16px; }".to_owned(),
+            &mut IgnoreDiagnostics,
+        );
         sheet_a.add_rule(sheet_b.rules.remove(0));
 
         // The only PropertyDeclaration in the first rule, `font-size: 12px`, is obsoleted by the
@@ -146,4 +336,77 @@ mod tests {
             _ => panic!("should always be a `StyleRule` CssRule"),
         }
     }
+
+    #[test]
+    fn reports_dropped_css() {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        parse_css_to_stylesheet(
+            Some("test.css".to_owned()),
+            &mut "
+.a {
+    colr: red;
+    width: wide;
+    height 10px;
+    color: blue;
+}
+@media screen { .b { margin: nope; } }"
+                .to_owned(),
+            &mut diagnostics,
+        );
+        let reported = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.kind, diagnostic.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reported,
+            vec![
+                (DiagnosticKind::UnknownProperty, "colr: red"),
+                (DiagnosticKind::InvalidValue, "width: wide"),
+                (DiagnosticKind::DroppedDeclaration, "height 10px"),
+                (DiagnosticKind::InvalidValue, "margin: nope"),
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.sheet_name == "test.css"));
+        assert_eq!(
+            (diagnostics[0].location.line, diagnostics[0].location.column),
+            (2, 5)
+        );
+    }
+
+    #[test]
+    fn reports_unsupported_at_rule() {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let sheet = parse_css_to_stylesheet(
+            None,
+            &mut "@font-face { font-family: x; } .a { color: red; }".to_owned(),
+            &mut diagnostics,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::UnsupportedAtRule);
+        assert_eq!(diagnostics[0].text, "@font-face");
+        // Only the unsupported rule is dropped, not the rules after it.
+        assert_eq!(sheet.rules.len(), 1);
+        match &sheet.rules[0] {
+            CssRule::Style(style_rule) => assert_eq!(style_rule.block.declarations().len(), 1),
+            _ => panic!("should always be a `StyleRule` CssRule"),
+        }
+    }
+
+    #[test]
+    fn skips_invalid_rules_in_group_rules() {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        let sheet = parse_css_to_stylesheet(
+            None,
+            &mut "@media screen { .a:nope { color: red; } .b { color: blue; } } .c { color: red; }"
+                .to_owned(),
+            &mut diagnostics,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::InvalidRule);
+        assert_eq!(sheet.rules.len(), 2);
+        match &sheet.rules[0] {
+            CssRule::Media(media_rule) => assert_eq!(media_rule.rules.len(), 1),
+            _ => panic!("should be the `@media` rule"),
+        }
+    }
 }
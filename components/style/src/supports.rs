@@ -0,0 +1,181 @@
+//! The `@supports` rule, which only applies the rules it contains when Kosmonaut supports the
+//! features its condition tests for.
+//!
+//! https://drafts.csswg.org/css-conditional-3/#at-supports
+
+use cssparser::{
+    parse_important, Delimiter, ParseError, Parser, ParserInput, SourceLocation, Token,
+};
+use kosmonaut_selectors::Selectors;
+
+use crate::properties::id::PropertyId;
+use crate::properties::PropertyDeclaration;
+use crate::{CssRule, StyleParseErrorKind};
+
+/// An `@supports` rule, e.g. `@supports (display: flex) { ... }`.
+#[derive(Clone, Debug)]
+pub struct SupportsRule {
+    pub condition: SupportsCondition,
+    /// Whether or not `condition` is true.  Support can't change once Kosmonaut is built, so this
+    /// is evaluated once when the rule is parsed.
+    pub enabled: bool,
+    /// The rules nested within this rule.
+    pub rules: Vec<CssRule>,
+    /// The location in the sheet where it was found.
+    pub source_location: SourceLocation,
+}
+
+/// The condition of an `@supports` rule.
+///
+/// https://drafts.csswg.org/css-conditional-3/#typedef-supports-condition
+#[derive(Clone, Debug, PartialEq)]
+pub enum SupportsCondition {
+    Not(Box<SupportsCondition>),
+    And(Vec<SupportsCondition>),
+    Or(Vec<SupportsCondition>),
+    /// A declaration, e.g. `(display: flex)`, holding the CSS text within the parentheses.
+    Declaration(String),
+    /// A `selector()` function, e.g. `selector(a > b)`, holding the CSS text of its argument.
+    Selector(String),
+    /// Anything else within parentheses or a function, reserved for future syntax, which is
+    /// always false.
+    ///
+    /// https://drafts.csswg.org/mediaqueries-4/#typedef-general-enclosed
+    FutureSyntax(String),
+}
+
+impl SupportsCondition {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("not")).is_ok() {
+            return Ok(SupportsCondition::Not(Box::new(
+                SupportsCondition::parse_in_parens(input)?,
+            )));
+        }
+        let first = SupportsCondition::parse_in_parens(input)?;
+        let is_and = match input.try_parse(|i| -> Result<_, ParseError<StyleParseErrorKind>> {
+            let location = i.current_source_location();
+            let ident = i.expect_ident_cloned()?;
+            match_ignore_ascii_case! { &ident,
+                "and" => Ok(true),
+                "or" => Ok(false),
+                _ => Err(location.new_unexpected_token_error(Token::Ident(ident.clone()))),
+            }
+        }) {
+            Ok(is_and) => is_and,
+            Err(_) => return Ok(first),
+        };
+        // `and` and `or` can't be mixed without parentheses.
+        let operator = if is_and { "and" } else { "or" };
+        let mut conditions = vec![first, SupportsCondition::parse_in_parens(input)?];
+        while input
+            .try_parse(|i| i.expect_ident_matching(operator))
+            .is_ok()
+        {
+            conditions.push(SupportsCondition::parse_in_parens(input)?);
+        }
+        Ok(if is_and {
+            SupportsCondition::And(conditions)
+        } else {
+            SupportsCondition::Or(conditions)
+        })
+    }
+
+    /// Parses a `<supports-in-parens>`.
+    fn parse_in_parens<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let token = input.next()?.clone();
+        match token {
+            Token::ParenthesisBlock => input.parse_nested_block(|i| {
+                if let Ok(condition) =
+                    i.try_parse(|i| -> Result<_, ParseError<StyleParseErrorKind>> {
+                        let condition = SupportsCondition::parse(i)?;
+                        i.expect_exhausted()?;
+                        Ok(condition)
+                    })
+                {
+                    return Ok(condition);
+                }
+                let start = i.position();
+                let is_declaration = i
+                    .try_parse(|i| -> Result<_, ParseError<StyleParseErrorKind>> {
+                        i.expect_ident()?;
+                        Ok(i.expect_colon()?)
+                    })
+                    .is_ok();
+                consume_remaining(i);
+                let css = i.slice_from(start).trim().to_owned();
+                Ok(if is_declaration {
+                    SupportsCondition::Declaration(css)
+                } else {
+                    SupportsCondition::FutureSyntax(css)
+                })
+            }),
+            Token::Function(ref name) => {
+                let is_selector = name.eq_ignore_ascii_case("selector");
+                input.parse_nested_block(|i| {
+                    let start = i.position();
+                    consume_remaining(i);
+                    let css = i.slice_from(start).trim().to_owned();
+                    Ok(if is_selector {
+                        SupportsCondition::Selector(css)
+                    } else {
+                        SupportsCondition::FutureSyntax(css)
+                    })
+                })
+            }
+            _ => Err(location.new_unexpected_token_error(token.clone())),
+        }
+    }
+
+    /// Whether or not Kosmonaut supports the features this condition tests for.
+    pub fn evaluate(&self) -> bool {
+        match self {
+            SupportsCondition::Not(condition) => !condition.evaluate(),
+            SupportsCondition::And(conditions) => conditions.iter().all(|c| c.evaluate()),
+            SupportsCondition::Or(conditions) => conditions.iter().any(|c| c.evaluate()),
+            SupportsCondition::Declaration(css) => declaration_supported(css),
+            SupportsCondition::Selector(css) => selector_supported(css),
+            SupportsCondition::FutureSyntax(_) => false,
+        }
+    }
+}
+
+/// Consumes the rest of `input`.  The tokens only need to be balanced, which the parser takes care
+/// of by treating blocks as single tokens.
+fn consume_remaining(input: &mut Parser) {
+    while input.next_including_whitespace_and_comments().is_ok() {}
+}
+
+/// Whether or not `css`, e.g. `display: flex`, is a declaration that Kosmonaut can parse.
+fn declaration_supported(css: &str) -> bool {
+    let mut parser_input = ParserInput::new(css);
+    let mut parser = Parser::new(&mut parser_input);
+    parser
+        .parse_entirely(|input| -> Result<(), ParseError<StyleParseErrorKind>> {
+            let name = input.expect_ident_cloned()?;
+            input.expect_colon()?;
+            let id = PropertyId::parse(&name).ok_or_else(|| {
+                input.new_custom_error(StyleParseErrorKind::UnknownProperty(name.clone()))
+            })?;
+            input.parse_until_before(Delimiter::Bang, |input| {
+                PropertyDeclaration::parse_into(&mut Vec::new(), id, input)
+            })?;
+            let _ = input.try_parse(parse_important);
+            Ok(())
+        })
+        .is_ok()
+}
+
+/// Whether or not `css` is a single complex selector that Kosmonaut can parse.
+///
+/// https://drafts.csswg.org/css-conditional-4/#support-definition-ext
+fn selector_supported(css: &str) -> bool {
+    match Selectors::compile_str(css) {
+        Ok(selectors) => selectors.inner().len() == 1,
+        Err(()) => false,
+    }
+}
@@ -18,6 +18,7 @@ pub fn font_size_px_or_panic(prop_decl: &PropertyDeclaration) -> &f32 {
                         AbsoluteLength::Px(float_val) => &float_val,
                         _ => panic!("should always be `px` AbsoluteLength units"),
                     },
+                    _ => panic!("should always be an `Absolute` NoCalcLength"),
                 },
                 _ => panic!("should always be a `length` variant, not a `calc` or `percentage`"),
             },
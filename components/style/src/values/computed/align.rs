@@ -0,0 +1,214 @@
+use crate::values::computed::length::LengthPercentage;
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
+use crate::values::specified;
+use crate::values::used::ToPx;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSPixelLength;
+
+/// The `justify-content` and `align-content` properties, which align the content of a box as a
+/// whole within it, distributing any space left over along one axis.  This is both the specified
+/// and computed value.
+///
+/// https://drafts.csswg.org/css-align-3/#align-justify-content
+// TODO: `left`, `right`, and the `safe` and `unsafe` overflow alignment keywords.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContentAlignment {
+    /// Behaves as `stretch` for `align-content` and as `flex-start` for `justify-content` in flex
+    /// layout, and as `stretch` in grid layout.
+    Normal,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    /// The first item is placed at the start, the last at the end, and the rest spaced evenly in
+    /// between.
+    SpaceBetween,
+    /// Each item gets an equal amount of space on either side of it.
+    SpaceAround,
+    /// The space between any two items, and before the first and after the last, is equal.
+    SpaceEvenly,
+    /// Any space left over is added equally to the size of the `auto`-sized items.
+    Stretch,
+}
+
+impl ContentAlignment {
+    pub fn initial_value() -> Self {
+        ContentAlignment::Normal
+    }
+}
+
+impl CssValueParse for ContentAlignment {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(ContentAlignment::Normal),
+            "start" => Ok(ContentAlignment::Start),
+            "end" => Ok(ContentAlignment::End),
+            "flex-start" => Ok(ContentAlignment::FlexStart),
+            "flex-end" => Ok(ContentAlignment::FlexEnd),
+            "center" => Ok(ContentAlignment::Center),
+            "space-between" => Ok(ContentAlignment::SpaceBetween),
+            "space-around" => Ok(ContentAlignment::SpaceAround),
+            "space-evenly" => Ok(ContentAlignment::SpaceEvenly),
+            "stretch" => Ok(ContentAlignment::Stretch),
+        }
+    }
+}
+
+impl ComputeValueWithContext for ContentAlignment {
+    type ComputedValue = ContentAlignment;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `align-items` and `justify-items` properties, which are the default alignment of the items
+/// of a box within the space given to each of them along one axis.  This is both the specified and
+/// computed value.
+///
+/// https://drafts.csswg.org/css-align-3/#align-items-property
+/// https://drafts.csswg.org/css-align-3/#justify-items-property
+// TODO: `last baseline`, `left`, `right`, and `legacy` for `justify-items`, and the `safe` and
+// `unsafe` overflow alignment keywords.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ItemAlignment {
+    /// Behaves as `stretch` in flex and grid layout.
+    Normal,
+    /// Items with an `auto` size are sized to fill the space given to them.
+    Stretch,
+    Start,
+    End,
+    SelfStart,
+    SelfEnd,
+    FlexStart,
+    FlexEnd,
+    Center,
+    /// Items are aligned so that their first baselines line up.
+    Baseline,
+}
+
+impl ItemAlignment {
+    pub fn initial_value() -> Self {
+        ItemAlignment::Normal
+    }
+}
+
+impl CssValueParse for ItemAlignment {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(ItemAlignment::Normal),
+            "stretch" => Ok(ItemAlignment::Stretch),
+            "start" => Ok(ItemAlignment::Start),
+            "end" => Ok(ItemAlignment::End),
+            "self-start" => Ok(ItemAlignment::SelfStart),
+            "self-end" => Ok(ItemAlignment::SelfEnd),
+            "flex-start" => Ok(ItemAlignment::FlexStart),
+            "flex-end" => Ok(ItemAlignment::FlexEnd),
+            "center" => Ok(ItemAlignment::Center),
+            "baseline" => Ok(ItemAlignment::Baseline),
+        }
+    }
+}
+
+impl ComputeValueWithContext for ItemAlignment {
+    type ComputedValue = ItemAlignment;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `align-self` and `justify-self` properties, which override the `align-items` or
+/// `justify-items` of a box's parent for that box alone.  This is both the specified and computed
+/// value.
+///
+/// https://drafts.csswg.org/css-align-3/#align-self-property
+/// https://drafts.csswg.org/css-align-3/#justify-self-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlignSelf {
+    /// Takes the `align-items` or `justify-items` of the parent box.
+    Auto,
+    Alignment(ItemAlignment),
+}
+
+impl AlignSelf {
+    pub fn initial_value() -> Self {
+        AlignSelf::Auto
+    }
+
+    /// The alignment of this box, where `parent_align_items` is the `align-items` (or
+    /// `justify-items`) of its parent.
+    pub fn resolve(self, parent_align_items: ItemAlignment) -> ItemAlignment {
+        match self {
+            AlignSelf::Auto => parent_align_items,
+            AlignSelf::Alignment(alignment) => alignment,
+        }
+    }
+}
+
+impl CssValueParse for AlignSelf {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(alignment) = input.try_parse(ItemAlignment::parse) {
+            return Ok(AlignSelf::Alignment(alignment));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(AlignSelf::Auto),
+        }
+    }
+}
+
+impl ComputeValueWithContext for AlignSelf {
+    type ComputedValue = AlignSelf;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// Computed value of a `row-gap` or `column-gap`, the size of the gutters between the rows or
+/// columns of items in a box.
+///
+/// https://drafts.csswg.org/css-align-3/#column-row-gap
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gap {
+    /// Zero in flex and grid layout, and `1em` between the columns of a multi-column container.
+    Normal,
+    LengthPercentage(LengthPercentage),
+}
+
+impl Gap {
+    pub fn initial_value() -> Gap {
+        Gap::Normal
+    }
+
+    /// The size of the gap in pixels, given the size of the content box of the box it's in along
+    /// the same axis, which percentages refer to.  `normal` is zero.
+    pub fn to_px(&self, containing_size: CSSPixelLength) -> CSSPixelLength {
+        match self {
+            Gap::Normal => CSSPixelLength::new(0.),
+            Gap::LengthPercentage(lp) => lp.to_px(containing_size),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Gap {
+    type ComputedValue = Gap;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::Gap::Normal => Gap::Normal,
+            specified::Gap::LengthPercentage(lp) => {
+                Gap::LengthPercentage(lp.compute_value_with_context(context))
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::values::computed::{ComputeContext, ComputeValueWithContext, ValueDefault};
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
 use crate::values::specified;
 use cssparser::RGBA;
 
@@ -28,11 +28,3 @@ impl ComputeValueWithContext for specified::BackgroundColor {
         BackgroundColor(self.unit().compute_value_with_context(context))
     }
 }
-
-impl ValueDefault for specified::BackgroundColor {
-    type ComputedValue = BackgroundColor;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        BackgroundColor::initial_value(context.color().rgba())
-    }
-}
@@ -1,15 +1,11 @@
-use crate::values::computed::{
-    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
-};
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
 use crate::values::specified::border::LineWidth;
 use crate::values::{specified, CssValueParse};
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser, RGBA};
 use primitives::units::CSSPixelLength;
 
-/// The intitial value for `border-<side>-style` properties.  This would normally be handled by
-/// an `impl ValueDefault`, but `border-<side>-style` properties are not complex enough to require
-/// a struct and thus have nothing to `impl ValueDefault` for.
+/// The intitial value for `border-<side>-style` properties.
 ///
 /// https://www.w3.org/TR/2017/CR-css-backgrounds-3-20171017/#the-border-style
 pub fn border_side_initial_style() -> LineStyle {
@@ -32,10 +28,11 @@ pub fn compute_border_side_color(
 pub fn compute_border_side_width(
     self_line_width: LineWidth,
     computed_side_style: LineStyle,
+    context: &ComputeContext,
 ) -> CSSPixelLength {
     match computed_side_style {
         LineStyle::None | LineStyle::Hidden => CSSPixelLength::new(0.),
-        _ => self_line_width.compute_value(),
+        _ => self_line_width.compute_value_with_context(context),
     }
 }
 
@@ -67,19 +64,6 @@ impl ComputeValueWithContext for specified::BorderColor {
     }
 }
 
-impl ValueDefault for specified::BorderColor {
-    type ComputedValue = BorderColor;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        BorderColor::initial_value(
-            context
-                .computed_color
-                .expect("color should've been computed before border-bottom-color value default")
-                .rgba(),
-        )
-    }
-}
-
 /// Computed `border-<side>-width`.
 ///
 /// https://www.w3.org/TR/css-backgrounds-3/#border-width
@@ -90,12 +74,15 @@ pub struct BorderWidth {
 
 impl BorderWidth {
     pub fn initial_value(computed_bottom_style: LineStyle) -> BorderWidth {
-        BorderWidth {
-            size: compute_border_side_width(
-                specified::BorderBottomWidth::initial_value().line_width,
-                computed_bottom_style,
-            ),
-        }
+        // The initial `<line-width>` is a keyword, so it needs no context to be computed.
+        let size = match computed_bottom_style {
+            LineStyle::None | LineStyle::Hidden => CSSPixelLength::new(0.),
+            _ => specified::BorderBottomWidth::initial_value()
+                .line_width
+                .keyword_px()
+                .expect("initial line width should be a keyword"),
+        };
+        BorderWidth { size }
     }
 }
 
@@ -104,73 +91,45 @@ impl ComputeValueWithContext for specified::BorderBottomWidth {
 
     fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         BorderWidth {
-            size: compute_border_side_width(self.line_width, context.border_bottom_style()),
+            size: compute_border_side_width(
+                self.line_width,
+                context.border_bottom_style(),
+                context,
+            ),
         }
     }
 }
 
-impl ValueDefault for specified::BorderBottomWidth {
-    type ComputedValue = BorderWidth;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        BorderWidth::initial_value(context.border_bottom_style())
-    }
-}
-
 impl ComputeValueWithContext for specified::BorderLeftWidth {
     type ComputedValue = BorderWidth;
 
     fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         BorderWidth {
-            size: compute_border_side_width(self.line_width, context.border_left_style()),
+            size: compute_border_side_width(self.line_width, context.border_left_style(), context),
         }
     }
 }
 
-impl ValueDefault for specified::BorderLeftWidth {
-    type ComputedValue = BorderWidth;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        BorderWidth::initial_value(context.border_left_style())
-    }
-}
-
 impl ComputeValueWithContext for specified::BorderRightWidth {
     type ComputedValue = BorderWidth;
 
     fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         BorderWidth {
-            size: compute_border_side_width(self.line_width, context.border_right_style()),
+            size: compute_border_side_width(self.line_width, context.border_right_style(), context),
         }
     }
 }
 
-impl ValueDefault for specified::BorderRightWidth {
-    type ComputedValue = BorderWidth;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        BorderWidth::initial_value(context.border_right_style())
-    }
-}
-
 impl ComputeValueWithContext for specified::BorderTopWidth {
     type ComputedValue = BorderWidth;
 
     fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         BorderWidth {
-            size: compute_border_side_width(self.line_width, context.border_top_style()),
+            size: compute_border_side_width(self.line_width, context.border_top_style(), context),
         }
     }
 }
 
-impl ValueDefault for specified::BorderTopWidth {
-    type ComputedValue = BorderWidth;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        BorderWidth::initial_value(context.border_top_style())
-    }
-}
-
 /// The border `<line-style>` type.
 ///
 /// https://www.w3.org/TR/2017/CR-css-backgrounds-3-20171017/#typedef-line-style
@@ -207,15 +166,27 @@ impl CssValueParse for LineStyle {
     }
 }
 
-impl ComputeValue for LineWidth {
+impl LineWidth {
+    /// The size of this line width if it is one of the `thin`, `medium`, or `thick` keywords.
+    pub fn keyword_px(&self) -> Option<CSSPixelLength> {
+        match self {
+            LineWidth::Thin => Some(CSSPixelLength::new(1.)),
+            LineWidth::Medium => Some(CSSPixelLength::new(3.)),
+            LineWidth::Thick => Some(CSSPixelLength::new(5.)),
+            LineWidth::Length(_) => None,
+        }
+    }
+}
+
+impl ComputeValueWithContext for LineWidth {
     type ComputedValue = CSSPixelLength;
 
-    fn compute_value(&self) -> Self::ComputedValue {
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         match self {
-            LineWidth::Thin => CSSPixelLength::new(1.),
-            LineWidth::Medium => CSSPixelLength::new(3.),
-            LineWidth::Thick => CSSPixelLength::new(5.),
-            LineWidth::Length(no_calc_len) => no_calc_len.compute_value(),
+            LineWidth::Length(no_calc_len) => no_calc_len.compute_value_with_context(context),
+            keyword => keyword
+                .keyword_px()
+                .expect("non-length line widths should be keywords"),
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::values::computed::{ComputeContext, ComputeValueWithContext, ValueDefault};
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
 use crate::values::specified;
 use cssparser::RGBA;
 
@@ -52,18 +52,9 @@ impl ComputeValueWithContext for specified::Color {
 
     fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         match self {
-            specified::Color::Inherit => context.parent_computed_values.color,
             specified::Color::Unit(color_unit) => {
                 Color(color_unit.compute_value_with_context(context))
             }
         }
     }
 }
-
-impl ValueDefault for specified::Color {
-    type ComputedValue = Color;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        context.parent_computed_values.color
-    }
-}
@@ -1,4 +1,4 @@
-use crate::values::computed::{ComputeContext, ValueDefault};
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
 use crate::values::CssValueParse;
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
@@ -27,11 +27,12 @@ impl CssValueParse for Direction {
     }
 }
 
-impl ValueDefault for Direction {
+/// `direction` is specified as its computed value.
+impl ComputeValueWithContext for Direction {
     type ComputedValue = Direction;
 
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        context.parent_computed_values.direction
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
     }
 }
 
@@ -78,10 +79,11 @@ impl CssValueParse for WritingMode {
     }
 }
 
-impl ValueDefault for WritingMode {
+/// `writing-mode` is specified as its computed value.
+impl ComputeValueWithContext for WritingMode {
     type ComputedValue = WritingMode;
 
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        context.parent_computed_values.writing_mode
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
     }
 }
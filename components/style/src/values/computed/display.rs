@@ -1,4 +1,4 @@
-use crate::values::computed::{ComputeContext, ValueDefault};
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
 use crate::values::CssValueParse;
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
@@ -9,6 +9,7 @@ use cssparser::{ParseError, Parser};
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Display {
     Full(FullDisplay),
+    Internal(InternalDisplay),
     Box(DisplayBox),
 }
 
@@ -36,6 +37,30 @@ impl Display {
     pub fn initial_value() -> Self {
         Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Flow)
     }
+
+    /// The inner display type of this `Display`, if it generates boxes other than the internal
+    /// boxes of a table.
+    pub fn inner(&self) -> Option<InnerDisplay> {
+        match self {
+            Display::Full(full_display) => Some(full_display.inner()),
+            Display::Internal(_) | Display::Box(_) => None,
+        }
+    }
+
+    /// This `Display` with its outer display type set to `block`, which is how boxes that can
+    /// only be block-level (like flex and grid items) are displayed.  An `inline-block` becomes a
+    /// `flow-root`, and the internal display types of tables become `block`.
+    ///
+    /// https://drafts.csswg.org/css-display-3/#blockify
+    pub fn blockified(self) -> Display {
+        match self {
+            Display::Full(full_display) => {
+                Display::new_full_display(OuterDisplay::Block, full_display.inner())
+            }
+            Display::Internal(_) => Display::new_block(),
+            Display::Box(_) => self,
+        }
+    }
 }
 
 impl CssValueParse for Display {
@@ -52,15 +77,37 @@ impl CssValueParse for Display {
             "inline flow" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Flow)),
             "inline-block" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::FlowRoot)),
             "inline flow-root" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::FlowRoot)),
+            "flex" => Ok(Display::new_full_display(OuterDisplay::Block, InnerDisplay::Flex)),
+            "block flex" => Ok(Display::new_full_display(OuterDisplay::Block, InnerDisplay::Flex)),
+            "inline-flex" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Flex)),
+            "inline flex" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Flex)),
+            "grid" => Ok(Display::new_full_display(OuterDisplay::Block, InnerDisplay::Grid)),
+            "block grid" => Ok(Display::new_full_display(OuterDisplay::Block, InnerDisplay::Grid)),
+            "inline-grid" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Grid)),
+            "inline grid" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Grid)),
+            "table" => Ok(Display::new_full_display(OuterDisplay::Block, InnerDisplay::Table)),
+            "block table" => Ok(Display::new_full_display(OuterDisplay::Block, InnerDisplay::Table)),
+            "inline-table" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Table)),
+            "inline table" => Ok(Display::new_full_display(OuterDisplay::Inline, InnerDisplay::Table)),
+            "table-row-group" => Ok(Display::Internal(InternalDisplay::TableRowGroup)),
+            "table-header-group" => Ok(Display::Internal(InternalDisplay::TableHeaderGroup)),
+            "table-footer-group" => Ok(Display::Internal(InternalDisplay::TableFooterGroup)),
+            "table-row" => Ok(Display::Internal(InternalDisplay::TableRow)),
+            "table-cell" => Ok(Display::Internal(InternalDisplay::TableCell)),
+            "table-column-group" => Ok(Display::Internal(InternalDisplay::TableColumnGroup)),
+            "table-column" => Ok(Display::Internal(InternalDisplay::TableColumn)),
+            "table-caption" => Ok(Display::Internal(InternalDisplay::TableCaption)),
         }
     }
 }
 
-impl ValueDefault for Display {
+// TODO: Should `display` be specified as its computed value?  Computing display might not be
+// straightforward — see: https://github.com/w3c/csswg-drafts/issues/1716
+impl ComputeValueWithContext for Display {
     type ComputedValue = Display;
 
-    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
-        Display::initial_value()
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
     }
 }
 
@@ -104,6 +151,45 @@ pub enum OuterDisplay {
 pub enum InnerDisplay {
     Flow,
     FlowRoot,
+    /// The box is a flex container, laying out its contents in a flex formatting context.
+    /// https://drafts.csswg.org/css-flexbox-1/#flex-containers
+    Flex,
+    /// The box is a grid container, laying out its contents in a grid formatting context.
+    /// https://drafts.csswg.org/css-grid-1/#grid-containers
+    Grid,
+    /// The box is a table, laying out its contents in a table formatting context.
+    /// https://www.w3.org/TR/CSS22/tables.html#table-display
+    Table,
+}
+
+/// Layout-internal display types, which give the role of a box within the structure of a table.
+/// Boxes with these display types that aren't inside a table of the right structure are wrapped
+/// in anonymous table boxes that complete it.
+///
+/// https://drafts.csswg.org/css-display-3/#layout-specific-display
+/// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InternalDisplay {
+    TableRowGroup,
+    TableHeaderGroup,
+    TableFooterGroup,
+    TableRow,
+    TableCell,
+    TableColumnGroup,
+    TableColumn,
+    TableCaption,
+}
+
+impl InternalDisplay {
+    /// Whether this is the display type of a row group: a body, header, or footer group.
+    pub fn is_row_group(self) -> bool {
+        matches!(
+            self,
+            InternalDisplay::TableRowGroup
+                | InternalDisplay::TableHeaderGroup
+                | InternalDisplay::TableFooterGroup
+        )
+    }
 }
 
 /// https://drafts.csswg.org/css-display/#typedef-display-box
@@ -0,0 +1,201 @@
+use crate::values::computed::length::LengthPercentageOrAuto;
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
+use crate::values::specified;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSFloat;
+
+/// The `flex-direction` property, which sets the direction of the main axis of a flex container,
+/// along which its items are placed.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-direction-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlexDirection {
+    /// The main axis is the inline axis of the container's writing mode.
+    Row,
+    RowReverse,
+    /// The main axis is the block axis of the container's writing mode.
+    Column,
+    ColumnReverse,
+}
+
+impl FlexDirection {
+    pub fn initial_value() -> Self {
+        FlexDirection::Row
+    }
+
+    /// Whether the main axis is the block axis.
+    pub fn is_column(self) -> bool {
+        matches!(self, FlexDirection::Column | FlexDirection::ColumnReverse)
+    }
+
+    /// Whether the main-start and main-end directions are swapped.
+    pub fn is_reverse(self) -> bool {
+        matches!(self, FlexDirection::RowReverse | FlexDirection::ColumnReverse)
+    }
+}
+
+impl CssValueParse for FlexDirection {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "row" => Ok(FlexDirection::Row),
+            "row-reverse" => Ok(FlexDirection::RowReverse),
+            "column" => Ok(FlexDirection::Column),
+            "column-reverse" => Ok(FlexDirection::ColumnReverse),
+        }
+    }
+}
+
+impl ComputeValueWithContext for FlexDirection {
+    type ComputedValue = FlexDirection;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `flex-wrap` property, which determines whether a flex container is single-line or
+/// multi-line.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-wrap-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlexWrap {
+    Nowrap,
+    Wrap,
+    /// Like `wrap`, but with the cross-start and cross-end directions swapped.
+    WrapReverse,
+}
+
+impl FlexWrap {
+    pub fn initial_value() -> Self {
+        FlexWrap::Nowrap
+    }
+}
+
+impl CssValueParse for FlexWrap {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "nowrap" => Ok(FlexWrap::Nowrap),
+            "wrap" => Ok(FlexWrap::Wrap),
+            "wrap-reverse" => Ok(FlexWrap::WrapReverse),
+        }
+    }
+}
+
+impl ComputeValueWithContext for FlexWrap {
+    type ComputedValue = FlexWrap;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The value of `flex-grow` or `flex-shrink`, a non-negative number giving how much a flex item
+/// grows or shrinks relative to the rest of the items on its line.  This is both the specified and
+/// computed value.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-grow-property
+/// https://drafts.csswg.org/css-flexbox-1/#flex-shrink-property
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlexFactor(CSSFloat);
+
+impl FlexFactor {
+    pub fn new(factor: CSSFloat) -> Self {
+        FlexFactor(factor)
+    }
+
+    pub fn get(self) -> CSSFloat {
+        self.0
+    }
+}
+
+impl CssValueParse for FlexFactor {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let factor = input.expect_number()?;
+        if factor >= 0. {
+            Ok(FlexFactor(factor))
+        } else {
+            Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+        }
+    }
+}
+
+impl ComputeValueWithContext for FlexFactor {
+    type ComputedValue = FlexFactor;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// Computed value of `flex-basis`, the initial main size of a flex item.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-basis-property
+#[derive(Clone, Debug, PartialEq)]
+pub enum FlexBasis {
+    /// The size of the item's content.
+    Content,
+    /// A size as given by `width` or `height`, where `auto` uses the item's main size property.
+    Width(LengthPercentageOrAuto),
+}
+
+impl FlexBasis {
+    pub fn initial_value() -> FlexBasis {
+        FlexBasis::Width(LengthPercentageOrAuto::Auto)
+    }
+}
+
+impl ComputeValueWithContext for specified::FlexBasis {
+    type ComputedValue = FlexBasis;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::FlexBasis::Content => FlexBasis::Content,
+            specified::FlexBasis::Width(lp_or_auto) => {
+                FlexBasis::Width(lp_or_auto.compute_value_with_context(context))
+            }
+        }
+    }
+}
+
+/// The `order` property, which places the items of a flex container in ascending order of this
+/// value rather than in the order they appear in the document.  This is both the specified and
+/// computed value.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#order-property
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Order(i32);
+
+impl Order {
+    pub fn new(order: i32) -> Self {
+        Order(order)
+    }
+
+    pub fn initial_value() -> Self {
+        Order(0)
+    }
+}
+
+impl CssValueParse for Order {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        Ok(Order(input.expect_integer()?))
+    }
+}
+
+impl ComputeValueWithContext for Order {
+    type ComputedValue = Order;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
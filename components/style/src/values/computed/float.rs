@@ -0,0 +1,119 @@
+use crate::values::computed::{ComputeContext, ComputeValueWithContext, Direction};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::sides::PhysicalSide;
+
+/// The `float` property, which takes a box out of flow and shifts it to one side of its containing
+/// block, letting content flow around it.  This is both the specified and computed value.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#float-position
+/// https://drafts.csswg.org/css-logical-1/#float-clear
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Float {
+    None,
+    Left,
+    Right,
+    InlineStart,
+    InlineEnd,
+}
+
+impl Float {
+    pub fn initial_value() -> Self {
+        Float::None
+    }
+
+    /// The physical side (`Left` or `Right`) of a containing block with the given `direction`
+    /// that this float is shifted to, or `None` if the box isn't floated.
+    // TODO: The inline axis is only horizontal for horizontal writing modes.
+    pub fn physical_side(self, direction: Direction) -> Option<PhysicalSide> {
+        match (self, direction) {
+            (Float::None, _) => None,
+            (Float::Left, _)
+            | (Float::InlineStart, Direction::Ltr)
+            | (Float::InlineEnd, Direction::Rtl) => Some(PhysicalSide::Left),
+            (Float::Right, _)
+            | (Float::InlineStart, Direction::Rtl)
+            | (Float::InlineEnd, Direction::Ltr) => Some(PhysicalSide::Right),
+        }
+    }
+}
+
+impl CssValueParse for Float {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(Float::None),
+            "left" => Ok(Float::Left),
+            "right" => Ok(Float::Right),
+            "inline-start" => Ok(Float::InlineStart),
+            "inline-end" => Ok(Float::InlineEnd),
+        }
+    }
+}
+
+impl ComputeValueWithContext for Float {
+    type ComputedValue = Float;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `clear` property, which moves a box below the floats on the given sides of it that precede
+/// it in the same block formatting context.  This is both the specified and computed value.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#flow-control
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Clear {
+    None,
+    Left,
+    Right,
+    Both,
+    InlineStart,
+    InlineEnd,
+}
+
+impl Clear {
+    pub fn initial_value() -> Self {
+        Clear::None
+    }
+
+    /// Whether this clears floats shifted to the physical `side` of a containing block with the
+    /// given `direction`.
+    pub fn clears(self, side: PhysicalSide, direction: Direction) -> bool {
+        let cleared = match self {
+            Clear::None => return false,
+            Clear::Both => return true,
+            Clear::Left => Float::Left,
+            Clear::Right => Float::Right,
+            Clear::InlineStart => Float::InlineStart,
+            Clear::InlineEnd => Float::InlineEnd,
+        };
+        cleared.physical_side(direction) == Some(side)
+    }
+}
+
+impl CssValueParse for Clear {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(Clear::None),
+            "left" => Ok(Clear::Left),
+            "right" => Ok(Clear::Right),
+            "both" => Ok(Clear::Both),
+            "inline-start" => Ok(Clear::InlineStart),
+            "inline-end" => Ok(Clear::InlineEnd),
+        }
+    }
+}
+
+impl ComputeValueWithContext for Clear {
+    type ComputedValue = Clear;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
@@ -1,11 +1,13 @@
-use crate::values::computed::{
-    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
-};
+use crate::values::computed::length::LengthPercentage as ComputedLengthPercentage;
+use crate::values::computed::{ComputeContext, ComputeValue, ComputeValueWithContext, Percentage};
 use crate::values::specified;
 use crate::values::specified::font::KeywordSize;
 use crate::values::specified::{LengthPercentage, NoCalcLength};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
 use app_units::Au;
-use primitives::units::CSSPixelLength;
+use cssparser::{ParseError, Parser, Token};
+use primitives::units::{CSSFloat, CSSPixelLength};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 /// The computed value of font-size
@@ -33,32 +35,81 @@ impl FontSize {
     }
 }
 
-impl ValueDefault for specified::FontSize {
-    type ComputedValue = FontSize;
-
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
-        context.parent_computed_values.font_size
-    }
-}
-
 impl ComputeValueWithContext for specified::FontSize {
     type ComputedValue = FontSize;
 
     fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        let parent_font = context.parent_computed_values.font_size;
         let (size_px, keyword_size) = match self {
             specified::FontSize::Keyword(keyword_size) => (
                 keyword_size.compute_value_with_context(&context),
                 Some(*keyword_size),
             ),
+            specified::FontSize::Larger => (
+                CSSPixelLength::from(Au::from(parent_font.size).scale_by(LARGER_FONT_SIZE_RATIO)),
+                None,
+            ),
+            specified::FontSize::Smaller => (
+                CSSPixelLength::from(
+                    Au::from(parent_font.size).scale_by(1. / LARGER_FONT_SIZE_RATIO),
+                ),
+                None,
+            ),
             specified::FontSize::Length(LengthPercentage::Length(NoCalcLength::Absolute(
                 abs_len,
             ))) => (abs_len.compute_value(), None),
-            specified::FontSize::Length(specified::LengthPercentage::Percentage(percentage)) => {
-                let parent_font = context.parent_computed_values.font_size;
-                (
-                    CSSPixelLength::from(Au::from(parent_font.size).scale_by(percentage.0)),
-                    None,
-                )
+            // Font-relative lengths in `font-size` refer to the parent's font size, since this
+            // element's font size is what is being computed.  `rem` on the root element refers to
+            // the initial font size.
+            // https://drafts.csswg.org/css-values/#font-relative-lengths
+            specified::FontSize::Length(LengthPercentage::Length(NoCalcLength::FontRelative(
+                font_relative_len,
+            ))) => (
+                CSSPixelLength::new(
+                    font_relative_len.to_px(
+                        parent_font.size.px(),
+                        context
+                            .root_font_size
+                            .unwrap_or_else(FontSize::initial_value)
+                            .size
+                            .px(),
+                    ),
+                ),
+                None,
+            ),
+            specified::FontSize::Length(LengthPercentage::Length(
+                NoCalcLength::ViewportPercentage(viewport_percentage_len),
+            )) => (
+                CSSPixelLength::new(viewport_percentage_len.to_px(context.viewport)),
+                None,
+            ),
+            specified::FontSize::Length(specified::LengthPercentage::Percentage(percentage)) => (
+                CSSPixelLength::from(Au::from(parent_font.size).scale_by(percentage.0)),
+                None,
+            ),
+            // Percentages in `font-size` refer to the parent's font size, so math functions can
+            // always be resolved to a length here.  Font sizes can't be negative, so the result is
+            // clamped.
+            // https://drafts.csswg.org/css-values-4/#calc-range
+            specified::FontSize::Length(specified::LengthPercentage::Calc(calc)) => {
+                let root_font_size = context
+                    .root_font_size
+                    .unwrap_or_else(FontSize::initial_value)
+                    .size
+                    .px();
+                let size_px = calc.resolve(
+                    &|len| match len {
+                        NoCalcLength::Absolute(abs_len) => abs_len.to_px(),
+                        NoCalcLength::FontRelative(font_relative_len) => {
+                            font_relative_len.to_px(parent_font.size.px(), root_font_size)
+                        }
+                        NoCalcLength::ViewportPercentage(viewport_percentage_len) => {
+                            viewport_percentage_len.to_px(context.viewport)
+                        }
+                    },
+                    parent_font.size.px(),
+                );
+                (CSSPixelLength::new(size_px.max(0.)), None)
             }
         };
 
@@ -69,6 +120,12 @@ impl ComputeValueWithContext for specified::FontSize {
     }
 }
 
+/// The ratio by which `font-size: larger` scales the parent's font size.  `font-size: smaller`
+/// scales by the inverse of this ratio.
+///
+/// https://drafts.csswg.org/css-fonts-3/#relative-size-value
+pub const LARGER_FONT_SIZE_RATIO: f32 = 1.2;
+
 /// The default font size.
 pub const FONT_MEDIUM_PX: i32 = 16;
 
@@ -91,3 +148,333 @@ impl ComputeValueWithContext for KeywordSize {
         .into()
     }
 }
+
+/// A generic font family, which the user agent maps to an installed font.
+///
+/// https://drafts.csswg.org/css-fonts-4/#generic-font-families
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GenericFontFamily {
+    Serif,
+    SansSerif,
+    Monospace,
+    Cursive,
+    Fantasy,
+    SystemUi,
+}
+
+/// An entry in a `font-family` list.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum FamilyName {
+    /// A specific family, e.g. `"Helvetica Neue"` or `Georgia`.
+    Specific(String),
+    Generic(GenericFontFamily),
+}
+
+impl FamilyName {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(name) = input.try_parse(|i| i.expect_string_cloned()) {
+            return Ok(FamilyName::Specific(name.to_string()));
+        }
+        let first_ident = input.expect_ident_cloned()?;
+        let mut idents = Vec::new();
+        while let Ok(ident) = input.try_parse(|i| i.expect_ident_cloned()) {
+            idents.push(ident);
+        }
+        // Generic families are keywords, so only match when unquoted and alone.
+        // https://drafts.csswg.org/css-fonts-4/#family-name-syntax
+        if idents.is_empty() {
+            let generic = match_ignore_ascii_case! { &first_ident,
+                "serif" => Some(GenericFontFamily::Serif),
+                "sans-serif" => Some(GenericFontFamily::SansSerif),
+                "monospace" => Some(GenericFontFamily::Monospace),
+                "cursive" => Some(GenericFontFamily::Cursive),
+                "fantasy" => Some(GenericFontFamily::Fantasy),
+                "system-ui" => Some(GenericFontFamily::SystemUi),
+                _ => None,
+            };
+            if let Some(generic) = generic {
+                return Ok(FamilyName::Generic(generic));
+            }
+        }
+        let mut name = first_ident.to_string();
+        for ident in idents {
+            name.push(' ');
+            name.push_str(&ident);
+        }
+        Ok(FamilyName::Specific(name))
+    }
+}
+
+/// The `font-family` property, a prioritized list of the families to render text with.  This is
+/// both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-family-prop
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FontFamily(pub Vec<FamilyName>);
+
+impl FontFamily {
+    /// The initial value of `font-family` depends on the user agent.  Like most browsers,
+    /// Kosmonaut uses a serif font.
+    pub fn initial_value() -> Self {
+        FontFamily(vec![FamilyName::Generic(GenericFontFamily::Serif)])
+    }
+}
+
+impl CssValueParse for FontFamily {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(FamilyName::parse)
+            .map(FontFamily)
+    }
+}
+
+impl ComputeValueWithContext for FontFamily {
+    type ComputedValue = FontFamily;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        self.clone()
+    }
+}
+
+/// The `font-style` property.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-style-prop
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+    /// An oblique face, slanted by the given angle in degrees.
+    Oblique(CSSFloat),
+}
+
+/// The angle `font-style: oblique` slants by when none is given.
+pub const DEFAULT_OBLIQUE_DEGREES: CSSFloat = 14.;
+
+impl FontStyle {
+    pub fn initial_value() -> Self {
+        FontStyle::Normal
+    }
+}
+
+impl CssValueParse for FontStyle {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let ident = input.expect_ident_cloned()?;
+        match_ignore_ascii_case! { &ident,
+            "normal" => Ok(FontStyle::Normal),
+            "italic" => Ok(FontStyle::Italic),
+            "oblique" => {
+                let degrees = input
+                    .try_parse(parse_oblique_angle)
+                    .unwrap_or(DEFAULT_OBLIQUE_DEGREES);
+                Ok(FontStyle::Oblique(degrees))
+            },
+            _ => Err(location.new_custom_error(StyleParseErrorKind::OtherInvalidValue(ident))),
+        }
+    }
+}
+
+/// Parses the angle of `font-style: oblique <angle>`, which must be between -90deg and 90deg.
+fn parse_oblique_angle<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<CSSFloat, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?.clone();
+    let degrees = match token {
+        Token::Dimension {
+            value, ref unit, ..
+        } => match_ignore_ascii_case! { unit,
+            "deg" => Some(value),
+            "grad" => Some(value * 360. / 400.),
+            "rad" => Some(value.to_degrees()),
+            "turn" => Some(value * 360.),
+            _ => None,
+        },
+        _ => None,
+    };
+    match degrees {
+        Some(degrees) if (-90. ..=90.).contains(&degrees) => Ok(degrees),
+        _ => Err(location.new_unexpected_token_error(token)),
+    }
+}
+
+impl ComputeValueWithContext for FontStyle {
+    type ComputedValue = FontStyle;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `font-stretch` property, as a percentage of the normal width of a face.  This is both the
+/// specified and computed value, since keywords compute to percentages.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-stretch-prop
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontStretch(pub Percentage);
+
+impl FontStretch {
+    pub fn initial_value() -> Self {
+        FontStretch(Percentage::hundred())
+    }
+
+    /// Parses one of the `font-stretch` keywords, which are all the `font` shorthand accepts.
+    pub fn parse_keyword<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let percentage = try_match_ident_ignore_ascii_case! { input,
+            "ultra-condensed" => 0.5,
+            "extra-condensed" => 0.625,
+            "condensed" => 0.75,
+            "semi-condensed" => 0.875,
+            "normal" => 1.,
+            "semi-expanded" => 1.125,
+            "expanded" => 1.25,
+            "extra-expanded" => 1.5,
+            "ultra-expanded" => 2.,
+        };
+        Ok(FontStretch(Percentage(percentage)))
+    }
+}
+
+impl CssValueParse for FontStretch {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        if let Ok(percentage) = input.try_parse(|i| i.expect_percentage()) {
+            return if percentage >= 0. {
+                Ok(FontStretch(Percentage(percentage)))
+            } else {
+                Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+            };
+        }
+        FontStretch::parse_keyword(input)
+    }
+}
+
+impl ComputeValueWithContext for FontStretch {
+    type ComputedValue = FontStretch;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The computed value of `font-weight`, a number between 1 and 1000, inclusive.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-weight-prop
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct FontWeight(pub CSSFloat);
+
+impl FontWeight {
+    pub fn initial_value() -> Self {
+        FontWeight::normal()
+    }
+
+    pub fn normal() -> Self {
+        FontWeight(400.)
+    }
+
+    pub fn bold() -> Self {
+        FontWeight(700.)
+    }
+
+    /// The weight `font-weight: bolder` gives when the parent's weight is `self`.
+    ///
+    /// https://drafts.csswg.org/css-fonts-4/#relative-weights
+    pub fn bolder(self) -> Self {
+        match self.0 {
+            w if w < 350. => FontWeight(400.),
+            w if w < 550. => FontWeight(700.),
+            w if w < 900. => FontWeight(900.),
+            _ => self,
+        }
+    }
+
+    /// The weight `font-weight: lighter` gives when the parent's weight is `self`.
+    ///
+    /// https://drafts.csswg.org/css-fonts-4/#relative-weights
+    pub fn lighter(self) -> Self {
+        match self.0 {
+            w if w < 100. => self,
+            w if w < 550. => FontWeight(100.),
+            w if w < 750. => FontWeight(400.),
+            _ => FontWeight(700.),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::FontWeight {
+    type ComputedValue = FontWeight;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        let parent_weight = context.parent_computed_values.font_weight;
+        match self {
+            specified::FontWeight::Absolute(weight) => FontWeight(*weight),
+            specified::FontWeight::Bolder => parent_weight.bolder(),
+            specified::FontWeight::Lighter => parent_weight.lighter(),
+        }
+    }
+}
+
+/// The computed value of `line-height`.
+///
+/// https://drafts.csswg.org/css2/#propdef-line-height
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineHeight {
+    Normal,
+    /// A multiple of the font size.  Unlike lengths, this is inherited as-is, so descendants with
+    /// a different font size get a proportional line height.
+    Number(CSSFloat),
+    Length(CSSPixelLength),
+}
+
+impl LineHeight {
+    pub fn initial_value() -> Self {
+        LineHeight::Normal
+    }
+}
+
+impl ComputeValueWithContext for specified::LineHeight {
+    type ComputedValue = LineHeight;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::LineHeight::Normal => LineHeight::Normal,
+            specified::LineHeight::Number(number) => LineHeight::Number(*number),
+            // Percentages refer to the element's own font size, so can always be resolved here.
+            specified::LineHeight::LengthPercentage(lp) => {
+                let font_size = context.font_size().size;
+                let length = match lp.compute_value_with_context(context) {
+                    ComputedLengthPercentage::Length(length) => length,
+                    ComputedLengthPercentage::Percentage(percentage) => {
+                        percentage.px_relative_to(font_size)
+                    }
+                    ComputedLengthPercentage::Calc(calc) => {
+                        CSSPixelLength::new(calc.resolve(&|len| len.px(), font_size.px()))
+                    }
+                };
+                LineHeight::Length(CSSPixelLength::new(length.px().max(0.)))
+            }
+        }
+    }
+}
+
+/// Everything needed to select a font face and size for an element's text.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-style-matching
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontDescription {
+    pub family: FontFamily,
+    pub style: FontStyle,
+    pub weight: FontWeight,
+    pub stretch: FontStretch,
+    pub size: FontSize,
+}
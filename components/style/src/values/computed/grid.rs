@@ -0,0 +1,531 @@
+use crate::values::computed::length::LengthPercentage;
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSFloat;
+use std::ops::Range;
+
+/// A sizing function giving the minimum or maximum size of a grid track, where `L` is the type of
+/// its lengths.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-track-breadth
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrackBreadth<L = LengthPercentage> {
+    Length(L),
+    /// A share of the space left over once the inflexible tracks are sized, in `fr` units.  Only
+    /// valid as a maximum.
+    Flex(CSSFloat),
+    /// The largest min-content contribution of the items in the track as a minimum, and the
+    /// largest max-content contribution as a maximum.
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+impl<L> TrackBreadth<L> {
+    fn map<M>(&self, f: &impl Fn(&L) -> M) -> TrackBreadth<M> {
+        match self {
+            TrackBreadth::Length(length) => TrackBreadth::Length(f(length)),
+            TrackBreadth::Flex(flex) => TrackBreadth::Flex(*flex),
+            TrackBreadth::Auto => TrackBreadth::Auto,
+            TrackBreadth::MinContent => TrackBreadth::MinContent,
+            TrackBreadth::MaxContent => TrackBreadth::MaxContent,
+        }
+    }
+}
+
+/// The size of a grid track, as given by `grid-template-columns`, `grid-auto-rows`, and the like.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-track-size
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrackSize<L = LengthPercentage> {
+    Breadth(TrackBreadth<L>),
+    /// `minmax(min, max)`.
+    MinMax(TrackBreadth<L>, TrackBreadth<L>),
+}
+
+impl<L: Clone> TrackSize<L> {
+    /// The minimum sizing function of this track size.  A lone flexible size has a minimum of
+    /// `auto`.
+    pub fn min(&self) -> TrackBreadth<L> {
+        match self {
+            TrackSize::Breadth(TrackBreadth::Flex(_)) => TrackBreadth::Auto,
+            TrackSize::Breadth(breadth) | TrackSize::MinMax(breadth, _) => breadth.clone(),
+        }
+    }
+
+    /// The maximum sizing function of this track size.
+    pub fn max(&self) -> TrackBreadth<L> {
+        match self {
+            TrackSize::Breadth(breadth) | TrackSize::MinMax(_, breadth) => breadth.clone(),
+        }
+    }
+
+    /// Whether this track size has a fixed minimum or maximum, as the tracks repeated to fill a
+    /// grid must.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#typedef-fixed-size
+    pub fn is_fixed(&self) -> bool {
+        matches!(self.min(), TrackBreadth::Length(_))
+            || matches!(self.max(), TrackBreadth::Length(_))
+    }
+}
+
+impl<L> TrackSize<L> {
+    fn map<M>(&self, f: &impl Fn(&L) -> M) -> TrackSize<M> {
+        match self {
+            TrackSize::Breadth(breadth) => TrackSize::Breadth(breadth.map(f)),
+            TrackSize::MinMax(min, max) => TrackSize::MinMax(min.map(f), max.map(f)),
+        }
+    }
+}
+
+/// How many times the tracks of an automatic `repeat()` are repeated.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-repeat
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepeatCount {
+    /// As many times as fit in the grid container.
+    AutoFill,
+    /// Like `auto-fill`, but repeated tracks that end up without any items are collapsed.
+    AutoFit,
+}
+
+impl CssValueParse for RepeatCount {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "auto-fill" => Ok(RepeatCount::AutoFill),
+            "auto-fit" => Ok(RepeatCount::AutoFit),
+        }
+    }
+}
+
+/// The `repeat(auto-fill, ...)` or `repeat(auto-fit, ...)` in a track list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AutoRepeat<L = LengthPercentage> {
+    pub count: RepeatCount,
+    /// The index of the track in the track list the repeated tracks are inserted before.
+    pub position: usize,
+    /// The names of the lines before, between, and after the repeated tracks.
+    pub line_names: Vec<Vec<String>>,
+    pub tracks: Vec<TrackSize<L>>,
+}
+
+/// The tracks of an explicit grid along one axis, and the names of the lines between them.
+/// Repetitions with a fixed count are expanded when parsed.
+///
+/// https://drafts.csswg.org/css-grid-1/#track-sizing
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackList<L = LengthPercentage> {
+    /// The names of each line, one more than there are `tracks`.  If there's an automatic
+    /// repetition, the line it's inserted at has two entries: the names of the line before it, and
+    /// those of the line after it.
+    pub line_names: Vec<Vec<String>>,
+    pub tracks: Vec<TrackSize<L>>,
+    pub auto_repeat: Option<AutoRepeat<L>>,
+}
+
+impl<L> TrackList<L> {
+    fn map<M>(&self, f: &impl Fn(&L) -> M) -> TrackList<M> {
+        TrackList {
+            line_names: self.line_names.clone(),
+            tracks: self.tracks.iter().map(|track| track.map(f)).collect(),
+            auto_repeat: self.auto_repeat.as_ref().map(|auto_repeat| AutoRepeat {
+                count: auto_repeat.count,
+                position: auto_repeat.position,
+                line_names: auto_repeat.line_names.clone(),
+                tracks: auto_repeat
+                    .tracks
+                    .iter()
+                    .map(|track| track.map(f))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+impl<L: Clone> TrackList<L> {
+    /// The names of the lines and the sizes of the tracks of this list once its automatic
+    /// repetition, if there is one, is repeated `repetitions` times.
+    pub fn expand(&self, repetitions: usize) -> (Vec<Vec<String>>, Vec<TrackSize<L>>) {
+        let auto_repeat = match &self.auto_repeat {
+            Some(auto_repeat) => auto_repeat,
+            None => return (self.line_names.clone(), self.tracks.clone()),
+        };
+        let position = auto_repeat.position;
+        let mut line_names = self.line_names[..=position].to_vec();
+        let mut tracks = self.tracks[..position].to_vec();
+        for _ in 0..repetitions {
+            extend_last(&mut line_names, &auto_repeat.line_names[0]);
+            for (track, names) in auto_repeat.tracks.iter().zip(&auto_repeat.line_names[1..]) {
+                tracks.push(track.clone());
+                line_names.push(names.clone());
+            }
+        }
+        extend_last(&mut line_names, &self.line_names[position + 1]);
+        for (track, names) in self.tracks[position..]
+            .iter()
+            .zip(&self.line_names[position + 2..])
+        {
+            tracks.push(track.clone());
+            line_names.push(names.clone());
+        }
+        (line_names, tracks)
+    }
+}
+
+/// Adds `names` to the last set of names in `line_names`.
+fn extend_last(line_names: &mut [Vec<String>], names: &[String]) {
+    if let Some(last) = line_names.last_mut() {
+        last.extend(names.iter().cloned());
+    }
+}
+
+/// Computed value of `grid-template-columns` or `grid-template-rows`, the tracks of the explicit
+/// grid along one axis.
+///
+/// https://drafts.csswg.org/css-grid-1/#track-sizing
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridTemplate<L = LengthPercentage> {
+    /// There are no explicit tracks, other than any `grid-template-areas` creates.
+    None,
+    TrackList(TrackList<L>),
+}
+
+impl GridTemplate {
+    pub fn initial_value() -> GridTemplate {
+        GridTemplate::None
+    }
+}
+
+impl<L: ComputeValueWithContext<ComputedValue = LengthPercentage>> ComputeValueWithContext
+    for GridTemplate<L>
+{
+    type ComputedValue = GridTemplate;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            GridTemplate::None => GridTemplate::None,
+            GridTemplate::TrackList(list) => GridTemplate::TrackList(
+                list.map(&|length: &L| length.compute_value_with_context(context)),
+            ),
+        }
+    }
+}
+
+/// Computed value of `grid-auto-columns` or `grid-auto-rows`, the sizes of the implicit tracks of
+/// a grid along one axis, which repeat in turn.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-tracks
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridAutoTracks<L = LengthPercentage>(pub Vec<TrackSize<L>>);
+
+impl GridAutoTracks {
+    pub fn initial_value() -> GridAutoTracks {
+        GridAutoTracks(vec![TrackSize::Breadth(TrackBreadth::Auto)])
+    }
+}
+
+impl<L: ComputeValueWithContext<ComputedValue = LengthPercentage>> ComputeValueWithContext
+    for GridAutoTracks<L>
+{
+    type ComputedValue = GridAutoTracks;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        let compute = |length: &L| length.compute_value_with_context(context);
+        GridAutoTracks(self.0.iter().map(|track| track.map(&compute)).collect())
+    }
+}
+
+/// The `grid-template-areas` property, which names rectangular areas of the grid.  This is both
+/// the specified and computed value.
+///
+/// https://drafts.csswg.org/css-grid-1/#grid-template-areas-property
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridTemplateAreas {
+    None,
+    Areas(TemplateAreas),
+}
+
+impl GridTemplateAreas {
+    pub fn initial_value() -> Self {
+        GridTemplateAreas::None
+    }
+}
+
+/// The named areas of a `grid-template-areas`, and the number of rows and columns they span.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateAreas {
+    pub rows: usize,
+    pub columns: usize,
+    pub areas: Vec<NamedArea>,
+}
+
+/// A named grid area, with the zero-based indices of the tracks it covers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedArea {
+    pub name: String,
+    pub rows: Range<usize>,
+    pub columns: Range<usize>,
+}
+
+impl CssValueParse for GridTemplateAreas {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(GridTemplateAreas::None);
+        }
+        let location = input.current_source_location();
+        let mut cells = Vec::new();
+        while let Ok(row) = input.try_parse(|i| i.expect_string_cloned()) {
+            // A sequence of periods is an empty cell.
+            let row = row
+                .split_whitespace()
+                .map(|cell| Some(cell).filter(|cell| !cell.chars().all(|c| c == '.')))
+                .map(|cell| cell.map(str::to_owned))
+                .collect::<Vec<_>>();
+            cells.push(row);
+        }
+        let columns = cells.first().map_or(0, Vec::len);
+        if columns == 0 || cells.iter().any(|row| row.len() != columns) {
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+
+        // Each name must fill the rectangle of cells spanning all of its occurrences.
+        let mut areas: Vec<NamedArea> = Vec::new();
+        for (row, row_cells) in cells.iter().enumerate() {
+            for (column, name) in row_cells.iter().enumerate() {
+                let name = match name {
+                    Some(name) => name,
+                    None => continue,
+                };
+                match areas.iter_mut().find(|area| &area.name == name) {
+                    Some(area) => {
+                        area.rows.end = area.rows.end.max(row + 1);
+                        area.columns.start = area.columns.start.min(column);
+                        area.columns.end = area.columns.end.max(column + 1);
+                    }
+                    None => areas.push(NamedArea {
+                        name: name.clone(),
+                        rows: row..row + 1,
+                        columns: column..column + 1,
+                    }),
+                }
+            }
+        }
+        for area in &areas {
+            let count = cells
+                .iter()
+                .flatten()
+                .filter(|cell| cell.as_ref() == Some(&area.name))
+                .count();
+            let rectangle_filled = area.rows.clone().all(|row| {
+                area.columns
+                    .clone()
+                    .all(|column| cells[row][column].as_ref() == Some(&area.name))
+            });
+            if !rectangle_filled || count != area.rows.len() * area.columns.len() {
+                return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+            }
+        }
+        Ok(GridTemplateAreas::Areas(TemplateAreas {
+            rows: cells.len(),
+            columns,
+            areas,
+        }))
+    }
+}
+
+impl ComputeValueWithContext for GridTemplateAreas {
+    type ComputedValue = GridTemplateAreas;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        self.clone()
+    }
+}
+
+/// The `grid-auto-flow` property, which determines how grid items that aren't explicitly placed
+/// are placed in the grid.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-grid-1/#grid-auto-flow-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GridAutoFlow {
+    /// Whether items are placed by filling each column in turn, rather than each row.
+    pub column: bool,
+    /// Whether items are placed in the earliest hole in the grid they fit in, even if that places
+    /// them before items that come before them.
+    pub dense: bool,
+}
+
+impl GridAutoFlow {
+    pub fn initial_value() -> Self {
+        GridAutoFlow {
+            column: false,
+            dense: false,
+        }
+    }
+}
+
+impl CssValueParse for GridAutoFlow {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let (mut column, mut dense) = (None, false);
+        for _ in 0..2 {
+            if column.is_none() {
+                if input.try_parse(|i| i.expect_ident_matching("row")).is_ok() {
+                    column = Some(false);
+                    continue;
+                }
+                if input
+                    .try_parse(|i| i.expect_ident_matching("column"))
+                    .is_ok()
+                {
+                    column = Some(true);
+                    continue;
+                }
+            }
+            if !dense
+                && input
+                    .try_parse(|i| i.expect_ident_matching("dense"))
+                    .is_ok()
+            {
+                dense = true;
+                continue;
+            }
+            break;
+        }
+        if column.is_none() && !dense {
+            let location = input.current_source_location();
+            return Err(location.new_unexpected_token_error(input.next()?.clone()));
+        }
+        Ok(GridAutoFlow {
+            column: column.unwrap_or(false),
+            dense,
+        })
+    }
+}
+
+impl ComputeValueWithContext for GridAutoFlow {
+    type ComputedValue = GridAutoFlow;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The furthest a grid line can be from the start of the explicit grid in either direction, and
+/// the most tracks a `repeat()` can expand to.  Larger line numbers, spans, and repetitions are
+/// clamped to this, so that a single declaration can't make a grid big enough to exhaust memory.
+///
+/// https://drafts.csswg.org/css-grid-1/#overlarge-grids
+pub const MAX_GRID_LINE: i32 = 1000;
+
+/// The value of `grid-row-start`, `grid-column-end`, and the like, which place a grid item by
+/// giving the line one edge of it is on.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-grid-1/#line-placement
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GridLine {
+    /// The item is placed automatically, or spans one track from its other edge.
+    Auto,
+    /// The line with the given (non-zero) index, counting from the end of the explicit grid if
+    /// it's negative, or the nth line with the given name if there's one.
+    Line { index: i32, name: Option<String> },
+    /// The edge is the given number of lines from the other edge of the item, or the given number
+    /// of lines with the given name from it.
+    Span { count: u32, name: Option<String> },
+    /// The edge of the named area with this name, or else the first line with this name.
+    Name(String),
+}
+
+impl GridLine {
+    pub fn initial_value() -> Self {
+        GridLine::Auto
+    }
+
+    /// The value an omitted end line of a `grid-row`, `grid-column`, or `grid-area` shorthand
+    /// takes from the start line: the same name if it's a name, and otherwise `auto`.
+    pub fn omitted_end(&self) -> GridLine {
+        match self {
+            GridLine::Name(name) => GridLine::Name(name.clone()),
+            _ => GridLine::Auto,
+        }
+    }
+}
+
+impl CssValueParse for GridLine {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+            return Ok(GridLine::Auto);
+        }
+        let location = input.current_source_location();
+        let (mut span, mut index, mut name) = (false, None, None);
+        for _ in 0..3 {
+            if !span && input.try_parse(|i| i.expect_ident_matching("span")).is_ok() {
+                span = true;
+                continue;
+            }
+            if index.is_none() {
+                if let Ok(parsed_index) = input.try_parse(|i| i.expect_integer()) {
+                    index = Some(parsed_index);
+                    continue;
+                }
+            }
+            if name.is_none() {
+                if let Ok(parsed_name) = input.try_parse(parse_custom_ident) {
+                    name = Some(parsed_name);
+                    continue;
+                }
+            }
+            break;
+        }
+        match (span, index, name) {
+            (true, Some(count), name) if count > 0 => Ok(GridLine::Span {
+                count: count.min(MAX_GRID_LINE) as u32,
+                name,
+            }),
+            (true, None, Some(name)) => Ok(GridLine::Span {
+                count: 1,
+                name: Some(name),
+            }),
+            (false, Some(index), name) if index != 0 => Ok(GridLine::Line {
+                index: index.clamp(-MAX_GRID_LINE, MAX_GRID_LINE),
+                name,
+            }),
+            (false, None, Some(name)) => Ok(GridLine::Name(name)),
+            _ => Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError)),
+        }
+    }
+}
+
+impl ComputeValueWithContext for GridLine {
+    type ComputedValue = GridLine;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        self.clone()
+    }
+}
+
+/// Parses a name for a grid line or area, which can be any identifier other than the keywords it
+/// would be confused with.  Names are case-sensitive.
+///
+/// https://drafts.csswg.org/css-values-4/#custom-idents
+pub fn parse_custom_ident<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<String, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let ident = input.expect_ident_cloned()?;
+    match_ignore_ascii_case! { &ident,
+        "auto" | "span" | "inherit" | "initial" | "unset" | "default" => Err(
+            location.new_custom_error(StyleParseErrorKind::OtherInvalidValue(ident.clone()))
+        ),
+        _ => Ok(ident.to_string()),
+    }
+}
@@ -1,9 +1,14 @@
-use crate::values::computed::{ComputeValue, Percentage};
+use crate::values::computed::{ComputeContext, ComputeValue, ComputeValueWithContext, Percentage};
 use crate::values::specified;
+use crate::values::specified::calc::{CalcNode, MinMaxOp};
 use primitives::units::CSSPixelLength;
 
+/// A computed math function.  Every length has been resolved to pixels, but percentages are kept
+/// around until the size they refer to is known.
+pub type CalcLengthPercentage = CalcNode<CSSPixelLength>;
+
 /// A computed `<length>` value, a computed `<percentage>` value, or the `auto` keyword.
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum LengthPercentageOrAuto {
     LengthPercentage(LengthPercentage),
     Auto,
@@ -27,11 +32,12 @@ impl From<CSSPixelLength> for LengthPercentageOrAuto {
     }
 }
 
-/// A computed `<length>` value, or a computed `<percentage>` value.
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// A computed `<length>` value, a computed `<percentage>` value, or a math function mixing the two.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum LengthPercentage {
     Length(CSSPixelLength),
     Percentage(Percentage),
+    Calc(Box<CalcLengthPercentage>),
 }
 
 impl LengthPercentage {
@@ -54,12 +60,106 @@ impl ComputeValue for specified::AbsoluteLength {
     }
 }
 
-impl ComputeValue for specified::NoCalcLength {
+impl ComputeValueWithContext for specified::NoCalcLength {
     type ComputedValue = CSSPixelLength;
 
-    fn compute_value(&self) -> Self::ComputedValue {
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         match self {
             specified::NoCalcLength::Absolute(abs_len) => abs_len.compute_value(),
+            specified::NoCalcLength::FontRelative(font_relative_len) => {
+                CSSPixelLength::new(font_relative_len.to_px(
+                    context.font_size().size.px(),
+                    context.root_font_size().size.px(),
+                ))
+            }
+            specified::NoCalcLength::ViewportPercentage(viewport_percentage_len) => {
+                CSSPixelLength::new(viewport_percentage_len.to_px(context.viewport))
+            }
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::CalcLengthPercentage {
+    type ComputedValue = LengthPercentage;
+
+    /// Resolves every length in the expression.  If the expression has no percentages it can be
+    /// evaluated entirely, otherwise it must wait until used-value time to be evaluated.
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        let computed = self.map_lengths(&|len| len.compute_value_with_context(context));
+        if computed.has_percentage() {
+            LengthPercentage::Calc(Box::new(computed))
+        } else {
+            LengthPercentage::new_len(computed.resolve(&|len| len.px(), 0.))
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::LengthPercentage {
+    type ComputedValue = LengthPercentage;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::LengthPercentage::Length(no_calc_length) => {
+                no_calc_length.compute_value_with_context(context).into()
+            }
+            specified::LengthPercentage::Percentage(percentage) => {
+                LengthPercentage::Percentage(*percentage)
+            }
+            specified::LengthPercentage::Calc(calc) => calc.compute_value_with_context(context),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::LengthPercentageOrAuto {
+    type ComputedValue = LengthPercentageOrAuto;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
+            specified::LengthPercentageOrAuto::LengthPercentage(lp) => {
+                LengthPercentageOrAuto::LengthPercentage(lp.compute_value_with_context(context))
+            }
+        }
+    }
+}
+
+impl specified::LengthPercentage {
+    /// Computes this value for a property that doesn't allow negative values.  Negative lengths
+    /// and percentages are rejected when such properties are parsed, but math functions can still
+    /// produce negative results, so they're clamped to zero by wrapping them in `max(0px, ...)`.
+    ///
+    /// https://drafts.csswg.org/css-values-4/#calc-range
+    pub fn compute_non_negative_with_context(&self, context: &ComputeContext) -> LengthPercentage {
+        match self {
+            specified::LengthPercentage::Calc(calc) => CalcNode::MinMax(
+                vec![
+                    CalcNode::Length(specified::NoCalcLength::Absolute(
+                        specified::AbsoluteLength::Px(0.),
+                    )),
+                    (**calc).clone(),
+                ],
+                MinMaxOp::Max,
+            )
+            .compute_value_with_context(context),
+            length_percentage => length_percentage.compute_value_with_context(context),
+        }
+    }
+}
+
+impl specified::LengthPercentageOrAuto {
+    /// Computes this value for a property that doesn't allow negative values, as with
+    /// `specified::LengthPercentage::compute_non_negative_with_context`.
+    pub fn compute_non_negative_with_context(
+        &self,
+        context: &ComputeContext,
+    ) -> LengthPercentageOrAuto {
+        match self {
+            specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
+            specified::LengthPercentageOrAuto::LengthPercentage(lp) => {
+                LengthPercentageOrAuto::LengthPercentage(
+                    lp.compute_non_negative_with_context(context),
+                )
+            }
         }
     }
 }
@@ -1,28 +1,39 @@
 /// Some of this code was taken from Servo: https://github.com/servo/servo
 /// Kosmonaut complies with Servo's license, the Mozilla Public License 2.0.
+pub mod align;
 pub mod background;
 pub mod border;
 pub mod color;
 pub mod direction;
 pub mod display;
+pub mod flex;
+pub mod float;
 pub mod font;
+pub mod grid;
 pub mod height;
 pub mod length;
 pub mod margin;
+pub mod multicol;
 pub mod padding;
 pub mod percentage;
+pub mod position;
+pub mod sizing;
+pub mod table;
+pub mod text;
 pub mod width;
 
-use crate::values::computed::height::Height;
 pub use crate::values::computed::margin::Margin;
 pub use crate::values::computed::padding::Padding;
-use crate::values::computed::width::Width;
 
+use crate::custom_properties::compute_custom_properties;
 use crate::properties::id::LonghandId;
+pub use crate::properties::registry::{ComputedValues, ComputedValuesBuilder};
 use crate::properties::{ContextualPropertyDeclarations, PropertyDeclaration};
 pub use crate::values::computed::direction::WritingMode;
 use crate::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
-use crate::values::specified;
+use crate::viewport::ViewportSize;
+use crate::CssWideKeywords;
+pub use align::{AlignSelf, ContentAlignment, Gap, ItemAlignment};
 pub use background::BackgroundColor;
 pub use border::LineStyle;
 pub use border::{border_side_initial_style, BorderColor, BorderWidth};
@@ -30,12 +41,26 @@ pub use color::Color;
 use cssparser::RGBA;
 pub use direction::Direction;
 pub use display::Display;
-pub use font::FontSize;
+use display::InnerDisplay;
+pub use flex::{FlexBasis, FlexDirection, FlexFactor, FlexWrap, Order};
+pub use float::{Clear, Float};
+pub use font::{
+    FontDescription, FontFamily, FontSize, FontStretch, FontStyle, FontWeight, LineHeight,
+};
+pub use grid::{
+    GridAutoFlow, GridAutoTracks, GridLine, GridTemplate, GridTemplateAreas, TrackBreadth,
+    TrackSize,
+};
+pub use multicol::{BreakInside, ColumnCount, ColumnSpan, ColumnWidth};
 pub use percentage::Percentage;
+pub use position::{Inset, Position, ZIndex};
 use primitives::sides::FlowSide;
 use primitives::sides::PhysicalSide;
 use primitives::units::CSSPixelLength;
+pub use sizing::{BoxSizing, MaxSize, MinSize};
 use strum::IntoEnumIterator;
+pub use table::{BorderCollapse, BorderSpacing, CaptionSide, TableLayout};
+pub use text::{Spacing, TextAlign, TextIndent, TextTransform, WhiteSpace};
 
 /// A trait to represent the conversion between computed and specified values where a context is
 /// required to properly compute the specified value.
@@ -61,54 +86,18 @@ pub trait ComputeValue {
     fn compute_value(&self) -> Self::ComputedValue;
 }
 
-/// Trait to represent the behavior of defaulting a property's value when the cascade doesn't
-/// provide one.
-///
-/// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#defaulting
-pub trait ValueDefault {
-    /// The computed value type resulting from default.
-    type ComputedValue;
-
-    /// Perform the value-default.
-    fn value_default(context: &ComputeContext) -> Self::ComputedValue;
-}
-
-/// A finalized set of computed values.
-///
-/// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#computed-value
-#[derive(Debug, Clone, Builder)]
-pub struct ComputedValues {
-    pub background_color: BackgroundColor,
-    pub border_bottom_color: BorderColor,
-    pub border_left_color: BorderColor,
-    pub border_right_color: BorderColor,
-    pub border_top_color: BorderColor,
-    pub border_bottom_style: LineStyle,
-    pub border_left_style: LineStyle,
-    pub border_right_style: LineStyle,
-    pub border_top_style: LineStyle,
-    pub border_bottom_width: BorderWidth,
-    pub border_left_width: BorderWidth,
-    pub border_right_width: BorderWidth,
-    pub border_top_width: BorderWidth,
-    pub color: Color,
-    pub direction: Direction,
-    pub display: Display,
-    pub font_size: FontSize,
-    pub height: Height,
-    pub margin_bottom: Margin,
-    pub margin_left: Margin,
-    pub margin_right: Margin,
-    pub margin_top: Margin,
-    pub padding_bottom: Padding,
-    pub padding_left: Padding,
-    pub padding_right: Padding,
-    pub padding_top: Padding,
-    pub width: Width,
-    pub writing_mode: WritingMode,
-}
-
 impl ComputedValues {
+    /// The font this element's text should be rendered with.
+    pub fn font_description(&self) -> FontDescription {
+        FontDescription {
+            family: self.font_family.clone(),
+            style: self.font_style,
+            weight: self.font_weight,
+            stretch: self.font_stretch,
+            size: self.font_size,
+        }
+    }
+
     pub fn border_style(&self, side: PhysicalSide) -> LineStyle {
         match side {
             PhysicalSide::Bottom => self.border_bottom_style,
@@ -127,19 +116,68 @@ impl ComputedValues {
         }
     }
 
+    /// Whether this is the style of a multi-column container, whose content is laid out in
+    /// columns.
+    ///
+    /// https://drafts.csswg.org/css-multicol-1/#multi-column-container
+    pub fn is_multicol_container(&self) -> bool {
+        self.column_count != ColumnCount::Auto || self.column_width != ColumnWidth::Auto
+    }
+
+    pub fn inset(&self, side: PhysicalSide) -> LengthPercentageOrAuto {
+        match side {
+            PhysicalSide::Bottom => self.bottom.size.clone(),
+            PhysicalSide::Left => self.left.size.clone(),
+            PhysicalSide::Right => self.right.size.clone(),
+            PhysicalSide::Top => self.top.size.clone(),
+        }
+    }
+
     pub fn inline_size(&self, writing_mode: WritingMode) -> LengthPercentageOrAuto {
         if writing_mode.is_horizontal() {
-            self.width.size
+            self.width.size.clone()
         } else {
-            self.height.size
+            self.height.size.clone()
         }
     }
 
     pub fn block_size(&self, writing_mode: WritingMode) -> LengthPercentageOrAuto {
         if writing_mode.is_horizontal() {
-            self.height.size
+            self.height.size.clone()
+        } else {
+            self.width.size.clone()
+        }
+    }
+
+    pub fn min_inline_size(&self, writing_mode: WritingMode) -> LengthPercentageOrAuto {
+        if writing_mode.is_horizontal() {
+            self.min_width.size.clone()
         } else {
-            self.width.size
+            self.min_height.size.clone()
+        }
+    }
+
+    pub fn min_block_size(&self, writing_mode: WritingMode) -> LengthPercentageOrAuto {
+        if writing_mode.is_horizontal() {
+            self.min_height.size.clone()
+        } else {
+            self.min_width.size.clone()
+        }
+    }
+
+    pub fn max_inline_size(&self, writing_mode: WritingMode) -> MaxSize {
+        if writing_mode.is_horizontal() {
+            self.max_width.clone()
+        } else {
+            self.max_height.clone()
+        }
+    }
+
+    pub fn max_block_size(&self, writing_mode: WritingMode) -> MaxSize {
+        if writing_mode.is_horizontal() {
+            self.max_height.clone()
+        } else {
+            self.max_width.clone()
         }
     }
 
@@ -152,30 +190,30 @@ impl ComputedValues {
         match side {
             FlowSide::BlockEnd => {
                 if is_horizontal_mode {
-                    self.padding_bottom.size
+                    self.padding_bottom.size.clone()
                 } else {
-                    self.padding_right.size
+                    self.padding_right.size.clone()
                 }
             }
             FlowSide::InlineStart => {
                 if is_horizontal_mode {
-                    self.padding_left.size
+                    self.padding_left.size.clone()
                 } else {
-                    self.padding_top.size
+                    self.padding_top.size.clone()
                 }
             }
             FlowSide::InlineEnd => {
                 if is_horizontal_mode {
-                    self.padding_right.size
+                    self.padding_right.size.clone()
                 } else {
-                    self.padding_bottom.size
+                    self.padding_bottom.size.clone()
                 }
             }
             FlowSide::BlockStart => {
                 if is_horizontal_mode {
-                    self.padding_top.size
+                    self.padding_top.size.clone()
                 } else {
-                    self.padding_left.size
+                    self.padding_left.size.clone()
                 }
             }
         }
@@ -228,81 +266,39 @@ impl ComputedValues {
         match side {
             FlowSide::BlockEnd => {
                 if is_horizontal_mode {
-                    self.margin_bottom.size
+                    self.margin_bottom.size.clone()
                 } else {
-                    self.margin_right.size
+                    self.margin_right.size.clone()
                 }
             }
             FlowSide::InlineStart => {
                 if is_horizontal_mode {
-                    self.margin_left.size
+                    self.margin_left.size.clone()
                 } else {
-                    self.margin_top.size
+                    self.margin_top.size.clone()
                 }
             }
             FlowSide::InlineEnd => {
                 if is_horizontal_mode {
-                    self.margin_right.size
+                    self.margin_right.size.clone()
                 } else {
-                    self.margin_bottom.size
+                    self.margin_bottom.size.clone()
                 }
             }
             FlowSide::BlockStart => {
                 if is_horizontal_mode {
-                    self.margin_top.size
+                    self.margin_top.size.clone()
                 } else {
-                    self.margin_left.size
+                    self.margin_left.size.clone()
                 }
             }
         }
     }
 }
 
-/// Create a default set of computed values.  Likely most useful for the case in which we're working
-/// with the root node of a DOM, which has no parent to inherit from.
-impl Default for ComputedValues {
-    // TODO: We might eventually need to not use the `Default` trait here in case we need `ComputeContext`
-    // to calculate the default computed values.
-    fn default() -> Self {
-        let initial_color_prop = Color::initial_value();
-        let initial_border_style = border_side_initial_style();
-        ComputedValues {
-            background_color: BackgroundColor::initial_value(initial_color_prop.rgba()),
-            border_bottom_color: BorderColor::initial_value(initial_color_prop.rgba()),
-            border_left_color: BorderColor::initial_value(initial_color_prop.rgba()),
-            border_right_color: BorderColor::initial_value(initial_color_prop.rgba()),
-            border_top_color: BorderColor::initial_value(initial_color_prop.rgba()),
-            border_bottom_style: initial_border_style,
-            border_left_style: initial_border_style,
-            border_right_style: initial_border_style,
-            border_top_style: initial_border_style,
-            border_bottom_width: BorderWidth::initial_value(initial_border_style),
-            border_left_width: BorderWidth::initial_value(initial_border_style),
-            border_right_width: BorderWidth::initial_value(initial_border_style),
-            border_top_width: BorderWidth::initial_value(initial_border_style),
-            color: initial_color_prop,
-            direction: Direction::initial_value(),
-            display: Display::initial_value(),
-            font_size: FontSize::initial_value(),
-            height: Height::initial_value(),
-            margin_bottom: Margin::initial_value(),
-            margin_left: Margin::initial_value(),
-            margin_right: Margin::initial_value(),
-            margin_top: Margin::initial_value(),
-            padding_bottom: Padding::initial_value(),
-            padding_left: Padding::initial_value(),
-            padding_right: Padding::initial_value(),
-            padding_top: Padding::initial_value(),
-            width: Width::initial_value(),
-            writing_mode: WritingMode::initial_value(),
-        }
-    }
-}
-
 /// A `ComputeContext` is all the data a specified value could ever need to compute
 /// itself and be transformed to a computed value.
 pub struct ComputeContext<'a> {
-    // TODO: Viewport dimensions will be needed
     /// The computed values of the parent for cases where inheritance is necessary.  If the current
     /// node has no parent (it is the root node), this is `ComputedValues::default()`.
     pub parent_computed_values: &'a ComputedValues,
@@ -319,6 +315,29 @@ pub struct ComputeContext<'a> {
     ///
     /// `None` if these values haven't been computed yet.
     pub computed_border_styles: Option<BorderSideStyleContext>,
+
+    /// The computed value of the `column-rule-style` property for the node being computed, which
+    /// the computed value of `column-rule-width` depends on the same way border widths depend on
+    /// border styles.
+    ///
+    /// `None` if this value hasn't been computed yet.
+    pub computed_column_rule_style: Option<LineStyle>,
+
+    /// The computed value of the `font-size` property for the node being computed.  Font-relative
+    /// lengths, such as `em`, are resolved against this value.
+    ///
+    /// `None` if `font-size` has not been computed yet.
+    pub computed_font_size: Option<FontSize>,
+
+    /// The computed value of the `font-size` property for the root element, which `rem` lengths
+    /// are resolved against.
+    ///
+    /// `None` if the node being computed is the root element (or there is no root element yet).
+    pub root_font_size: Option<FontSize>,
+
+    /// The size of the viewport, which viewport-percentage lengths (e.g. `vw`) are resolved
+    /// against.
+    pub viewport: ViewportSize,
 }
 
 impl ComputeContext<'_> {
@@ -340,10 +359,63 @@ impl ComputeContext<'_> {
         self.border_styles().top
     }
 
-    fn border_styles(&self) -> BorderSideStyleContext {
+    pub fn font_size(&self) -> FontSize {
+        self.computed_font_size
+            .expect("font-size property not yet computed and applied to compute context")
+    }
+
+    /// The font size `rem` lengths should be resolved against.  When computing the root element
+    /// itself, this is the root element's own font size.
+    pub fn root_font_size(&self) -> FontSize {
+        self.root_font_size.unwrap_or_else(|| self.font_size())
+    }
+
+    pub fn border_styles(&self) -> BorderSideStyleContext {
         self.computed_border_styles
             .expect("border styles properties not yet computed and applied to compute context")
     }
+
+    pub fn column_rule_style(&self) -> LineStyle {
+        self.computed_column_rule_style
+            .expect("column-rule-style property not yet computed and applied to compute context")
+    }
+}
+
+/// The computed values that the initial values of other properties depend on, e.g. `color`, which
+/// the initial value of `border-<side>-color` (`currentColor`) refers to.
+#[derive(Clone, Copy, Debug)]
+pub struct InitialValueContext {
+    pub color: Color,
+    pub border_styles: BorderSideStyleContext,
+    pub column_rule_style: LineStyle,
+}
+
+impl InitialValueContext {
+    /// The context in which every property has its initial value, e.g. for a node with no parent
+    /// to inherit from.
+    pub fn initial() -> Self {
+        let initial_border_style = border_side_initial_style();
+        InitialValueContext {
+            color: Color::initial_value(),
+            border_styles: BorderSideStyleContext {
+                bottom: initial_border_style,
+                left: initial_border_style,
+                right: initial_border_style,
+                top: initial_border_style,
+            },
+            column_rule_style: initial_border_style,
+        }
+    }
+}
+
+impl From<&ComputeContext<'_>> for InitialValueContext {
+    fn from(context: &ComputeContext) -> Self {
+        InitialValueContext {
+            color: context.color(),
+            border_styles: context.border_styles(),
+            column_rule_style: context.column_rule_style(),
+        }
+    }
 }
 
 /// Container for computation context information about the `border-<side>-style` properties.
@@ -355,138 +427,116 @@ pub struct BorderSideStyleContext {
     pub top: LineStyle,
 }
 
+/// Computes the values for a node given its cascaded declarations.
+///
+/// `root_font_size` is the computed font size of the root element, or `None` if the node being
+/// computed is the root element.  `viewport` is the size of the viewport the node is being styled
+/// for.
 pub fn compute_values(
     contextual_declarations: &ContextualPropertyDeclarations,
     parent_computed_values: &ComputedValues,
+    root_font_size: Option<FontSize>,
+    viewport: ViewportSize,
 ) -> ComputedValues {
+    let custom_properties = compute_custom_properties(
+        &contextual_declarations.custom_properties(),
+        &parent_computed_values.custom_properties,
+    );
+    let resolved_declarations;
+    let contextual_declarations = if contextual_declarations.needs_resolution() {
+        resolved_declarations = contextual_declarations.resolve_winners(&custom_properties);
+        &resolved_declarations
+    } else {
+        contextual_declarations
+    };
+    let physical_declarations;
+    let contextual_declarations = if contextual_declarations.has_logical() {
+        let writing_mode = cascaded_writing_mode(contextual_declarations, parent_computed_values);
+        physical_declarations = contextual_declarations.to_physical(writing_mode);
+        &physical_declarations
+    } else {
+        contextual_declarations
+    };
+
     let mut cv_builder = ComputedValuesBuilder::default();
+    cv_builder.custom_properties(custom_properties);
     let mut context = ComputeContext {
         parent_computed_values,
         computed_color: None,
         computed_border_styles: None,
+        computed_column_rule_style: None,
+        computed_font_size: None,
+        root_font_size,
+        viewport,
     };
     compute_early_properties(contextual_declarations, &mut context);
 
-    LonghandId::iter().for_each(|longhand: LonghandId| {
-        match contextual_declarations.get_by_longhand(longhand) {
-            Some(contextual_decl) => {
-                match &contextual_decl.inner_decl {
-                    PropertyDeclaration::BackgroundColor(background_color) => {
-                        cv_builder.background_color(
-                            background_color.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderBottomColor(border_bottom_color) => {
-                        cv_builder.border_bottom_color(
-                            border_bottom_color.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderLeftColor(border_left_color) => {
-                        cv_builder.border_left_color(
-                            border_left_color.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderRightColor(border_right_color) => {
-                        cv_builder.border_right_color(
-                            border_right_color.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderTopColor(border_top_color) => {
-                        cv_builder.border_top_color(
-                            border_top_color.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderBottomStyle(_) => {
-                        cv_builder.border_bottom_style(context.border_bottom_style());
-                    }
-                    PropertyDeclaration::BorderLeftStyle(_) => {
-                        cv_builder.border_left_style(context.border_left_style());
-                    }
-                    PropertyDeclaration::BorderRightStyle(_) => {
-                        cv_builder.border_right_style(context.border_right_style());
-                    }
-                    PropertyDeclaration::BorderTopStyle(_) => {
-                        cv_builder.border_top_style(context.border_top_style());
-                    }
-                    PropertyDeclaration::BorderBottomWidth(border_bottom_width) => {
-                        cv_builder.border_bottom_width(
-                            border_bottom_width.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderLeftWidth(border_left_width) => {
-                        cv_builder.border_left_width(
-                            border_left_width.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderRightWidth(border_right_width) => {
-                        cv_builder.border_right_width(
-                            border_right_width.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::BorderTopWidth(border_top_width) => {
-                        cv_builder.border_top_width(
-                            border_top_width.compute_value_with_context(&context),
-                        );
-                    }
-                    PropertyDeclaration::Color(_) => {
-                        cv_builder.color(context.color());
-                    }
-                    PropertyDeclaration::Direction(direction) => {
-                        cv_builder.direction(*direction);
-                    }
-                    PropertyDeclaration::Display(display) => {
-                        // TODO: Should we copying `display` here (taking the specified value), rather than computing the value?
-                        // There is currently no `specified/display.rs`, so that would need to be remedied.
-                        // Computing display might not be straightforward — see: https://github.com/w3c/csswg-drafts/issues/1716
-                        cv_builder.display(*display);
-                    }
-                    PropertyDeclaration::Height(height) => {
-                        cv_builder.height(height.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::FontSize(font_size) => {
-                        cv_builder.font_size(font_size.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::MarginBottom(margin_bottom) => {
-                        cv_builder
-                            .margin_bottom(margin_bottom.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::MarginLeft(margin_left) => {
-                        cv_builder.margin_left(margin_left.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::MarginRight(margin_right) => {
-                        cv_builder.margin_right(margin_right.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::MarginTop(margin_top) => {
-                        cv_builder.margin_top(margin_top.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::PaddingBottom(padding_bottom) => {
-                        cv_builder
-                            .padding_bottom(padding_bottom.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::PaddingLeft(padding_left) => {
-                        cv_builder.padding_left(padding_left.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::PaddingRight(padding_right) => {
-                        cv_builder
-                            .padding_right(padding_right.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::PaddingTop(padding_top) => {
-                        cv_builder.padding_top(padding_top.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::Width(width) => {
-                        cv_builder.width(width.compute_value_with_context(&context));
-                    }
-                    PropertyDeclaration::WritingMode(writing_mode) => {
-                        cv_builder.writing_mode(*writing_mode);
-                    }
+    // Logical longhands have been mapped onto physical longhands above, so have nothing to compute.
+    LonghandId::iter()
+        .filter(|longhand| !longhand.is_logical())
+        .for_each(|longhand: LonghandId| {
+            match contextual_declarations.get_by_longhand(longhand) {
+                Some(contextual_decl) => {
+                    contextual_decl
+                        .inner_decl
+                        .compute_into(&mut cv_builder, &context);
                 }
-            }
-            None => {
-                longhand.value_default(&mut cv_builder, &context);
-            }
-        };
-    });
-    cv_builder.build().expect("couldn't build computed values")
+                None => {
+                    longhand.value_default(&mut cv_builder, &context);
+                }
+            };
+        });
+    let mut computed_values = cv_builder.build().expect("couldn't build computed values");
+    // The children of flex and grid containers are flex and grid items, which are always
+    // block-level.
+    // https://drafts.csswg.org/css-flexbox-1/#flex-items
+    // https://drafts.csswg.org/css-grid-1/#grid-item-display
+    if matches!(
+        parent_computed_values.display.inner(),
+        Some(InnerDisplay::Flex) | Some(InnerDisplay::Grid)
+    ) {
+        computed_values.display = computed_values.display.blockified();
+    }
+    computed_values
+}
+
+/// Computes the values of an anonymous box, which isn't generated by an element and so has no
+/// declarations of its own.  Its inherited properties take their values from
+/// `parent_computed_values`, those of the element whose box contains it, and the rest take their
+/// initial values.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#anonymous-block-level
+pub fn compute_anonymous_values(parent_computed_values: &ComputedValues) -> ComputedValues {
+    // Without any declarations, there are no font-relative or viewport-percentage lengths to
+    // resolve, so neither the root font size nor the viewport matter.
+    compute_values(
+        &ContextualPropertyDeclarations::new(),
+        parent_computed_values,
+        None,
+        ViewportSize::from_px(0., 0.),
+    )
+}
+
+/// The writing mode of the element being styled, which its logical longhands are mapped onto
+/// physical longhands with.
+fn cascaded_writing_mode(
+    contextual_declarations: &ContextualPropertyDeclarations,
+    parent_computed_values: &ComputedValues,
+) -> WritingMode {
+    match contextual_declarations
+        .get_by_longhand(LonghandId::WritingMode)
+        .map(|contextual_decl| &contextual_decl.inner_decl)
+    {
+        Some(PropertyDeclaration::WritingMode(writing_mode)) => *writing_mode,
+        Some(PropertyDeclaration::CssWideKeyword(decl))
+            if decl.keyword == CssWideKeywords::Initial =>
+        {
+            WritingMode::initial_value()
+        }
+        // `writing-mode` is inherited, so every other keyword (and no declaration at all) gives
+        // the parent's value.
+        _ => parent_computed_values.writing_mode,
+    }
 }
 
 /// Computes "early phase" properties and adds them to the compute context.  Early phase properties
@@ -499,23 +549,74 @@ fn compute_early_properties(
     if let Some(contextual_decl) = contextual_declarations.get_by_longhand(LonghandId::Color) {
         context.computed_color = match &contextual_decl.inner_decl {
             PropertyDeclaration::Color(color) => Some(color.compute_value_with_context(&context)),
+            PropertyDeclaration::CssWideKeyword(decl) => Some(match decl.keyword {
+                CssWideKeywords::Initial => Color::initial_value(),
+                CssWideKeywords::Inherit => context.parent_computed_values.color,
+                // `color` is inherited, so `unset` gives the parent's value.
+                CssWideKeywords::Unset | CssWideKeywords::Revert => {
+                    context.parent_computed_values.color
+                }
+            }),
             _ => panic!("needed color property declaration"),
         }
     } else {
-        context.computed_color = Some(specified::Color::value_default(&context));
+        context.computed_color = Some(context.parent_computed_values.color);
     }
 
-    context.computed_border_styles = Some(compute_border_styles_early(contextual_declarations));
+    if let Some(contextual_decl) = contextual_declarations.get_by_longhand(LonghandId::FontSize) {
+        context.computed_font_size = match &contextual_decl.inner_decl {
+            PropertyDeclaration::FontSize(font_size) => {
+                Some(font_size.compute_value_with_context(&context))
+            }
+            PropertyDeclaration::CssWideKeyword(decl) => Some(match decl.keyword {
+                CssWideKeywords::Initial => FontSize::initial_value(),
+                CssWideKeywords::Inherit => context.parent_computed_values.font_size,
+                // `font-size` is inherited, so `unset` gives the parent's value.
+                CssWideKeywords::Unset | CssWideKeywords::Revert => {
+                    context.parent_computed_values.font_size
+                }
+            }),
+            _ => panic!("needed font-size property declaration"),
+        }
+    } else {
+        context.computed_font_size = Some(context.parent_computed_values.font_size);
+    }
+
+    context.computed_border_styles = Some(compute_border_styles_early(
+        contextual_declarations,
+        context.parent_computed_values,
+    ));
+
+    context.computed_column_rule_style = Some(
+        if let Some(contextual_decl) =
+            contextual_declarations.get_by_longhand(LonghandId::ColumnRuleStyle)
+        {
+            match &contextual_decl.inner_decl {
+                PropertyDeclaration::ColumnRuleStyle(line_style) => *line_style,
+                PropertyDeclaration::CssWideKeyword(decl) => border_style_for_keyword(
+                    decl.keyword,
+                    context.parent_computed_values.column_rule_style,
+                ),
+                _ => panic!("needed column-rule-style property declaration"),
+            }
+        } else {
+            border_side_initial_style()
+        },
+    );
 }
 
 fn compute_border_styles_early(
     contextual_declarations: &ContextualPropertyDeclarations,
+    parent_computed_values: &ComputedValues,
 ) -> BorderSideStyleContext {
     let bottom = if let Some(contextual_decl) =
         contextual_declarations.get_by_longhand(LonghandId::BorderBottomStyle)
     {
         match &contextual_decl.inner_decl {
             PropertyDeclaration::BorderBottomStyle(line_style) => *line_style,
+            PropertyDeclaration::CssWideKeyword(decl) => {
+                border_style_for_keyword(decl.keyword, parent_computed_values.border_bottom_style)
+            }
             _ => panic!("needed border-bottom-style property declaration"),
         }
     } else {
@@ -527,6 +628,9 @@ fn compute_border_styles_early(
     {
         match &contextual_decl.inner_decl {
             PropertyDeclaration::BorderLeftStyle(line_style) => *line_style,
+            PropertyDeclaration::CssWideKeyword(decl) => {
+                border_style_for_keyword(decl.keyword, parent_computed_values.border_left_style)
+            }
             _ => panic!("needed border-left-style property declaration"),
         }
     } else {
@@ -538,6 +642,9 @@ fn compute_border_styles_early(
     {
         match &contextual_decl.inner_decl {
             PropertyDeclaration::BorderRightStyle(line_style) => *line_style,
+            PropertyDeclaration::CssWideKeyword(decl) => {
+                border_style_for_keyword(decl.keyword, parent_computed_values.border_right_style)
+            }
             _ => panic!("needed border-right-style property declaration"),
         }
     } else {
@@ -549,6 +656,9 @@ fn compute_border_styles_early(
     {
         match &contextual_decl.inner_decl {
             PropertyDeclaration::BorderTopStyle(line_style) => *line_style,
+            PropertyDeclaration::CssWideKeyword(decl) => {
+                border_style_for_keyword(decl.keyword, parent_computed_values.border_top_style)
+            }
             _ => panic!("needed border-top-style property declaration"),
         }
     } else {
@@ -562,3 +672,14 @@ fn compute_border_styles_early(
         top,
     }
 }
+
+/// The `border-<side>-style` or `column-rule-style` set by `keyword`.  Neither is inherited, so
+/// `unset` gives the initial value.
+fn border_style_for_keyword(keyword: CssWideKeywords, parent_style: LineStyle) -> LineStyle {
+    match keyword {
+        CssWideKeywords::Inherit => parent_style,
+        CssWideKeywords::Initial | CssWideKeywords::Unset | CssWideKeywords::Revert => {
+            border_side_initial_style()
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::values::computed::border::compute_border_side_width;
+use crate::values::computed::{BorderWidth, ComputeContext, ComputeValueWithContext};
+use crate::values::{specified, CssValueParse};
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSPixelLength;
+
+/// The `column-count` property, the number of columns of a multi-column container.  This is both
+/// the specified and computed value.
+///
+/// https://drafts.csswg.org/css-multicol-1/#cc
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColumnCount {
+    /// The number of columns is determined by `column-width`.
+    Auto,
+    /// At most this many columns, which is always at least one.
+    Integer(u32),
+}
+
+impl ColumnCount {
+    pub fn initial_value() -> Self {
+        ColumnCount::Auto
+    }
+}
+
+impl ComputeValueWithContext for ColumnCount {
+    type ComputedValue = ColumnCount;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// Computed `column-width`.
+///
+/// https://drafts.csswg.org/css-multicol-1/#cw
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnWidth {
+    /// The width of the columns is determined by `column-count`.
+    Auto,
+    /// The ideal width of the columns, which may be wider or narrower to fill the container.
+    Length(CSSPixelLength),
+}
+
+impl ColumnWidth {
+    pub fn initial_value() -> Self {
+        ColumnWidth::Auto
+    }
+}
+
+impl ComputeValueWithContext for specified::ColumnWidth {
+    type ComputedValue = ColumnWidth;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::ColumnWidth::Auto => ColumnWidth::Auto,
+            specified::ColumnWidth::Length(length) => {
+                ColumnWidth::Length(length.compute_value_with_context(context))
+            }
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::ColumnRuleWidth {
+    type ComputedValue = BorderWidth;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        BorderWidth {
+            size: compute_border_side_width(self.line_width, context.column_rule_style(), context),
+        }
+    }
+}
+
+/// The `column-span` property, which lets a box span every column of its multi-column container.
+/// This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-multicol-1/#column-span
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColumnSpan {
+    None,
+    /// The box spans every column, splitting the columns of its container into the ones before it
+    /// and the ones after it.
+    All,
+}
+
+impl ColumnSpan {
+    pub fn initial_value() -> Self {
+        ColumnSpan::None
+    }
+}
+
+impl CssValueParse for ColumnSpan {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(ColumnSpan::None),
+            "all" => Ok(ColumnSpan::All),
+        }
+    }
+}
+
+impl ComputeValueWithContext for ColumnSpan {
+    type ComputedValue = ColumnSpan;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `break-inside` property, which can keep a box from being broken across columns or pages.
+/// This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-break-3/#break-within
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreakInside {
+    Auto,
+    /// Avoid breaking inside the box at all.
+    Avoid,
+    /// Avoid breaking inside the box across pages.
+    AvoidPage,
+    /// Avoid breaking inside the box across columns.
+    AvoidColumn,
+}
+
+impl BreakInside {
+    pub fn initial_value() -> Self {
+        BreakInside::Auto
+    }
+
+    /// Whether breaks between columns should be avoided inside the box.
+    pub fn avoids_column_breaks(self) -> bool {
+        matches!(self, BreakInside::Avoid | BreakInside::AvoidColumn)
+    }
+}
+
+impl CssValueParse for BreakInside {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(BreakInside::Auto),
+            "avoid" => Ok(BreakInside::Avoid),
+            "avoid-page" => Ok(BreakInside::AvoidPage),
+            "avoid-column" => Ok(BreakInside::AvoidColumn),
+        }
+    }
+}
+
+impl ComputeValueWithContext for BreakInside {
+    type ComputedValue = BreakInside;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
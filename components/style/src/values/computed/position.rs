@@ -0,0 +1,133 @@
+use crate::values::computed::length::LengthPercentageOrAuto;
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
+use crate::values::specified;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// The `position` property, which determines the positioning scheme used to lay out a box.  This
+/// is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-position-3/#position-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Position {
+    /// Laid out in flow, ignoring the inset properties.
+    Static,
+    /// Laid out in flow, then offset by the inset properties without affecting other boxes.
+    Relative,
+    /// Laid out in flow, then offset to stay within the scrollport while its containing block is
+    /// in view.
+    Sticky,
+    /// Taken out of flow and positioned against the padding box of the nearest positioned
+    /// ancestor.
+    Absolute,
+    /// Taken out of flow and positioned against the viewport.
+    Fixed,
+}
+
+impl Position {
+    pub fn initial_value() -> Self {
+        Position::Static
+    }
+
+    /// Whether boxes with this position are positioned, which makes them the containing block of
+    /// absolutely positioned descendants and lets them take a `z-index`.
+    pub fn is_positioned(self) -> bool {
+        self != Position::Static
+    }
+
+    /// Whether boxes with this position are taken out of flow.
+    ///
+    /// https://drafts.csswg.org/css-position-3/#absolute-position
+    pub fn is_absolutely_positioned(self) -> bool {
+        matches!(self, Position::Absolute | Position::Fixed)
+    }
+}
+
+impl CssValueParse for Position {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "static" => Ok(Position::Static),
+            "relative" => Ok(Position::Relative),
+            "sticky" => Ok(Position::Sticky),
+            "absolute" => Ok(Position::Absolute),
+            "fixed" => Ok(Position::Fixed),
+        }
+    }
+}
+
+impl ComputeValueWithContext for Position {
+    type ComputedValue = Position;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// Computed value of `top`, `right`, `bottom`, or `left`.  Percentages refer to the size of the
+/// containing block along the same axis, so are resolved during layout.
+///
+/// https://drafts.csswg.org/css-position-3/#insets
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inset {
+    pub size: LengthPercentageOrAuto,
+}
+
+impl Inset {
+    pub fn initial_value() -> Inset {
+        Inset {
+            size: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Inset {
+    type ComputedValue = Inset;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        Inset {
+            size: self.lp_or_auto.compute_value_with_context(context),
+        }
+    }
+}
+
+/// The `z-index` property, which orders positioned boxes along the z-axis.  This is both the
+/// specified and computed value.
+///
+/// https://www.w3.org/TR/CSS22/visuren.html#z-index
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZIndex {
+    /// The box doesn't establish a stacking context, and is painted at stack level 0.
+    Auto,
+    /// The box establishes a stacking context, and is painted at this stack level.
+    Integer(i32),
+}
+
+impl ZIndex {
+    pub fn initial_value() -> Self {
+        ZIndex::Auto
+    }
+}
+
+impl CssValueParse for ZIndex {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(level) = input.try_parse(|i| i.expect_integer()) {
+            return Ok(ZIndex::Integer(level));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(ZIndex::Auto),
+        }
+    }
+}
+
+impl ComputeValueWithContext for ZIndex {
+    type ComputedValue = ZIndex;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
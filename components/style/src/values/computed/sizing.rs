@@ -0,0 +1,109 @@
+use crate::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
+use crate::values::specified;
+use crate::values::used::ToPx;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSPixelLength;
+
+/// The `box-sizing` property, which determines whether `width`, `height`, and their `min-` and
+/// `max-` constraints size the content box or the border box.  This is both the specified and
+/// computed value.
+///
+/// https://drafts.csswg.org/css-sizing-3/#box-sizing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoxSizing {
+    ContentBox,
+    /// Sizes include padding and border, which are subtracted to find the content box size (to a
+    /// minimum of zero).
+    BorderBox,
+}
+
+impl BoxSizing {
+    pub fn initial_value() -> Self {
+        BoxSizing::ContentBox
+    }
+}
+
+impl CssValueParse for BoxSizing {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "content-box" => Ok(BoxSizing::ContentBox),
+            "border-box" => Ok(BoxSizing::BorderBox),
+        }
+    }
+}
+
+impl ComputeValueWithContext for BoxSizing {
+    type ComputedValue = BoxSizing;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// Computed value of a `min-width` or `min-height`.  `auto` is zero for the boxes Kosmonaut lays
+/// out, so is resolved by `ToPx` like any other `auto`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinSize {
+    pub size: LengthPercentageOrAuto,
+}
+
+impl MinSize {
+    pub fn initial_value() -> MinSize {
+        MinSize {
+            size: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::MinSize {
+    type ComputedValue = MinSize;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::MinSize::LengthPercentageOrAuto(lp_auto) => MinSize {
+                size: lp_auto.compute_non_negative_with_context(context),
+            },
+        }
+    }
+}
+
+/// Computed value of a `max-width` or `max-height`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MaxSize {
+    /// No limit on the size of the box.
+    None,
+    LengthPercentage(LengthPercentage),
+}
+
+impl MaxSize {
+    pub fn initial_value() -> MaxSize {
+        MaxSize::None
+    }
+
+    /// The maximum size in pixels, if any, given the size of the containing block along the same
+    /// axis.
+    pub fn to_px(&self, containing_size: CSSPixelLength) -> Option<CSSPixelLength> {
+        match self {
+            MaxSize::None => None,
+            MaxSize::LengthPercentage(lp) => Some(lp.to_px(containing_size)),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::MaxSize {
+    type ComputedValue = MaxSize;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::MaxSize::None => MaxSize::None,
+            specified::MaxSize::LengthPercentage(lp) => {
+                MaxSize::LengthPercentage(lp.compute_non_negative_with_context(context))
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
+use crate::values::specified;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSPixelLength;
+
+/// The `border-collapse` property, which selects the borders model of a table.  This is both the
+/// specified and computed value.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#borders
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BorderCollapse {
+    /// Each cell has borders of its own, `border-spacing` apart from those of its neighbors.
+    Separate,
+    /// Adjacent cells share the borders between them, and there's no spacing between cells.
+    Collapse,
+}
+
+impl BorderCollapse {
+    pub fn initial_value() -> Self {
+        BorderCollapse::Separate
+    }
+}
+
+impl CssValueParse for BorderCollapse {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "separate" => Ok(BorderCollapse::Separate),
+            "collapse" => Ok(BorderCollapse::Collapse),
+        }
+    }
+}
+
+impl ComputeValueWithContext for BorderCollapse {
+    type ComputedValue = BorderCollapse;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The computed value of `border-spacing`: the horizontal and vertical distances between the
+/// borders of adjacent cells, and between the cells at the edges of a table and its padding.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#separated-borders
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderSpacing {
+    pub horizontal: CSSPixelLength,
+    pub vertical: CSSPixelLength,
+}
+
+impl BorderSpacing {
+    pub fn initial_value() -> Self {
+        BorderSpacing {
+            horizontal: CSSPixelLength::new(0.),
+            vertical: CSSPixelLength::new(0.),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::BorderSpacing {
+    type ComputedValue = BorderSpacing;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        BorderSpacing {
+            horizontal: self.horizontal.compute_value_with_context(context),
+            vertical: self.vertical.compute_value_with_context(context),
+        }
+    }
+}
+
+/// The `table-layout` property, which selects the algorithm used to size the columns of a table.
+/// This is both the specified and computed value.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#width-layout
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableLayout {
+    /// Columns are sized by the content of all of their cells.
+    Auto,
+    /// Columns are sized by the width of the table and the widths of its columns and first row
+    /// alone.  Only applies to tables whose width isn't `auto`.
+    Fixed,
+}
+
+impl TableLayout {
+    pub fn initial_value() -> Self {
+        TableLayout::Auto
+    }
+}
+
+impl CssValueParse for TableLayout {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(TableLayout::Auto),
+            "fixed" => Ok(TableLayout::Fixed),
+        }
+    }
+}
+
+impl ComputeValueWithContext for TableLayout {
+    type ComputedValue = TableLayout;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The `caption-side` property, which places the captions of a table before or after its rows in
+/// the block flow direction.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-tables-3/#caption-side
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaptionSide {
+    Top,
+    Bottom,
+}
+
+impl CaptionSide {
+    pub fn initial_value() -> Self {
+        CaptionSide::Top
+    }
+}
+
+impl CssValueParse for CaptionSide {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "top" => Ok(CaptionSide::Top),
+            "bottom" => Ok(CaptionSide::Bottom),
+        }
+    }
+}
+
+impl ComputeValueWithContext for CaptionSide {
+    type ComputedValue = CaptionSide;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
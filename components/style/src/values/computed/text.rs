@@ -0,0 +1,353 @@
+use crate::values::computed::length::LengthPercentage;
+use crate::values::computed::{ComputeContext, ComputeValueWithContext, Direction};
+use crate::values::specified;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use primitives::units::CSSPixelLength;
+
+/// The computed value of `text-align`, which aligns the inline-level content of a line box along
+/// the inline axis.
+///
+/// https://drafts.csswg.org/css-text-3/#text-align-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextAlign {
+    /// Aligned to the inline-start edge of the line box.
+    Start,
+    /// Aligned to the inline-end edge of the line box.
+    End,
+    /// Aligned to the line-left edge of the line box, which is the left edge in horizontal
+    /// writing modes.
+    Left,
+    /// Aligned to the line-right edge of the line box, which is the right edge in horizontal
+    /// writing modes.
+    Right,
+    Center,
+    /// Stretched to fill the line box by expanding the space at justification opportunities.
+    /// The last line of a block (and any line ending in a forced break) is start-aligned.
+    Justify,
+}
+
+impl TextAlign {
+    pub fn initial_value() -> Self {
+        TextAlign::Start
+    }
+
+    /// Resolves `left` and `right` to the flow-relative alignment they're equivalent to given the
+    /// `direction` of the line's content.  Line-left is always the side LTR text starts on, no
+    /// matter the writing mode.
+    ///
+    /// https://drafts.csswg.org/css-writing-modes-4/#line-left
+    pub fn to_flow_relative(self, direction: Direction) -> Self {
+        match (self, direction) {
+            (TextAlign::Left, Direction::Ltr) | (TextAlign::Right, Direction::Rtl) => {
+                TextAlign::Start
+            }
+            (TextAlign::Left, Direction::Rtl) | (TextAlign::Right, Direction::Ltr) => {
+                TextAlign::End
+            }
+            (flow_relative, _) => flow_relative,
+        }
+    }
+
+    /// Resolves `start` and `end` to the line-relative alignment they're equivalent to given
+    /// the `direction` of the line's content.
+    pub fn to_line_relative(self, direction: Direction) -> Self {
+        match (self, direction) {
+            (TextAlign::Start, Direction::Ltr) | (TextAlign::End, Direction::Rtl) => {
+                TextAlign::Left
+            }
+            (TextAlign::Start, Direction::Rtl) | (TextAlign::End, Direction::Ltr) => {
+                TextAlign::Right
+            }
+            (line_relative, _) => line_relative,
+        }
+    }
+}
+
+impl CssValueParse for TextAlign {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "start" => Ok(TextAlign::Start),
+            "end" => Ok(TextAlign::End),
+            "left" => Ok(TextAlign::Left),
+            "right" => Ok(TextAlign::Right),
+            "center" => Ok(TextAlign::Center),
+            "justify" => Ok(TextAlign::Justify),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::TextAlign {
+    type ComputedValue = TextAlign;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::TextAlign::Keyword(text_align) => *text_align,
+            // `match-parent` takes the parent's alignment, with `start` and `end` resolved against
+            // the parent's direction rather than this element's.
+            specified::TextAlign::MatchParent => {
+                let parent = context.parent_computed_values;
+                parent.text_align.to_line_relative(parent.direction)
+            }
+        }
+    }
+}
+
+/// The computed value of `text-indent`, the indentation of the first line of a block container.
+/// Percentages refer to the inline size of the block container, so are resolved during layout.
+///
+/// https://drafts.csswg.org/css-text-3/#text-indent-property
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextIndent(pub LengthPercentage);
+
+impl TextIndent {
+    pub fn initial_value() -> Self {
+        TextIndent(LengthPercentage::new_len(0.))
+    }
+}
+
+impl ComputeValueWithContext for specified::TextIndent {
+    type ComputedValue = TextIndent;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        TextIndent(self.0.compute_value_with_context(context))
+    }
+}
+
+/// The case mapping `text-transform` applies.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextTransformCase {
+    None,
+    /// The first letter of each word is uppercased.
+    Capitalize,
+    Uppercase,
+    Lowercase,
+}
+
+/// The `text-transform` property.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-text-3/#text-transform-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TextTransform {
+    pub case: TextTransformCase,
+    /// Whether ASCII characters are replaced with their fullwidth forms, e.g. for setting Latin
+    /// characters alongside ideographs.
+    pub full_width: bool,
+}
+
+impl TextTransform {
+    pub fn initial_value() -> Self {
+        TextTransform::none()
+    }
+
+    pub fn none() -> Self {
+        TextTransform {
+            case: TextTransformCase::None,
+            full_width: false,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == TextTransform::none()
+    }
+
+    /// Applies this transform to `text`.  This must happen before the text is measured, since the
+    /// transformed text can have a different length than the original.
+    pub fn transform(&self, text: &str) -> String {
+        let cased = match self.case {
+            TextTransformCase::None => text.to_owned(),
+            TextTransformCase::Capitalize => capitalize(text),
+            TextTransformCase::Uppercase => text.to_uppercase(),
+            TextTransformCase::Lowercase => text.to_lowercase(),
+        };
+        if self.full_width {
+            cased.chars().map(to_full_width).collect()
+        } else {
+            cased
+        }
+    }
+}
+
+impl CssValueParse for TextTransform {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(TextTransform::none());
+        }
+        // The case mapping and `full-width` may be given in either order, but each at most once.
+        let mut case = None;
+        let mut full_width = false;
+        while let Ok(ident) = input.try_parse(|i| i.expect_ident_cloned()) {
+            let parsed_case = match_ignore_ascii_case! { &ident,
+                "capitalize" => Some(TextTransformCase::Capitalize),
+                "uppercase" => Some(TextTransformCase::Uppercase),
+                "lowercase" => Some(TextTransformCase::Lowercase),
+                "full-width" => None,
+                _ => {
+                    return Err(input.new_custom_error(StyleParseErrorKind::OtherInvalidValue(ident)))
+                },
+            };
+            let repeated = match parsed_case {
+                Some(parsed_case) => case.replace(parsed_case).is_some(),
+                None => std::mem::replace(&mut full_width, true),
+            };
+            if repeated {
+                return Err(input.new_custom_error(StyleParseErrorKind::OtherInvalidValue(ident)));
+            }
+        }
+        if case.is_none() && !full_width {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        Ok(TextTransform {
+            case: case.unwrap_or(TextTransformCase::None),
+            full_width,
+        })
+    }
+}
+
+impl ComputeValueWithContext for TextTransform {
+    type ComputedValue = TextTransform;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// Uppercases the first letter of each word in `text`.  Apostrophes don't end a word, so
+/// "don't" becomes "Don't" rather than "Don'T".
+fn capitalize(text: &str) -> String {
+    let mut capitalized = String::with_capacity(text.len());
+    let mut in_word = false;
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            if in_word {
+                capitalized.push(c);
+            } else {
+                capitalized.extend(c.to_uppercase());
+            }
+            in_word = true;
+        } else {
+            capitalized.push(c);
+            in_word = in_word && (c == '\'' || c == '\u{2019}');
+        }
+    }
+    capitalized
+}
+
+/// Maps printable ASCII characters to their fullwidth forms.
+///
+/// https://drafts.csswg.org/css-text-3/#full-width
+fn to_full_width(c: char) -> char {
+    match c {
+        ' ' => '\u{3000}',
+        '!'..='~' => std::char::from_u32(c as u32 + 0xFEE0).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// The `white-space` property, which determines whether and how white space in text is collapsed
+/// and whether lines may wrap.  This is both the specified and computed value.
+///
+/// https://drafts.csswg.org/css-text-3/#white-space-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WhiteSpace {
+    Normal,
+    Pre,
+    Nowrap,
+    PreWrap,
+    PreLine,
+    BreakSpaces,
+}
+
+impl WhiteSpace {
+    pub fn initial_value() -> Self {
+        WhiteSpace::Normal
+    }
+
+    /// Whether sequences of spaces and tabs collapse into a single space.
+    pub fn collapses_spaces(self) -> bool {
+        matches!(
+            self,
+            WhiteSpace::Normal | WhiteSpace::Nowrap | WhiteSpace::PreLine
+        )
+    }
+
+    /// Whether segment breaks (newlines) in the source are kept as forced line breaks, rather than
+    /// collapsed like spaces.
+    pub fn preserves_segment_breaks(self) -> bool {
+        !matches!(self, WhiteSpace::Normal | WhiteSpace::Nowrap)
+    }
+
+    /// Whether lines may be broken at soft wrap opportunities.
+    pub fn wraps(self) -> bool {
+        !matches!(self, WhiteSpace::Nowrap | WhiteSpace::Pre)
+    }
+}
+
+impl CssValueParse for WhiteSpace {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(WhiteSpace::Normal),
+            "pre" => Ok(WhiteSpace::Pre),
+            "nowrap" => Ok(WhiteSpace::Nowrap),
+            "pre-wrap" => Ok(WhiteSpace::PreWrap),
+            "pre-line" => Ok(WhiteSpace::PreLine),
+            "break-spaces" => Ok(WhiteSpace::BreakSpaces),
+        }
+    }
+}
+
+impl ComputeValueWithContext for WhiteSpace {
+    type ComputedValue = WhiteSpace;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        *self
+    }
+}
+
+/// The computed value of `letter-spacing` or `word-spacing`, the extra space added between
+/// characters or words respectively.  `normal` computes to zero.
+///
+/// https://drafts.csswg.org/css-text-3/#spacing
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spacing(pub CSSPixelLength);
+
+impl Spacing {
+    pub fn initial_value() -> Self {
+        Spacing::normal()
+    }
+
+    pub fn normal() -> Self {
+        Spacing(CSSPixelLength::new(0.))
+    }
+}
+
+impl ComputeValueWithContext for specified::Spacing {
+    type ComputedValue = Spacing;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::Spacing::Normal => Spacing::normal(),
+            // Percentages refer to the element's own font size, so can always be resolved here.
+            // https://drafts.csswg.org/css-text-4/#letter-spacing-property
+            specified::Spacing::LengthPercentage(lp) => {
+                let font_size = context.font_size().size;
+                Spacing(match lp.compute_value_with_context(context) {
+                    LengthPercentage::Length(length) => length,
+                    LengthPercentage::Percentage(percentage) => {
+                        percentage.px_relative_to(font_size)
+                    }
+                    LengthPercentage::Calc(calc) => {
+                        CSSPixelLength::new(calc.resolve(&|len| len.px(), font_size.px()))
+                    }
+                })
+            }
+        }
+    }
+}
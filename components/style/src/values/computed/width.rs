@@ -1,11 +1,9 @@
-use crate::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
-use crate::values::computed::{
-    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
-};
+use crate::values::computed::length::LengthPercentageOrAuto;
+use crate::values::computed::{ComputeContext, ComputeValueWithContext};
 use crate::values::specified;
 
 /// Computed value of a `width`.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct Width {
     pub size: LengthPercentageOrAuto,
 }
@@ -21,23 +19,11 @@ impl Width {
 impl ComputeValueWithContext for specified::Width {
     type ComputedValue = Width;
 
-    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
         let computed_lp_auto: LengthPercentageOrAuto = match self {
-            // TODO: I think we repeat computing the value of specified::LengthPercentageOrAuto a lot...eventually consider
-            // simply implementing `ComputeValueWithContext` for specified::LengthPercentageOrAuto.
-            specified::Width::LengthPercentageOrAuto(lp_auto) => match lp_auto {
-                specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
-                specified::LengthPercentageOrAuto::LengthPercentage(lp) => match lp {
-                    specified::LengthPercentage::Length(no_calc_length) => {
-                        no_calc_length.compute_value().into()
-                    }
-                    specified::LengthPercentage::Percentage(percentage) => {
-                        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(
-                            *percentage,
-                        ))
-                    }
-                },
-            },
+            specified::Width::LengthPercentageOrAuto(lp_auto) => {
+                lp_auto.compute_non_negative_with_context(context)
+            }
         };
 
         Width {
@@ -45,11 +31,3 @@ impl ComputeValueWithContext for specified::Width {
         }
     }
 }
-
-impl ValueDefault for specified::Width {
-    type ComputedValue = Width;
-
-    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
-        Width::initial_value()
-    }
-}
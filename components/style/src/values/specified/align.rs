@@ -0,0 +1,44 @@
+use crate::properties::PropertyDeclaration;
+use crate::values::specified::LengthPercentage;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Parses the `gap` shorthand, `<'row-gap'> <'column-gap'>?`, where an omitted column gap is the
+/// same as the row gap.
+///
+/// https://drafts.csswg.org/css-align-3/#gap-shorthand
+pub fn parse_gap_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let row_gap = Gap::parse(input)?;
+    let column_gap = input
+        .try_parse(|i| Gap::parse(i))
+        .unwrap_or_else(|_| row_gap.clone());
+    declarations.push(PropertyDeclaration::RowGap(row_gap));
+    declarations.push(PropertyDeclaration::ColumnGap(column_gap));
+    Ok(())
+}
+
+/// Specified value for `row-gap` and `column-gap`.
+///
+/// https://drafts.csswg.org/css-align-3/#column-row-gap
+#[derive(Clone, Debug)]
+pub enum Gap {
+    Normal,
+    LengthPercentage(LengthPercentage),
+}
+
+impl CssValueParse for Gap {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse) {
+            return Ok(Gap::LengthPercentage(lp));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(Gap::Normal),
+        }
+    }
+}
@@ -1,8 +1,23 @@
+use crate::properties::PropertyDeclaration;
 use crate::values::specified::ColorUnit;
 use crate::values::CssValueParse;
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
 
+/// Parses the `background` shorthand.  `background-color` is the only background longhand Kosmonaut
+/// supports, so only a color is accepted.
+///
+/// https://www.w3.org/TR/2017/CR-css-backgrounds-3-20171017/#the-background
+pub fn parse_background_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    declarations.push(PropertyDeclaration::BackgroundColor(
+        BackgroundColor::parse(input)?,
+    ));
+    Ok(())
+}
+
 /// Specified value for the `background-color` property.
 ///
 /// https://www.w3.org/TR/2017/CR-css-backgrounds-3-20171017/#propdef-background-color
@@ -135,7 +135,7 @@ pub fn parse_border_width_shorthand_into<'i, 't>(
     Ok(())
 }
 
-fn parse_border_shorthand_inner<'i, 't>(
+pub fn parse_border_shorthand_inner<'i, 't>(
     input: &mut Parser<'i, 't>,
 ) -> Result<
     (Option<LineWidth>, Option<LineStyle>, Option<ColorUnit>),
@@ -144,7 +144,11 @@ fn parse_border_shorthand_inner<'i, 't>(
     // There are three optional components in the `border-<side>` shorthand that can appear in any
     // order.
     let (mut line_width, mut line_style, mut color) = (None, None, None);
-    for _ in 0..3 {
+    for component in 0..3 {
+        // At least one of the components must be given.
+        if component > 0 && input.is_exhausted() {
+            break;
+        }
         let parsed_line_width = input.try_parse(|i| LineWidth::parse(i));
         if let Ok(parsed_line_width) = parsed_line_width {
             if line_width.is_none() {
@@ -328,6 +332,12 @@ impl CssValueParse for LineWidth {
                     Err(_) => Err(location.new_unexpected_token_error(token.clone())),
                 }
             }
+            Token::Number { value, .. } => {
+                return match NoCalcLength::parse_unitless_zero(value) {
+                    Ok(no_calc_len) => Ok(LineWidth::Length(no_calc_len)),
+                    Err(_) => Err(location.new_unexpected_token_error(token.clone())),
+                }
+            }
             _ => {}
         };
         input.reset(&start);
@@ -0,0 +1,323 @@
+//! Math functions (`calc()`, `min()`, `max()`, and `clamp()`) in `<length-percentage>` values.
+//!
+//! https://drafts.csswg.org/css-values-4/#math
+
+use crate::values::specified::NoCalcLength;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, Token};
+use primitives::units::CSSFloat;
+
+/// A specified math function whose result is a `<length>`, a `<percentage>`, or a mix of both.
+pub type CalcLengthPercentage = CalcNode<NoCalcLength>;
+
+/// Whether a `min()` or `max()` node resolves to the smallest or the largest of its arguments.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum MinMaxOp {
+    Min,
+    Max,
+}
+
+/// A node in the expression tree of a math function.
+///
+/// This is generic over the type of its length leaves so that the same tree can represent both
+/// specified values (with relative lengths like `em`) and computed values (with every length
+/// resolved to pixels, but percentages kept symbolic until used-value time).
+///
+/// https://drafts.csswg.org/css-values-4/#calc-internal
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum CalcNode<L> {
+    Length(L),
+    /// A percentage, stored as a unit value (e.g. `50%` is stored as `0.5`).
+    Percentage(CSSFloat),
+    Number(CSSFloat),
+    Sum(Vec<CalcNode<L>>),
+    Negate(Box<CalcNode<L>>),
+    Product(Vec<CalcNode<L>>),
+    /// The reciprocal of a number, used to represent division.
+    Invert(Box<CalcNode<L>>),
+    MinMax(Vec<CalcNode<L>>, MinMaxOp),
+    Clamp {
+        min: Box<CalcNode<L>>,
+        center: Box<CalcNode<L>>,
+        max: Box<CalcNode<L>>,
+    },
+}
+
+/// The type of a math function expression.
+///
+/// https://drafts.csswg.org/css-values-4/#calc-type-checking
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CalcType {
+    Number,
+    Length,
+    Percentage,
+    LengthPercentage,
+}
+
+impl CalcType {
+    /// The type of the sum of two expressions, which must either be the same type or both be
+    /// some combination of lengths and percentages.
+    fn add(self, other: CalcType) -> Option<CalcType> {
+        match (self, other) {
+            (a, b) if a == b => Some(a),
+            (CalcType::Number, _) | (_, CalcType::Number) => None,
+            _ => Some(CalcType::LengthPercentage),
+        }
+    }
+
+    /// The type of the product of two expressions, at least one of which must be a number.
+    fn multiply(self, other: CalcType) -> Option<CalcType> {
+        match (self, other) {
+            (CalcType::Number, other) | (other, CalcType::Number) => Some(other),
+            _ => None,
+        }
+    }
+}
+
+impl<L> CalcNode<L> {
+    /// Type checks this expression, returning `None` if it could not be resolved to a single
+    /// value (e.g. `10px * 10px`, or `10px + 2`).
+    pub fn calc_type(&self) -> Option<CalcType> {
+        match self {
+            CalcNode::Length(_) => Some(CalcType::Length),
+            CalcNode::Percentage(_) => Some(CalcType::Percentage),
+            CalcNode::Number(_) => Some(CalcType::Number),
+            CalcNode::Negate(child) => child.calc_type(),
+            CalcNode::Invert(child) => match child.calc_type()? {
+                CalcType::Number => Some(CalcType::Number),
+                _ => None,
+            },
+            CalcNode::Sum(children) | CalcNode::MinMax(children, _) => {
+                fold_types(children, CalcType::add)
+            }
+            CalcNode::Product(children) => fold_types(children, CalcType::multiply),
+            CalcNode::Clamp { min, center, max } => min
+                .calc_type()?
+                .add(center.calc_type()?)?
+                .add(max.calc_type()?),
+        }
+    }
+
+    /// Whether or not this expression contains any percentages, meaning it can't be fully
+    /// resolved until the size the percentages refer to is known.
+    pub fn has_percentage(&self) -> bool {
+        match self {
+            CalcNode::Percentage(_) => true,
+            CalcNode::Length(_) | CalcNode::Number(_) => false,
+            CalcNode::Negate(child) | CalcNode::Invert(child) => child.has_percentage(),
+            CalcNode::Sum(children)
+            | CalcNode::Product(children)
+            | CalcNode::MinMax(children, _) => children.iter().any(CalcNode::has_percentage),
+            CalcNode::Clamp { min, center, max } => {
+                min.has_percentage() || center.has_percentage() || max.has_percentage()
+            }
+        }
+    }
+
+    /// Evaluates this expression, resolving lengths with `resolve_length` and percentages
+    /// against `percentage_basis`.
+    pub fn resolve<F: Fn(&L) -> CSSFloat>(
+        &self,
+        resolve_length: &F,
+        percentage_basis: CSSFloat,
+    ) -> CSSFloat {
+        let resolve = |node: &CalcNode<L>| node.resolve(resolve_length, percentage_basis);
+        match self {
+            CalcNode::Length(len) => resolve_length(len),
+            CalcNode::Percentage(unit_value) => unit_value * percentage_basis,
+            CalcNode::Number(value) => *value,
+            CalcNode::Sum(children) => children.iter().map(resolve).sum(),
+            CalcNode::Negate(child) => -resolve(child),
+            CalcNode::Product(children) => children.iter().map(resolve).product(),
+            CalcNode::Invert(child) => 1. / resolve(child),
+            CalcNode::MinMax(children, MinMaxOp::Min) => children
+                .iter()
+                .map(resolve)
+                .fold(CSSFloat::INFINITY, CSSFloat::min),
+            CalcNode::MinMax(children, MinMaxOp::Max) => children
+                .iter()
+                .map(resolve)
+                .fold(CSSFloat::NEG_INFINITY, CSSFloat::max),
+            // When `min` is greater than `max`, `min` wins.
+            // https://drafts.csswg.org/css-values-4/#funcdef-clamp
+            CalcNode::Clamp { min, center, max } => {
+                resolve(center).min(resolve(max)).max(resolve(min))
+            }
+        }
+    }
+
+    /// Converts every length in this expression with `f`, leaving the rest of the tree intact.
+    pub fn map_lengths<M, F: Fn(&L) -> M>(&self, f: &F) -> CalcNode<M> {
+        let map_all =
+            |children: &[CalcNode<L>]| children.iter().map(|c| c.map_lengths(f)).collect();
+        match self {
+            CalcNode::Length(len) => CalcNode::Length(f(len)),
+            CalcNode::Percentage(unit_value) => CalcNode::Percentage(*unit_value),
+            CalcNode::Number(value) => CalcNode::Number(*value),
+            CalcNode::Sum(children) => CalcNode::Sum(map_all(children)),
+            CalcNode::Negate(child) => CalcNode::Negate(Box::new(child.map_lengths(f))),
+            CalcNode::Product(children) => CalcNode::Product(map_all(children)),
+            CalcNode::Invert(child) => CalcNode::Invert(Box::new(child.map_lengths(f))),
+            CalcNode::MinMax(children, op) => CalcNode::MinMax(map_all(children), *op),
+            CalcNode::Clamp { min, center, max } => CalcNode::Clamp {
+                min: Box::new(min.map_lengths(f)),
+                center: Box::new(center.map_lengths(f)),
+                max: Box::new(max.map_lengths(f)),
+            },
+        }
+    }
+}
+
+fn fold_types<L>(
+    children: &[CalcNode<L>],
+    combine: fn(CalcType, CalcType) -> Option<CalcType>,
+) -> Option<CalcType> {
+    let mut types = children.iter().map(CalcNode::calc_type);
+    let first = types.next()??;
+    types.try_fold(first, |acc, ty| combine(acc, ty?))
+}
+
+impl CalcNode<NoCalcLength> {
+    /// Parses the arguments of the math function named `name`, where the function token itself
+    /// has just been consumed from `input`.  The result is type checked to be a `<length>`, a
+    /// `<percentage>`, or a mix of both.
+    pub fn parse_length_percentage<'i, 't>(
+        name: &str,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let node = input.parse_nested_block(|i| CalcNode::parse_function_arguments(name, i))?;
+        match node.calc_type() {
+            Some(CalcType::Length)
+            | Some(CalcType::Percentage)
+            | Some(CalcType::LengthPercentage) => Ok(node),
+            _ => Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError)),
+        }
+    }
+
+    /// Whether or not `name` is the name of a math function Kosmonaut supports.
+    pub fn is_math_function(name: &str) -> bool {
+        match_ignore_ascii_case! { name,
+            "calc" | "min" | "max" | "clamp" => true,
+            _ => false,
+        }
+    }
+
+    fn parse_function_arguments<'i, 't>(
+        name: &str,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        match_ignore_ascii_case! { name,
+            "calc" => CalcNode::parse_sum(input),
+            "min" => Ok(CalcNode::MinMax(
+                input.parse_comma_separated(CalcNode::parse_sum)?,
+                MinMaxOp::Min,
+            )),
+            "max" => Ok(CalcNode::MinMax(
+                input.parse_comma_separated(CalcNode::parse_sum)?,
+                MinMaxOp::Max,
+            )),
+            "clamp" => {
+                let min = CalcNode::parse_sum(input)?;
+                input.expect_comma()?;
+                let center = CalcNode::parse_sum(input)?;
+                input.expect_comma()?;
+                let max = CalcNode::parse_sum(input)?;
+                Ok(CalcNode::Clamp {
+                    min: Box::new(min),
+                    center: Box::new(center),
+                    max: Box::new(max),
+                })
+            },
+            _ => Err(input.new_custom_error(StyleParseErrorKind::UnexpectedFunction(name.to_owned().into()))),
+        }
+    }
+
+    /// Parses `<calc-sum> = <calc-product> [ [ '+' | '-' ] <calc-product> ]*`.
+    ///
+    /// `+` and `-` must be surrounded by whitespace, so whitespace is significant here.
+    fn parse_sum<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut terms = vec![CalcNode::parse_product(input)?];
+        loop {
+            let start = input.state();
+            match input.next_including_whitespace() {
+                Ok(&Token::WhiteSpace(_)) => {
+                    if input.is_exhausted() {
+                        break;
+                    }
+                    match *input.next()? {
+                        Token::Delim('+') => terms.push(CalcNode::parse_product(input)?),
+                        Token::Delim('-') => {
+                            terms.push(CalcNode::Negate(Box::new(CalcNode::parse_product(input)?)))
+                        }
+                        // Anything else (e.g. the comma between `clamp()` arguments) is left for
+                        // the caller to deal with.
+                        _ => {
+                            input.reset(&start);
+                            break;
+                        }
+                    }
+                }
+                _ => {
+                    input.reset(&start);
+                    break;
+                }
+            }
+        }
+        Ok(if terms.len() == 1 {
+            terms.pop().unwrap()
+        } else {
+            CalcNode::Sum(terms)
+        })
+    }
+
+    /// Parses `<calc-product> = <calc-value> [ [ '*' | '/' ] <calc-value> ]*`.
+    fn parse_product<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut factors = vec![CalcNode::parse_value(input)?];
+        loop {
+            let start = input.state();
+            match input.next() {
+                Ok(&Token::Delim('*')) => factors.push(CalcNode::parse_value(input)?),
+                Ok(&Token::Delim('/')) => {
+                    factors.push(CalcNode::Invert(Box::new(CalcNode::parse_value(input)?)))
+                }
+                _ => {
+                    input.reset(&start);
+                    break;
+                }
+            }
+        }
+        Ok(if factors.len() == 1 {
+            factors.pop().unwrap()
+        } else {
+            CalcNode::Product(factors)
+        })
+    }
+
+    /// Parses `<calc-value> = <number> | <dimension> | <percentage> | ( <calc-sum> )`, along with
+    /// nested math functions.
+    fn parse_value<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let token = input.next()?.clone();
+        match token {
+            Token::Number { value, .. } => Ok(CalcNode::Number(value)),
+            Token::Percentage { unit_value, .. } => Ok(CalcNode::Percentage(unit_value)),
+            Token::Dimension {
+                value, ref unit, ..
+            } => NoCalcLength::parse_dimension(value, unit)
+                .map(CalcNode::Length)
+                .map_err(|()| location.new_unexpected_token_error(token.clone())),
+            Token::ParenthesisBlock => input.parse_nested_block(CalcNode::parse_sum),
+            Token::Function(ref name) if CalcNode::is_math_function(name) => {
+                input.parse_nested_block(|i| CalcNode::parse_function_arguments(name, i))
+            }
+            _ => Err(location.new_unexpected_token_error(token)),
+        }
+    }
+}
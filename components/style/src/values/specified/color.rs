@@ -12,8 +12,6 @@ use cssparser::{
 pub enum Color {
     /// A `<color>`-unit.
     Unit(ColorUnit),
-    /// The `inherit` keyword.
-    Inherit,
 }
 
 impl Color {
@@ -28,13 +26,7 @@ impl CssValueParse for Color {
     fn parse<'i, 't>(
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
-        if let Ok(color_unit) = input.try_parse(|i| ColorUnit::parse(i)) {
-            return Ok(Color::Unit(color_unit));
-        };
-
-        try_match_ident_ignore_ascii_case! { input,
-            "inherit" => Ok(Color::Inherit),
-        }
+        Ok(Color::Unit(ColorUnit::parse(input)?))
     }
 }
 
@@ -0,0 +1,116 @@
+use crate::properties::PropertyDeclaration;
+use crate::values::computed::{FlexDirection, FlexFactor, FlexWrap};
+use crate::values::specified::{
+    AbsoluteLength, LengthPercentage, LengthPercentageOrAuto, NoCalcLength,
+};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Parses the `flex` shorthand, `none | [ <'flex-grow'> <'flex-shrink'>? || <'flex-basis'> ]`.
+/// An omitted flex factor is one, and an omitted basis is zero.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-property
+pub fn parse_flex_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let (grow, shrink, basis) = if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+        (
+            FlexFactor::new(0.),
+            FlexFactor::new(0.),
+            FlexBasis::Width(LengthPercentageOrAuto::Auto),
+        )
+    } else {
+        let (mut factors, mut basis) = (None, None);
+        for _ in 0..2 {
+            if factors.is_none() {
+                if let Ok(grow) = input.try_parse(|i| FlexFactor::parse(i)) {
+                    let shrink = input.try_parse(|i| FlexFactor::parse(i)).ok();
+                    factors = Some((grow, shrink));
+                    continue;
+                }
+            }
+            if basis.is_none() {
+                if let Ok(parsed_basis) = input.try_parse(|i| FlexBasis::parse(i)) {
+                    basis = Some(parsed_basis);
+                    continue;
+                }
+            }
+            break;
+        }
+        if factors.is_none() && basis.is_none() {
+            let location = input.current_source_location();
+            return Err(location.new_unexpected_token_error(input.next()?.clone()));
+        }
+        let (grow, shrink) = factors.unwrap_or((FlexFactor::new(1.), None));
+        let basis = basis.unwrap_or(FlexBasis::Width(LengthPercentageOrAuto::LengthPercentage(
+            LengthPercentage::Length(NoCalcLength::Absolute(AbsoluteLength::Px(0.))),
+        )));
+        (grow, shrink.unwrap_or(FlexFactor::new(1.)), basis)
+    };
+    declarations.push(PropertyDeclaration::FlexGrow(grow));
+    declarations.push(PropertyDeclaration::FlexShrink(shrink));
+    declarations.push(PropertyDeclaration::FlexBasis(basis));
+    Ok(())
+}
+
+/// Parses the `flex-flow` shorthand, `<'flex-direction'> || <'flex-wrap'>`.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-flow-property
+pub fn parse_flex_flow_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let (mut direction, mut wrap) = (None, None);
+    for _ in 0..2 {
+        if direction.is_none() {
+            if let Ok(parsed_direction) = input.try_parse(|i| FlexDirection::parse(i)) {
+                direction = Some(parsed_direction);
+                continue;
+            }
+        }
+        if wrap.is_none() {
+            if let Ok(parsed_wrap) = input.try_parse(|i| FlexWrap::parse(i)) {
+                wrap = Some(parsed_wrap);
+                continue;
+            }
+        }
+        break;
+    }
+    if direction.is_none() && wrap.is_none() {
+        let location = input.current_source_location();
+        return Err(location.new_unexpected_token_error(input.next()?.clone()));
+    }
+    declarations.push(PropertyDeclaration::FlexDirection(
+        direction.unwrap_or_else(FlexDirection::initial_value),
+    ));
+    declarations.push(PropertyDeclaration::FlexWrap(
+        wrap.unwrap_or_else(FlexWrap::initial_value),
+    ));
+    Ok(())
+}
+
+/// Specified value for `flex-basis`.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-basis-property
+#[derive(Clone, Debug)]
+pub enum FlexBasis {
+    /// The size of the item's content.
+    Content,
+    /// Takes the same values as `width` and `height`.
+    Width(LengthPercentageOrAuto),
+}
+
+impl CssValueParse for FlexBasis {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp_or_auto) = input.try_parse(|i| LengthPercentageOrAuto::parse(i)) {
+            return Ok(FlexBasis::Width(lp_or_auto));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "content" => Ok(FlexBasis::Content),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
 
+use crate::properties::PropertyDeclaration;
+use crate::values::computed::font::{FontFamily, FontStretch, FontStyle};
 use crate::values::specified::length::LengthPercentage;
 use crate::values::CssValueParse;
 
@@ -12,12 +14,12 @@ pub const FONT_MEDIUM_PX: i32 = 16;
 pub enum FontSize {
     /// A keyword size, e.g. medium
     Keyword(KeywordSize),
-    // font-size: larger
-    // Larger,
+    /// font-size: larger
+    Larger,
     /// A length; e.g. 10px.
     Length(LengthPercentage),
-    // font-size: smaller
-    // Smaller,
+    /// font-size: smaller
+    Smaller,
 }
 
 /// CSS font keywords
@@ -64,9 +66,161 @@ impl CssValueParse for FontSize {
         if let Ok(lp) = input.try_parse(|i| LengthPercentage::parse(i)) {
             return Ok(FontSize::Length(lp));
         }
-        match input.try_parse(|i| KeywordSize::parse(i)) {
-            Ok(kws) => Ok(FontSize::Keyword(kws)),
-            Err(parse_err) => Err(parse_err),
+        if let Ok(kws) = input.try_parse(|i| KeywordSize::parse(i)) {
+            return Ok(FontSize::Keyword(kws));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "larger" => Ok(FontSize::Larger),
+            "smaller" => Ok(FontSize::Smaller),
+        }
+    }
+}
+
+/// A specified `font-weight` value.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-weight-prop
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FontWeight {
+    /// A numeric weight between 1 and 1000, inclusive.  `normal` is 400 and `bold` is 700.
+    Absolute(f32),
+    /// font-weight: bolder
+    Bolder,
+    /// font-weight: lighter
+    Lighter,
+}
+
+impl FontWeight {
+    pub fn initial_value() -> Self {
+        FontWeight::Absolute(400.)
+    }
+
+    /// Parses an `<font-weight-absolute>`, which excludes the relative `bolder` and `lighter`
+    /// keywords.
+    pub fn parse_absolute<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        if let Ok(weight) = input.try_parse(|i| i.expect_number()) {
+            return if (1. ..=1000.).contains(&weight) {
+                Ok(FontWeight::Absolute(weight))
+            } else {
+                Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+            };
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(FontWeight::Absolute(400.)),
+            "bold" => Ok(FontWeight::Absolute(700.)),
+        }
+    }
+}
+
+impl CssValueParse for FontWeight {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(weight) = input.try_parse(FontWeight::parse_absolute) {
+            return Ok(weight);
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "bolder" => Ok(FontWeight::Bolder),
+            "lighter" => Ok(FontWeight::Lighter),
         }
     }
 }
+
+/// A specified `line-height` value.
+///
+/// https://drafts.csswg.org/css2/#propdef-line-height
+#[derive(Clone, Debug, PartialEq)]
+pub enum LineHeight {
+    Normal,
+    /// A unitless multiple of the element's font size, which is inherited as the number itself
+    /// rather than the length it computes to.
+    Number(f32),
+    LengthPercentage(LengthPercentage),
+}
+
+impl CssValueParse for LineHeight {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        if let Ok(number) = input.try_parse(|i| i.expect_number()) {
+            return if number >= 0. {
+                Ok(LineHeight::Number(number))
+            } else {
+                Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+            };
+        }
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse) {
+            return Ok(LineHeight::LengthPercentage(lp));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(LineHeight::Normal),
+        }
+    }
+}
+
+/// Parses the `font` shorthand, which resets every font longhand not given to its initial value.
+/// System font keywords (e.g. `caption`) and `font-variant` values other than `normal` aren't
+/// supported.
+///
+/// https://drafts.csswg.org/css-fonts-4/#font-prop
+pub fn parse_font_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let mut style = None;
+    let mut weight = None;
+    let mut stretch = None;
+    // Style, variant, weight, and stretch may come in any order before the size, and `normal`
+    // could be any of them.
+    for _ in 0..4 {
+        if input
+            .try_parse(|i| i.expect_ident_matching("normal"))
+            .is_ok()
+        {
+            continue;
+        }
+        if style.is_none() {
+            if let Ok(value) = input.try_parse(FontStyle::parse) {
+                style = Some(value);
+                continue;
+            }
+        }
+        if weight.is_none() {
+            if let Ok(value) = input.try_parse(FontWeight::parse_absolute) {
+                weight = Some(value);
+                continue;
+            }
+        }
+        if stretch.is_none() {
+            if let Ok(value) = input.try_parse(FontStretch::parse_keyword) {
+                stretch = Some(value);
+                continue;
+            }
+        }
+        break;
+    }
+    let size = FontSize::parse(input)?;
+    let line_height = if input.try_parse(|i| i.expect_delim('/')).is_ok() {
+        LineHeight::parse(input)?
+    } else {
+        LineHeight::Normal
+    };
+    let family = FontFamily::parse(input)?;
+
+    declarations.push(PropertyDeclaration::FontStyle(
+        style.unwrap_or_else(FontStyle::initial_value),
+    ));
+    declarations.push(PropertyDeclaration::FontWeight(
+        weight.unwrap_or_else(FontWeight::initial_value),
+    ));
+    declarations.push(PropertyDeclaration::FontStretch(
+        stretch.unwrap_or_else(FontStretch::initial_value),
+    ));
+    declarations.push(PropertyDeclaration::FontSize(size));
+    declarations.push(PropertyDeclaration::LineHeight(line_height));
+    declarations.push(PropertyDeclaration::FontFamily(family));
+    Ok(())
+}
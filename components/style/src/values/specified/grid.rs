@@ -0,0 +1,297 @@
+use crate::properties::PropertyDeclaration;
+use crate::values::computed::grid::{
+    self as computed_grid, AutoRepeat, RepeatCount, MAX_GRID_LINE,
+};
+use crate::values::computed::GridLine;
+use crate::values::specified::LengthPercentage;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, Token};
+use primitives::units::CSSFloat;
+
+/// Specified value of a minimum or maximum grid track size.
+pub type TrackBreadth = computed_grid::TrackBreadth<LengthPercentage>;
+/// Specified value of a grid track size.
+pub type TrackSize = computed_grid::TrackSize<LengthPercentage>;
+/// Specified value of `grid-template-columns` and `grid-template-rows`.
+pub type GridTemplate = computed_grid::GridTemplate<LengthPercentage>;
+/// Specified value of `grid-auto-columns` and `grid-auto-rows`.
+pub type GridAutoTracks = computed_grid::GridAutoTracks<LengthPercentage>;
+type TrackList = computed_grid::TrackList<LengthPercentage>;
+
+impl CssValueParse for TrackBreadth {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse) {
+            return Ok(TrackBreadth::Length(lp));
+        }
+        if let Ok(flex) = input.try_parse(parse_flex) {
+            return Ok(TrackBreadth::Flex(flex));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(TrackBreadth::Auto),
+            "min-content" => Ok(TrackBreadth::MinContent),
+            "max-content" => Ok(TrackBreadth::MaxContent),
+        }
+    }
+}
+
+/// Parses a non-negative `<flex>`, a number of `fr` units.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-flex
+fn parse_flex<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<CSSFloat, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } if unit.eq_ignore_ascii_case("fr") && value >= 0. => Ok(value),
+        _ => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+impl CssValueParse for TrackSize {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input
+            .try_parse(|i| i.expect_function_matching("minmax"))
+            .is_ok()
+        {
+            return input.parse_nested_block(|i| {
+                let location = i.current_source_location();
+                let min = TrackBreadth::parse(i)?;
+                i.expect_comma()?;
+                let max = TrackBreadth::parse(i)?;
+                match min {
+                    TrackBreadth::Flex(_) => {
+                        Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+                    }
+                    _ => Ok(TrackSize::MinMax(min, max)),
+                }
+            });
+        }
+        Ok(TrackSize::Breadth(TrackBreadth::parse(input)?))
+    }
+}
+
+/// Parses a bracketed list of line names, like `[header-start main-start]`.
+fn parse_line_names<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Vec<String>, ParseError<'i, StyleParseErrorKind<'i>>> {
+    input.expect_square_bracket_block()?;
+    input.parse_nested_block(|i| {
+        let mut names = Vec::new();
+        while let Ok(name) = i.try_parse(computed_grid::parse_custom_ident) {
+            names.push(name);
+        }
+        Ok(names)
+    })
+}
+
+/// Parses track sizes and the line names between them, adding them to `line_names` and `tracks`,
+/// until neither is next.  `line_names` must already have an entry for the line before the
+/// first track.
+fn parse_tracks_into<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    line_names: &mut Vec<Vec<String>>,
+    tracks: &mut Vec<TrackSize>,
+) {
+    loop {
+        if let Ok(names) = input.try_parse(parse_line_names) {
+            line_names.last_mut().unwrap().extend(names);
+        } else if let Ok(track) = input.try_parse(TrackSize::parse) {
+            tracks.push(track);
+            line_names.push(Vec::new());
+        } else {
+            break;
+        }
+    }
+}
+
+/// Parses the arguments of a `repeat()` in a track list, adding the tracks and line names it
+/// gives to `list`.  Repetitions with a fixed count are expanded here.
+///
+/// https://drafts.csswg.org/css-grid-1/#repeat-notation
+fn parse_repeat_into<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    list: &mut TrackList,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let repetitions = input.try_parse(|i| i.expect_integer()).ok();
+    let auto_count = match repetitions {
+        Some(_) => None,
+        None => Some(RepeatCount::parse(input)?),
+    };
+    input.expect_comma()?;
+    let mut line_names = vec![Vec::new()];
+    let mut tracks = Vec::new();
+    parse_tracks_into(input, &mut line_names, &mut tracks);
+    let invalid = || location.new_custom_error(StyleParseErrorKind::UnspecifiedError);
+    if tracks.is_empty() {
+        return Err(invalid());
+    }
+
+    match (repetitions, auto_count) {
+        (_, Some(count)) => {
+            // There can be only one automatic repetition, and the size of its tracks mustn't
+            // depend on their content.
+            if list.auto_repeat.is_some() || !tracks.iter().all(TrackSize::is_fixed) {
+                return Err(invalid());
+            }
+            list.auto_repeat = Some(AutoRepeat {
+                count,
+                position: list.tracks.len(),
+                line_names,
+                tracks,
+            });
+            list.line_names.push(Vec::new());
+        }
+        (Some(repetitions), None) if repetitions > 0 => {
+            // Repetitions that would take the list past the limit on the size of a grid are
+            // dropped.
+            let max_tracks = MAX_GRID_LINE as usize;
+            let repetitions = (max_tracks.saturating_sub(list.tracks.len()) / tracks.len())
+                .min(repetitions as usize);
+            for _ in 0..repetitions {
+                list.line_names
+                    .last_mut()
+                    .unwrap()
+                    .extend(line_names[0].iter().cloned());
+                for (track, names) in tracks.iter().zip(&line_names[1..]) {
+                    list.tracks.push(track.clone());
+                    list.line_names.push(names.clone());
+                }
+            }
+        }
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
+
+impl CssValueParse for GridTemplate {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(GridTemplate::None);
+        }
+        let location = input.current_source_location();
+        let mut list = TrackList {
+            line_names: vec![Vec::new()],
+            tracks: Vec::new(),
+            auto_repeat: None,
+        };
+        loop {
+            parse_tracks_into(input, &mut list.line_names, &mut list.tracks);
+            if input
+                .try_parse(|i| i.expect_function_matching("repeat"))
+                .is_err()
+            {
+                break;
+            }
+            input.parse_nested_block(|i| parse_repeat_into(i, &mut list))?;
+        }
+        if list.tracks.is_empty() && list.auto_repeat.is_none() {
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        Ok(GridTemplate::TrackList(list))
+    }
+}
+
+impl CssValueParse for GridAutoTracks {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut tracks = vec![TrackSize::parse(input)?];
+        while let Ok(track) = input.try_parse(TrackSize::parse) {
+            tracks.push(track);
+        }
+        Ok(computed_grid::GridAutoTracks(tracks))
+    }
+}
+
+/// Parses the `grid-row` or `grid-column` shorthand, `<grid-line> [ / <grid-line> ]?`, where an
+/// omitted end line is the same as the start line if it's a name, and otherwise `auto`.
+///
+/// https://drafts.csswg.org/css-grid-1/#placement-shorthands
+pub fn parse_grid_line_shorthand_into<'i, 't>(
+    column: bool,
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let start = GridLine::parse(input)?;
+    let end = if input.try_parse(|i| i.expect_delim('/')).is_ok() {
+        GridLine::parse(input)?
+    } else {
+        start.omitted_end()
+    };
+    if column {
+        declarations.push(PropertyDeclaration::GridColumnStart(start));
+        declarations.push(PropertyDeclaration::GridColumnEnd(end));
+    } else {
+        declarations.push(PropertyDeclaration::GridRowStart(start));
+        declarations.push(PropertyDeclaration::GridRowEnd(end));
+    }
+    Ok(())
+}
+
+/// Parses the `grid-area` shorthand, which gives up to four lines in the order row-start,
+/// column-start, row-end, and column-end, separated by slashes.  An omitted column-start or
+/// row-end is taken from row-start, and an omitted column-end from column-start.
+///
+/// https://drafts.csswg.org/css-grid-1/#propdef-grid-area
+pub fn parse_grid_area_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let mut lines = vec![GridLine::parse(input)?];
+    while lines.len() < 4 && input.try_parse(|i| i.expect_delim('/')).is_ok() {
+        lines.push(GridLine::parse(input)?);
+    }
+    let row_start = lines[0].clone();
+    let column_start = lines
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| row_start.omitted_end());
+    let row_end = lines
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| row_start.omitted_end());
+    let column_end = lines
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| column_start.omitted_end());
+    declarations.push(PropertyDeclaration::GridRowStart(row_start));
+    declarations.push(PropertyDeclaration::GridColumnStart(column_start));
+    declarations.push(PropertyDeclaration::GridRowEnd(row_end));
+    declarations.push(PropertyDeclaration::GridColumnEnd(column_end));
+    Ok(())
+}
+
+/// Parses the `grid-template` shorthand, `none | <'grid-template-rows'> /
+/// <'grid-template-columns'>`, which also resets `grid-template-areas`.
+///
+/// https://drafts.csswg.org/css-grid-1/#explicit-grid-shorthand
+// TODO: The form of `grid-template` that gives the areas along with the rows.
+pub fn parse_grid_template_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let (rows, columns) = if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+        (GridTemplate::None, GridTemplate::None)
+    } else {
+        let rows = GridTemplate::parse(input)?;
+        input.expect_delim('/')?;
+        (rows, GridTemplate::parse(input)?)
+    };
+    declarations.push(PropertyDeclaration::GridTemplateRows(rows));
+    declarations.push(PropertyDeclaration::GridTemplateColumns(columns));
+    declarations.push(PropertyDeclaration::GridTemplateAreas(
+        computed_grid::GridTemplateAreas::None,
+    ));
+    Ok(())
+}
@@ -7,7 +7,7 @@ use cssparser::{ParseError, Parser};
 ///
 /// https://www.w3.org/TR/css-sizing-3/#property-index
 // TODO: Need to support various other value types, such as `{min, max}-content`.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum Height {
     LengthPercentageOrAuto(LengthPercentageOrAuto),
 }
@@ -17,7 +17,7 @@ impl CssValueParse for Height {
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
         input
-            .try_parse(|i| LengthPercentageOrAuto::parse(i))
+            .try_parse(LengthPercentageOrAuto::parse_non_negative)
             .map(Height::LengthPercentageOrAuto)
     }
 }
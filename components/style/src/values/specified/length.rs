@@ -1,4 +1,6 @@
+use crate::values::specified::calc::CalcLengthPercentage;
 use crate::values::{computed, CssValueParse};
+use crate::viewport::ViewportSize;
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser, Token};
 use primitives::units::{
@@ -14,6 +16,16 @@ pub enum NoCalcLength {
     ///
     /// <https://drafts.csswg.org/css-values/#absolute-length>
     Absolute(AbsoluteLength),
+
+    /// A font-relative length
+    ///
+    /// <https://drafts.csswg.org/css-values/#font-relative-lengths>
+    FontRelative(FontRelativeLength),
+
+    /// A viewport-relative length
+    ///
+    /// <https://drafts.csswg.org/css-values/#viewport-relative-lengths>
+    ViewportPercentage(ViewportPercentageLength),
 }
 
 /// Represents an absolute length with its unit
@@ -68,6 +80,93 @@ impl AbsoluteLength {
     }
 }
 
+/// A font-relative length, which is resolved against the font size of an element (or the root
+/// element, in the case of `rem`).
+///
+/// <https://drafts.csswg.org/css-values/#font-relative-lengths>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FontRelativeLength {
+    /// A "em" value: https://drafts.csswg.org/css-values/#em
+    Em(CSSFloat),
+    /// A "ex" value: https://drafts.csswg.org/css-values/#ex
+    Ex(CSSFloat),
+    /// A "ch" value: https://drafts.csswg.org/css-values/#ch
+    Ch(CSSFloat),
+    /// A "rem" value: https://drafts.csswg.org/css-values/#rem
+    Rem(CSSFloat),
+}
+
+impl FontRelativeLength {
+    /// Convert this into a pixel value, given the font size to resolve `em`, `ex`, and `ch`
+    /// against, and the font size of the root element to resolve `rem` against.
+    #[inline]
+    pub fn to_px(self, em_base_px: CSSFloat, root_font_size_px: CSSFloat) -> CSSFloat {
+        match self {
+            FontRelativeLength::Em(value) => value * em_base_px,
+            // We don't yet have access to font metrics at style time, so follow the spec's
+            // fallback of 0.5em for both `ex` and `ch`:
+            // https://drafts.csswg.org/css-values/#ex
+            // https://drafts.csswg.org/css-values/#ch
+            FontRelativeLength::Ex(value) | FontRelativeLength::Ch(value) => {
+                value * em_base_px * 0.5
+            }
+            FontRelativeLength::Rem(value) => value * root_font_size_px,
+        }
+    }
+}
+
+/// A viewport-percentage length, which is resolved against the size of the initial containing
+/// block.  The small (`sv*`), large (`lv*`), and dynamic (`dv*`) variants are tracked separately
+/// so they can be distinguished should Kosmonaut's viewport ever change size dynamically.
+///
+/// <https://drafts.csswg.org/css-values/#viewport-relative-lengths>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ViewportPercentageLength {
+    Vw(CSSFloat),
+    Svw(CSSFloat),
+    Lvw(CSSFloat),
+    Dvw(CSSFloat),
+    Vh(CSSFloat),
+    Svh(CSSFloat),
+    Lvh(CSSFloat),
+    Dvh(CSSFloat),
+    Vmin(CSSFloat),
+    Svmin(CSSFloat),
+    Lvmin(CSSFloat),
+    Dvmin(CSSFloat),
+    Vmax(CSSFloat),
+    Svmax(CSSFloat),
+    Lvmax(CSSFloat),
+    Dvmax(CSSFloat),
+}
+
+impl ViewportPercentageLength {
+    /// Convert this into a pixel value given the size of the viewport.
+    #[inline]
+    pub fn to_px(self, viewport: ViewportSize) -> CSSFloat {
+        let (width, height) = (viewport.width.px(), viewport.height.px());
+        let (factor, basis) = match self {
+            ViewportPercentageLength::Vw(value)
+            | ViewportPercentageLength::Svw(value)
+            | ViewportPercentageLength::Lvw(value)
+            | ViewportPercentageLength::Dvw(value) => (value, width),
+            ViewportPercentageLength::Vh(value)
+            | ViewportPercentageLength::Svh(value)
+            | ViewportPercentageLength::Lvh(value)
+            | ViewportPercentageLength::Dvh(value) => (value, height),
+            ViewportPercentageLength::Vmin(value)
+            | ViewportPercentageLength::Svmin(value)
+            | ViewportPercentageLength::Lvmin(value)
+            | ViewportPercentageLength::Dvmin(value) => (value, width.min(height)),
+            ViewportPercentageLength::Vmax(value)
+            | ViewportPercentageLength::Svmax(value)
+            | ViewportPercentageLength::Lvmax(value)
+            | ViewportPercentageLength::Dvmax(value) => (value, width.max(height)),
+        };
+        factor * basis / 100.
+    }
+}
+
 impl NoCalcLength {
     /// Parse a given absolute or relative dimension.
     pub fn parse_dimension(value: CSSFloat, unit: &str) -> Result<Self, ()> {
@@ -79,23 +178,51 @@ impl NoCalcLength {
             "q" => NoCalcLength::Absolute(AbsoluteLength::Q(value)),
             "pt" => NoCalcLength::Absolute(AbsoluteLength::Pt(value)),
             "pc" => NoCalcLength::Absolute(AbsoluteLength::Pc(value)),
+            "em" => NoCalcLength::FontRelative(FontRelativeLength::Em(value)),
+            "ex" => NoCalcLength::FontRelative(FontRelativeLength::Ex(value)),
+            "ch" => NoCalcLength::FontRelative(FontRelativeLength::Ch(value)),
+            "rem" => NoCalcLength::FontRelative(FontRelativeLength::Rem(value)),
+            "vw" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Vw(value)),
+            "svw" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Svw(value)),
+            "lvw" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Lvw(value)),
+            "dvw" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Dvw(value)),
+            "vh" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Vh(value)),
+            "svh" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Svh(value)),
+            "lvh" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Lvh(value)),
+            "dvh" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Dvh(value)),
+            "vmin" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Vmin(value)),
+            "svmin" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Svmin(value)),
+            "lvmin" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Lvmin(value)),
+            "dvmin" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Dvmin(value)),
+            "vmax" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Vmax(value)),
+            "svmax" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Svmax(value)),
+            "lvmax" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Lvmax(value)),
+            "dvmax" => NoCalcLength::ViewportPercentage(ViewportPercentageLength::Dvmax(value)),
             _ => return Err(())
         })
     }
+
+    /// Parses a unitless number into a length, which is only valid for zero.
+    ///
+    /// https://drafts.csswg.org/css-values/#lengths
+    pub fn parse_unitless_zero(value: CSSFloat) -> Result<Self, ()> {
+        if value == 0. {
+            Ok(NoCalcLength::Absolute(AbsoluteLength::Px(0.)))
+        } else {
+            Err(())
+        }
+    }
 }
 
 /// A `<length-percentage>` value. This can be either a `<length>`, a
-/// `<percentage>`, or a combination of both via `calc()`.
-///
-/// TODO: We don't yet support calc expressions.  If we did, we would need a specified::LengthPercentage
-/// that had a Calc(Box<CalcLengthPercentage>) variant.
+/// `<percentage>`, or a combination of both via `calc()` and the other math functions.
 ///
 /// https://drafts.csswg.org/css-values-4/#typedef-length-percentage
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum LengthPercentage {
     Length(NoCalcLength),
     Percentage(computed::Percentage),
-    //    Calc(Box<CalcLengthPercentage>),
+    Calc(Box<CalcLengthPercentage>),
 }
 
 impl CssValueParse for LengthPercentage {
@@ -110,18 +237,41 @@ impl CssValueParse for LengthPercentage {
             } => NoCalcLength::parse_dimension(value, unit)
                 .map(LengthPercentage::Length)
                 .map_err(|()| location.new_unexpected_token_error(token.clone())),
+            Token::Number { value, .. } => NoCalcLength::parse_unitless_zero(value)
+                .map(LengthPercentage::Length)
+                .map_err(|()| location.new_unexpected_token_error(token.clone())),
             Token::Percentage { unit_value, .. } => Ok(LengthPercentage::Percentage(
                 computed::Percentage(unit_value),
             )),
+            Token::Function(ref name) if CalcLengthPercentage::is_math_function(name) => {
+                let name = name.clone();
+                CalcLengthPercentage::parse_length_percentage(&name, input)
+                    .map(|calc| LengthPercentage::Calc(Box::new(calc)))
+            }
             _ => Err(location.new_unexpected_token_error(token.clone())),
         }
     }
 }
 
+impl LengthPercentage {
+    /// Parses a `<length-percentage>` for a property that doesn't allow negative values.  Negative
+    /// lengths and percentages are rejected outright, but math functions are parsed as usual,
+    /// since whether they're negative can't be known until they're resolved.  Their results are
+    /// clamped to the property's range when they're computed instead.
+    ///
+    /// https://drafts.csswg.org/css-values-4/#calc-range
+    pub fn parse_non_negative<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        reject_negative_literal(input)?;
+        LengthPercentage::parse(input)
+    }
+}
+
 /// A `<length-percentage>` value, or the `auto` keyword.
 ///
 /// Some details on `auto`: https://www.w3.org/TR/css-sizing-3/#sizing-values
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum LengthPercentageOrAuto {
     LengthPercentage(LengthPercentage),
     Auto,
@@ -145,11 +295,25 @@ impl CssValueParse for LengthPercentageOrAuto {
                 }
                 Err(_) => return Err(location.new_unexpected_token_error(token.clone())),
             },
+            Token::Number { value, .. } => match NoCalcLength::parse_unitless_zero(value) {
+                Ok(no_calc_len) => {
+                    return Ok(LengthPercentageOrAuto::LengthPercentage(
+                        LengthPercentage::Length(no_calc_len),
+                    ))
+                }
+                Err(_) => return Err(location.new_unexpected_token_error(token.clone())),
+            },
             Token::Percentage { unit_value, .. } => {
                 return Ok(LengthPercentageOrAuto::LengthPercentage(
                     LengthPercentage::Percentage(computed::Percentage(unit_value)),
                 ));
             }
+            Token::Function(ref name) if CalcLengthPercentage::is_math_function(name) => {
+                let name = name.clone();
+                return CalcLengthPercentage::parse_length_percentage(&name, input).map(|calc| {
+                    LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Calc(Box::new(calc)))
+                });
+            }
             _ => {}
         };
         input.reset(&start);
@@ -158,3 +322,51 @@ impl CssValueParse for LengthPercentageOrAuto {
         }
     }
 }
+
+impl LengthPercentageOrAuto {
+    /// Parses a `<length-percentage>` or `auto` for a property that doesn't allow negative values,
+    /// as with `LengthPercentage::parse_non_negative`.
+    pub fn parse_non_negative<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        reject_negative_literal(input)?;
+        LengthPercentageOrAuto::parse(input)
+    }
+}
+
+/// Fails if the next token is a negative length or percentage, without consuming it.
+fn reject_negative_literal<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let start = input.state();
+    let location = input.current_source_location();
+    if let Ok(token) = input.next() {
+        match *token {
+            Token::Dimension { value, .. }
+            | Token::Percentage {
+                unit_value: value, ..
+            } if value < 0. => {
+                return Err(location.new_unexpected_token_error(token.clone()));
+            }
+            _ => {}
+        }
+    }
+    input.reset(&start);
+    Ok(())
+}
+
+/// Parses a non-negative `<length>`.
+pub fn parse_non_negative_length<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<NoCalcLength, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    let length = match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } if value >= 0. => NoCalcLength::parse_dimension(value, unit),
+        Token::Number { value, .. } => NoCalcLength::parse_unitless_zero(value),
+        _ => Err(()),
+    };
+    length.map_err(|()| location.new_unexpected_token_error(token.clone()))
+}
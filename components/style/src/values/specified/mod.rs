@@ -9,16 +9,27 @@
 ///
 /// https://developer.mozilla.org/en-US/docs/Web/CSS/specified_value
 /// https://www.w3.org/TR/CSS22/cascade.html#specified-value
+pub mod align;
 pub mod background;
 pub mod border;
+pub mod calc;
 pub mod color;
+pub mod flex;
 pub mod font;
+pub mod grid;
 pub mod height;
 pub mod length;
 pub mod margin;
+pub mod multicol;
 pub mod padding;
+pub mod position;
+pub mod sizing;
+pub mod table;
+pub mod text;
 pub mod width;
 
+pub use align::Gap;
+
 pub use background::BackgroundColor;
 
 pub use border::BorderBottomWidth;
@@ -27,22 +38,47 @@ pub use border::BorderLeftWidth;
 pub use border::BorderRightWidth;
 pub use border::BorderTopWidth;
 
+pub use calc::CalcLengthPercentage;
+
 pub use color::Color;
 pub use color::ColorUnit;
 
+pub use flex::FlexBasis;
+
 pub use font::FontSize;
+pub use font::FontWeight;
+pub use font::LineHeight;
 pub use font::FONT_MEDIUM_PX;
 
+pub use grid::GridAutoTracks;
+pub use grid::GridTemplate;
+
 pub use height::Height;
 
 pub use length::AbsoluteLength;
+pub use length::FontRelativeLength;
 pub use length::LengthPercentage;
 pub use length::LengthPercentageOrAuto;
 pub use length::NoCalcLength;
+pub use length::ViewportPercentageLength;
 
 pub use margin::Margin;
+
+pub use multicol::ColumnRuleWidth;
+pub use multicol::ColumnWidth;
 pub use padding::Padding;
 
+pub use position::Inset;
+
+pub use sizing::MaxSize;
+pub use sizing::MinSize;
+
+pub use table::BorderSpacing;
+
+pub use text::Spacing;
+pub use text::TextAlign;
+pub use text::TextIndent;
+
 use crate::values::CssValueParse;
 use crate::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
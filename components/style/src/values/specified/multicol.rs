@@ -0,0 +1,137 @@
+use crate::properties::PropertyDeclaration;
+use crate::values::computed::{ColumnCount, LineStyle};
+use crate::values::specified::border::{parse_border_shorthand_inner, LineWidth};
+use crate::values::specified::length::parse_non_negative_length;
+use crate::values::specified::{BorderColor, NoCalcLength};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Parses the `columns` shorthand, which sets `column-width` and `column-count` in either order.
+/// Whichever is omitted is set to `auto`.
+///
+/// https://drafts.csswg.org/css-multicol-1/#columns
+pub fn parse_columns_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let (mut column_width, mut column_count) = (None, None);
+    // `auto` could be either longhand, so it's only taken as `column-width` once there's no
+    // other way to parse the value.
+    let mut autos = 0;
+    for _ in 0..2 {
+        if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+            autos += 1;
+            continue;
+        }
+        if column_width.is_none() {
+            if let Ok(length) = input.try_parse(parse_non_negative_length) {
+                column_width = Some(ColumnWidth::Length(length));
+                continue;
+            }
+        }
+        if column_count.is_none() {
+            if let Ok(count) = input.try_parse(parse_column_count_integer) {
+                column_count = Some(count);
+                continue;
+            }
+        }
+        break;
+    }
+    if autos == 0 && column_width.is_none() && column_count.is_none() {
+        let location = input.current_source_location();
+        return Err(location.new_unexpected_token_error(input.next()?.clone()));
+    }
+    declarations.push(PropertyDeclaration::ColumnWidth(
+        column_width.unwrap_or(ColumnWidth::Auto),
+    ));
+    declarations.push(PropertyDeclaration::ColumnCount(
+        column_count.unwrap_or(ColumnCount::Auto),
+    ));
+    Ok(())
+}
+
+/// Parses the `column-rule` shorthand, which sets the width, style, and color of the rules between
+/// columns in any order.  Whichever are omitted are set to their initial values.
+///
+/// https://drafts.csswg.org/css-multicol-1/#column-rule
+pub fn parse_column_rule_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let (line_width, line_style, color) = parse_border_shorthand_inner(input)?;
+    declarations.push(PropertyDeclaration::ColumnRuleWidth(ColumnRuleWidth {
+        line_width: line_width.unwrap_or(LineWidth::Medium),
+    }));
+    declarations.push(PropertyDeclaration::ColumnRuleStyle(
+        line_style.unwrap_or(LineStyle::None),
+    ));
+    declarations.push(PropertyDeclaration::ColumnRuleColor(
+        color.map_or_else(BorderColor::initial_value, |color| BorderColor { color }),
+    ));
+    Ok(())
+}
+
+/// Parses the positive `<integer>` of `column-count`.
+fn parse_column_count_integer<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<ColumnCount, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let count = input.expect_integer()?;
+    if count < 1 {
+        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+    }
+    Ok(ColumnCount::Integer(count as u32))
+}
+
+impl CssValueParse for ColumnCount {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(count) = input.try_parse(parse_column_count_integer) {
+            return Ok(count);
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(ColumnCount::Auto),
+        }
+    }
+}
+
+/// Specified value for `column-width`, the ideal width of the columns of a multi-column
+/// container.
+///
+/// https://drafts.csswg.org/css-multicol-1/#cw
+#[derive(Clone, Copy, Debug)]
+pub enum ColumnWidth {
+    Auto,
+    Length(NoCalcLength),
+}
+
+impl CssValueParse for ColumnWidth {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(length) = input.try_parse(parse_non_negative_length) {
+            return Ok(ColumnWidth::Length(length));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(ColumnWidth::Auto),
+        }
+    }
+}
+
+/// Specified value for `column-rule-width`.
+///
+/// https://drafts.csswg.org/css-multicol-1/#crw
+#[derive(Clone, Copy, Debug)]
+pub struct ColumnRuleWidth {
+    pub line_width: LineWidth,
+}
+
+impl CssValueParse for ColumnRuleWidth {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        LineWidth::parse(input).map(|line_width| ColumnRuleWidth { line_width })
+    }
+}
@@ -8,19 +8,11 @@ pub fn parse_padding_shorthand_into<'i, 't>(
     declarations: &mut Vec<PropertyDeclaration>,
     input: &mut Parser<'i, 't>,
 ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
-    let parsed_sides = parse_shorthand_sides::<LengthPercentage>(input)?;
-    declarations.push(PropertyDeclaration::PaddingTop(Padding {
-        length_percentage: parsed_sides.top,
-    }));
-    declarations.push(PropertyDeclaration::PaddingRight(Padding {
-        length_percentage: parsed_sides.right,
-    }));
-    declarations.push(PropertyDeclaration::PaddingBottom(Padding {
-        length_percentage: parsed_sides.bottom,
-    }));
-    declarations.push(PropertyDeclaration::PaddingLeft(Padding {
-        length_percentage: parsed_sides.left,
-    }));
+    let parsed_sides = parse_shorthand_sides::<Padding>(input)?;
+    declarations.push(PropertyDeclaration::PaddingTop(parsed_sides.top));
+    declarations.push(PropertyDeclaration::PaddingRight(parsed_sides.right));
+    declarations.push(PropertyDeclaration::PaddingBottom(parsed_sides.bottom));
+    declarations.push(PropertyDeclaration::PaddingLeft(parsed_sides.left));
     Ok(())
 }
 
@@ -37,7 +29,7 @@ impl CssValueParse for Padding {
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
         input
-            .try_parse(|i| LengthPercentage::parse(i))
+            .try_parse(LengthPercentage::parse_non_negative)
             .map(|lp| Padding {
                 length_percentage: lp,
             })
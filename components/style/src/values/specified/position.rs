@@ -0,0 +1,43 @@
+use crate::properties::PropertyDeclaration;
+use crate::values::specified::{parse_shorthand_sides, LengthPercentageOrAuto};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+pub fn parse_inset_shorthand_into<'i, 't>(
+    declarations: &mut Vec<PropertyDeclaration>,
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let parsed_sides = parse_shorthand_sides::<LengthPercentageOrAuto>(input)?;
+    declarations.push(PropertyDeclaration::Top(Inset {
+        lp_or_auto: parsed_sides.top,
+    }));
+    declarations.push(PropertyDeclaration::Right(Inset {
+        lp_or_auto: parsed_sides.right,
+    }));
+    declarations.push(PropertyDeclaration::Bottom(Inset {
+        lp_or_auto: parsed_sides.bottom,
+    }));
+    declarations.push(PropertyDeclaration::Left(Inset {
+        lp_or_auto: parsed_sides.left,
+    }));
+    Ok(())
+}
+
+/// Specified value for `top`, `right`, `bottom`, and `left`.
+///
+/// https://drafts.csswg.org/css-position-3/#insets
+#[derive(Clone, Debug)]
+pub struct Inset {
+    pub lp_or_auto: LengthPercentageOrAuto,
+}
+
+impl CssValueParse for Inset {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .try_parse(|i| LengthPercentageOrAuto::parse(i))
+            .map(|lp_or_auto| Inset { lp_or_auto })
+    }
+}
@@ -0,0 +1,45 @@
+use crate::values::specified::{LengthPercentage, LengthPercentageOrAuto};
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Specified value for the `min-width` and `min-height` properties.
+///
+/// https://drafts.csswg.org/css-sizing-3/#min-size-properties
+// TODO: Need to support various other value types, such as `{min, max}-content`.
+#[derive(Clone, Debug)]
+pub enum MinSize {
+    LengthPercentageOrAuto(LengthPercentageOrAuto),
+}
+
+impl CssValueParse for MinSize {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .try_parse(LengthPercentageOrAuto::parse_non_negative)
+            .map(MinSize::LengthPercentageOrAuto)
+    }
+}
+
+/// Specified value for the `max-width` and `max-height` properties.
+///
+/// https://drafts.csswg.org/css-sizing-3/#max-size-properties
+#[derive(Clone, Debug)]
+pub enum MaxSize {
+    None,
+    LengthPercentage(LengthPercentage),
+}
+
+impl CssValueParse for MaxSize {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse_non_negative) {
+            return Ok(MaxSize::LengthPercentage(lp));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(MaxSize::None),
+        }
+    }
+}
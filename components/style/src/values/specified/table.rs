@@ -0,0 +1,30 @@
+use crate::values::specified::length::parse_non_negative_length;
+use crate::values::specified::NoCalcLength;
+use crate::values::CssValueParse;
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Specified value for `border-spacing`, the distance between the borders of adjacent cells of a
+/// table in the separated borders model.  One length sets both distances.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#separated-borders
+#[derive(Clone, Copy, Debug)]
+pub struct BorderSpacing {
+    pub horizontal: NoCalcLength,
+    pub vertical: NoCalcLength,
+}
+
+impl CssValueParse for BorderSpacing {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let horizontal = parse_non_negative_length(input)?;
+        let vertical = input
+            .try_parse(parse_non_negative_length)
+            .unwrap_or(horizontal);
+        Ok(BorderSpacing {
+            horizontal,
+            vertical,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use crate::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+use crate::values::computed;
+use crate::values::specified::length::LengthPercentage;
+use crate::values::CssValueParse;
+
+/// A specified `text-align` value.
+///
+/// https://drafts.csswg.org/css-text-3/#text-align-property
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextAlign {
+    Keyword(computed::TextAlign),
+    /// text-align: match-parent
+    MatchParent,
+}
+
+impl CssValueParse for TextAlign {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input
+            .try_parse(|i| i.expect_ident_matching("match-parent"))
+            .is_ok()
+        {
+            return Ok(TextAlign::MatchParent);
+        }
+        computed::TextAlign::parse(input).map(TextAlign::Keyword)
+    }
+}
+
+/// A specified `text-indent` value.
+///
+/// https://drafts.csswg.org/css-text-3/#text-indent-property
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextIndent(pub LengthPercentage);
+
+impl CssValueParse for TextIndent {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        LengthPercentage::parse(input).map(TextIndent)
+    }
+}
+
+/// A specified `letter-spacing` or `word-spacing` value.
+///
+/// https://drafts.csswg.org/css-text-4/#letter-spacing-property
+/// https://drafts.csswg.org/css-text-4/#word-spacing-property
+#[derive(Clone, Debug, PartialEq)]
+pub enum Spacing {
+    Normal,
+    /// Extra space, which may be negative.  Percentages are of the element's font size.
+    LengthPercentage(LengthPercentage),
+}
+
+impl CssValueParse for Spacing {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse) {
+            return Ok(Spacing::LengthPercentage(lp));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(Spacing::Normal),
+        }
+    }
+}
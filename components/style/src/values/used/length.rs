@@ -22,6 +22,9 @@ impl ToPx for LengthPercentage {
         match self {
             LengthPercentage::Length(len) => *len,
             LengthPercentage::Percentage(percentage) => percentage.px_relative_to(containing_size),
+            LengthPercentage::Calc(calc) => {
+                CSSPixelLength::new(calc.resolve(&|len| len.px(), containing_size.px()))
+            }
         }
     }
 }
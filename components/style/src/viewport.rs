@@ -0,0 +1,24 @@
+use primitives::units::{CSSFloat, CSSPixelLength};
+
+/// The size of the viewport that styles are computed against, in CSS pixels.  Viewport-percentage
+/// lengths (e.g. `vw`) are resolved against this.
+///
+/// Kosmonaut has no dynamic user agent interface (e.g. a retracting URL bar), so the small, large,
+/// and dynamic viewport sizes are all equal to this size.
+///
+/// https://drafts.csswg.org/css-values-4/#viewport-relative-lengths
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViewportSize {
+    pub width: CSSPixelLength,
+    pub height: CSSPixelLength,
+}
+
+impl ViewportSize {
+    pub fn new(width: CSSPixelLength, height: CSSPixelLength) -> Self {
+        ViewportSize { width, height }
+    }
+
+    pub fn from_px(width: CSSFloat, height: CSSFloat) -> Self {
+        ViewportSize::new(CSSPixelLength::new(width), CSSPixelLength::new(height))
+    }
+}
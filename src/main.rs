@@ -6,14 +6,15 @@ use std::fs::File;
 use glutin::event::{Event, WindowEvent};
 use glutin::event_loop::EventLoop;
 
-use cli::commands::{get_command, Command, DumpLayoutCmd, RenderCmd, SimilarityCmd};
-use cli::setup_and_get_cli_args;
+use cli::commands::{get_command, Command, DumpLayoutCmd, LintCssCmd, RenderCmd, SimilarityCmd};
+use cli::{setup_and_get_cli_args, LintCssFormat};
 use cssparser::RGBA;
 use display_list::{build_display_list, DisplayCommand, DisplayList};
 use dom::parser::parse_html;
-use dom::styling::{apply_styles, extract_embedded_styles};
+use dom::styling::{apply_inline_styles, apply_styles, extract_embedded_styles};
 use dom::tree::NodeRef;
 use gfx::char::CharHandle;
+use gfx::font::FontHandle;
 use gfx::headed::init_window_and_gl;
 use gfx::headless::init_framebuffer_and_gl;
 use gfx::paint::MasterPainter;
@@ -27,13 +28,16 @@ use glutin::{PossiblyCurrent, WindowedContext};
 use html5ever::tendril::TendrilSink;
 use layout::box_tree::build_box_tree;
 use layout::layout_box::LayoutBox;
+use layout::text::ApproximateTextMeasurer;
 use layout::{global_layout, DumpLayout, LayoutViewportDimensions};
 use std::cmp::{max, min};
 use std::error::Error;
 use std::io::Write;
 use std::path::Path;
-use style::parse_css_to_rules;
-use style::stylesheet::Stylesheet;
+use style::diagnostics::{Diagnostic, DiagnosticReporter, DiagnosticSink, IgnoreDiagnostics};
+use style::media_queries::{ColorScheme, Device, MediaType};
+use style::stylesheet::{load_stylesheet, resolve_imports, Stylesheet};
+use style::{parse_css_to_rules, CssRule};
 use url::Url;
 
 const UA_STYLESHEET_STR: &str = include_str!("../web/useragent.css");
@@ -48,6 +52,7 @@ fn main() {
     let arg_matches = setup_and_get_cli_args();
     if let Err(err_msg) = get_command(&arg_matches).run() {
         eprintln!("{}", err_msg);
+        std::process::exit(1);
     };
 }
 
@@ -55,6 +60,7 @@ pub enum CommandReturn {
     DumpLayout(<DumpLayoutCmd as CliCommand>::RunReturn),
     Render(<RenderCmd as CliCommand>::RunReturn),
     Similarity(<SimilarityCmd as CliCommand>::RunReturn),
+    LintCss(<LintCssCmd as CliCommand>::RunReturn),
 }
 
 pub trait CliCommand {
@@ -70,6 +76,7 @@ impl CliCommand for Command {
             Command::Render(cmd) => cmd.run().map(|_| CommandReturn::Render(())),
             Command::DumpLayout(cmd) => cmd.run().map(|_| CommandReturn::DumpLayout(())),
             Command::Similarity(cmd) => cmd.run().map(CommandReturn::Similarity),
+            Command::LintCss(cmd) => cmd.run().map(CommandReturn::LintCss),
         }
     }
 }
@@ -79,16 +86,28 @@ impl CliCommand for DumpLayoutCmd {
 
     fn run(&self) -> Result<Self::RunReturn, String> {
         let html_file_path = html_file_path_from_files(self.file_paths.clone()).unwrap();
+        let viewport = LayoutViewportDimensions::from_px(self.window_width, self.window_height);
         let styled_dom = load_and_style_dom_from_file(
             html_file_path,
-            get_author_sheets(self.file_paths.clone()),
+            get_author_sheets(self.file_paths.clone(), &mut IgnoreDiagnostics),
+            &DeviceOptions {
+                media_type: self.media_type,
+                color_scheme: self.color_scheme,
+            }
+            .device(viewport, self.scale_factor),
         );
 
-        let viewport = LayoutViewportDimensions::from_px(self.window_width, self.window_height);
         let write_to = &mut std::io::stdout();
         match build_box_tree(styled_dom, None) {
             Some(mut box_tree) => {
-                global_layout(&mut box_tree, viewport, self.scale_factor);
+                // Layout dumps are snapshot tested, so measure text the same way on every machine
+                // rather than with whatever fonts happen to be installed.
+                global_layout(
+                    &mut box_tree,
+                    viewport,
+                    self.scale_factor,
+                    &ApproximateTextMeasurer,
+                );
                 box_tree.dump_layout(write_to, 0, self.verbosity);
             }
             None => {
@@ -117,7 +136,14 @@ impl CliCommand for SimilarityCmd {
             headless_gfx_context.bind_framebuffer();
             let mut painter = MasterPainter::new(headless_gfx_context.gl(), scale_factor)?;
             let char_handle = CharHandle::new(headless_gfx_context.gl());
-            layout_and_paint_headless(box_tree, viewport, &char_handle, &mut painter, scale_factor);
+            layout_and_paint_headless(
+                box_tree,
+                viewport,
+                &char_handle,
+                &FontHandle::new(),
+                &mut painter,
+                scale_factor,
+            );
             Ok(headless_gfx_context.read_pixels(viewport_width, viewport_height))
         }
 
@@ -140,12 +166,6 @@ impl CliCommand for SimilarityCmd {
             html_file_paths.get(0).unwrap(),
             html_file_paths.get(1).unwrap(),
         );
-        let (dom_one, dom_two) = (
-            load_and_style_dom_from_file(html_file_one, vec![]),
-            load_and_style_dom_from_file(html_file_two, vec![]),
-        );
-        let (box_tree_one, box_tree_two) =
-            (build_box_tree(dom_one, None), build_box_tree(dom_two, None));
         let viewport = LayoutViewportDimensions::from_px(
             self.window_width
                 .unwrap_or(DEFAULT_LAYOUT_VIEWPORT_WIDTH_PX),
@@ -153,6 +173,17 @@ impl CliCommand for SimilarityCmd {
                 .unwrap_or(DEFAULT_LAYOUT_VIEWPORT_HEIGHT_PX),
         );
         let scale_factor = self.scale_factor.unwrap_or(1.0);
+        let device = DeviceOptions {
+            media_type: self.media_type,
+            color_scheme: self.color_scheme,
+        }
+        .device(viewport, scale_factor);
+        let (dom_one, dom_two) = (
+            load_and_style_dom_from_file(html_file_one, vec![], &device),
+            load_and_style_dom_from_file(html_file_two, vec![], &device),
+        );
+        let (box_tree_one, box_tree_two) =
+            (build_box_tree(dom_one, None), build_box_tree(dom_two, None));
         let pixels_one = paint_and_get_pixels(box_tree_one, viewport, scale_factor)?;
         let pixels_two = paint_and_get_pixels(box_tree_two, viewport, scale_factor)?;
         let longest_len = max(pixels_one.len(), pixels_two.len());
@@ -193,6 +224,77 @@ impl CliCommand for SimilarityCmd {
     }
 }
 
+impl CliCommand for LintCssCmd {
+    type RunReturn = Vec<Diagnostic>;
+
+    fn run(&self) -> Result<Self::RunReturn, String> {
+        let mut diagnostics = Vec::new();
+        for file_path in &self.file_paths {
+            let path = Path::new(file_path);
+            match path.extension().and_then(|extension| extension.to_str()) {
+                // Files that can't be loaded are reported along with everything else, so the
+                // diagnostics for the rest are still printed.
+                Some("css") => {
+                    if let Err(err) = load_stylesheet(path, &mut diagnostics) {
+                        DiagnosticReporter::new(file_path, &mut diagnostics)
+                            .report_failed_load(&err);
+                    }
+                }
+                Some("html") => match dom_from_file_or_url(file_path) {
+                    Ok(dom) => {
+                        DocumentStyles::new(&dom, Some(path), Vec::new(), &mut diagnostics);
+                        apply_inline_styles(&dom, &mut diagnostics);
+                    }
+                    Err(err) => {
+                        DiagnosticReporter::new(file_path, &mut diagnostics)
+                            .report_failed_load(&err);
+                    }
+                },
+                _ => {
+                    return Err(format!(
+                        "The `lint-css` command only accepts .css and .html files (got {}).",
+                        file_path
+                    ))
+                }
+            }
+        }
+
+        match self.format {
+            LintCssFormat::Human => diagnostics
+                .iter()
+                .for_each(|diagnostic| println!("{}", diagnostic)),
+            LintCssFormat::Json => {
+                let json_diagnostics = diagnostics
+                    .iter()
+                    .map(|diagnostic| {
+                        serde_json::json!({
+                            "kind": diagnostic.kind.name(),
+                            "message": diagnostic.kind.to_string(),
+                            "sheet": diagnostic.sheet_name,
+                            "line": diagnostic.location.line + 1,
+                            "column": diagnostic.location.column,
+                            "text": diagnostic.text,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json_diagnostics)
+                        .expect("error serializing diagnostics")
+                );
+            }
+        }
+        if diagnostics.is_empty() {
+            Ok(diagnostics)
+        } else {
+            Err(format!(
+                "found {} piece(s) of CSS that Kosmonaut ignores",
+                diagnostics.len()
+            ))
+        }
+    }
+}
+
 impl CliCommand for RenderCmd {
     type RunReturn = ();
 
@@ -201,24 +303,31 @@ impl CliCommand for RenderCmd {
         let author_sheets = self
             .files_or_urls
             .clone()
-            .map(get_author_sheets)
+            .map(|files_or_urls| get_author_sheets(files_or_urls, &mut IgnoreDiagnostics))
             .unwrap_or_default();
-        let styled_dom = if let Some(files_or_urls) = self.files_or_urls.clone() {
-            style_dom(
-                dom_from_file_or_url(files_or_urls.get(0).unwrap())?,
-                author_sheets,
-            )
-        } else {
-            load_and_style_dom_from_file(fallback_local_html, author_sheets)
+        let document_path = match self.files_or_urls.clone() {
+            Some(files_or_urls) => files_or_urls.get(0).unwrap().to_owned(),
+            None => fallback_local_html,
         };
+        let dom = dom_from_file_or_url(&document_path)?;
+        // Embedded styles can only `@import` other stylesheets when the document is a local file.
+        let document_path = Some(Path::new(&document_path)).filter(|path| path.is_file());
+        // The DOM is styled once the window has been created, since styles depend on its size.
+        let document_styles =
+            DocumentStyles::new(&dom, document_path, author_sheets, &mut IgnoreDiagnostics);
         let (windowed_context, event_loop, gl) =
             init_window_and_gl(self.window_width, self.window_height, LogGlInfo::Yes);
         run_event_loop(
             event_loop,
             gl,
-            styled_dom,
+            dom,
+            document_styles,
             windowed_context,
             self.scale_factor,
+            DeviceOptions {
+                media_type: self.media_type,
+                color_scheme: self.color_scheme,
+            },
         );
         Ok(())
     }
@@ -293,51 +402,97 @@ fn css_file_paths_from_files<S: AsRef<str>>(files: Vec<S>) -> Vec<String> {
         .collect::<Vec<_>>()
 }
 
-fn style_dom(dom: NodeRef, author_sheets: Vec<Stylesheet>) -> NodeRef {
-    let mut embedded_styles_str = extract_embedded_styles(dom.clone());
-    let embedded_styles = match parse_css_to_rules(&mut embedded_styles_str) {
-        Ok(rules) => rules,
-        Err(parse_error) => {
-            println!("error parsing embedded styles: {:?}", parse_error);
-            Vec::new()
+/// The styles that apply to a document, kept around so that the document can be restyled when the
+/// device it's displayed on changes.
+pub struct DocumentStyles {
+    embedded_styles: Vec<CssRule>,
+    ua_sheets: Vec<Stylesheet>,
+    author_sheets: Vec<Stylesheet>,
+}
+
+impl DocumentStyles {
+    /// Extracts the embedded styles from `dom`, pairing them with the user agent stylesheet and
+    /// the given `author_sheets`.  Stylesheets imported by the embedded styles are loaded relative
+    /// to `document_path`, the path of the file `dom` was parsed from, if any.  CSS dropped from
+    /// the embedded styles or the stylesheets they import is reported to `sink`.
+    fn new(
+        dom: &NodeRef,
+        document_path: Option<&Path>,
+        author_sheets: Vec<Stylesheet>,
+        sink: &mut dyn DiagnosticSink,
+    ) -> Self {
+        let mut embedded_styles_str = extract_embedded_styles(dom.clone());
+        let sheet_name = match document_path {
+            Some(document_path) => format!("{} <style>", document_path.display()),
+            None => "<style>".to_owned(),
+        };
+        let mut reporter = DiagnosticReporter::new(&sheet_name, sink);
+        let rules = parse_css_to_rules(&mut embedded_styles_str, &mut reporter);
+        let embedded_styles = match document_path {
+            Some(document_path) => resolve_imports(rules, document_path, &mut reporter),
+            None => rules,
+        };
+        let ua_sheet = style::stylesheet::parse_css_to_stylesheet(
+            Some("browser.css".to_owned()),
+            &mut UA_STYLESHEET_STR.to_owned(),
+            &mut IgnoreDiagnostics,
+        );
+        DocumentStyles {
+            embedded_styles,
+            ua_sheets: vec![ua_sheet],
+            author_sheets,
         }
-    };
-    let ua_sheet = style::stylesheet::parse_css_to_stylesheet(
-        Some("browser.css".to_owned()),
-        &mut UA_STYLESHEET_STR.to_owned(),
-    )
-    .expect("parse stylesheet fail");
-    apply_styles(
-        dom.clone(),
-        &embedded_styles,
-        &[ua_sheet],
-        &[],
-        &author_sheets,
-    );
-    dom
+    }
+
+    fn apply(&self, dom: &NodeRef, device: &Device) {
+        apply_styles(
+            dom.clone(),
+            &self.embedded_styles,
+            &self.ua_sheets,
+            &[],
+            &self.author_sheets,
+            device,
+        );
+    }
+}
+
+fn load_dom_from_file<P: AsRef<Path>>(html_file_path: P) -> NodeRef {
+    parse_html()
+        .from_utf8()
+        .read_from(&mut File::open(html_file_path).unwrap())
+        .unwrap()
 }
 
 fn load_and_style_dom_from_file<P: AsRef<Path>>(
     html_file_path: P,
     author_sheets: Vec<Stylesheet>,
+    device: &Device,
 ) -> NodeRef {
-    let dom = parse_html()
-        .from_utf8()
-        .read_from(&mut File::open(html_file_path).unwrap())
-        .unwrap();
-    style_dom(dom, author_sheets)
+    let dom = load_dom_from_file(&html_file_path);
+    DocumentStyles::new(
+        &dom,
+        Some(html_file_path.as_ref()),
+        author_sheets,
+        &mut IgnoreDiagnostics,
+    )
+    .apply(&dom, device);
+    dom
 }
 
-fn get_author_sheets<S: AsRef<str>>(file_paths: Vec<S>) -> Vec<Stylesheet> {
+/// Loads the stylesheets among `file_paths`.  Stylesheets that can't be loaded are reported to
+/// `sink` and skipped, as is any CSS dropped from the ones that can.
+fn get_author_sheets<S: AsRef<str>>(
+    file_paths: Vec<S>,
+    sink: &mut dyn DiagnosticSink,
+) -> Vec<Stylesheet> {
     css_file_paths_from_files(file_paths)
         .iter()
-        .map(|css_file_path| {
-            style::stylesheet::parse_css_to_stylesheet(
-                Some((*css_file_path).to_owned()),
-                &mut std::fs::read_to_string(css_file_path)
-                    .expect("couldn't read css file to string"),
-            )
-            .expect("error parsing stylesheet")
+        .filter_map(|css_file_path| match load_stylesheet(css_file_path, sink) {
+            Ok(sheet) => Some(sheet),
+            Err(err) => {
+                DiagnosticReporter::new(css_file_path, sink).report_failed_load(&err);
+                None
+            }
         })
         .collect::<Vec<_>>()
 }
@@ -345,23 +500,32 @@ fn get_author_sheets<S: AsRef<str>>(file_paths: Vec<S>) -> Vec<Stylesheet> {
 pub fn run_event_loop(
     event_loop: EventLoop<()>,
     gl: Gl,
-    styled_dom: NodeRef,
+    dom: NodeRef,
+    document_styles: DocumentStyles,
     windowed_context: WindowedContext<PossiblyCurrent>,
     cli_specified_scale_factor: Option<f32>,
+    device_options: DeviceOptions,
 ) {
-    // An un-laid-out tree of boxes, to be cloned from whenever a global layout is required.
-    // This saves us from having to rebuild the entire box tree from the DOM when necessary,
-    // instead only needing a clone.
-    let clean_box_tree = build_box_tree(styled_dom, None);
     let char_handle = CharHandle::new(&gl);
+    let font_handle = FontHandle::new();
     let mut scale = cli_specified_scale_factor.unwrap_or_else(|| {
         sanitize_windowed_context_scale_factor(windowed_context.window().scale_factor() as f32)
     });
     let mut master_painter = MasterPainter::new(&gl, scale).unwrap();
+    // Styles can depend on the size of the viewport and the scale factor (e.g. viewport-percentage
+    // lengths and media queries), so the DOM is restyled and the box tree rebuilt whenever the
+    // window changes size.
     layout_and_paint_headed(
-        clean_box_tree.clone(),
+        restyle_for_viewport(
+            &dom,
+            &document_styles,
+            windowed_context.window().inner_size().into(),
+            scale,
+            device_options,
+        ),
         &windowed_context,
         &char_handle,
+        &font_handle,
         &mut master_painter,
         scale,
     );
@@ -374,9 +538,16 @@ pub fn run_event_loop(
                 WindowEvent::Resized(physical_size) => {
                     resize_window(&gl, &windowed_context, physical_size);
                     layout_and_paint_headed(
-                        clean_box_tree.clone(),
+                        restyle_for_viewport(
+                            &dom,
+                            &document_styles,
+                            (*physical_size).into(),
+                            scale,
+                            device_options,
+                        ),
                         &windowed_context,
                         &char_handle,
+                        &font_handle,
                         &mut master_painter,
                         scale,
                     )
@@ -388,9 +559,16 @@ pub fn run_event_loop(
                     scale = *scale_factor as f32;
                     resize_window(&gl, &windowed_context, new_inner_size);
                     layout_and_paint_headed(
-                        clean_box_tree.clone(),
+                        restyle_for_viewport(
+                            &dom,
+                            &document_styles,
+                            (**new_inner_size).into(),
+                            scale,
+                            device_options,
+                        ),
                         &windowed_context,
                         &char_handle,
+                        &font_handle,
                         &mut master_painter,
                         scale,
                     )
@@ -403,10 +581,46 @@ pub fn run_event_loop(
     });
 }
 
+/// Restyles `dom` for a device with the given `viewport` and `scale_factor`, re-evaluating any
+/// media queries, and returns a freshly built box tree from the result.
+fn restyle_for_viewport(
+    dom: &NodeRef,
+    document_styles: &DocumentStyles,
+    viewport: LayoutViewportDimensions,
+    scale_factor: f32,
+    device_options: DeviceOptions,
+) -> Option<LayoutBox> {
+    document_styles.apply(dom, &device_options.device(viewport, scale_factor));
+    build_box_tree(dom.clone(), None)
+}
+
+/// The characteristics of the device documents are styled for that are given on the command line,
+/// rather than by the window.
+#[derive(Clone, Copy)]
+pub struct DeviceOptions {
+    media_type: cli::MediaType,
+    color_scheme: cli::ColorScheme,
+}
+
+impl DeviceOptions {
+    fn device(self, viewport: LayoutViewportDimensions, scale_factor: f32) -> Device {
+        Device::screen(viewport.style_viewport(scale_factor), scale_factor)
+            .with_media_type(match self.media_type {
+                cli::MediaType::Screen => MediaType::Screen,
+                cli::MediaType::Print => MediaType::Print,
+            })
+            .with_color_scheme(match self.color_scheme {
+                cli::ColorScheme::Light => ColorScheme::Light,
+                cli::ColorScheme::Dark => ColorScheme::Dark,
+            })
+    }
+}
+
 fn layout_and_paint_headed(
     box_tree_opt: Option<LayoutBox>,
     windowed_context: &WindowedContext<PossiblyCurrent>,
     char_handle: &CharHandle,
+    font_handle: &FontHandle,
     painter: &mut MasterPainter,
     scale_factor: f32,
 ) {
@@ -414,6 +628,7 @@ fn layout_and_paint_headed(
         box_tree_opt,
         windowed_context.window().inner_size().into(),
         char_handle,
+        font_handle,
         scale_factor,
     );
     painter.paint_headed(&windowed_context, &display_list);
@@ -423,11 +638,17 @@ fn layout_and_paint_headless(
     box_tree_opt: Option<LayoutBox>,
     viewport: LayoutViewportDimensions,
     char_handle: &CharHandle,
+    font_handle: &FontHandle,
     painter: &mut MasterPainter,
     scale_factor: f32,
 ) {
-    let display_list =
-        display_list_from_box_tree(box_tree_opt, viewport, char_handle, scale_factor);
+    let display_list = display_list_from_box_tree(
+        box_tree_opt,
+        viewport,
+        char_handle,
+        font_handle,
+        scale_factor,
+    );
     painter.paint_headless(viewport, &display_list);
 }
 
@@ -435,10 +656,11 @@ fn display_list_from_box_tree(
     box_tree_opt: Option<LayoutBox>,
     viewport: LayoutViewportDimensions,
     _char_handle: &CharHandle,
+    font_handle: &FontHandle,
     scale_factor: f32,
 ) -> DisplayList {
     if let Some(mut box_tree) = box_tree_opt {
-        global_layout(&mut box_tree, viewport, scale_factor);
+        global_layout(&mut box_tree, viewport, scale_factor, font_handle);
         build_display_list(&box_tree, scale_factor)
     } else {
         // There is no box tree to paint (e.g. in the case of `html { display: none }`, so paint
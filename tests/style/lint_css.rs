@@ -0,0 +1,67 @@
+use crate::util::CommandUnderTest;
+use insta::assert_snapshot;
+
+fn lint_css_cmd(format: &str) -> CommandUnderTest {
+    let mut cmd = CommandUnderTest::new();
+    cmd.arg("lint-css");
+    cmd.arg("--format");
+    cmd.arg(format);
+    cmd
+}
+
+fn snapshot_lint_css_cmd(lint_css_cmd: CommandUnderTest, snapshot_name: &str) {
+    let mut settings = insta::Settings::clone_current();
+    settings.set_snapshot_path("../snapshots/style/lint");
+    settings.set_prepend_module_to_snapshot(false);
+    settings.bind(|| {
+        assert_snapshot!(snapshot_name, lint_css_cmd.stdout());
+    });
+}
+
+#[test]
+fn lint_css_human_format() {
+    let mut cmd = lint_css_cmd("human");
+    cmd.args(&[
+        "tests/websrc/style/lint/lint.css",
+        "tests/websrc/style/lint/lint.html",
+    ])
+    .fails();
+    snapshot_lint_css_cmd(cmd, "lint-css-human");
+}
+
+#[test]
+fn lint_css_json_format() {
+    let mut cmd = lint_css_cmd("json");
+    cmd.args(&[
+        "tests/websrc/style/lint/lint.css",
+        "tests/websrc/style/lint/lint.html",
+    ])
+    .fails();
+    snapshot_lint_css_cmd(cmd, "lint-css-json");
+}
+
+#[test]
+fn lint_css_succeeds_without_diagnostics() {
+    lint_css_cmd("human")
+        .arg("tests/websrc/style/supports.dumplayout.html")
+        .succeeds()
+        .no_stdout();
+}
+
+#[test]
+fn lint_css_reports_unsupported_at_rules_and_unloadable_sheets() {
+    let mut cmd = lint_css_cmd("human");
+    cmd.args(&[
+        "tests/websrc/style/lint/at-rules.css",
+        "tests/websrc/style/lint/missing.css",
+    ])
+    .fails();
+    snapshot_lint_css_cmd(cmd, "lint-css-at-rules");
+}
+
+#[test]
+fn lint_css_reports_import_cycles() {
+    let mut cmd = lint_css_cmd("human");
+    cmd.arg("tests/websrc/style/lint/cycle-a.css").fails();
+    snapshot_lint_css_cmd(cmd, "lint-css-import-cycle");
+}
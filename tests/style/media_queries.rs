@@ -0,0 +1,34 @@
+use crate::{dump_layout_cmd, dump_layout_cmd_verbose_scaled, snapshot_dump_layout_cmd_scaled};
+use cli::DumpLayoutVerbosity;
+use insta::assert_snapshot;
+use std::path::Path;
+
+/// The default dump-layout run of this file is done automatically, but a scale factor of 2 also
+/// halves the size of the viewport in CSS pixels, which changes which of its media queries match.
+#[test]
+fn media_queries_two_scale_factor() {
+    let scale_factor = 2.0;
+    let mut dump_layout_cmd =
+        dump_layout_cmd_verbose_scaled(DumpLayoutVerbosity::NonVerbose, scale_factor);
+    let path = Path::new("tests/websrc/style/media-queries.dumplayout.html");
+    dump_layout_cmd.arg(path.to_str().unwrap()).succeeds();
+    snapshot_dump_layout_cmd_scaled(dump_layout_cmd, path, scale_factor);
+}
+
+/// Media queries can also test the media type and the color scheme the user prefers, which are
+/// given on the command line.
+#[test]
+fn media_queries_print_dark() {
+    let mut dump_layout_cmd = dump_layout_cmd(DumpLayoutVerbosity::NonVerbose);
+    dump_layout_cmd.args(&["--media-type", "print", "--color-scheme", "dark"]);
+    let path = Path::new("tests/websrc/style/media-queries.dumplayout.html");
+    dump_layout_cmd.arg(path.to_str().unwrap()).succeeds();
+    let mut settings = insta::Settings::clone_current();
+    settings.set_input_file(path);
+    settings.set_snapshot_path("../snapshots/style");
+    settings.set_prepend_module_to_snapshot(false);
+    settings.set_snapshot_suffix("print-dark");
+    settings.bind(|| {
+        assert_snapshot!("media-queries.dumplayout.html", dump_layout_cmd.stdout());
+    });
+}
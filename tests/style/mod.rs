@@ -1 +1,3 @@
+pub mod lint_css;
+pub mod media_queries;
 pub mod shorthands;